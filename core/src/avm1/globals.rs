@@ -53,6 +53,7 @@ pub(crate) mod shared_object;
 pub(crate) mod sound;
 mod stage;
 pub(crate) mod string;
+mod style_sheet;
 pub(crate) mod system;
 pub(crate) mod system_capabilities;
 pub(crate) mod system_ime;
@@ -538,6 +539,7 @@ pub fn create_globals<'gc>(
 
     let text_field_proto = text_field::create_proto(context, object_proto, function_proto);
     let text_format_proto = text_format::create_proto(context, object_proto, function_proto);
+    let style_sheet_proto = style_sheet::create_proto(context, object_proto, function_proto);
 
     let array_proto = array::create_proto(context, object_proto, function_proto);
 
@@ -656,6 +658,13 @@ pub fn create_globals<'gc>(
         function_proto,
         text_field_proto,
     );
+    let style_sheet = style_sheet::create_class(context, style_sheet_proto, function_proto);
+    text_field.define_value(
+        gc_context,
+        "StyleSheet",
+        style_sheet.into(),
+        Attribute::DONT_ENUM,
+    );
     let text_format = FunctionObject::constructor(
         gc_context,
         Executable::Native(text_format::constructor),
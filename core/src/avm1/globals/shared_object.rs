@@ -4,7 +4,8 @@ use crate::avm1::{
     Activation, Attribute, Error, Executable, NativeObject, Object, ScriptObject, TObject, Value,
 };
 use crate::avm1_stub;
-use crate::context::GcContext;
+use crate::backend::storage::QuotaCheck;
+use crate::context::{ActionType, GcContext};
 use crate::display_object::TDisplayObject;
 use crate::string::AvmString;
 use flash_lso::amf0::read::AMF0Decoder;
@@ -491,21 +492,52 @@ fn connect<'gc>(
 pub(crate) fn flush<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let NativeObject::SharedObject(shared_object) = this.native() else {
         return Ok(Value::Undefined);
     };
+    let min_disk_space = match args.get(0) {
+        Some(min_disk_space) => min_disk_space.coerce_to_i32(activation)?.max(0) as usize,
+        None => 0,
+    };
     let name = shared_object.read().name();
     let data = this.get("data", activation)?.coerce_to_object(activation);
     let mut lso = new_lso(activation, &name, data);
     flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
     // Flash does not write empty LSOs to disk
     if lso.body.is_empty() {
-        Ok(true.into())
-    } else {
-        let bytes = flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
-        Ok(activation.context.storage.put(&name, &bytes).into())
+        return Ok(true.into());
+    }
+
+    let bytes = flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
+    match activation
+        .context
+        .storage
+        .check_quota(&name, bytes.len().max(min_disk_space))
+    {
+        QuotaCheck::Allowed => Ok(activation.context.storage.put(&name, &bytes).into()),
+        QuotaCheck::Exceeded => {
+            // Flash Player would ask the user for more space here.
+            // We have no such prompt, so the request is always declined.
+            let info = ScriptObject::new(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes().object),
+            );
+            info.set("code", "SharedObject.Flush.Failed".into(), activation)?;
+            info.set("level", "error".into(), activation)?;
+            activation.context.action_queue.queue_action(
+                activation.base_clip(),
+                ActionType::Method {
+                    object: this,
+                    name: "onStatus",
+                    args: vec![info.into()],
+                },
+                false,
+            );
+            Ok("pending".into())
+        }
+        QuotaCheck::Denied => Ok(false.into()),
     }
 }
 
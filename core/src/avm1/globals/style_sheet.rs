@@ -0,0 +1,204 @@
+//! `TextField.StyleSheet` impl
+
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::object::NativeObject;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Activation, ArrayObject, Error, Object, ScriptObject, TObject, Value};
+use crate::avm1_stub;
+use crate::context::GcContext;
+use crate::html::{transform_style, CssStyle, StyleSheet, CSS_PROPERTIES};
+use crate::string::{AvmString, WStr};
+use gc_arena::Gc;
+use std::cell::RefCell;
+
+macro_rules! style_sheet_method {
+    ($fn:expr) => {
+        |activation, this, args| {
+            if let NativeObject::StyleSheet(style_sheet) = this.native() {
+                return $fn(activation, style_sheet, args);
+            }
+            Ok(Value::Undefined)
+        }
+    };
+}
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "getStyleNames" => method(style_sheet_method!(get_style_names); DONT_ENUM | DONT_DELETE);
+    "getStyle" => method(style_sheet_method!(get_style); DONT_ENUM | DONT_DELETE);
+    "setStyle" => method(style_sheet_method!(set_style); DONT_ENUM | DONT_DELETE);
+    "clear" => method(style_sheet_method!(clear); DONT_ENUM | DONT_DELETE);
+    "parseCSS" => method(style_sheet_method!(parse_css); DONT_ENUM | DONT_DELETE);
+    "transform" => method(transform; DONT_ENUM | DONT_DELETE);
+    "load" => method(load; DONT_ENUM | DONT_DELETE);
+};
+
+/// `TextField.StyleSheet` constructor
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    this.set_native(
+        activation.context.gc_context,
+        NativeObject::StyleSheet(Gc::new(
+            activation.context.gc_context,
+            RefCell::new(StyleSheet::new()),
+        )),
+    );
+    Ok(this.into())
+}
+
+fn get_style_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style_sheet: Gc<'gc, RefCell<StyleSheet>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let gc_context = activation.context.gc_context;
+    let names: Vec<Value<'gc>> = style_sheet
+        .borrow()
+        .style_names()
+        .map(|name| AvmString::new(gc_context, name).into())
+        .collect();
+    Ok(ArrayObject::new(
+        gc_context,
+        activation.context.avm1.prototypes().array,
+        names,
+    )
+    .into())
+}
+
+fn get_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style_sheet: Gc<'gc, RefCell<StyleSheet>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let Some(style) = style_sheet.borrow().style(&name).cloned() else {
+        return Ok(Value::Null);
+    };
+
+    let object = ScriptObject::new(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().object),
+    );
+    for (name, value) in style {
+        let name = AvmString::new(activation.context.gc_context, name);
+        let value = AvmString::new(activation.context.gc_context, value);
+        object.set(name, value.into(), activation)?;
+    }
+    Ok(object.into())
+}
+
+fn set_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style_sheet: Gc<'gc, RefCell<StyleSheet>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let style = match args.get(1) {
+        Some(Value::Object(object)) => Some(css_style_from_object(activation, *object)?),
+        _ => None,
+    };
+    style_sheet.borrow_mut().set_style(&name, style);
+    Ok(Value::Undefined)
+}
+
+fn clear<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    style_sheet: Gc<'gc, RefCell<StyleSheet>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    style_sheet.borrow_mut().clear();
+    Ok(Value::Undefined)
+}
+
+fn parse_css<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style_sheet: Gc<'gc, RefCell<StyleSheet>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    Ok(style_sheet.borrow_mut().parse_css(&css).into())
+}
+
+fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(Value::Object(style)) = args.get(0) else {
+        return Ok(Value::Null);
+    };
+
+    let style = css_style_from_object(activation, *style)?;
+    let text_format = transform_style(&style);
+    let object = ScriptObject::new(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().text_format),
+    );
+    object.set_native(
+        activation.context.gc_context,
+        NativeObject::TextFormat(Gc::new(activation.context.gc_context, text_format.into())),
+    );
+    Ok(object.into())
+}
+
+fn load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    avm1_stub!(activation, "TextField.StyleSheet", "load");
+    Ok(false.into())
+}
+
+/// Reads all CSS properties known to Flash Player from a style object.
+fn css_style_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<CssStyle, Error<'gc>> {
+    let mut style = CssStyle::new();
+    for &property in CSS_PROPERTIES {
+        let name = AvmString::new(activation.context.gc_context, WStr::from_units(property));
+        let value = object.get(name, activation)?;
+        if matches!(value, Value::Undefined | Value::Null) {
+            continue;
+        }
+        let value = value.coerce_to_string(activation)?;
+        style.push((name.as_wstr().into(), value.as_wstr().into()));
+    }
+    Ok(style)
+}
+
+pub fn create_proto<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let style_sheet_proto = ScriptObject::new(context.gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, context, style_sheet_proto, fn_proto);
+    style_sheet_proto.into()
+}
+
+pub fn create_class<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    style_sheet_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    FunctionObject::constructor(
+        context.gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        style_sheet_proto,
+    )
+}
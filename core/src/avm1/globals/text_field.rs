@@ -82,6 +82,7 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "restrict" => property(tf_getter!(restrict), tf_setter!(set_restrict));
     "scroll" => property(tf_getter!(scroll), tf_setter!(set_scroll));
     "selectable" => property(tf_getter!(selectable), tf_setter!(set_selectable));
+    "styleSheet" => property(tf_getter!(style_sheet), tf_setter!(set_style_sheet); VERSION_7);
     "text" => property(tf_getter!(text), tf_setter!(set_text));
     "textColor" => property(tf_getter!(text_color), tf_setter!(set_text_color));
     "textHeight" => property(tf_getter!(text_height));
@@ -389,6 +390,34 @@ pub fn set_html_text<'gc>(
     Ok(())
 }
 
+pub fn style_sheet<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .style_sheet_object()
+        .map_or(Value::Undefined, Value::Object))
+}
+
+pub fn set_style_sheet<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    match value {
+        Value::Object(object) => match object.native() {
+            // The style sheet is copied, so later changes to it only apply once it's set again.
+            NativeObject::StyleSheet(style_sheet) => {
+                let style_sheet = style_sheet.borrow().clone();
+                this.set_style_sheet(Some(style_sheet), Some(object), &mut activation.context);
+            }
+            _ => this.set_style_sheet(None, None, &mut activation.context),
+        },
+        _ => this.set_style_sheet(None, None, &mut activation.context),
+    }
+    Ok(())
+}
+
 pub fn background<'gc>(
     this: EditText<'gc>,
     _activation: &mut Activation<'_, 'gc>,
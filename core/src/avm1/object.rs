@@ -23,7 +23,7 @@ use crate::avm1::{Activation, Attribute, Error, ScriptObject, SoundObject, Stage
 use crate::bitmap::bitmap_data::BitmapDataWrapper;
 use crate::display_object::DisplayObject;
 use crate::display_object::TDisplayObject;
-use crate::html::TextFormat;
+use crate::html::{StyleSheet, TextFormat};
 use crate::streams::NetStream;
 use crate::string::AvmString;
 use crate::xml::XmlNode;
@@ -63,6 +63,7 @@ pub enum NativeObject<'gc> {
     XmlNode(XmlNode<'gc>),
     SharedObject(GcCell<'gc, SharedObject>),
     XmlSocket(XmlSocket<'gc>),
+    StyleSheet(Gc<'gc, RefCell<StyleSheet>>),
     FileReference(FileReferenceObject<'gc>),
 }
 
@@ -190,6 +190,10 @@ pub struct Avm2<'gc> {
     pub debug_output: bool,
 
     pub optimizer_enabled: bool,
+
+    /// Whether an `Event.PASTE` is being dispatched.
+    /// Outside of it, Flash Player doesn't let movies read `Clipboard.generalClipboard`.
+    pub is_in_paste_event: bool,
 }
 
 impl<'gc> Avm2<'gc> {
@@ -261,6 +265,7 @@ impl<'gc> Avm2<'gc> {
             debug_output: false,
 
             optimizer_enabled: true,
+            is_in_paste_event: false,
         }
     }
 
@@ -1,12 +1,14 @@
 //! `flash` namespace
 
 pub mod crypto;
+pub mod desktop;
 pub mod display;
 #[allow(non_snake_case)]
 pub mod display3D;
 pub mod events;
 pub mod external;
 pub mod geom;
+pub mod globalization;
 pub mod media;
 pub mod net;
 pub mod system;
@@ -1,11 +1,16 @@
 package flash.desktop {
-    import __ruffle__.stub_getter;
     import __ruffle__.stub_method;
-    import flash.system.System;
 
     public class Clipboard {
         private static var _generalClipboard = new Clipboard();
 
+        // Handlers registered with `setDataHandler`, keyed by format.
+        // Each handler is called the first time its data is requested.
+        // Handlers stay here and are never passed to the UiBackend, so other
+        // applications can't see deferred data until this movie reads it:
+        // only then is the result written to the system clipboard.
+        private var _handlers:Object = {};
+
         public static function get generalClipboard(): Clipboard {
             return Clipboard._generalClipboard;
         }
@@ -14,41 +19,78 @@ package flash.desktop {
             // TODO: This should only be callable in AIR
         }
 
+        private static function isSupportedFormat(format: String): Boolean {
+            return format == ClipboardFormats.TEXT_FORMAT ||
+                format == ClipboardFormats.HTML_FORMAT ||
+                format == ClipboardFormats.RICH_TEXT_FORMAT ||
+                format == ClipboardFormats.URL_FORMAT;
+        }
+
+        private native function nativeCheckReadable(): void;
+        private native function nativeFormats(): Array;
+        private native function nativeGetData(format: String): Object;
+        private native function nativeSetData(format: String, data: String): Boolean;
+        private native function nativeClearData(format: String): void;
+        private native function nativeClear(): void;
+
         public function get formats(): Array {
-            stub_getter("flash.desktop.Clipboard", "formats");
-            return new Array();
+            this.nativeCheckReadable();
+            var formats:Array = this.nativeFormats();
+            for (var format:String in this._handlers) {
+                if (formats.indexOf(format) == -1) {
+                    formats.push(format);
+                }
+            }
+            return formats;
         }
 
         public function clear(): void {
-            stub_method("flash.desktop.Clipboard", "clear");
+            this._handlers = {};
+            this.nativeClear();
         }
 
         public function clearData(format: String): void {
-            stub_method("flash.desktop.Clipboard", "clearData");
+            delete this._handlers[format];
+            this.nativeClearData(format);
         }
 
         public function getData(format: String, transferMode: String = ClipboardTransferMode.ORIGINAL_PREFERRED): Object {
-            stub_method("flash.desktop.Clipboard", "getData");
-            return null;
+            this.nativeCheckReadable();
+            if (this._handlers.hasOwnProperty(format)) {
+                var handler:Function = this._handlers[format];
+                delete this._handlers[format];
+
+                var data:Object = handler();
+                if (data != null) {
+                    this.nativeSetData(format, String(data));
+                }
+                return data;
+            }
+            return this.nativeGetData(format);
         }
 
         public function hasFormat(format: String): Boolean {
-            stub_method("flash.desktop.Clipboard", "hasFormat");
-            return false;
+            this.nativeCheckReadable();
+            return this._handlers.hasOwnProperty(format) || this.nativeFormats().indexOf(format) != -1;
         }
 
         public function setData(format: String, data: Object, serializable: Boolean = true): Boolean {
-            stub_method("flash.desktop.Clipboard", "setData");
-            if (format == ClipboardFormats.TEXT_FORMAT) {
-                System.setClipboard(data);
-                return true;
+            if (!isSupportedFormat(format)) {
+                stub_method("flash.desktop.Clipboard", "setData", "with non-text formats");
+                return false;
             }
-            return false;
+            delete this._handlers[format];
+            return this.nativeSetData(format, String(data));
         }
 
         public function setDataHandler(format: String, handler: Function, serializable: Boolean = true): Boolean {
-            stub_method("flash.desktop.Clipboard", "setDataHandler");
-            return false;
+            if (!isSupportedFormat(format)) {
+                stub_method("flash.desktop.Clipboard", "setDataHandler", "with non-text formats");
+                return false;
+            }
+            this.nativeClearData(format);
+            this._handlers[format] = handler;
+            return true;
         }
     }
 }
@@ -0,0 +1,155 @@
+//! `flash.desktop.Clipboard` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::security_error;
+use crate::avm2::object::{ArrayObject, Object};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{ArrayStorage, Error};
+use crate::backend::ui::{ClipboardFormat, UiBackend};
+use crate::string::{AvmString, WStr};
+use crate::PlayerRuntime;
+
+fn format_from_name(name: &WStr) -> Option<ClipboardFormat> {
+    if name == b"air:text" {
+        Some(ClipboardFormat::Text)
+    } else if name == b"air:html" {
+        Some(ClipboardFormat::Html)
+    } else if name == b"air:rtf" {
+        Some(ClipboardFormat::RichText)
+    } else if name == b"air:url" {
+        Some(ClipboardFormat::Url)
+    } else {
+        None
+    }
+}
+
+fn format_name(format: ClipboardFormat) -> &'static str {
+    match format {
+        ClipboardFormat::Text => "air:text",
+        ClipboardFormat::Html => "air:html",
+        ClipboardFormat::RichText => "air:rtf",
+        ClipboardFormat::Url => "air:url",
+    }
+}
+
+/// Read the whole clipboard, in every format it is available in.
+fn clipboard_content(ui: &mut dyn UiBackend) -> Vec<(ClipboardFormat, String)> {
+    ui.clipboard_formats()
+        .into_iter()
+        .filter_map(|format| Some((format, ui.clipboard_content_in_format(format)?)))
+        .collect()
+}
+
+/// Implements `Clipboard.nativeCheckReadable`
+///
+/// Outside of AIR, the clipboard may only be read while handling a paste event,
+/// so that movies can't take its contents without the user pasting.
+pub fn native_check_readable<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if activation.avm2().player_runtime == PlayerRuntime::FlashPlayer
+        && !activation.avm2().is_in_paste_event
+    {
+        return Err(Error::AvmError(security_error(
+            activation,
+            "Error #2179: The Clipboard.generalClipboard object may only be read while processing a flash.events.Event.PASTE event.",
+            2179,
+        )?));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Clipboard.nativeFormats`
+pub fn native_formats<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let formats: Vec<Value<'gc>> = activation
+        .context
+        .ui
+        .clipboard_formats()
+        .into_iter()
+        .map(|format| format_name(format).into())
+        .collect();
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&formats))?.into())
+}
+
+/// Implements `Clipboard.nativeGetData`
+pub fn native_get_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(format) = format_from_name(&args.get_string(activation, 0)?) else {
+        return Ok(Value::Null);
+    };
+
+    Ok(
+        match activation.context.ui.clipboard_content_in_format(format) {
+            Some(content) => AvmString::new_utf8(activation.context.gc_context, content).into(),
+            None => Value::Null,
+        },
+    )
+}
+
+/// Implements `Clipboard.nativeSetData`
+pub fn native_set_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(format) = format_from_name(&args.get_string(activation, 0)?) else {
+        return Ok(false.into());
+    };
+    let data = args.get_string(activation, 1)?.to_string();
+
+    let mut content = clipboard_content(activation.context.ui);
+    content.retain(|(existing_format, _)| *existing_format != format);
+    content.push((format, data));
+    activation
+        .context
+        .ui
+        .set_clipboard_content_in_formats(content);
+
+    Ok(true.into())
+}
+
+/// Implements `Clipboard.nativeClearData`
+pub fn native_clear_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(format) = format_from_name(&args.get_string(activation, 0)?) else {
+        return Ok(Value::Undefined);
+    };
+
+    let mut content = clipboard_content(activation.context.ui);
+    content.retain(|(existing_format, _)| *existing_format != format);
+    activation
+        .context
+        .ui
+        .set_clipboard_content_in_formats(content);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Clipboard.nativeClear`
+pub fn native_clear<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation
+        .context
+        .ui
+        .set_clipboard_content_in_formats(Vec::new());
+
+    Ok(Value::Undefined)
+}
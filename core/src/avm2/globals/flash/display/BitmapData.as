@@ -69,6 +69,30 @@ package flash.display {
         public native function merge(
             sourceBitmapData:BitmapData, sourceRect:Rectangle, destPoint:Point, redMultiplier:uint, greenMultiplier:uint, blueMultiplier:uint, alphaMultiplier:uint
         ):void 
+        public native function histogram(hRect:Rectangle = null):Vector.<Vector.<Number>>;
+
+        public function encode(rect:Rectangle, compressor:Object, byteArray:ByteArray = null):ByteArray {
+            if (byteArray == null) {
+                byteArray = new ByteArray();
+            }
+            if (compressor is PNGEncoderOptions) {
+                this.encodePNG(rect, compressor.fastCompression, byteArray);
+            } else if (compressor is JPEGEncoderOptions) {
+                this.encodeJPEG(rect, compressor.quality, byteArray);
+            } else if (compressor is JPEGXREncoderOptions) {
+                // FIXME - There's no JPEG-XR encoder available to us (the jpegxr crate can only decode).
+                // Throw rather than return an empty ByteArray that the caller would take as an image.
+                stub_method("flash.display.BitmapData", "encode", "with JPEGXREncoderOptions");
+                throw new Error("Encoding a BitmapData with JPEGXREncoderOptions is not supported");
+            } else {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            return byteArray;
+        }
+
+        private native function encodePNG(rect:Rectangle, fastCompression:Boolean, byteArray:ByteArray):void;
+        private native function encodeJPEG(rect:Rectangle, quality:uint, byteArray:ByteArray):void;
+
         public function generateFilterRect(sourceRect:Rectangle, filter:BitmapFilter):Rectangle {
             // Flash always reports that a ShaderFilter affects the entire BitampData, ignoring SourceRect.
             if (filter is ShaderFilter) {
@@ -12,7 +12,7 @@ use crate::avm2::vector::VectorStorage;
 use crate::avm2::Error;
 use crate::avm2_stub_method;
 use crate::bitmap::bitmap_data::{
-    BitmapData, BitmapDataWrapper, ChannelOptions, ThresholdOperation,
+    BitmapData, BitmapDataWrapper, ChannelOptions, ImageEncoding, ThresholdOperation,
 };
 use crate::bitmap::bitmap_data::{BitmapDataDrawError, IBitmapDrawable};
 use crate::bitmap::{is_size_valid, operations};
@@ -383,6 +383,106 @@ pub fn get_vector<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `BitmapData.histogram`.
+pub fn histogram<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.as_bitmap_data() {
+        bitmap_data.check_valid(activation)?;
+        let (x, y, width, height) = match args.try_get_object(activation, 0) {
+            Some(rectangle) => get_rectangle_x_y_width_height(activation, rectangle)?,
+            None => (
+                0,
+                0,
+                bitmap_data.width() as i32,
+                bitmap_data.height() as i32,
+            ),
+        };
+
+        let counts = operations::histogram(
+            bitmap_data,
+            activation.context.renderer,
+            x,
+            y,
+            width,
+            height,
+        );
+
+        let number_class = activation.avm2().classes().number;
+        let mut channels = Vec::with_capacity(counts.len());
+        for channel in counts {
+            let values = channel.into_iter().map(Value::from).collect();
+            let storage = VectorStorage::from_values(values, false, Some(number_class));
+            channels.push(VectorObject::from_vector(storage, activation)?.into());
+        }
+
+        let vector_class = activation.avm2().classes().generic_vector;
+        let channel_class = vector_class.apply(activation, &[number_class.into()])?;
+        let storage = VectorStorage::from_values(channels, false, Some(channel_class));
+
+        return Ok(VectorObject::from_vector(storage, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements the PNG case of `BitmapData.encode`.
+pub fn encode_png<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let fast_compression = args.get_bool(1);
+    encode(
+        activation,
+        this,
+        args,
+        ImageEncoding::Png { fast_compression },
+    )
+}
+
+/// Implements the JPEG case of `BitmapData.encode`.
+pub fn encode_jpeg<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let quality = args.get_u32(activation, 1)?.min(100) as u8;
+    encode(activation, this, args, ImageEncoding::Jpeg { quality })
+}
+
+fn encode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+    encoding: ImageEncoding,
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.as_bitmap_data() {
+        bitmap_data.check_valid(activation)?;
+        let rectangle = args.get_object(activation, 0, "rect")?;
+        let (x, y, width, height) = get_rectangle_x_y_width_height(activation, rectangle)?;
+        let byte_array = args.get_object(activation, 2, "byteArray")?;
+        let mut storage = byte_array
+            .as_bytearray_mut(activation.context.gc_context)
+            .unwrap();
+
+        operations::encode(
+            activation,
+            bitmap_data,
+            x,
+            y,
+            width,
+            height,
+            encoding,
+            &mut storage,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Implements `BitmapData.getPixel`.
 pub fn get_pixel<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -0,0 +1,32 @@
+package flash.globalization {
+    import flash.globalization.LastOperationStatus;
+    import flash.globalization.LocaleID;
+
+    // Returns the name of the bundled locale that best matches the requested one.
+    internal native function resolveLocaleIDName(requestedLocaleIDName:String):String;
+
+    // Returns an object describing the formatting conventions of a bundled locale.
+    internal native function getLocaleData(localeIDName:String):Object;
+
+    internal native function availableLocaleIDNames():Array;
+
+    internal native function formatDateTime(dateTime:Date, pattern:String, localeIDName:String, utc:Boolean):String;
+
+    internal function throwNonNull(name:String):void {
+        throw new TypeError("Error #2007: Parameter " + name + " must be non-null.", 2007);
+    }
+
+    // The status of a formatter after resolving its locale.
+    internal function localeStatus(requestedLocaleIDName:String, actualLocaleIDName:String):String {
+        if (requestedLocaleIDName == LocaleID.DEFAULT) {
+            return LastOperationStatus.USING_DEFAULT_WARNING;
+        }
+
+        var normalized:String = requestedLocaleIDName.split("_").join("-").split("@")[0];
+        if (normalized.toLowerCase() != actualLocaleIDName.toLowerCase()) {
+            return LastOperationStatus.USING_FALLBACK_WARNING;
+        }
+
+        return LastOperationStatus.NO_ERROR;
+    }
+}
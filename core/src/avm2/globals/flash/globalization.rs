@@ -0,0 +1,186 @@
+//! `flash.globalization` namespace
+
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{Activation, Error};
+use crate::locale::{self, LocaleData, NumberFormat};
+use crate::string::AvmString;
+use chrono::FixedOffset;
+
+pub mod collator;
+pub mod currency_formatter;
+pub mod number_formatter;
+pub mod string_tools;
+
+/// Picks the bundled locale for a requested locale ID,
+/// using the user's language for `LocaleID.DEFAULT`.
+fn resolve<'gc>(activation: &mut Activation<'_, 'gc>, requested: &str) -> &'static LocaleData {
+    let default_locale = activation.context.ui.language().to_string();
+    locale::resolve_locale(requested, &default_locale)
+}
+
+/// Implements `flash.globalization.resolveLocaleIDName`
+pub fn resolve_locale_id_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string(activation, 0)?.to_utf8_lossy().to_string();
+    let locale = resolve(activation, &requested);
+    Ok(AvmString::new_utf8(activation.context.gc_context, locale.name).into())
+}
+
+/// Implements `flash.globalization.getLocaleData`
+pub fn get_locale_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?.to_utf8_lossy().to_string();
+    let locale = resolve(activation, &name);
+
+    let result = activation
+        .avm2()
+        .classes()
+        .object
+        .construct(activation, &[])?;
+
+    let strings = [
+        ("name", locale.name),
+        ("decimalSeparator", locale.decimal_separator),
+        ("groupingSeparator", locale.grouping_separator),
+        ("groupingPattern", locale.grouping_pattern),
+        ("currencyISOCode", locale.currency_iso_code),
+        ("currencySymbol", locale.currency_symbol),
+        ("longDatePattern", locale.date_patterns[0]),
+        ("mediumDatePattern", locale.date_patterns[1]),
+        ("shortDatePattern", locale.date_patterns[2]),
+        ("longTimePattern", locale.time_patterns[0]),
+        ("mediumTimePattern", locale.time_patterns[1]),
+        ("shortTimePattern", locale.time_patterns[2]),
+    ];
+    for (name, value) in strings {
+        let value = AvmString::new_utf8(activation.context.gc_context, value);
+        result.set_public_property(name, value.into(), activation)?;
+    }
+
+    let numbers = [
+        ("negativeNumberFormat", locale.negative_number_format),
+        (
+            "currencyFractionalDigits",
+            locale.currency_fractional_digits,
+        ),
+        ("positiveCurrencyFormat", locale.positive_currency_format),
+        ("negativeCurrencyFormat", locale.negative_currency_format),
+        ("firstWeekday", locale.first_weekday),
+    ];
+    for (name, value) in numbers {
+        result.set_public_property(name, value.into(), activation)?;
+    }
+
+    let names: [(&str, &[&str]); 6] = [
+        ("monthNames", &locale.month_names),
+        ("formatMonthNames", locale.format_month_names()),
+        ("monthAbbreviations", &locale.month_abbreviations),
+        ("weekdayNames", &locale.weekday_names),
+        ("weekdayAbbreviations", &locale.weekday_abbreviations),
+        ("amPm", &locale.am_pm),
+    ];
+    for (name, values) in names {
+        let array = string_array(activation, values)?;
+        result.set_public_property(name, array.into(), activation)?;
+    }
+
+    Ok(result.into())
+}
+
+/// Implements `flash.globalization.availableLocaleIDNames`
+pub fn available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names: Vec<&str> = locale::LOCALES.iter().map(|locale| locale.name).collect();
+    Ok(string_array(activation, &names)?.into())
+}
+
+/// Implements `flash.globalization.formatDateTime`
+pub fn format_date_time<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let date = args.get_object(activation, 0, "dateTime")?;
+    let pattern = args.get_string(activation, 1)?.to_utf8_lossy().to_string();
+    let name = args.get_string(activation, 2)?.to_utf8_lossy().to_string();
+    let utc = args.get_bool(3);
+
+    let Some(date_time) = date.as_date_object().and_then(|date| date.date_time()) else {
+        return Ok(activation.context.interner.empty().into());
+    };
+    let date_time = if utc {
+        date_time.with_timezone(&FixedOffset::east_opt(0).expect("UTC is a valid offset"))
+    } else {
+        date_time.with_timezone(&locale::get_timezone())
+    };
+
+    let locale = resolve(activation, &name);
+    let formatted = locale::format_date_time(locale, &pattern, &date_time);
+    Ok(AvmString::new_utf8(activation.context.gc_context, formatted).into())
+}
+
+/// Reads the number formatting options from a `NumberFormatter` or `CurrencyFormatter`.
+pub fn number_format_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    formatter: Object<'gc>,
+) -> Result<NumberFormat, Error<'gc>> {
+    let mut get_string = |name: &'static str| -> Result<String, Error<'gc>> {
+        Ok(formatter
+            .get_public_property(name, activation)?
+            .coerce_to_string(activation)?
+            .to_utf8_lossy()
+            .to_string())
+    };
+    let decimal_separator = get_string("decimalSeparator")?;
+    let grouping_separator = get_string("groupingSeparator")?;
+    let grouping_pattern = get_string("groupingPattern")?;
+    let negative_symbol = get_string("negativeSymbol")?;
+
+    let fractional_digits = formatter
+        .get_public_property("fractionalDigits", activation)?
+        .coerce_to_i32(activation)?
+        .max(0) as u32;
+    let leading_zero = formatter
+        .get_public_property("leadingZero", activation)?
+        .coerce_to_boolean();
+    let trailing_zeros = formatter
+        .get_public_property("trailingZeros", activation)?
+        .coerce_to_boolean();
+    let use_grouping = formatter
+        .get_public_property("useGrouping", activation)?
+        .coerce_to_boolean();
+
+    Ok(NumberFormat {
+        decimal_separator,
+        grouping_separator,
+        grouping_pattern,
+        fractional_digits,
+        leading_zero,
+        trailing_zeros,
+        use_grouping,
+        negative_symbol,
+    })
+}
+
+fn string_array<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    values: &[&str],
+) -> Result<Object<'gc>, Error<'gc>> {
+    let values: Vec<Value<'gc>> = values
+        .iter()
+        .map(|value| AvmString::new_utf8(activation.context.gc_context, *value).into())
+        .collect();
+    ArrayObject::from_storage(activation, ArrayStorage::from_args(&values))
+}
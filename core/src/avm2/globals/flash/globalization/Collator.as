@@ -0,0 +1,114 @@
+package flash.globalization {
+    import flash.globalization.CollatorMode;
+    import flash.globalization.LastOperationStatus;
+
+    public final class Collator {
+        private var _actualLocaleIDName:String;
+        private var _ignoreCase:Boolean;
+        private var _ignoreCharacterWidth:Boolean;
+        private var _ignoreDiacritics:Boolean;
+        private var _ignoreKanaType:Boolean;
+        private var _ignoreSymbols:Boolean;
+        private var _lastOperationStatus:String;
+        private var _numericComparison:Boolean = false;
+        private var _requestedLocaleIDName:String;
+
+        public function Collator(requestedLocaleIDName:String, initialMode:String = CollatorMode.SORTING) {
+            if (requestedLocaleIDName == null) throwNonNull("requestedLocaleIDName");
+            if (initialMode == null) throwNonNull("initialMode");
+            if (initialMode != CollatorMode.SORTING && initialMode != CollatorMode.MATCHING) {
+                throw new ArgumentError("Error #2008: Parameter initialMode must be one of the accepted values.", 2008);
+            }
+
+            this._requestedLocaleIDName = requestedLocaleIDName;
+            this._actualLocaleIDName = resolveLocaleIDName(requestedLocaleIDName);
+            this._lastOperationStatus = localeStatus(requestedLocaleIDName, this._actualLocaleIDName);
+
+            // Matching ignores every difference that sorting takes into account.
+            var matching:Boolean = initialMode == CollatorMode.MATCHING;
+            this._ignoreCase = matching;
+            this._ignoreCharacterWidth = matching;
+            this._ignoreDiacritics = matching;
+            this._ignoreKanaType = matching;
+            this._ignoreSymbols = matching;
+        }
+
+        public function get actualLocaleIDName():String {
+            return this._actualLocaleIDName;
+        }
+
+        public function get ignoreCase():Boolean {
+            return this._ignoreCase;
+        }
+        public function set ignoreCase(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._ignoreCase = value;
+        }
+
+        public function get ignoreCharacterWidth():Boolean {
+            return this._ignoreCharacterWidth;
+        }
+        public function set ignoreCharacterWidth(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._ignoreCharacterWidth = value;
+        }
+
+        public function get ignoreDiacritics():Boolean {
+            return this._ignoreDiacritics;
+        }
+        public function set ignoreDiacritics(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._ignoreDiacritics = value;
+        }
+
+        public function get ignoreKanaType():Boolean {
+            return this._ignoreKanaType;
+        }
+        public function set ignoreKanaType(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._ignoreKanaType = value;
+        }
+
+        public function get ignoreSymbols():Boolean {
+            return this._ignoreSymbols;
+        }
+        public function set ignoreSymbols(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._ignoreSymbols = value;
+        }
+
+        public function get lastOperationStatus():String {
+            return this._lastOperationStatus;
+        }
+
+        public function get numericComparison():Boolean {
+            return this._numericComparison;
+        }
+        public function set numericComparison(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._numericComparison = value;
+        }
+
+        public function get requestedLocaleIDName():String {
+            return this._requestedLocaleIDName;
+        }
+
+        // Returns -1, 0 or 1.
+        private native function innerCompare(string1:String, string2:String):int;
+
+        public function compare(string1:String, string2:String):int {
+            if (string1 == null) throwNonNull("string1");
+            if (string2 == null) throwNonNull("string2");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this.innerCompare(string1, string2);
+        }
+
+        public function equals(string1:String, string2:String):Boolean {
+            return this.compare(string1, string2) == 0;
+        }
+
+        public static function getAvailableLocaleIDNames():Vector.<String> {
+            return Vector.<String>(availableLocaleIDNames());
+        }
+    }
+}
@@ -1,18 +1,199 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_method;
+    import flash.globalization.CurrencyParseResult;
+    import flash.globalization.LastOperationStatus;
+    import flash.globalization.NationalDigitsType;
 
     public final class CurrencyFormatter {
+        private var _actualLocaleIDName:String;
+        private var _currencyISOCode:String;
+        private var _currencySymbol:String;
+        private var _decimalSeparator:String;
+        private var _digitsType:uint = NationalDigitsType.EUROPEAN;
+        private var _fractionalDigits:int;
+        private var _groupingPattern:String;
+        private var _groupingSeparator:String;
+        private var _lastOperationStatus:String;
+        private var _leadingZero:Boolean = true;
+        private var _negativeCurrencyFormat:uint;
+        private var _negativeSymbol:String = "-";
+        private var _positiveCurrencyFormat:uint;
+        private var _requestedLocaleIDName:String;
+        private var _trailingZeros:Boolean = true;
+        private var _useGrouping:Boolean = true;
 
-        public var fractionalDigits: int = 0;
+        public function CurrencyFormatter(requestedLocaleIDName:String) {
+            if (requestedLocaleIDName == null) throwNonNull("requestedLocaleIDName");
+            this._requestedLocaleIDName = requestedLocaleIDName;
+            this._actualLocaleIDName = resolveLocaleIDName(requestedLocaleIDName);
+            this._lastOperationStatus = localeStatus(requestedLocaleIDName, this._actualLocaleIDName);
 
-        public function CurrencyFormatter(locale: String) {
-            stub_constructor("flash.globalization.CurrencyFormatter");
+            var data:Object = getLocaleData(this._actualLocaleIDName);
+            this._currencyISOCode = data.currencyISOCode;
+            this._currencySymbol = data.currencySymbol;
+            this._decimalSeparator = data.decimalSeparator;
+            this._fractionalDigits = data.currencyFractionalDigits;
+            this._groupingPattern = data.groupingPattern;
+            this._groupingSeparator = data.groupingSeparator;
+            this._negativeCurrencyFormat = data.negativeCurrencyFormat;
+            this._positiveCurrencyFormat = data.positiveCurrencyFormat;
         }
 
+        public function get actualLocaleIDName():String {
+            return this._actualLocaleIDName;
+        }
+
+        public function get currencyISOCode():String {
+            return this._currencyISOCode;
+        }
+
+        public function get currencySymbol():String {
+            return this._currencySymbol;
+        }
+
+        public function get decimalSeparator():String {
+            return this._decimalSeparator;
+        }
+        public function set decimalSeparator(value:String):void {
+            if (value == null) throwNonNull("decimalSeparator");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._decimalSeparator = value;
+        }
+
+        public function get digitsType():uint {
+            return this._digitsType;
+        }
+        public function set digitsType(value:uint):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._digitsType = value;
+        }
+
+        public function get fractionalDigits():int {
+            return this._fractionalDigits;
+        }
+        public function set fractionalDigits(value:int):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._fractionalDigits = value;
+        }
+
+        public function get groupingPattern():String {
+            return this._groupingPattern;
+        }
+        public function set groupingPattern(value:String):void {
+            if (value == null) throwNonNull("groupingPattern");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._groupingPattern = value;
+        }
+
+        public function get groupingSeparator():String {
+            return this._groupingSeparator;
+        }
+        public function set groupingSeparator(value:String):void {
+            if (value == null) throwNonNull("groupingSeparator");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._groupingSeparator = value;
+        }
+
+        public function get lastOperationStatus():String {
+            return this._lastOperationStatus;
+        }
+
+        public function get leadingZero():Boolean {
+            return this._leadingZero;
+        }
+        public function set leadingZero(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._leadingZero = value;
+        }
+
+        public function get negativeCurrencyFormat():uint {
+            return this._negativeCurrencyFormat;
+        }
+        public function set negativeCurrencyFormat(value:uint):void {
+            if (value > 15) {
+                throw new ArgumentError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._negativeCurrencyFormat = value;
+        }
+
+        public function get negativeSymbol():String {
+            return this._negativeSymbol;
+        }
+        public function set negativeSymbol(value:String):void {
+            if (value == null) throwNonNull("negativeSymbol");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._negativeSymbol = value;
+        }
+
+        public function get positiveCurrencyFormat():uint {
+            return this._positiveCurrencyFormat;
+        }
+        public function set positiveCurrencyFormat(value:uint):void {
+            if (value > 3) {
+                throw new ArgumentError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._positiveCurrencyFormat = value;
+        }
+
+        public function get requestedLocaleIDName():String {
+            return this._requestedLocaleIDName;
+        }
+
+        public function get trailingZeros():Boolean {
+            return this._trailingZeros;
+        }
+        public function set trailingZeros(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._trailingZeros = value;
+        }
+
+        public function get useGrouping():Boolean {
+            return this._useGrouping;
+        }
+        public function set useGrouping(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._useGrouping = value;
+        }
+
+        private native function innerFormat(value:Number, withCurrencySymbol:Boolean):String;
+
+        // Returns `[value, currencyString]`, or `null` if the string isn't an amount of currency.
+        private native function innerParse(inputString:String):Array;
+
         public function format(value:Number, withCurrencySymbol:Boolean = false):String {
-            stub_method("flash.globalization.CurrencyFormatter", "format");
-            return value.toString();
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this.innerFormat(value, withCurrencySymbol);
+        }
+
+        public function formattingWithCurrencySymbolIsSafe(requestedISOCode:String):Boolean {
+            if (requestedISOCode == null) throwNonNull("requestedISOCode");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return requestedISOCode.toUpperCase() == this._currencyISOCode.toUpperCase();
+        }
+
+        public static function getAvailableLocaleIDNames():Vector.<String> {
+            return Vector.<String>(availableLocaleIDNames());
+        }
+
+        public function parse(inputString:String):CurrencyParseResult {
+            if (inputString == null) throwNonNull("inputString");
+            var result:Array = this.innerParse(inputString);
+            if (result == null) {
+                this._lastOperationStatus = LastOperationStatus.PARSE_ERROR;
+                return new CurrencyParseResult();
+            }
+
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return new CurrencyParseResult(result[0], result[1]);
+        }
+
+        public function setCurrency(currencyISOCode:String, currencySymbol:String):void {
+            if (currencyISOCode == null) throwNonNull("currencyISOCode");
+            if (currencySymbol == null) throwNonNull("currencySymbol");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._currencyISOCode = currencyISOCode;
+            this._currencySymbol = currencySymbol;
         }
     }
 }
@@ -1,56 +1,53 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
+    import flash.globalization.DateTimeNameContext;
+    import flash.globalization.DateTimeNameStyle;
+    import flash.globalization.DateTimeStyle;
     import flash.globalization.LastOperationStatus;
 
     public final class DateTimeFormatter {
+        private var _actualLocaleIDName:String;
         private var _dateStyle:String;
         private var _dateTimePattern:String;
-        private var _localeIDName:String;
+        private var _lastOperationStatus:String;
+        private var _localeData:Object;
+        private var _requestedLocaleIDName:String;
         private var _timeStyle:String;
 
-        private static function throwNonNull(name: String) {
-            throw new TypeError("Error #2007: Parameter " + name + " must be non-null.", 2007);
-        }
-
         public function DateTimeFormatter(requestedLocaleIDName:String, dateStyle:String = "long", timeStyle:String = "long") {
-            stub_constructor("flash.globalization.DateTimeFormatter");
             if (requestedLocaleIDName == null) throwNonNull("requestedLocaleIDName");
-            this._localeIDName = requestedLocaleIDName;
-            this._dateTimePattern = "EEEE, MMMM d, yyyy h:mm:ss a";
+            this._requestedLocaleIDName = requestedLocaleIDName;
+            this._actualLocaleIDName = resolveLocaleIDName(requestedLocaleIDName);
+            this._localeData = getLocaleData(this._actualLocaleIDName);
             this.setDateTimeStyles(dateStyle, timeStyle);
+            this._lastOperationStatus = localeStatus(requestedLocaleIDName, this._actualLocaleIDName);
         }
 
         public function get actualLocaleIDName():String {
-            stub_getter("flash.globalization.DateTimeFormatter", "actualLocaleIDName");
-            return this._localeIDName;
+            return this._actualLocaleIDName;
         }
 
         public function get lastOperationStatus():String {
-            stub_getter("flash.globalization.DateTimeFormatter", "lastOperationStatus");
-            return LastOperationStatus.NO_ERROR;
+            return this._lastOperationStatus;
         }
 
         public function get requestedLocaleIDName():String {
-            return this._localeIDName;
+            return this._requestedLocaleIDName;
         }
 
         public function format(dateTime:Date):String {
-            stub_method("flash.globalization.DateTimeFormatter", "format");
             if (dateTime == null) throwNonNull("dateTime");
-            return dateTime.toString();
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return formatDateTime(dateTime, this._dateTimePattern, this._actualLocaleIDName, false);
         }
 
         public function formatUTC(dateTime:Date):String {
-            stub_method("flash.globalization.DateTimeFormatter", "formatUTC");
             if (dateTime == null) throwNonNull("dateTime");
-            return dateTime.toUTCString();
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return formatDateTime(dateTime, this._dateTimePattern, this._actualLocaleIDName, true);
         }
 
         public static function getAvailableLocaleIDNames():Vector.<String> {
-            stub_method("flash.globalization.DateTimeFormatter", "getAvailableLocaleIDNames");
-            return new <String>["en-US"];
+            return Vector.<String>(availableLocaleIDNames());
         }
 
         public function getDateStyle():String {
@@ -62,15 +59,16 @@ package flash.globalization {
         }
 
         public function getFirstWeekday():int {
-            stub_method("flash.globalization.DateTimeFormatter", "getFirstWeekday");
-            return 0;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._localeData.firstWeekday;
         }
 
         public function getMonthNames(nameStyle:String = "full", context:String = "standalone"):Vector.<String> {
-            stub_method("flash.globalization.DateTimeFormatter", "getMonthNames");
             if (nameStyle == null) throwNonNull("nameStyle");
             if (context == null) throwNonNull("context");
-            return new <String>["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"];
+
+            var full:Array = context == DateTimeNameContext.FORMAT ? this._localeData.formatMonthNames : this._localeData.monthNames;
+            return this.getNames(full, this._localeData.monthAbbreviations, nameStyle, context);
         }
 
         public function getTimeStyle():String {
@@ -78,24 +76,75 @@ package flash.globalization {
         }
 
         public function getWeekdayNames(nameStyle:String = "full", context:String = "standalone"):Vector.<String> {
-            stub_method("flash.globalization.DateTimeFormatter", "getWeekdayNames");
             if (nameStyle == null) throwNonNull("nameStyle");
             if (context == null) throwNonNull("context");
-            return new <String>["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
+
+            return this.getNames(this._localeData.weekdayNames, this._localeData.weekdayAbbreviations, nameStyle, context);
+        }
+
+        private function getNames(full:Array, abbreviations:Array, nameStyle:String, context:String):Vector.<String> {
+            if (context != DateTimeNameContext.FORMAT && context != DateTimeNameContext.STANDALONE) {
+                throw new ArgumentError("Error #2008: Parameter context must be one of the accepted values.", 2008);
+            }
+
+            var names:Vector.<String> = new Vector.<String>();
+            switch (nameStyle) {
+                case DateTimeNameStyle.FULL:
+                    names = Vector.<String>(full);
+                    break;
+                case DateTimeNameStyle.LONG_ABBREVIATION:
+                    names = Vector.<String>(abbreviations);
+                    break;
+                case DateTimeNameStyle.SHORT_ABBREVIATION:
+                    for each (var name:String in full) {
+                        names.push(name.charAt(0));
+                    }
+                    break;
+                default:
+                    throw new ArgumentError("Error #2008: Parameter nameStyle must be one of the accepted values.", 2008);
+            }
+
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return names;
         }
 
         public function setDateTimePattern(pattern:String):void {
-            stub_method("flash.globalization.DateTimeFormatter", "setDateTimePattern");
             if (pattern == null) throwNonNull("pattern");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._dateTimePattern = pattern;
+            this._dateStyle = DateTimeStyle.CUSTOM;
+            this._timeStyle = DateTimeStyle.CUSTOM;
         }
 
         public function setDateTimeStyles(dateStyle:String, timeStyle:String):void {
-            stub_method("flash.globalization.DateTimeFormatter", "setDateTimeStyles");
             if (dateStyle == null) throwNonNull("dateStyle");
             if (timeStyle == null) throwNonNull("timeStyle");
+
+            var datePattern:String = this.stylePattern(dateStyle, "DatePattern", "dateStyle");
+            var timePattern:String = this.stylePattern(timeStyle, "TimePattern", "timeStyle");
+
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._dateStyle = dateStyle;
             this._timeStyle = timeStyle;
+            if (datePattern != "" && timePattern != "") {
+                this._dateTimePattern = datePattern + " " + timePattern;
+            } else {
+                this._dateTimePattern = datePattern + timePattern;
+            }
+        }
+
+        // Looks up the locale's pattern for a date or time style.
+        private function stylePattern(style:String, kind:String, parameterName:String):String {
+            switch (style) {
+                case DateTimeStyle.LONG:
+                case DateTimeStyle.MEDIUM:
+                case DateTimeStyle.SHORT:
+                    return this._localeData[style + kind];
+                case DateTimeStyle.NONE:
+                    return "";
+                default:
+                    throw new ArgumentError("Error #2008: Parameter " + parameterName + " must be one of the accepted values.", 2008);
+            }
         }
     }
-}
\ No newline at end of file
+}
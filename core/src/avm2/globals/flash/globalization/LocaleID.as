@@ -1,22 +1,55 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
     import flash.globalization.LastOperationStatus;
 
     public final class LocaleID {
         public static const DEFAULT:String = "i-default";
 
+        // Languages and scripts that are written from right to left.
+        private static const RIGHT_TO_LEFT_LANGUAGES:Array = ["ar", "dv", "fa", "he", "iw", "ps", "syr", "ug", "ur", "yi"];
+        private static const RIGHT_TO_LEFT_SCRIPTS:Array = ["Arab", "Hebr", "Syrc", "Thaa"];
+
+        private var _keysAndValues:Object = {};
+        private var _language:String = "";
+        private var _lastOperationStatus:String = LastOperationStatus.NO_ERROR;
         private var _name:String;
+        private var _region:String = "";
+        private var _script:String = "";
+        private var _variant:String = "";
 
         public function LocaleID(name:String) {
-            stub_constructor("flash.globalization.LocaleID");
-            this._name = name;
+            if (name == null) throwNonNull("name");
+
+            // Names such as `de_DE@collation=phonebook;currency=EUR` are split into
+            // subtags and keywords. Underscores are accepted in place of hyphens.
+            var parts:Array = name.split("@");
+            var tags:Array = String(parts[0]).split("_").join("-").split("-");
+            this._name = tags.join("-") + (parts.length > 1 ? "@" + parts.slice(1).join("@") : "");
+
+            var index:int = 0;
+            this._language = String(tags[index++]).toLowerCase();
+            if (index < tags.length && /^[A-Za-z]{4}$/.test(tags[index])) {
+                var script:String = tags[index++];
+                this._script = script.charAt(0).toUpperCase() + script.substr(1).toLowerCase();
+            }
+            if (index < tags.length && /^([A-Za-z]{2}|[0-9]{3})$/.test(tags[index])) {
+                this._region = String(tags[index++]).toUpperCase();
+            }
+            if (index < tags.length) {
+                this._variant = tags.slice(index).join("-").toUpperCase();
+            }
+
+            if (parts.length > 1) {
+                for each (var keyword:String in String(parts[1]).split(";")) {
+                    var separator:int = keyword.indexOf("=");
+                    if (separator > 0) {
+                        this._keysAndValues[keyword.substr(0, separator)] = keyword.substr(separator + 1);
+                    }
+                }
+            }
         }
 
         public function get lastOperationStatus():String {
-            stub_getter("flash.globalization.LocaleID", "lastOperationStatus");
-            return LastOperationStatus.NO_ERROR;
+            return this._lastOperationStatus;
         }
 
         public function get name():String {
@@ -24,38 +57,72 @@ package flash.globalization {
         }
 
         public static function determinePreferredLocales(want:Vector.<String>, have:Vector.<String>, keyword:String = "userinterface"):Vector.<String> {
-            stub_method("flash.globalization.LocaleID", "determinePreferredLocales");
-            return null;
+            if (want == null) throwNonNull("want");
+            if (have == null) throwNonNull("have");
+
+            var result:Vector.<String> = new Vector.<String>();
+            for each (var wanted:String in want) {
+                var wantedID:LocaleID = new LocaleID(wanted);
+                var match:String = null;
+
+                // An exact match is preferred over one that only shares the language.
+                for each (var available:String in have) {
+                    if (new LocaleID(available).name.toLowerCase() == wantedID.name.toLowerCase()) {
+                        match = available;
+                        break;
+                    }
+                }
+                if (match == null) {
+                    for each (available in have) {
+                        if (new LocaleID(available).getLanguage() == wantedID.getLanguage()) {
+                            match = available;
+                            break;
+                        }
+                    }
+                }
+
+                if (match != null && result.indexOf(match) == -1) {
+                    result.push(match);
+                }
+            }
+            return result;
         }
 
         public function getKeysAndValues():Object {
-            stub_method("flash.globalization.LocaleID", "getKeysAndValues");
-            return new Object();
+            var result:Object = {};
+            for (var key:String in this._keysAndValues) {
+                result[key] = this._keysAndValues[key];
+            }
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return result;
         }
 
         public function getLanguage():String {
-            stub_method("flash.globalization.LocaleID", "getLanguage");
-            return this._name;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._language;
         }
 
         public function getRegion():String {
-            stub_method("flash.globalization.LocaleID", "getRegion");
-            return "";
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._region;
         }
 
         public function getScript():String {
-            stub_method("flash.globalization.LocaleID", "getScript");
-            return "";
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._script;
         }
 
         public function getVariant():String {
-            stub_method("flash.globalization.LocaleID", "getVariant");
-            return "";
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._variant;
         }
 
         public function isRightToLeft():Boolean {
-            stub_method("flash.globalization.LocaleID", "isRightToLeft");
-            return false;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            if (this._script != "") {
+                return RIGHT_TO_LEFT_SCRIPTS.indexOf(this._script) != -1;
+            }
+            return RIGHT_TO_LEFT_LANGUAGES.indexOf(this._language) != -1;
         }
     }
 }
@@ -1,38 +1,46 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
     import flash.globalization.LastOperationStatus;
     import flash.globalization.NationalDigitsType;
     import flash.globalization.NumberParseResult;
 
     public final class NumberFormatter {
-        private var _decimalSeparator = ".";
-        private var _digitsType = NationalDigitsType.EUROPEAN;
-        private var _fractionalDigits = 2;
-        private var _groupingPattern = "3;*";
-        private var _groupingSeparator = ",";
-        private var _leadingZero = true;
-        private var _localeIDName:String;
-        private var _negativeNumberFormat = 1;
-        private var _negativeSymbol = "-";
-        private var _trailingZeros = true;
-        private var _useGrouping = true;
+        private var _actualLocaleIDName:String;
+        private var _decimalSeparator:String;
+        private var _digitsType:uint = NationalDigitsType.EUROPEAN;
+        private var _fractionalDigits:int = 2;
+        private var _groupingPattern:String;
+        private var _groupingSeparator:String;
+        private var _lastOperationStatus:String;
+        private var _leadingZero:Boolean = true;
+        private var _negativeNumberFormat:uint;
+        private var _negativeSymbol:String = "-";
+        private var _requestedLocaleIDName:String;
+        private var _trailingZeros:Boolean = false;
+        private var _useGrouping:Boolean = true;
 
         public function NumberFormatter(requestedLocaleIDName:String) {
-            stub_constructor("flash.globalization.NumberFormatter");
-            this._localeIDName = requestedLocaleIDName;
+            if (requestedLocaleIDName == null) throwNonNull("requestedLocaleIDName");
+            this._requestedLocaleIDName = requestedLocaleIDName;
+            this._actualLocaleIDName = resolveLocaleIDName(requestedLocaleIDName);
+            this._lastOperationStatus = localeStatus(requestedLocaleIDName, this._actualLocaleIDName);
+
+            var data:Object = getLocaleData(this._actualLocaleIDName);
+            this._decimalSeparator = data.decimalSeparator;
+            this._groupingPattern = data.groupingPattern;
+            this._groupingSeparator = data.groupingSeparator;
+            this._negativeNumberFormat = data.negativeNumberFormat;
         }
 
         public function get actualLocaleIDName():String {
-            stub_getter("flash.globalization.NumberFormatter", "actualLocaleIDName");
-            return this._localeIDName;
+            return this._actualLocaleIDName;
         }
 
         public function get decimalSeparator():String {
             return this._decimalSeparator;
         }
         public function set decimalSeparator(value:String):void {
+            if (value == null) throwNonNull("decimalSeparator");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._decimalSeparator = value;
         }
 
@@ -40,6 +48,7 @@ package flash.globalization {
             return this._digitsType;
         }
         public function set digitsType(value:uint):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._digitsType = value;
         }
 
@@ -47,6 +56,7 @@ package flash.globalization {
             return this._fractionalDigits;
         }
         public function set fractionalDigits(value:int):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._fractionalDigits = value;
         }
 
@@ -54,6 +64,8 @@ package flash.globalization {
             return this._groupingPattern;
         }
         public function set groupingPattern(value:String):void {
+            if (value == null) throwNonNull("groupingPattern");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._groupingPattern = value;
         }
 
@@ -61,18 +73,20 @@ package flash.globalization {
             return this._groupingSeparator;
         }
         public function set groupingSeparator(value:String):void {
+            if (value == null) throwNonNull("groupingSeparator");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._groupingSeparator = value;
         }
 
         public function get lastOperationStatus():String {
-            stub_getter("flash.globalization.NumberFormatter", "lastOperationStatus");
-            return LastOperationStatus.NO_ERROR;
+            return this._lastOperationStatus;
         }
 
         public function get leadingZero():Boolean {
             return this._leadingZero;
         }
         public function set leadingZero(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._leadingZero = value;
         }
 
@@ -80,26 +94,31 @@ package flash.globalization {
             return this._negativeNumberFormat;
         }
         public function set negativeNumberFormat(value:uint):void {
-            if (value >= 0 && value <= 4) {
-                this._negativeNumberFormat = value;
+            if (value > 4) {
+                throw new ArgumentError("Error #2006: The supplied index is out of bounds.", 2006);
             }
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._negativeNumberFormat = value;
         }
 
         public function get negativeSymbol():String {
             return this._negativeSymbol;
         }
         public function set negativeSymbol(value:String):void {
+            if (value == null) throwNonNull("negativeSymbol");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._negativeSymbol = value;
         }
 
         public function get requestedLocaleIDName():String {
-            return this._localeIDName;
+            return this._requestedLocaleIDName;
         }
 
         public function get trailingZeros():Boolean {
             return this._trailingZeros;
         }
         public function set trailingZeros(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._trailingZeros = value;
         }
 
@@ -107,37 +126,55 @@ package flash.globalization {
             return this._useGrouping;
         }
         public function set useGrouping(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._useGrouping = value;
         }
 
+        private native function innerFormat(value:Number):String;
+
+        // Returns `[value, startIndex, endIndex]`, or `null` if no number was found.
+        private native function innerParse(parseString:String):Array;
+
+        private native function innerParseNumber(parseString:String):Number;
+
         public function formatInt(value:int):String {
-            stub_method("flash.globalization.NumberFormatter", "formatInt");
-            return value.toString();
+            return this.formatNumber(value);
         }
 
         public function formatNumber(value:Number):String {
-            stub_method("flash.globalization.NumberFormatter", "formatNumber");
-            return value.toString();
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this.innerFormat(value);
         }
 
         public function formatUint(value:uint):String {
-            stub_method("flash.globalization.NumberFormatter", "formatUint");
-            return value.toString();
+            return this.formatNumber(value);
         }
 
         public static function getAvailableLocaleIDNames():Vector.<String> {
-            stub_method("flash.globalization.NumberFormatter", "getAvailableLocaleIDNames");
-            return new <String>["en-US"];
+            return Vector.<String>(availableLocaleIDNames());
         }
 
         public function parse(parseString:String):NumberParseResult {
-            stub_method("flash.globalization.NumberFormatter", "parse");
-            return new NumberParseResult();
+            if (parseString == null) throwNonNull("parseString");
+            var result:Array = this.innerParse(parseString);
+            if (result == null) {
+                this._lastOperationStatus = LastOperationStatus.PARSE_ERROR;
+                return new NumberParseResult();
+            }
+
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return new NumberParseResult(result[0], result[1], result[2]);
         }
 
         public function parseNumber(parseString:String):Number {
-            stub_method("flash.globalization.NumberFormatter", "parseNumber");
-            return Number(parseString);
+            if (parseString == null) throwNonNull("parseString");
+            var result:Number = this.innerParseNumber(parseString);
+            if (isNaN(result)) {
+                this._lastOperationStatus = LastOperationStatus.PARSE_ERROR;
+            } else {
+                this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            }
+            return result;
         }
     }
 }
@@ -0,0 +1,49 @@
+package flash.globalization {
+    import flash.globalization.LastOperationStatus;
+
+    public final class StringTools {
+        private var _actualLocaleIDName:String;
+        private var _lastOperationStatus:String;
+        private var _requestedLocaleIDName:String;
+
+        public function StringTools(requestedLocaleIDName:String) {
+            if (requestedLocaleIDName == null) throwNonNull("requestedLocaleIDName");
+            this._requestedLocaleIDName = requestedLocaleIDName;
+            this._actualLocaleIDName = resolveLocaleIDName(requestedLocaleIDName);
+            this._lastOperationStatus = localeStatus(requestedLocaleIDName, this._actualLocaleIDName);
+        }
+
+        public function get actualLocaleIDName():String {
+            return this._actualLocaleIDName;
+        }
+
+        public function get lastOperationStatus():String {
+            return this._lastOperationStatus;
+        }
+
+        public function get requestedLocaleIDName():String {
+            return this._requestedLocaleIDName;
+        }
+
+        // Unlike `String.toLowerCase` and `String.toUpperCase`, these use the full Unicode case
+        // mappings, which can change the length of the string.
+        private native function innerToLowerCase(s:String):String;
+        private native function innerToUpperCase(s:String):String;
+
+        public function toLowerCase(s:String):String {
+            if (s == null) throwNonNull("s");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this.innerToLowerCase(s);
+        }
+
+        public function toUpperCase(s:String):String {
+            if (s == null) throwNonNull("s");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this.innerToUpperCase(s);
+        }
+
+        public static function getAvailableLocaleIDNames():Vector.<String> {
+            return Vector.<String>(availableLocaleIDNames());
+        }
+    }
+}
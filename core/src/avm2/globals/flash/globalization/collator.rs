@@ -0,0 +1,33 @@
+//! `flash.globalization.Collator` native function definitions
+
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{Activation, Error};
+use crate::locale::CollatorOptions;
+
+/// Implements `Collator.innerCompare`
+pub fn inner_compare<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let string1 = args.get_string(activation, 0)?.to_utf8_lossy().to_string();
+    let string2 = args.get_string(activation, 1)?.to_utf8_lossy().to_string();
+
+    let mut get_bool = |name: &'static str| -> Result<bool, Error<'gc>> {
+        Ok(this
+            .get_public_property(name, activation)?
+            .coerce_to_boolean())
+    };
+    let options = CollatorOptions {
+        ignore_case: get_bool("ignoreCase")?,
+        ignore_character_width: get_bool("ignoreCharacterWidth")?,
+        ignore_diacritics: get_bool("ignoreDiacritics")?,
+        ignore_kana_type: get_bool("ignoreKanaType")?,
+        ignore_symbols: get_bool("ignoreSymbols")?,
+        numeric_comparison: get_bool("numericComparison")?,
+    };
+
+    Ok((options.compare(&string1, &string2) as i32).into())
+}
@@ -0,0 +1,77 @@
+//! `flash.globalization.CurrencyFormatter` native function definitions
+
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::globals::flash::globalization::number_format_from_object;
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{Activation, Error};
+use crate::string::AvmString;
+
+/// Implements `CurrencyFormatter.innerFormat`
+pub fn inner_format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_f64(activation, 0)?;
+    let with_currency_symbol = args.get_bool(1);
+    let format = number_format_from_object(activation, this)?;
+
+    let currency_name = if with_currency_symbol {
+        "currencySymbol"
+    } else {
+        "currencyISOCode"
+    };
+    let currency = this
+        .get_public_property(currency_name, activation)?
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .to_string();
+    let positive_currency_format = this
+        .get_public_property("positiveCurrencyFormat", activation)?
+        .coerce_to_u32(activation)?;
+    let negative_currency_format = this
+        .get_public_property("negativeCurrencyFormat", activation)?
+        .coerce_to_u32(activation)?;
+
+    let formatted = format.format_currency(
+        value,
+        &currency,
+        positive_currency_format,
+        negative_currency_format,
+    );
+    Ok(AvmString::new_utf8(activation.context.gc_context, formatted).into())
+}
+
+/// Implements `CurrencyFormatter.innerParse`, which returns `[value, currencyString]`
+/// or `null` if the string isn't an amount of currency.
+pub fn inner_parse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let input = args.get_string(activation, 0)?.to_utf8_lossy().to_string();
+    let format = number_format_from_object(activation, this)?;
+    let currency_symbol = this
+        .get_public_property("currencySymbol", activation)?
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .to_string();
+    let currency_iso_code = this
+        .get_public_property("currencyISOCode", activation)?
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .to_string();
+
+    let Some((value, currency)) =
+        format.parse_currency(&input, &currency_symbol, &currency_iso_code)
+    else {
+        return Ok(Value::Null);
+    };
+    let values: [Value<'gc>; 2] = [
+        value.into(),
+        AvmString::new_utf8(activation.context.gc_context, currency).into(),
+    ];
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&values))?.into())
+}
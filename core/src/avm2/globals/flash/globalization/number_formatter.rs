@@ -0,0 +1,58 @@
+//! `flash.globalization.NumberFormatter` native function definitions
+
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::globals::flash::globalization::number_format_from_object;
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{Activation, Error};
+use crate::string::AvmString;
+
+/// Implements `NumberFormatter.innerFormat`
+pub fn inner_format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_f64(activation, 0)?;
+    let format = number_format_from_object(activation, this)?;
+    let negative_number_format = this
+        .get_public_property("negativeNumberFormat", activation)?
+        .coerce_to_u32(activation)?;
+
+    let formatted = format.format_number(value, negative_number_format);
+    Ok(AvmString::new_utf8(activation.context.gc_context, formatted).into())
+}
+
+/// Implements `NumberFormatter.innerParse`, which returns `[value, startIndex, endIndex]`
+/// or `null` if no number was found.
+pub fn inner_parse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let input = args.get_string(activation, 0)?.to_utf8_lossy().to_string();
+    let format = number_format_from_object(activation, this)?;
+
+    let Some(parsed) = format.parse(&input) else {
+        return Ok(Value::Null);
+    };
+    let values: [Value<'gc>; 3] = [
+        parsed.value.into(),
+        (parsed.start_index as i32).into(),
+        (parsed.end_index as i32).into(),
+    ];
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&values))?.into())
+}
+
+/// Implements `NumberFormatter.innerParseNumber`, which returns `NaN` unless the
+/// string is entirely a number.
+pub fn inner_parse_number<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let input = args.get_string(activation, 0)?.to_utf8_lossy().to_string();
+    let format = number_format_from_object(activation, this)?;
+    Ok(format.parse_exact(&input).unwrap_or(f64::NAN).into())
+}
@@ -0,0 +1,33 @@
+//! `flash.globalization.StringTools` native function definitions
+
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{Activation, Error};
+use crate::string::AvmString;
+
+/// Implements `StringTools.innerToLowerCase`
+pub fn inner_to_lower_case<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let s = args
+        .get_string(activation, 0)?
+        .to_utf8_lossy()
+        .to_lowercase();
+    Ok(AvmString::new_utf8(activation.context.gc_context, s).into())
+}
+
+/// Implements `StringTools.innerToUpperCase`
+pub fn inner_to_upper_case<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let s = args
+        .get_string(activation, 0)?
+        .to_utf8_lossy()
+        .to_uppercase();
+    Ok(AvmString::new_utf8(activation.context.gc_context, s).into())
+}
@@ -1,6 +1,7 @@
-use crate::avm2::error::type_error;
+use crate::avm2::error::{make_error_2008, type_error};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::streams::AppendBytesAction;
 
 pub use crate::avm2::object::netstream_allocator as net_stream_allocator;
 
@@ -28,6 +29,52 @@ pub fn get_bytes_total<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn append_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let bytearray = args.get_object(activation, 0, "bytes")?;
+        let Some(bytearray) = bytearray.as_bytearray() else {
+            return Err(Error::AvmError(type_error(
+                activation,
+                &format!(
+                    "Error #1034: Type Coercion failed: cannot convert {bytearray:?} to flash.utils.ByteArray."
+                ),
+                1034,
+            )?));
+        };
+
+        ns.append_bytes(&mut activation.context, bytearray.bytes());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn append_bytes_action<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let action = args.get_string(activation, 0)?;
+        let action = if &action == b"resetBegin" {
+            AppendBytesAction::ResetBegin
+        } else if &action == b"resetSeek" {
+            AppendBytesAction::ResetSeek
+        } else if &action == b"endSequence" {
+            AppendBytesAction::EndSequence
+        } else {
+            return Err(make_error_2008(activation, "action"));
+        };
+
+        ns.append_bytes_action(&mut activation.context, action);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn play<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
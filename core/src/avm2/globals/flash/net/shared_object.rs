@@ -2,10 +2,11 @@
 
 use crate::avm2::api_version::ApiVersion;
 use crate::avm2::error::error;
-use crate::avm2::object::TObject;
+use crate::avm2::object::{EventObject, TObject};
 use crate::avm2::Error::AvmError;
 use crate::avm2::Multiname;
-use crate::avm2::{Activation, Error, Namespace, Object, Value};
+use crate::avm2::{Activation, Avm2, Error, Namespace, Object, Value};
+use crate::backend::storage::QuotaCheck;
 use crate::string::AvmString;
 use crate::{avm2_stub_getter, avm2_stub_method, avm2_stub_setter};
 use flash_lso::types::{AMFVersion, Lso};
@@ -199,8 +200,13 @@ pub fn get_local<'gc>(
 pub fn flush<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    // This is also called without arguments when the player exits.
+    let min_disk_space = match args.get(0) {
+        Some(min_disk_space) => min_disk_space.coerce_to_i32(activation)?.max(0) as usize,
+        None => 0,
+    };
     let data = this
         .get_public_property("data", activation)?
         .coerce_to_object(activation)?;
@@ -221,20 +227,61 @@ pub fn flush<'gc>(
     let mut lso = new_lso(activation, &name, data)?;
     // Flash does not write empty LSOs to disk
     if lso.body.is_empty() {
-        Ok("flushed".into())
-    } else {
-        let bytes = flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
-        if activation.context.storage.put(&name, &bytes) {
-            Ok("flushed".into())
-        } else {
-            Err(AvmError(error(
-                activation,
-                "Error #2130: Unable to flush SharedObject.",
-                2130,
-            )?))
+        return Ok("flushed".into());
+    }
+
+    let bytes = flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
+    match activation
+        .context
+        .storage
+        .check_quota(&name, bytes.len().max(min_disk_space))
+    {
+        QuotaCheck::Allowed => {
+            if activation.context.storage.put(&name, &bytes) {
+                return Ok("flushed".into());
+            }
+        }
+        QuotaCheck::Exceeded => {
+            // Flash Player would ask the user for more space here.
+            // We have no such prompt, so the request is always declined.
+            dispatch_flush_failed(activation, name.to_string());
+            return Ok("pending".into());
         }
+        QuotaCheck::Denied => {}
     }
-    // FIXME - We should dispatch a NetStatusEvent after this function returns
+
+    Err(AvmError(error(
+        activation,
+        "Error #2130: Unable to flush SharedObject.",
+        2130,
+    )?))
+}
+
+/// Dispatches a `SharedObject.Flush.Failed` status event once the current script has finished,
+/// so that listeners added after `flush` returned "pending" still receive it.
+fn dispatch_flush_failed(activation: &mut Activation<'_, '_>, name: String) {
+    // The player may already be shutting down, if this was flushed on exit.
+    let Some(player) = activation.context.player.upgrade() else {
+        return;
+    };
+    activation
+        .context
+        .navigator
+        .spawn_future(Box::pin(async move {
+            player.lock().unwrap().update(|uc| {
+                let Some(&shared_object) = uc.avm2_shared_objects.get(&name) else {
+                    return;
+                };
+                let mut activation = Activation::from_nothing(uc.reborrow());
+                let event = EventObject::net_status_event(
+                    &mut activation,
+                    "netStatus",
+                    vec![("code", "SharedObject.Flush.Failed"), ("level", "error")],
+                );
+                Avm2::dispatch_event(&mut activation.context, event, shared_object);
+            });
+            Ok(())
+        }));
 }
 
 pub fn get_size<'gc>(
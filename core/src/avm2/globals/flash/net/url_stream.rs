@@ -0,0 +1,102 @@
+//! `flash.net.URLStream` native function definitions
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::io_error;
+use crate::avm2::globals::flash::display::loader::request_from_url_request;
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, Multiname, Object};
+
+/// Native function definition for `URLStream.load`
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let request = args.get_object(activation, 0, "request")?;
+    let request = request_from_url_request(activation, request)?;
+
+    // Loading again discards anything received by a previous load.
+    clear_buffer(activation, this)?;
+    set_connected(activation, this, true)?;
+
+    let future = activation.context.load_manager.load_data_into_url_stream(
+        activation.context.player.clone(),
+        this,
+        request,
+    );
+    activation.context.navigator.spawn_future(future);
+    Ok(Value::Undefined)
+}
+
+/// Native function definition for `URLStream.close`
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let connected = this
+        .get_property(&connected_name(activation), activation)?
+        .coerce_to_boolean();
+    if !connected {
+        return Err(Error::AvmError(io_error(
+            activation,
+            "Error #2029: This URLStream object does not have a stream opened.",
+            2029,
+        )?));
+    }
+
+    activation.context.load_manager.cancel_url_stream(this);
+    set_connected(activation, this, false)?;
+    Ok(Value::Undefined)
+}
+
+fn buffer_name<'gc>(activation: &mut Activation<'_, 'gc>) -> Multiname<'gc> {
+    Multiname::new(activation.avm2().flash_net_internal, "_buffer")
+}
+
+fn connected_name<'gc>(activation: &mut Activation<'_, 'gc>) -> Multiname<'gc> {
+    Multiname::new(activation.avm2().flash_net_internal, "_connected")
+}
+
+fn clear_buffer<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    stream: Object<'gc>,
+) -> Result<(), Error<'gc>> {
+    let buffer = stream.get_property(&buffer_name(activation), activation)?;
+    if let Some(buffer) = buffer.as_object() {
+        if let Some(mut buffer) = buffer.as_bytearray_mut(activation.context.gc_context) {
+            buffer.clear();
+        }
+    }
+    Ok(())
+}
+
+/// Appends data received by a `URLStream` load to the end of its buffer.
+///
+/// The read position is left untouched, so script can keep reading where it left off.
+pub fn append_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    stream: Object<'gc>,
+    data: &[u8],
+) -> Result<(), Error<'gc>> {
+    let buffer = stream.get_property(&buffer_name(activation), activation)?;
+    if let Some(buffer) = buffer.as_object() {
+        if let Some(mut buffer) = buffer.as_bytearray_mut(activation.context.gc_context) {
+            let len = buffer.len();
+            if buffer.write_at(data, len).is_err() {
+                tracing::warn!("URLStream buffer exceeded the maximum ByteArray length");
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn set_connected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    stream: Object<'gc>,
+    connected: bool,
+) -> Result<(), Error<'gc>> {
+    stream.set_property(&connected_name(activation), connected.into(), activation)
+}
@@ -1,37 +1,58 @@
 package flash.text {
-    
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_method;
-    import __ruffle__.stub_getter;
-    
     public dynamic class StyleSheet {
+        // Copies of every style object, keyed by their lowercase style name.
+        private var _css:Object = {};
+
         public function StyleSheet() {}
-        
+
         public function get styleNames():Array {
-            stub_getter("flash.text.StyleSheet", "styleNames");
-            return [];
+            var names:Array = [];
+            for (var name:String in this._css) {
+                names.push(name);
+            }
+            return names;
         }
-        
+
         public function clear():void {
-            stub_method("flash.text.StyleSheet", "clear");
+            this._css = {};
         }
-        
+
         public function getStyle(styleName:String):Object {
-            stub_method("flash.text.StyleSheet", "getStyle");
-            return null;
+            return copyStyle(this._css[styleName.toLowerCase()]);
         }
-        
+
         public function parseCSS(CSSText:String):void {
-            stub_method("flash.text.StyleSheet", "parseCSS");
+            var parsed:Object = innerParseCSS(CSSText);
+            if (parsed == null) {
+                return;
+            }
+            for (var name:String in parsed) {
+                this._css[name] = parsed[name];
+            }
         }
-        
+
         public function setStyle(styleName:String, styleObject:Object):void {
-            stub_method("flash.text.StyleSheet", "setStyle");
+            if (styleObject == null) {
+                delete this._css[styleName.toLowerCase()];
+            } else {
+                this._css[styleName.toLowerCase()] = copyStyle(styleObject);
+            }
         }
-        
-        public function transform(formatObject:Object):TextFormat {
-            stub_method("flash.text.StyleSheet", "transform");
-            return null;
+
+        public native function transform(formatObject:Object):TextFormat;
+
+        private function copyStyle(style:Object):Object {
+            if (style == null) {
+                return null;
+            }
+            var copy:Object = {};
+            for (var key:String in style) {
+                copy[key] = style[key];
+            }
+            return copy;
         }
+
+        // Returns `null` if the CSS is malformed.
+        private native function innerParseCSS(css:String):Object;
     }
 }
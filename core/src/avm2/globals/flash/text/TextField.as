@@ -78,9 +78,11 @@ package flash.text {
         }
         public function set styleSheet(value:StyleSheet):void {
             this._styleSheet = value;
-            stub_setter("flash.text.TextField", "styleSheet");
+            this.applyStyleSheet(value);
         }
 
+        private native function applyStyleSheet(value:StyleSheet):void;
+
         public native function get text():String;
         public native function set text(value:String):void;
 
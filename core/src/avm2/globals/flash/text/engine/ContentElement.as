@@ -28,5 +28,13 @@ package flash.text.engine {
         public function set elementFormat(value:ElementFormat):void {
             this._elementFormat = value;
         }
+
+        // Flattens this element into `runs` as (text, elementFormat, graphicElement)
+        // triples, for use by `TextBlock.createTextLine`.
+        internal function appendRuns(runs:Array, parentFormat:ElementFormat):void {
+            if (this._text) {
+                runs.push(this._text, this._elementFormat || parentFormat, null);
+            }
+        }
     }
 }
@@ -70,6 +70,13 @@ package flash.text.engine {
             return newTextElement;
         }
 
+        override internal function appendRuns(runs:Array, parentFormat:ElementFormat):void {
+            var format:ElementFormat = this.elementFormat || parentFormat;
+            for (var i = 0; i < this._elements.length; i ++) {
+                this._elements[i].appendRuns(runs, format);
+            }
+        }
+
         // FIXME: This is wrong, FP doesn't do an override of `get text` in GroupElement
         override public function get text():String {
             var resultingText:String = "";
@@ -16,6 +16,11 @@ package flash.text.engine {
 
         internal var _textLineCreationResult:String = null;
         internal var _firstLine:TextLine = null;
+        internal var _lastLine:TextLine = null;
+
+        // The flattened content, kept by `createTextLine` for the lines after the first.
+        internal var _rawText:String = null;
+        internal var _runs:Array = null;
 
 
         public function TextBlock(content:ContentElement = null,
@@ -130,6 +135,8 @@ package flash.text.engine {
 
         public function set content(value:ContentElement):void {
             this._content = value;
+            this._rawText = null;
+            this._runs = null;
         }
 
         public native function createTextLine(previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0, fitSomething:Boolean = false):TextLine;
@@ -160,16 +167,40 @@ package flash.text.engine {
         }
 
         public function get lastLine():TextLine {
-            return this._firstLine;
+            return this._lastLine;
         }
-        
+
         public function releaseLines(start:TextLine, end:TextLine):void {
-            if (start != end || end != this._firstLine) {
-                stub_method("flash.text.engine.TextBlock", "releaseLines", "with start != end or multiple lines");
-                return;
+            if (start == null || end == null || start._textBlock != this || end._textBlock != this) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            var before:TextLine = start._previousLine;
+            var after:TextLine = end._nextLine;
+
+            var line:TextLine = start;
+            while (line != null) {
+                var next:TextLine = line._nextLine;
+                line._validity = TextLineValidity.INVALID;
+                line._textBlock = null;
+                line._previousLine = null;
+                line._nextLine = null;
+                if (line == end) {
+                    break;
+                }
+                line = next;
+            }
+
+            if (before) {
+                before._nextLine = after;
+            } else {
+                this._firstLine = after;
+            }
+            if (after) {
+                after._previousLine = before;
+            } else {
+                this._lastLine = before;
             }
-            this._firstLine._validity = "invalid";
-            this._firstLine._textBlock = null;
         }
     }
 }
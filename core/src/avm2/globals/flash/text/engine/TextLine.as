@@ -1,9 +1,7 @@
 package flash.text.engine {
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_setter;
-    import __ruffle__.stub_method;
-
+    import flash.display.DisplayObject;
     import flash.display.DisplayObjectContainer;
+    import flash.geom.Point;
     import flash.geom.Rectangle;
 
     // FIXME: None of the DisplayObjectContainer methods actually work on
@@ -16,6 +14,21 @@ package flash.text.engine {
         internal var _textBlock:TextBlock = null;
         internal var _rawTextLength:int = 0;
         internal var _validity:String = "valid";
+        internal var _textBlockBeginIndex:int = 0;
+        internal var _ascent:Number = 0.0;
+        internal var _descent:Number = 0.0;
+        internal var _unjustifiedTextWidth:Number = 0.0;
+        internal var _hasGraphicElement:Boolean = false;
+        internal var _hasTabs:Boolean = false;
+        internal var _previousLine:TextLine = null;
+        internal var _nextLine:TextLine = null;
+
+        // Atom data, set by `TextBlock.createTextLine`. Atom bounds are
+        // relative to the start of the baseline, and begin indices are
+        // relative to the start of the text block.
+        internal var _atomBounds:Array = [];
+        internal var _atomBeginIndices:Array = [];
+        internal var _atomGraphics:Array = [];
 
         public static const MAX_LINE_WIDTH:int = 1000000;
 
@@ -30,8 +43,7 @@ package flash.text.engine {
         }
 
         public function get textBlockBeginIndex():int {
-            stub_getter("flash.text.engine.TextLine", "textBlockBeginIndex");
-            return 0;
+            return this._textBlockBeginIndex;
         }
 
         public function get specifiedWidth():Number {
@@ -43,74 +55,141 @@ package flash.text.engine {
         }
 
         public function get ascent():Number {
-            stub_getter("flash.text.engine.TextLine", "ascent");
-            return 12.0;
+            return this._ascent;
         }
 
         public function get descent():Number {
-            stub_getter("flash.text.engine.TextLine", "descent");
-            return 3.0;
+            return this._descent;
         }
 
         public function get unjustifiedTextWidth():Number {
-            stub_getter("flash.text.engine.TextLine", "unjustifiedTextWidth");
-            return this._specifiedWidth;
+            return this._unjustifiedTextWidth;
         }
 
         public native function get textWidth():Number;
         public native function get textHeight():Number;
 
         public function get validity():String {
-            stub_getter("flash.text.engine.TextLine", "validity");
             return this._validity;
         }
 
         public function set validity(value:String):void {
-            stub_setter("flash.text.engine.TextLine", "validity");
+            if (value == TextLineValidity.VALID || this._validity == TextLineValidity.STATIC) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
             this._validity = value;
         }
 
         public function get hasGraphicElement():Boolean {
-            stub_getter("flash.text.engine.TextLine", "hasGraphicElement");
-            return false;
+            return this._hasGraphicElement;
         }
 
         public function get atomCount():int {
-            stub_getter("flash.text.engine.TextLine", "atomCount");
-            return this._rawTextLength;
+            return this._atomBounds.length;
         }
 
         public function get nextLine():TextLine {
-            return null;
+            return this._nextLine;
         }
 
         public function get previousLine():TextLine {
-            return null;
+            return this._previousLine;
         }
 
         public function getBaselinePosition(baseline:String):Number {
-            stub_method("flash.text.engine.TextLine", "getBaselinePosition");
-            return 0.0;
+            switch (baseline) {
+                case TextBaseline.ROMAN:
+                    return 0.0;
+                case TextBaseline.ASCENT:
+                case TextBaseline.IDEOGRAPHIC_TOP:
+                    return -this._ascent;
+                case TextBaseline.DESCENT:
+                case TextBaseline.IDEOGRAPHIC_BOTTOM:
+                    return this._descent;
+                case TextBaseline.IDEOGRAPHIC_CENTER:
+                    return (this._descent - this._ascent) / 2;
+                default:
+                    throw new ArgumentError("Error #2008: Parameter baseline must be one of the accepted values.", 2008);
+            }
+        }
+
+        public function get hasTabs():Boolean {
+            return this._hasTabs;
         }
 
-        public function hasTabs():Boolean {
-            stub_getter("flash.text.engine.TextLine", "hasTabs");
-            return false;
+        private function checkAtomIndex(index:int):void {
+            if (index < 0 || index >= this._atomBounds.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
         }
 
         public function getAtomIndexAtPoint(stageX:Number, stageY:Number):int {
-            stub_method("flash.text.engine.TextLine", "getAtomIndexAtPoint");
+            var point:Point = this.globalToLocal(new Point(stageX, stageY));
+            for (var i = 0; i < this._atomBounds.length; i ++) {
+                if (this._atomBounds[i].containsPoint(point)) {
+                    return i;
+                }
+            }
             return -1;
         }
 
         public function getAtomIndexAtCharIndex(charIndex:int):int {
-            stub_method("flash.text.engine.TextLine", "getAtomIndexAtCharIndex");
+            for (var i = 0; i < this._atomBounds.length; i ++) {
+                if (charIndex >= this._atomBeginIndices[i] && charIndex < this.getAtomTextBlockEndIndex(i)) {
+                    return i;
+                }
+            }
             return -1;
         }
 
-        public function getAtomBounds(index:int):Rectangle {
-            stub_method("flash.text.engine.TextLine", "getAtomBounds");
-            return new Rectangle(0, 0, 0, 0);
+        public function getAtomBounds(atomIndex:int):Rectangle {
+            this.checkAtomIndex(atomIndex);
+            return this._atomBounds[atomIndex].clone();
+        }
+
+        public function getAtomCenter(atomIndex:int):Number {
+            this.checkAtomIndex(atomIndex);
+            var bounds:Rectangle = this._atomBounds[atomIndex];
+            return bounds.x + bounds.width / 2;
+        }
+
+        public function getAtomGraphic(atomIndex:int):DisplayObject {
+            this.checkAtomIndex(atomIndex);
+            return this._atomGraphics[atomIndex];
+        }
+
+        public function getAtomTextBlockBeginIndex(atomIndex:int):int {
+            this.checkAtomIndex(atomIndex);
+            return this._atomBeginIndices[atomIndex];
+        }
+
+        public function getAtomTextBlockEndIndex(atomIndex:int):int {
+            this.checkAtomIndex(atomIndex);
+            if (atomIndex + 1 < this._atomBeginIndices.length) {
+                return this._atomBeginIndices[atomIndex + 1];
+            }
+            return this._textBlockBeginIndex + this._rawTextLength;
+        }
+
+        public function getAtomBidiLevel(atomIndex:int):int {
+            this.checkAtomIndex(atomIndex);
+            // Bidirectional text isn't supported, so every atom has the level of the block.
+            return this._textBlock ? this._textBlock.bidiLevel : 0;
+        }
+
+        public function getAtomTextRotation(atomIndex:int):String {
+            this.checkAtomIndex(atomIndex);
+            return TextRotation.ROTATE_0;
+        }
+
+        public function getAtomWordBoundaryOnLeft(atomIndex:int):Boolean {
+            this.checkAtomIndex(atomIndex);
+            var beginIndex:int = this._atomBeginIndices[atomIndex];
+            if (beginIndex == 0 || !this._textBlock || !this._textBlock.content) {
+                return true;
+            }
+            var rawText:String = this._textBlock.content.rawText;
+            return rawText.charAt(beginIndex - 1) == " " || rawText.charAt(beginIndex) == " ";
         }
 
         // This function does nothing in Flash Player 32
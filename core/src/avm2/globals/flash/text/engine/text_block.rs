@@ -1,64 +1,107 @@
 use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
 use crate::avm2::error::Error;
 use crate::avm2::globals::flash::display::display_object::initialize_for_allocator;
-use crate::avm2::object::{Object, TObject};
+use crate::avm2::object::{ArrayObject, Object, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Multiname;
 use crate::avm2_stub_method;
-use crate::display_object::{EditText, TDisplayObject};
+use crate::display_object::EditText;
 use crate::html::TextFormat;
-use crate::string::WStr;
+use crate::prelude::*;
+use crate::string::{AvmString, WStr, WString};
+use std::ops::Range;
+
+/// The character used by `GraphicElement`s in the raw text of a text block.
+const GRAPHIC_ELEMENT: u16 = 0xFDEF;
+
+/// The character laid out in place of a `GraphicElement`.
+///
+/// Its letter spacing is used to reserve the width of the graphic. A space is
+/// used because every font has a glyph for it, and characters without a glyph
+/// take up no room at all.
+const GRAPHIC_ELEMENT_PLACEHOLDER: u16 = b' ' as u16;
+
+/// How many characters are laid out at first when looking for the end of a line.
+///
+/// Laying out all of the remaining text for every line would make creating all
+/// the lines of a text block quadratic, so the window is only grown while all of
+/// it fits on the line.
+const LAYOUT_WINDOW: usize = 256;
+
+/// A run of text within a text block, as flattened by `ContentElement.appendRuns`.
+struct Run<'gc> {
+    /// The index of the first character of this run within the text block.
+    begin: usize,
+    end: usize,
+    element_format: Option<Object<'gc>>,
+    graphic_element: Option<Object<'gc>>,
+}
+
+/// Information about a single atom of a laid-out line.
+struct Atom<'gc> {
+    /// The index of the first character of this atom within the line.
+    index: usize,
+    bounds: Rectangle<Twips>,
+    graphic: Option<DisplayObject<'gc>>,
+}
 
 pub fn create_text_line<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.text.TextBlock", "createTextLine");
-
     let previous_text_line = args.try_get_object(activation, 0);
     let width = args.get_f64(activation, 1)?;
+    let line_offset = args.get_f64(activation, 2)?;
+    let fit_something = args.get_bool(3);
 
-    let content = this.get_public_property("content", activation)?;
+    let content = match this.get_public_property("content", activation)? {
+        Value::Object(content) => content,
+        _ => return Ok(Value::Null),
+    };
 
-    let content = if matches!(content, Value::Null) {
+    if previous_text_line.is_none()
+        && matches!(
+            content.get_public_property("text", activation)?,
+            Value::Null
+        )
+    {
+        // FP returns a null TextLine when `o` is null- note that
+        // `o` is already coerced to a String because of the AS bindings.
         return Ok(Value::Null);
+    }
+
+    let (text, runs) = get_runs(activation, this, content, previous_text_line.is_some())?;
+    let text = text.as_wstr();
+
+    let begin_index = if let Some(previous_text_line) = previous_text_line {
+        let begin_index = get_internal(activation, previous_text_line, "_textBlockBeginIndex")?
+            .coerce_to_u32(activation)?;
+        let length = get_internal(activation, previous_text_line, "_rawTextLength")?
+            .coerce_to_u32(activation)?;
+        (begin_index + length) as usize
     } else {
-        content.as_object().unwrap()
+        0
     };
 
-    let text = match previous_text_line {
-        Some(_) => {
-            // Some SWFs rely on eventually getting `null` from createLineText.
-            // TODO: Support multiple lines
-            this.set_property(
-                &Multiname::new(
-                    activation.avm2().flash_text_engine_internal,
-                    "_textLineCreationResult",
-                ),
-                "complete".into(),
-                activation,
-            )?;
-            return Ok(Value::Null);
-        }
-        // Get the content element's text property.
-        // TODO: GraphicElement?
-        None => {
-            let txt = content
-                .get_public_property("text", activation)
-                .unwrap_or("".into());
-
-            if matches!(txt, Value::Null) {
-                // FP returns a null TextLine when `o` is null- note that
-                // `o` is already coerced to a String because of the AS bindings.
-                return Ok(Value::Null);
-            } else {
-                txt.coerce_to_string(activation)
-                    .expect("Guaranteed by AS bindings")
-            }
-        }
-    };
+    if previous_text_line.is_some() && begin_index >= text.len() {
+        set_internal(
+            activation,
+            this,
+            "_textLineCreationResult",
+            "complete".into(),
+        )?;
+        return Ok(Value::Null);
+    }
+
+    let remaining = &text[begin_index..];
+    // A line never continues past a line break.
+    let paragraph_end = remaining
+        .iter()
+        .position(|c| c == u16::from(b'\n') || c == u16::from(b'\r'))
+        .map_or(remaining.len(), |line_break| line_break + 1);
 
     let class = activation.avm2().classes().textline;
     let movie = activation.caller_movie_or_root();
@@ -67,131 +110,681 @@ pub fn create_text_line<'gc>(
     let display_object: EditText =
         EditText::new_tlf(&mut activation.context, movie, 0.0, 0.0, width, 15.0);
 
-    display_object.set_text(text.as_wstr(), &mut activation.context);
+    let mut window = paragraph_end.min(LAYOUT_WINDOW);
+    let (line_text, line_runs, letter_spacings) = loop {
+        // Graphics are laid out as placeholder characters, reserving their width.
+        let mut line_text = WString::new();
+        for c in remaining[..window].iter() {
+            line_text.push(if c == GRAPHIC_ELEMENT {
+                GRAPHIC_ELEMENT_PLACEHOLDER
+            } else {
+                c
+            });
+        }
+        let line_runs = get_runs_in_range(activation, runs, begin_index..begin_index + window)?;
+        let letter_spacings = lay_out_text(
+            activation,
+            this,
+            display_object,
+            &line_text,
+            &line_runs,
+            begin_index,
+            line_offset,
+        )?;
 
-    // FIXME: This needs to use `intrinsic_bounds` to measure the width
-    // of the provided text, and set the width of the EditText to that.
-    // Some games depend on this (e.g. Realm Grinder).
+        let wrapped = display_object
+            .line_range(0)
+            .is_some_and(|range| range.end > 0 && range.end < window);
+        if wrapped || window == paragraph_end {
+            break (line_text, line_runs, letter_spacings);
+        }
+        window = (window * 2).min(paragraph_end);
+    };
+    let text_length = line_text.len();
 
-    let element_format = content
-        .get_public_property("elementFormat", activation)?
-        .as_object();
+    // The line ends where layout wrapped it, or at the first line break.
+    let mut line_end = display_object
+        .line_range(0)
+        .map_or(text_length, |range| range.end);
+    let mandatory_break = line_text
+        .iter()
+        .position(|c| c == u16::from(b'\n') || c == u16::from(b'\r'));
+    let mandatory_break = match mandatory_break {
+        Some(line_break) if line_break < line_end || line_end == 0 => {
+            line_end = line_break + 1;
+            true
+        }
+        Some(line_break) if line_break == line_end => {
+            line_end += 1;
+            true
+        }
+        _ => false,
+    };
+    if line_end == 0 {
+        line_end = text_length;
+    }
 
-    apply_format(activation, display_object, text.as_wstr(), element_format)?;
+    let is_word_char = |c: Option<u16>| {
+        c.and_then(|c| char::from_u32(c.into()))
+            .is_some_and(|c| !c.is_whitespace())
+    };
+    let creation_result = if line_end < text_length
+        && !mandatory_break
+        && is_word_char(line_text.get(line_end - 1))
+        && is_word_char(line_text.get(line_end))
+    {
+        "emergency"
+    } else {
+        "success"
+    };
+
+    if line_end < text_length {
+        display_object.replace_text(
+            line_end,
+            text_length,
+            WStr::empty(),
+            &mut activation.context,
+        );
+    }
+    // The line has been broken, so layout must not wrap it again.
+    display_object.set_word_wrap(false, &mut activation.context);
+
+    let char_layouts = display_object.char_layouts();
+    let line_width = char_layouts
+        .iter()
+        .map(|char_layout| char_layout.x + char_layout.advance)
+        .max()
+        .unwrap_or_default();
+
+    if !fit_something {
+        if let Some(first_char) = char_layouts.first().filter(|layout| layout.index == 0) {
+            if first_char.advance.to_pixels() > width {
+                set_internal(
+                    activation,
+                    this,
+                    "_textLineCreationResult",
+                    "insufficientWidth".into(),
+                )?;
+                return Ok(Value::Null);
+            }
+        }
+    }
+
+    let is_last_line = begin_index + line_end >= text.len();
+    if should_justify(activation, this, is_last_line, mandatory_break)? {
+        // Distribute the remaining width among the spaces between words. The
+        // original text is used so that graphics aren't mistaken for spaces.
+        let trimmed_end = remaining[..line_end].trim_end().len();
+        let trimmed_width = char_layouts
+            .iter()
+            .filter(|char_layout| char_layout.index < trimmed_end)
+            .map(|char_layout| char_layout.x + char_layout.advance)
+            .max()
+            .unwrap_or_default();
+        let spaces: Vec<usize> = remaining[..trimmed_end]
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| *c == u16::from(b' '))
+            .map(|(i, _)| i)
+            .collect();
+        // Letter spacing of device fonts is rounded to whole pixels, so whole
+        // pixels are distributed to keep the line within its width.
+        let extra_width = (width - trimmed_width.to_pixels()).floor();
+        if !spaces.is_empty() && extra_width > 0.0 {
+            let extra_width = extra_width as usize;
+            let extra_spacing = extra_width / spaces.len();
+            let remainder = extra_width % spaces.len();
+            for (i, space) in spaces.into_iter().enumerate() {
+                let extra_spacing = extra_spacing + usize::from(i < remainder);
+                let format = TextFormat {
+                    letter_spacing: Some(letter_spacings[space] + extra_spacing as f64),
+                    ..TextFormat::default()
+                };
+                display_object.set_text_format(space, space + 1, format, &mut activation.context);
+            }
+        }
+    }
+
+    let measured_text = display_object.measure_text(&mut activation.context);
+    display_object.set_height(&mut activation.context, measured_text.1.to_pixels());
+
+    // Lay out the atoms of this line, one for each code point.
+    let char_layouts = display_object.char_layouts();
+    let baseline = char_layouts
+        .iter()
+        .map(|char_layout| char_layout.baseline)
+        .max()
+        .unwrap_or_default();
+    let mut ascent = char_layouts
+        .iter()
+        .map(|char_layout| baseline - char_layout.baseline + char_layout.ascent)
+        .max()
+        .unwrap_or_default();
+    let descent = char_layouts
+        .iter()
+        .map(|char_layout| char_layout.baseline - baseline + char_layout.descent)
+        .max()
+        .unwrap_or_default();
+
+    let mut layout_by_index = vec![None; line_end];
+    for char_layout in char_layouts.iter() {
+        if let Some(slot) = layout_by_index.get_mut(char_layout.index) {
+            *slot = Some(char_layout);
+        }
+    }
+    // The left edge of the next laid-out character after each index.
+    let mut next_layout_x = vec![None; line_end + 1];
+    for index in (0..line_end).rev() {
+        next_layout_x[index] = match (layout_by_index.get(index + 1), next_layout_x[index + 1]) {
+            (Some(Some(next)), Some(x)) => Some(next.x.min(x)),
+            (Some(Some(next)), None) => Some(next.x),
+            (_, x) => x,
+        };
+    }
+
+    let mut atoms = Vec::new();
+    let mut x = Twips::ZERO;
+    for (index, _) in line_text[..line_end].char_indices() {
+        let bounds = if let Some(char_layout) = layout_by_index[index] {
+            x = char_layout.x + char_layout.advance;
+            Rectangle {
+                x_min: char_layout.x,
+                y_min: char_layout.baseline - baseline - char_layout.ascent,
+                x_max: x,
+                y_max: char_layout.baseline - baseline + char_layout.descent,
+            }
+        } else {
+            // Characters that weren't laid out (such as tabs) span the gap
+            // until the next character.
+            let next_x = next_layout_x[index].unwrap_or(x).max(x);
+            let x_min = x;
+            x = next_x;
+            Rectangle {
+                x_min,
+                y_min: -ascent,
+                x_max: next_x,
+                y_max: descent,
+            }
+        };
+
+        atoms.push(Atom {
+            index,
+            bounds,
+            graphic: None,
+        });
+    }
+
+    let mut graphics = Vec::new();
+    let mut has_graphic_element = false;
+    for run in line_runs.iter() {
+        let Some(graphic_element) = run.graphic_element else {
+            continue;
+        };
+        if run.begin < begin_index || run.begin >= begin_index + line_end {
+            continue;
+        }
+        has_graphic_element = true;
+        let Ok(atom) = atoms.binary_search_by_key(&(run.begin - begin_index), |atom| atom.index)
+        else {
+            continue;
+        };
+        let atom = &mut atoms[atom];
+
+        let element_height = Twips::from_pixels(
+            graphic_element
+                .get_public_property("elementHeight", activation)?
+                .coerce_to_number(activation)?,
+        );
+        ascent = ascent.max(element_height);
+        atom.bounds.y_min = -element_height;
+        atom.bounds.y_max = Twips::ZERO;
+
+        let graphic = graphic_element
+            .get_public_property("graphic", activation)?
+            .as_object()
+            .and_then(|graphic| graphic.as_display_object());
+        if let Some(graphic) = graphic {
+            // The graphic sits on the baseline, which is the origin of the line.
+            graphic.set_x(activation.context.gc_context, atom.bounds.x_min);
+            graphic.set_y(activation.context.gc_context, -element_height);
+            graphics.push(graphic);
+            atom.graphic = Some(graphic);
+        }
+    }
+
+    let has_tabs = line_text[..line_end].contains(b'\t');
+
+    display_object.set_text_origin(
+        activation.context.gc_context,
+        Point::new(Twips::ZERO, baseline),
+    );
 
     let instance = initialize_for_allocator(activation, display_object.into(), class)?;
     class.call_native_init(instance.into(), &[], activation)?;
 
-    instance.set_property(
-        &Multiname::new(activation.avm2().flash_text_engine_internal, "_textBlock"),
-        this.into(),
+    // The graphics now belong to this line.
+    let mut container = display_object;
+    for (index, graphic) in graphics.into_iter().enumerate() {
+        container.insert_at_index(&mut activation.context, graphic, index);
+    }
+
+    let rectangle_class = activation.avm2().classes().rectangle;
+    let mut atom_bounds = ArrayStorage::new(0);
+    let mut atom_begin_indices = ArrayStorage::new(0);
+    let mut atom_graphics = ArrayStorage::new(0);
+    for atom in atoms.iter() {
+        let bounds = rectangle_class.construct(
+            activation,
+            &[
+                atom.bounds.x_min.to_pixels().into(),
+                atom.bounds.y_min.to_pixels().into(),
+                atom.bounds.width().to_pixels().into(),
+                atom.bounds.height().to_pixels().into(),
+            ],
+        )?;
+        atom_bounds.push(bounds.into());
+        atom_begin_indices.push(((begin_index + atom.index) as u32).into());
+        atom_graphics.push(
+            atom.graphic
+                .map_or(Value::Null, |graphic| graphic.object2()),
+        );
+    }
+    let atom_bounds = ArrayObject::from_storage(activation, atom_bounds)?;
+    let atom_begin_indices = ArrayObject::from_storage(activation, atom_begin_indices)?;
+    let atom_graphics = ArrayObject::from_storage(activation, atom_graphics)?;
+
+    set_internal(activation, instance, "_textBlock", this.into())?;
+    set_internal(activation, instance, "_specifiedWidth", args.get_value(1))?;
+    set_internal(
         activation,
+        instance,
+        "_textBlockBeginIndex",
+        (begin_index as u32).into(),
     )?;
-
-    instance.set_property(
-        &Multiname::new(
-            activation.avm2().flash_text_engine_internal,
-            "_specifiedWidth",
-        ),
-        args.get_value(1),
+    set_internal(
         activation,
+        instance,
+        "_rawTextLength",
+        (line_end as u32).into(),
     )?;
-
-    instance.set_property(
-        &Multiname::new(
-            activation.avm2().flash_text_engine_internal,
-            "_rawTextLength",
-        ),
-        text.len().into(),
+    set_internal(activation, instance, "_ascent", ascent.to_pixels().into())?;
+    set_internal(activation, instance, "_descent", descent.to_pixels().into())?;
+    set_internal(
         activation,
+        instance,
+        "_unjustifiedTextWidth",
+        line_width.to_pixels().into(),
     )?;
-
-    this.set_property(
-        &Multiname::new(
-            activation.avm2().flash_text_engine_internal,
-            "_textLineCreationResult",
-        ),
-        "success".into(),
+    set_internal(
+        activation,
+        instance,
+        "_hasGraphicElement",
+        has_graphic_element.into(),
+    )?;
+    set_internal(activation, instance, "_hasTabs", has_tabs.into())?;
+    set_internal(activation, instance, "_atomBounds", atom_bounds.into())?;
+    set_internal(
         activation,
+        instance,
+        "_atomBeginIndices",
+        atom_begin_indices.into(),
     )?;
+    set_internal(activation, instance, "_atomGraphics", atom_graphics.into())?;
 
-    this.set_property(
-        &Multiname::new(activation.avm2().flash_text_engine_internal, "_firstLine"),
-        instance.into(),
+    if let Some(previous_text_line) = previous_text_line {
+        set_internal(
+            activation,
+            instance,
+            "_previousLine",
+            previous_text_line.into(),
+        )?;
+        set_internal(activation, previous_text_line, "_nextLine", instance.into())?;
+    } else {
+        set_internal(activation, this, "_firstLine", instance.into())?;
+    }
+    set_internal(activation, this, "_lastLine", instance.into())?;
+    set_internal(
         activation,
+        this,
+        "_textLineCreationResult",
+        creation_result.into(),
     )?;
 
     Ok(instance.into())
 }
 
-fn apply_format<'gc>(
+fn get_internal<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    display_object: EditText<'gc>,
-    text: &WStr,
-    element_format: Option<Object<'gc>>,
+    object: Object<'gc>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error<'gc>> {
+    object.get_property(
+        &Multiname::new(activation.avm2().flash_text_engine_internal, name),
+        activation,
+    )
+}
+
+fn set_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: &'static str,
+    value: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    if let Some(element_format) = element_format {
-        // TODO: Support more ElementFormat properties
-        let color = element_format
-            .get_public_property("color", activation)?
-            .coerce_to_u32(activation)?;
-        let size = element_format
-            .get_public_property("fontSize", activation)?
-            .coerce_to_number(activation)?;
+    object.set_property(
+        &Multiname::new(activation.avm2().flash_text_engine_internal, name),
+        value,
+        activation,
+    )
+}
 
-        let (font, bold, italic, is_device_font) = if let Value::Object(font_description) =
-            element_format.get_public_property("fontDescription", activation)?
-        {
-            (
-                Some(
-                    font_description
-                        .get_public_property("fontName", activation)?
-                        .coerce_to_string(activation)?
-                        .as_wstr()
-                        .into(),
-                ),
-                Some(
-                    &font_description
-                        .get_public_property("fontWeight", activation)?
-                        .coerce_to_string(activation)?
-                        == b"bold",
-                ),
-                Some(
-                    &font_description
-                        .get_public_property("fontPosture", activation)?
-                        .coerce_to_string(activation)?
-                        == b"italic",
-                ),
-                &font_description
-                    .get_public_property("fontLookup", activation)?
-                    .coerce_to_string(activation)?
-                    == b"device",
-            )
-        } else {
-            (None, None, None, true)
-        };
+/// Sets the text of a line and applies the formats of its runs, tab stops and
+/// word wrapping, returning the letter spacing of each character.
+fn lay_out_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    text_block: Object<'gc>,
+    display_object: EditText<'gc>,
+    line_text: &WStr,
+    line_runs: &[Run<'gc>],
+    begin_index: usize,
+    line_offset: f64,
+) -> Result<Vec<f64>, Error<'gc>> {
+    let text_length = line_text.len();
+    display_object.set_word_wrap(false, &mut activation.context);
+    display_object.set_text(line_text, &mut activation.context);
 
-        let format = TextFormat {
-            color: Some(swf::Color::from_rgb(color, 0xFF)),
-            size: Some(size),
-            font,
-            bold,
-            italic,
-            ..TextFormat::default()
-        };
+    let mut letter_spacings = vec![0.0; text_length];
+    let mut default_format = None;
+    for run in line_runs.iter() {
+        let start = run.begin.saturating_sub(begin_index);
+        let end = (run.end - begin_index).min(text_length);
 
-        display_object.set_is_device_font(&mut activation.context, is_device_font);
-        display_object.set_text_format(0, text.len(), format.clone(), &mut activation.context);
-        display_object.set_new_text_format(format, &mut activation.context);
+        let (mut format, is_device_font) =
+            element_format_to_text_format(activation, run.element_format)?;
+        if let Some(graphic_element) = run.graphic_element {
+            let element_width = graphic_element
+                .get_public_property("elementWidth", activation)?
+                .coerce_to_number(activation)?;
+            format.size = Some(1.0);
+            format.kerning = Some(false);
+            format.letter_spacing = Some(element_width);
+        }
+        letter_spacings[start..end].fill(format.letter_spacing.unwrap_or_default());
+
+        if default_format.is_none() {
+            display_object.set_is_device_font(&mut activation.context, is_device_font);
+            default_format = Some(format.clone());
+        }
+        display_object.set_text_format(start, end, format, &mut activation.context);
+    }
+    if let Some(default_format) = default_format {
+        display_object.set_new_text_format(default_format, &mut activation.context);
     } else {
         display_object.set_is_device_font(&mut activation.context, true);
     }
 
+    let tab_stops = get_tab_stops(activation, text_block, line_offset)?;
+    if !tab_stops.is_empty() {
+        let format = TextFormat {
+            tab_stops: Some(tab_stops),
+            ..TextFormat::default()
+        };
+        display_object.set_text_format(0, text_length, format, &mut activation.context);
+    }
+
     display_object.set_word_wrap(true, &mut activation.context);
 
-    let measured_text = display_object.measure_text(&mut activation.context);
+    Ok(letter_spacings)
+}
 
-    display_object.set_height(&mut activation.context, measured_text.1.to_pixels());
+/// Returns the raw text of a text block and its runs, as an array of four values
+/// per run: its begin and end indices, its element format and its graphic element.
+///
+/// The content is flattened when the first line is created, and reused by the
+/// lines that follow it.
+fn get_runs<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    text_block: Object<'gc>,
+    content: Object<'gc>,
+    is_continuation: bool,
+) -> Result<(AvmString<'gc>, Object<'gc>), Error<'gc>> {
+    if is_continuation {
+        let text = get_internal(activation, text_block, "_rawText")?;
+        let runs = get_internal(activation, text_block, "_runs")?;
+        if let (Value::String(text), Value::Object(runs)) = (text, runs) {
+            return Ok((text, runs));
+        }
+    }
+
+    let runs_array = ArrayObject::empty(activation)?;
+    content.call_property(
+        &Multiname::new(activation.avm2().flash_text_engine_internal, "appendRuns"),
+        &[runs_array.into(), Value::Null],
+        activation,
+    )?;
+
+    let values: Vec<Value<'gc>> = runs_array
+        .as_array_storage()
+        .map(|storage| {
+            storage
+                .iter()
+                .map(|value| value.unwrap_or(Value::Undefined))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut text = WString::new();
+    let mut runs = ArrayStorage::new(0);
+    for run in values.chunks_exact(3) {
+        let run_text: AvmString<'gc> = run[0].coerce_to_string(activation)?;
+        let begin = text.len();
+        text.push_str(&run_text);
+        runs.push((begin as u32).into());
+        runs.push((text.len() as u32).into());
+        runs.push(run[1]);
+        runs.push(run[2]);
+    }
+
+    let text = AvmString::new(activation.context.gc_context, text);
+    let runs = ArrayObject::from_storage(activation, runs)?;
+    set_internal(activation, text_block, "_rawText", text.into())?;
+    set_internal(activation, text_block, "_runs", runs.into())?;
+
+    Ok((text, runs))
+}
+
+/// Returns the runs that overlap the given range of a text block.
+fn get_runs_in_range<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    runs: Object<'gc>,
+    range: Range<usize>,
+) -> Result<Vec<Run<'gc>>, Error<'gc>> {
+    let values: Vec<Value<'gc>> = {
+        let Some(storage) = runs.as_array_storage() else {
+            return Ok(Vec::new());
+        };
+        let run_count = storage.length() / 4;
+        let get = |index: usize| storage.get(index).unwrap_or(Value::Undefined);
+        let run_end = |run: usize| match get(run * 4 + 1) {
+            Value::Integer(end) => end as usize,
+            _ => 0,
+        };
+
+        // Runs are in order, so the first one that overlaps the range can be searched for.
+        let mut low = 0;
+        let mut high = run_count;
+        while low < high {
+            let middle = (low + high) / 2;
+            if run_end(middle) <= range.start {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+
+        let mut values = Vec::new();
+        for run in low..run_count {
+            let begin = get(run * 4);
+            if matches!(begin, Value::Integer(begin) if begin as usize >= range.end) {
+                break;
+            }
+            values.extend([begin, get(run * 4 + 1), get(run * 4 + 2), get(run * 4 + 3)]);
+        }
+        values
+    };
+
+    let mut result = Vec::with_capacity(values.len() / 4);
+    for run in values.chunks_exact(4) {
+        result.push(Run {
+            begin: run[0].coerce_to_u32(activation)? as usize,
+            end: run[1].coerce_to_u32(activation)? as usize,
+            element_format: run[2].as_object(),
+            graphic_element: run[3].as_object(),
+        });
+    }
+
+    Ok(result)
+}
+
+/// Convert the tab stops of a text block into `TextFormat` tab stops,
+/// relative to the start of a line at `line_offset`.
+fn get_tab_stops<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    text_block: Object<'gc>,
+    line_offset: f64,
+) -> Result<Vec<f64>, Error<'gc>> {
+    let Some(tab_stops) = text_block
+        .get_public_property("tabStops", activation)?
+        .as_object()
+    else {
+        return Ok(Vec::new());
+    };
+    let tab_stops: Vec<Value<'gc>> = tab_stops
+        .as_vector_storage()
+        .map(|storage| storage.iter().collect())
+        .unwrap_or_default();
+
+    let mut positions = Vec::new();
+    for tab_stop in tab_stops {
+        let Some(tab_stop) = tab_stop.as_object() else {
+            continue;
+        };
+        let alignment = tab_stop
+            .get_public_property("alignment", activation)?
+            .coerce_to_string(activation)?;
+        if &alignment != b"start" {
+            avm2_stub_method!(
+                activation,
+                "flash.text.engine.TextBlock",
+                "createTextLine",
+                "with non-start tab stop alignment"
+            );
+        }
+        let position = tab_stop
+            .get_public_property("position", activation)?
+            .coerce_to_number(activation)?;
+        if position > line_offset {
+            positions.push(position - line_offset);
+        }
+    }
+
+    Ok(positions)
+}
+
+/// Whether a line should be justified, according to the text block's justifier.
+fn should_justify<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    text_block: Object<'gc>,
+    is_last_line: bool,
+    mandatory_break: bool,
+) -> Result<bool, Error<'gc>> {
+    let Some(justifier) = text_block
+        .get_public_property("textJustifier", activation)?
+        .as_object()
+    else {
+        return Ok(false);
+    };
+    let line_justification = justifier
+        .get_public_property("lineJustification", activation)?
+        .coerce_to_string(activation)?;
+
+    Ok(if &line_justification == b"allIncludingLast" {
+        true
+    } else if &line_justification == b"allButLast" {
+        !is_last_line
+    } else if &line_justification == b"allButMandatoryBreak" {
+        !is_last_line && !mandatory_break
+    } else {
+        false
+    })
+}
+
+fn element_format_to_text_format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    element_format: Option<Object<'gc>>,
+) -> Result<(TextFormat, bool), Error<'gc>> {
+    let Some(element_format) = element_format else {
+        return Ok((TextFormat::default(), true));
+    };
+
+    // TODO: Support more ElementFormat properties
+    let color = element_format
+        .get_public_property("color", activation)?
+        .coerce_to_u32(activation)?;
+    let size = element_format
+        .get_public_property("fontSize", activation)?
+        .coerce_to_number(activation)?;
+    let kerning = element_format
+        .get_public_property("kerning", activation)?
+        .coerce_to_string(activation)?;
+    let tracking_left = element_format
+        .get_public_property("trackingLeft", activation)?
+        .coerce_to_number(activation)?;
+    let tracking_right = element_format
+        .get_public_property("trackingRight", activation)?
+        .coerce_to_number(activation)?;
+
+    let (font, bold, italic, is_device_font) = if let Value::Object(font_description) =
+        element_format.get_public_property("fontDescription", activation)?
+    {
+        (
+            Some(
+                font_description
+                    .get_public_property("fontName", activation)?
+                    .coerce_to_string(activation)?
+                    .as_wstr()
+                    .into(),
+            ),
+            Some(
+                &font_description
+                    .get_public_property("fontWeight", activation)?
+                    .coerce_to_string(activation)?
+                    == b"bold",
+            ),
+            Some(
+                &font_description
+                    .get_public_property("fontPosture", activation)?
+                    .coerce_to_string(activation)?
+                    == b"italic",
+            ),
+            &font_description
+                .get_public_property("fontLookup", activation)?
+                .coerce_to_string(activation)?
+                == b"device",
+        )
+    } else {
+        (None, None, None, true)
+    };
+
+    let format = TextFormat {
+        color: Some(swf::Color::from_rgb(color, 0xFF)),
+        size: Some(size),
+        font,
+        bold,
+        italic,
+        kerning: Some(&kerning != b"off"),
+        letter_spacing: Some(tracking_left + tracking_right),
+        ..TextFormat::default()
+    };
 
-    Ok(())
+    Ok((format, is_device_font))
 }
@@ -0,0 +1,77 @@
+//! `flash.text.StyleSheet` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject, TextFormatObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::html::{self, CssStyle, CSS_PROPERTIES};
+use crate::string::{AvmString, WStr};
+
+/// Implements `StyleSheet.innerParseCSS`, which returns an object of style objects
+/// or `null` if the CSS is malformed.
+pub fn inner_parse_css<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css = args.get_string(activation, 0)?;
+    let Some(styles) = html::parse_css(&css) else {
+        return Ok(Value::Null);
+    };
+
+    let result = activation
+        .avm2()
+        .classes()
+        .object
+        .construct(activation, &[])?;
+    for (selector, style) in styles {
+        let style_object = activation
+            .avm2()
+            .classes()
+            .object
+            .construct(activation, &[])?;
+        for (name, value) in style {
+            let name = AvmString::new(activation.context.gc_context, name);
+            let value = AvmString::new(activation.context.gc_context, value);
+            style_object.set_public_property(name, value.into(), activation)?;
+        }
+        let selector = AvmString::new(activation.context.gc_context, selector);
+        result.set_public_property(selector, style_object.into(), activation)?;
+    }
+
+    Ok(result.into())
+}
+
+/// Implements `StyleSheet.transform`
+pub fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(format_object) = args.try_get_object(activation, 0) else {
+        return Ok(Value::Null);
+    };
+
+    let style = css_style_from_object(activation, format_object)?;
+    let text_format = html::transform_style(&style);
+    Ok(TextFormatObject::from_text_format(activation, text_format)?.into())
+}
+
+/// Reads all CSS properties known to Flash Player from a style object.
+pub fn css_style_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<CssStyle, Error<'gc>> {
+    let mut style = CssStyle::new();
+    for &property in CSS_PROPERTIES {
+        let name = AvmString::new(activation.context.gc_context, WStr::from_units(property));
+        let value = object.get_public_property(name, activation)?;
+        if matches!(value, Value::Undefined | Value::Null) {
+            continue;
+        }
+        let value = value.coerce_to_string(activation)?;
+        style.push((name.as_wstr().into(), value.as_wstr().into()));
+    }
+    Ok(style)
+}
@@ -3,12 +3,13 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::error::{make_error_2006, make_error_2008};
 use crate::avm2::globals::flash::display::display_object::initialize_for_allocator;
+use crate::avm2::globals::flash::text::style_sheet::css_style_from_object;
 use crate::avm2::object::{ClassObject, Object, TObject, TextFormatObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::display_object::{AutoSizeMode, EditText, TDisplayObject, TextSelection};
-use crate::html::TextFormat;
+use crate::html::{StyleSheet, TextFormat};
 use crate::string::AvmString;
 use crate::{avm2_stub_getter, avm2_stub_setter};
 use swf::Color;
@@ -392,6 +393,44 @@ pub fn set_html_text<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn apply_style_sheet<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let style_sheet = match args.try_get_object(activation, 0) {
+            Some(object) => {
+                let names = object.get_public_property("styleNames", activation)?;
+                let names: Vec<_> = names
+                    .as_object()
+                    .and_then(|names| names.as_array_storage().map(|s| s.iter().collect()))
+                    .unwrap_or_default();
+
+                let mut style_sheet = StyleSheet::new();
+                for name in names.into_iter().flatten() {
+                    let name = name.coerce_to_string(activation)?;
+                    let style =
+                        object.call_public_property("getStyle", &[name.into()], activation)?;
+                    if let Some(style) = style.as_object() {
+                        let style = css_style_from_object(activation, style)?;
+                        style_sheet.set_style(&name, Some(style));
+                    }
+                }
+                Some(style_sheet)
+            }
+            None => None,
+        };
+
+        this.set_style_sheet(style_sheet, None, &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_length<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -1,26 +1,65 @@
 package flash.ui {
 
+    import flash.display.BitmapData;
+    import flash.geom.Point;
     import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
 
     public final class Mouse {
+        // The names of the cursors registered with `registerCursor`.
+        private static var _registeredCursors:Object = {};
+
         public static native function hide(): void;
         public static native function show(): void;
+
+        private static native function nativeSetCursor(name:String):void;
+        private static native function nativeRegisterCursor(
+            name:String, data:Vector.<BitmapData>, hotSpotX:int, hotSpotY:int, frameRate:Number
+        ):void;
+        private static native function nativeUnregisterCursor(name:String):void;
+
         public static function get supportsCursor():Boolean {
             stub_getter("flash.ui.Mouse", "supportsCursor");
             return true;
         }
         public static function get supportsNativeCursor():Boolean {
-            stub_getter("flash.ui.Mouse", "supportsNativeCursor");
             return true;
         }
 
+        public static native function get cursor():String;
+        public static function set cursor(value:String):void {
+            if (value != MouseCursor.AUTO &&
+                value != MouseCursor.ARROW &&
+                value != MouseCursor.BUTTON &&
+                value != MouseCursor.HAND &&
+                value != MouseCursor.IBEAM &&
+                !_registeredCursors.hasOwnProperty(value)) {
+                throw new ArgumentError("Error #2008: Parameter cursor must be one of the accepted values.", 2008);
+            }
+            nativeSetCursor(value);
+        }
+
         public static function registerCursor(name:String, cursor:MouseCursorData):void {
-            stub_method("flash.ui.Mouse", "registerCursor");
+            var data:Vector.<BitmapData> = cursor.data;
+            if (data == null || data.length == 0) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            var hotSpot:Point = cursor.hotSpot;
+            // Throws for bitmaps larger than 32x32, in which case the name stays unregistered.
+            nativeRegisterCursor(name, data, hotSpot.x, hotSpot.y, cursor.frameRate);
+            _registeredCursors[name] = true;
         }
 
         public static function unregisterCursor(name:String):void {
-            stub_method("flash.ui.Mouse", "unregisterCursor");
+            if (!_registeredCursors.hasOwnProperty(name)) {
+                return;
+            }
+
+            delete _registeredCursors[name];
+            if (Mouse.cursor == name) {
+                nativeSetCursor(MouseCursor.AUTO);
+            }
+            nativeUnregisterCursor(name);
         }
     }
 }
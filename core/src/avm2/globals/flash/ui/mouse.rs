@@ -1,9 +1,14 @@
 //! `flash.ui.Mouse` builtin
 
 use crate::avm2::activation::Activation;
-use crate::avm2::object::Object;
+use crate::avm2::error::argument_error;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::backend::ui::{CustomMouseCursor, MouseCursor};
+use crate::string::AvmString;
+use ruffle_render::bitmap::PixelRegion;
 
 pub fn hide<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -22,3 +27,147 @@ pub fn show<'gc>(
     activation.context.ui.set_mouse_visible(true);
     Ok(Value::Undefined)
 }
+
+/// Implements `Mouse.cursor`'s getter
+pub fn get_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = match &*activation.context.mouse_cursor_override {
+        None => "auto",
+        Some(MouseCursor::Arrow) => "arrow",
+        Some(MouseCursor::Hand) => "button",
+        Some(MouseCursor::Grab) => "hand",
+        Some(MouseCursor::IBeam) => "ibeam",
+        Some(MouseCursor::Custom(name)) => name.as_str(),
+    };
+    Ok(AvmString::new_utf8(activation.context.gc_context, name).into())
+}
+
+/// Sets the cursor to a name that was already validated by `Mouse.cursor`'s setter.
+pub fn native_set_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?;
+    let cursor = if &name == b"auto" {
+        None
+    } else if &name == b"arrow" {
+        Some(MouseCursor::Arrow)
+    } else if &name == b"button" {
+        Some(MouseCursor::Hand)
+    } else if &name == b"hand" {
+        Some(MouseCursor::Grab)
+    } else if &name == b"ibeam" {
+        Some(MouseCursor::IBeam)
+    } else {
+        Some(MouseCursor::Custom(name.to_string()))
+    };
+
+    if *activation.context.mouse_cursor_override != cursor {
+        let displayed = cursor
+            .clone()
+            .unwrap_or_else(|| activation.context.mouse_cursor.clone());
+        activation.context.ui.set_mouse_cursor(displayed);
+        *activation.context.mouse_cursor_override = cursor;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// The largest width and height of a custom cursor.
+const MAX_CURSOR_SIZE: u32 = 32;
+
+/// Passes the frames of a `MouseCursorData` to the UI backend as RGBA pixels.
+pub fn native_register_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?;
+    let data = args.get_object(activation, 1, "data")?;
+    let hotspot_x = args.get_i32(activation, 2)?.max(0) as u32;
+    let hotspot_y = args.get_i32(activation, 3)?.max(0) as u32;
+    let frame_rate = args.get_f64(activation, 4)?;
+
+    let frames: Vec<Value<'gc>> = data
+        .as_vector_storage()
+        .map(|storage| storage.iter().collect())
+        .unwrap_or_default();
+
+    let mut cursor = CustomMouseCursor {
+        width: 0,
+        height: 0,
+        frames: Vec::with_capacity(frames.len()),
+        hotspot: (0, 0),
+        frame_rate: if frame_rate.is_finite() && frame_rate > 0.0 {
+            frame_rate
+        } else {
+            0.0
+        },
+    };
+    for frame in frames {
+        let Some(bitmap_data) = frame.as_object().and_then(|o| o.as_bitmap_data()) else {
+            continue;
+        };
+        bitmap_data.check_valid(activation)?;
+
+        let (width, height) = (bitmap_data.width(), bitmap_data.height());
+        if width > MAX_CURSOR_SIZE || height > MAX_CURSOR_SIZE {
+            return Err(Error::AvmError(argument_error(
+                activation,
+                "Error #2004: One of the parameters is invalid.",
+                2004,
+            )?));
+        }
+        if cursor.frames.is_empty() {
+            cursor.width = width;
+            cursor.height = height;
+        } else if (width, height) != (cursor.width, cursor.height) {
+            // Every frame of an animated cursor shares the size of the first one.
+            continue;
+        }
+
+        let read = bitmap_data.read_area(
+            PixelRegion::for_whole_size(width, height),
+            activation.context.renderer,
+        );
+        let mut pixels = Vec::with_capacity((width * height) as usize * 4);
+        for color in read.pixels() {
+            let color = color.to_un_multiplied_alpha();
+            pixels.extend_from_slice(&[color.red(), color.green(), color.blue(), color.alpha()]);
+        }
+        cursor.frames.push(pixels);
+    }
+
+    if cursor.frames.is_empty() {
+        return Ok(Value::Undefined);
+    }
+    cursor.hotspot = (
+        hotspot_x.min(cursor.width.saturating_sub(1)),
+        hotspot_y.min(cursor.height.saturating_sub(1)),
+    );
+
+    activation
+        .context
+        .ui
+        .register_mouse_cursor(name.to_string(), cursor);
+
+    Ok(Value::Undefined)
+}
+
+/// Removes a cursor registered by `Mouse.registerCursor` from the UI backend.
+pub fn native_unregister_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?;
+    activation
+        .context
+        .ui
+        .unregister_mouse_cursor(&name.to_string());
+    Ok(Value::Undefined)
+}
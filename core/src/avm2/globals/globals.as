@@ -218,7 +218,9 @@ include "flash/geom/Rectangle.as"
 include "flash/geom/Transform.as"
 include "flash/geom/Utils3D.as"
 include "flash/geom/Vector3D.as"
+include "flash/globalization.as"
 include "flash/globalization/CollatorMode.as"
+include "flash/globalization/Collator.as"
 include "flash/globalization/CurrencyParseResult.as"
 include "flash/globalization/CurrencyFormatter.as"
 include "flash/globalization/DateTimeFormatter.as"
@@ -230,6 +232,7 @@ include "flash/globalization/LocaleID.as"
 include "flash/globalization/NationalDigitsType.as"
 include "flash/globalization/NumberFormatter.as"
 include "flash/globalization/NumberParseResult.as"
+include "flash/globalization/StringTools.as"
 
 include "flash/media/AudioDecoder.as"
 include "flash/media/AudioOutputChangeReason.as"
@@ -271,6 +274,7 @@ include "flash/net/NetGroupReplicationStrategy.as"
 include "flash/net/NetGroupSendMode.as"
 include "flash/net/NetGroupSendResult.as"
 include "flash/net/NetStream.as"
+include "flash/net/NetStreamAppendBytesAction.as"
 include "flash/net/NetStreamInfo.as"
 include "flash/net/NetStreamMulticastInfo.as"
 include "flash/net/NetStreamPlayOptions.as"
@@ -63,6 +63,10 @@ pub struct SoundStreamInfo {
     pub stream_format: swf::SoundFormat,
     pub num_samples_per_block: u16,
     pub latency_seek: i16,
+
+    /// Codec-specific setup data that isn't part of the audio data itself,
+    /// such as the `AudioSpecificConfig` of an AAC stream.
+    pub codec_config: Option<Vec<u8>>,
 }
 
 impl From<swf::SoundStreamHead> for SoundStreamInfo {
@@ -72,6 +76,7 @@ impl From<swf::SoundStreamHead> for SoundStreamInfo {
             stream_format: swfhead.stream_format,
             num_samples_per_block: swfhead.num_samples_per_block,
             latency_seek: swfhead.latency_seek,
+            codec_config: None,
         }
     }
 }
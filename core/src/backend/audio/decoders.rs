@@ -1,5 +1,7 @@
 //! Audio decoders.
 
+#[cfg(feature = "aac")]
+mod aac;
 mod adpcm;
 #[cfg(feature = "mp3")]
 mod mp3;
@@ -7,6 +9,8 @@ mod mp3;
 mod nellymoser;
 mod pcm;
 
+#[cfg(feature = "aac")]
+pub use aac::AacDecoder;
 pub use adpcm::AdpcmDecoder;
 #[cfg(feature = "mp3")]
 pub use mp3::{mp3_metadata, Mp3Decoder};
@@ -27,6 +31,10 @@ pub enum Error {
     #[error("Couldn't decode MP3")]
     InvalidMp3(#[from] mp3::Error),
 
+    #[cfg(feature = "aac")]
+    #[error("Couldn't decode AAC")]
+    InvalidAac(#[from] aac::Error),
+
     #[error("Couldn't decode ADPCM")]
     InvalidAdpcm(#[from] adpcm::Error),
 
@@ -76,6 +84,8 @@ pub fn make_decoder<R: 'static + Read + Send + Sync>(
         )?),
         #[cfg(feature = "mp3")]
         AudioCompression::Mp3 => Box::new(Mp3Decoder::new(data)?),
+        #[cfg(feature = "aac")]
+        AudioCompression::Aac => Box::new(AacDecoder::new(data)?),
         #[cfg(feature = "nellymoser")]
         AudioCompression::Nellymoser => {
             Box::new(NellymoserDecoder::new(data, format.sample_rate.into()))
@@ -491,12 +501,17 @@ pub fn make_substream_decoder(
     stream_info: &SoundStreamInfo,
     data_stream: Substream,
 ) -> Result<Box<dyn Decoder + Send>, Error> {
-    let decoder: Box<dyn Decoder + Send> =
-        if stream_info.stream_format.compression == AudioCompression::Adpcm {
-            Box::new(AdpcmSubstreamDecoder::new(stream_info, data_stream)?)
-        } else {
-            Box::new(StandardSubstreamDecoder::new(stream_info, data_stream)?)
-        };
+    let decoder: Box<dyn Decoder + Send> = match stream_info.stream_format.compression {
+        AudioCompression::Adpcm => Box::new(AdpcmSubstreamDecoder::new(stream_info, data_stream)?),
+        // Each chunk holds exactly one raw AAC frame, so they're passed to the decoder as is.
+        #[cfg(feature = "aac")]
+        AudioCompression::Aac => Box::new(AacDecoder::new_raw(
+            stream_info.codec_config.as_deref(),
+            &stream_info.stream_format,
+            SubstreamTagReader::new(stream_info, data_stream),
+        )?),
+        _ => Box::new(StandardSubstreamDecoder::new(stream_info, data_stream)?),
+    };
     Ok(decoder)
 }
 
@@ -0,0 +1,442 @@
+use crate::backend::audio::decoders::{Decoder, SeekableDecoder};
+use crate::buffer::Slice;
+use std::io::{Cursor, Read};
+use swf::SoundFormat;
+use symphonia::{
+    core::{
+        self, audio,
+        codecs::{self, Decoder as _},
+        errors,
+        formats::{self, FormatReader},
+        io,
+    },
+    default::{codecs::AacDecoder as SymphoniaAacDecoder, formats::AdtsReader},
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Couldn't decode AAC frame")]
+    FrameDecode(#[from] errors::Error),
+
+    #[error("No default track")]
+    NoDefaultTrack,
+
+    #[error("Invalid AudioSpecificConfig")]
+    InvalidConfig,
+
+    #[error("Invalid sample rate")]
+    InvalidSampleRate,
+
+    #[error("Invalid channels")]
+    InvalidChannels,
+}
+
+/// The sample rates that an `AudioSpecificConfig` can refer to by index.
+const SAMPLE_RATES: [u32; 13] = [
+    96000, 88200, 64000, 48000, 44100, 32000, 24000, 22050, 16000, 12000, 11025, 8000, 7350,
+];
+
+/// Where the AAC frames come from.
+enum PacketSource {
+    /// An ADTS stream, where each frame has its own header. Used by SWF sounds.
+    Adts(AdtsReader),
+
+    /// Raw AAC frames, one per item. Used by FLV streams, where the
+    /// `AudioSpecificConfig` is sent ahead of the frames in a sequence header.
+    Raw {
+        frames: Box<dyn Iterator<Item = Slice> + Send + Sync>,
+        timestamp: u64,
+    },
+}
+
+impl PacketSource {
+    fn next_packet(&mut self) -> Option<formats::Packet> {
+        match self {
+            PacketSource::Adts(reader) => reader.next_packet().ok(),
+            PacketSource::Raw { frames, timestamp } => {
+                let frame = frames.next()?;
+                let packet = formats::Packet::new_from_slice(
+                    0,
+                    *timestamp,
+                    AacDecoder::SAMPLE_BUFFER_DURATION,
+                    &frame.data(),
+                );
+                *timestamp += AacDecoder::SAMPLE_BUFFER_DURATION;
+                Some(packet)
+            }
+        }
+    }
+}
+
+/// AAC-LC decoder.
+pub struct AacDecoder {
+    source: PacketSource,
+    decoder: SymphoniaAacDecoder,
+    sample_buf: audio::SampleBuffer<i16>,
+    cur_sample: usize,
+    sample_rate: u16,
+    num_channels: u8,
+    stream_ended: bool,
+}
+
+impl AacDecoder {
+    // AAC-LC frames contain 1024 samples.
+    const SAMPLE_BUFFER_DURATION: u64 = 1024;
+
+    /// Creates a decoder for an ADTS stream.
+    pub fn new<R: 'static + Read + Send + Sync>(reader: R) -> Result<Self, Error> {
+        let source = Box::new(io::ReadOnlySource::new(reader)) as Box<dyn io::MediaSource>;
+        Self::new_adts(source)
+    }
+
+    /// Creates a decoder for an ADTS stream that can be seeked.
+    pub fn new_seekable<R: 'static + AsRef<[u8]> + Send + Sync>(
+        reader: Cursor<R>,
+    ) -> Result<Self, Error> {
+        Self::new_adts(Box::new(reader))
+    }
+
+    fn new_adts(source: Box<dyn io::MediaSource>) -> Result<Self, Error> {
+        let source = io::MediaSourceStream::new(source, Default::default());
+        let reader = AdtsReader::try_new(source, &Default::default())?;
+        let track = reader.default_track().ok_or(Error::NoDefaultTrack)?;
+        let codec_params = track.codec_params.clone();
+        let sample_rate = codec_params.sample_rate.ok_or(Error::InvalidSampleRate)?;
+        let channels = codec_params.channels.ok_or(Error::InvalidChannels)?;
+        Self::with_source(
+            PacketSource::Adts(reader),
+            &codec_params,
+            sample_rate,
+            channels.count(),
+        )
+    }
+
+    /// Creates a decoder for raw AAC frames.
+    ///
+    /// `config` is the `AudioSpecificConfig` of the stream. Without one, the
+    /// sample rate and channels of `format` are used instead.
+    pub fn new_raw(
+        config: Option<&[u8]>,
+        format: &SoundFormat,
+        frames: impl Iterator<Item = Slice> + Send + Sync + 'static,
+    ) -> Result<Self, Error> {
+        let format_channels = if format.is_stereo { 2 } else { 1 };
+        // The config is read here rather than passed on as extra data, as Symphonia can't handle
+        // an explicit sampling frequency or a channel layout given by a program config element.
+        let (sample_rate, num_channels) = if let Some(config) = config {
+            let config = parse_audio_specific_config(config)?;
+            (
+                config.sample_rate,
+                config.num_channels.unwrap_or(format_channels),
+            )
+        } else {
+            (format.sample_rate.into(), format_channels)
+        };
+        let spec = decoder_spec(sample_rate, num_channels)?;
+        let mut codec_params = codecs::CodecParameters::new();
+        codec_params
+            .for_codec(codecs::CODEC_TYPE_AAC)
+            .with_sample_rate(spec.rate)
+            .with_channels(spec.channels);
+        let source = PacketSource::Raw {
+            frames: Box::new(frames),
+            timestamp: 0,
+        };
+        Self::with_source(source, &codec_params, sample_rate, num_channels)
+    }
+
+    fn with_source(
+        source: PacketSource,
+        codec_params: &codecs::CodecParameters,
+        sample_rate: u32,
+        num_channels: usize,
+    ) -> Result<Self, Error> {
+        let decoder = SymphoniaAacDecoder::try_new(codec_params, &Default::default())?;
+        Ok(AacDecoder {
+            source,
+            decoder,
+            sample_buf: audio::SampleBuffer::new(
+                Self::SAMPLE_BUFFER_DURATION,
+                decoder_spec(sample_rate, num_channels)?,
+            ),
+            cur_sample: 0,
+            num_channels: num_channels
+                .try_into()
+                .map_err(|_| Error::InvalidChannels)?,
+            sample_rate: sample_rate
+                .try_into()
+                .map_err(|_| Error::InvalidSampleRate)?,
+            stream_ended: false,
+        })
+    }
+
+    fn next_frame(&mut self) {
+        if self.stream_ended {
+            return;
+        }
+
+        self.cur_sample = 0;
+        while let Some(packet) = self.source.next_packet() {
+            match self.decoder.decode(&packet) {
+                Ok(decoded) => {
+                    if self.sample_buf.capacity() < decoded.capacity() {
+                        // Ensure our buffer has enough space for the decoded samples.
+                        self.sample_buf = audio::SampleBuffer::new(
+                            decoded.capacity() as core::units::Duration,
+                            *decoded.spec(),
+                        );
+                    }
+                    self.sample_buf.copy_interleaved_ref(decoded);
+                    return;
+                }
+                // Decode errors are not fatal.
+                Err(errors::Error::DecodeError(_)) => (),
+                Err(_) => break,
+            }
+        }
+        // EOF reached.
+        self.stream_ended = true;
+    }
+}
+
+/// The signal spec of a stream with the given sample rate and number of channels.
+fn decoder_spec(sample_rate: u32, num_channels: usize) -> Result<audio::SignalSpec, Error> {
+    let channels = match num_channels {
+        1 => audio::Channels::FRONT_CENTRE,
+        2 => audio::Channels::FRONT_LEFT | audio::Channels::FRONT_RIGHT,
+        _ => return Err(Error::InvalidChannels),
+    };
+    Ok(audio::SignalSpec::new(sample_rate, channels))
+}
+
+/// The parts of an `AudioSpecificConfig` that are needed to decode AAC-LC.
+#[derive(Debug, PartialEq, Eq)]
+struct AudioSpecificConfig {
+    sample_rate: u32,
+
+    /// `None` when the channels are described by a program config element instead.
+    num_channels: Option<usize>,
+}
+
+/// Reads the sample rate and number of channels from an `AudioSpecificConfig`,
+/// as defined by ISO/IEC 14496-3.
+fn parse_audio_specific_config(config: &[u8]) -> Result<AudioSpecificConfig, Error> {
+    let mut bytes = [0; 8];
+    let len = config.len().min(bytes.len());
+    bytes[..len].copy_from_slice(&config[..len]);
+    let bits = u64::from_be_bytes(bytes);
+
+    let mut pos = 0;
+    let mut read = |count: u32| {
+        pos += count;
+        (bits >> (64 - pos)) & ((1 << count) - 1)
+    };
+
+    // The audio object type, with 31 escaping to a larger type.
+    if read(5) == 31 {
+        read(6);
+    }
+    // The sampling frequency index, with 15 meaning it's given explicitly.
+    let sample_rate = match read(4) {
+        15 => read(24) as u32,
+        index => SAMPLE_RATES.get(index as usize).copied().unwrap_or(0),
+    };
+    let num_channels = match read(4) {
+        0 => None,
+        channels => Some(channels as usize),
+    };
+
+    if pos as usize > config.len() * 8 {
+        return Err(Error::InvalidConfig);
+    }
+    if sample_rate == 0 {
+        return Err(Error::InvalidSampleRate);
+    }
+    Ok(AudioSpecificConfig {
+        sample_rate,
+        num_channels,
+    })
+}
+
+impl Iterator for AacDecoder {
+    type Item = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cur_sample >= self.sample_buf.len() {
+            self.next_frame();
+            if self.stream_ended {
+                return None;
+            }
+        }
+
+        let sample_buf = self.sample_buf.samples();
+        if self.num_channels == 2 {
+            let samples: [i16; 2] = [sample_buf[self.cur_sample], sample_buf[self.cur_sample + 1]];
+            self.cur_sample += 2;
+            Some(samples)
+        } else {
+            let sample = sample_buf[self.cur_sample];
+            self.cur_sample += 1;
+            Some([sample, sample])
+        }
+    }
+}
+
+impl SeekableDecoder for AacDecoder {
+    #[inline]
+    fn reset(&mut self) {
+        self.seek_to_sample_frame(0);
+    }
+
+    #[inline]
+    fn seek_to_sample_frame(&mut self, frame: u32) {
+        // Raw frames come from a stream, which can't be seeked.
+        let PacketSource::Adts(reader) = &mut self.source else {
+            return;
+        };
+        let seek_result = reader.seek(
+            formats::SeekMode::Accurate,
+            formats::SeekTo::TimeStamp {
+                track_id: 0,
+                ts: frame.into(),
+            },
+        );
+        self.sample_buf.clear();
+        self.decoder.reset();
+        self.cur_sample = 0;
+        self.stream_ended = false;
+        // Seeking isn't exact, so we may end up slightly before our desired position.
+        // Pump samples until we get to the exact position.
+        let samples_remaining =
+            seek_result.map_or(0, |seek| seek.required_ts.saturating_sub(seek.actual_ts));
+        for _ in 0..samples_remaining {
+            self.next();
+        }
+    }
+}
+
+impl Decoder for AacDecoder {
+    #[inline]
+    fn num_channels(&self) -> u8 {
+        self.num_channels
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u16 {
+        self.sample_rate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn audio_specific_config() {
+        // AAC-LC, 44.1 kHz, stereo.
+        assert_eq!(
+            parse_audio_specific_config(&[0x12, 0x10]).unwrap(),
+            AudioSpecificConfig {
+                sample_rate: 44100,
+                num_channels: Some(2),
+            }
+        );
+    }
+
+    #[test]
+    fn audio_specific_config_explicit_frequency() {
+        // AAC-LC, frequency index 15 followed by 22050 Hz as 24 bits, mono.
+        assert_eq!(
+            parse_audio_specific_config(&[0x17, 0x80, 0x2B, 0x11, 0x08]).unwrap(),
+            AudioSpecificConfig {
+                sample_rate: 22050,
+                num_channels: Some(1),
+            }
+        );
+    }
+
+    #[test]
+    fn audio_specific_config_program_config_element() {
+        // AAC-LC, 44.1 kHz, channel configuration 0.
+        assert_eq!(
+            parse_audio_specific_config(&[0x12, 0x00]).unwrap(),
+            AudioSpecificConfig {
+                sample_rate: 44100,
+                num_channels: None,
+            }
+        );
+    }
+
+    #[test]
+    fn audio_specific_config_truncated() {
+        assert!(matches!(
+            parse_audio_specific_config(&[]),
+            Err(Error::InvalidConfig)
+        ));
+        assert!(matches!(
+            parse_audio_specific_config(&[0x12]),
+            Err(Error::InvalidConfig)
+        ));
+        assert!(matches!(
+            parse_audio_specific_config(&[0x17, 0x80, 0x2B]),
+            Err(Error::InvalidConfig)
+        ));
+    }
+
+    #[test]
+    fn audio_specific_config_reserved_frequency() {
+        // Frequency index 13 is reserved.
+        assert!(matches!(
+            parse_audio_specific_config(&[0x16, 0x90]),
+            Err(Error::InvalidSampleRate)
+        ));
+    }
+
+    #[test]
+    fn decodes_raw_frames_from_substream() {
+        use crate::backend::audio::decoders::make_substream_decoder;
+        use crate::backend::audio::{SoundStreamInfo, SoundStreamWrapping};
+        use crate::buffer::{Buffer, Substream};
+        use swf::AudioCompression;
+
+        // A mono AAC-LC frame holding a single spectral line of magnitude 1 in the first
+        // scalefactor band, with a global gain of 180.
+        let frame = [0x01, 0x68, 0x00, 0x84, 0x21, 0x0E];
+        let buffer = Buffer::from(frame.repeat(3));
+        let mut substream = Substream::new(buffer.clone());
+        for i in 0..3 {
+            let chunk = buffer.get(i * frame.len()..(i + 1) * frame.len()).unwrap();
+            substream.append(chunk).unwrap();
+        }
+
+        let stream_info = SoundStreamInfo {
+            wrapping: SoundStreamWrapping::Unwrapped,
+            stream_format: SoundFormat {
+                compression: AudioCompression::Aac,
+                sample_rate: 44000,
+                is_stereo: true,
+                is_16_bit: true,
+            },
+            num_samples_per_block: 0,
+            latency_seek: 0,
+            // AAC-LC, 44.1 kHz, mono.
+            codec_config: Some(vec![0x12, 0x08]),
+        };
+        let decoder = make_substream_decoder(&stream_info, substream).unwrap();
+        assert_eq!(decoder.num_channels(), 1);
+        assert_eq!(decoder.sample_rate(), 44100);
+
+        let samples: Vec<[i16; 2]> = decoder.collect();
+        assert_eq!(samples.len(), 3 * 1024);
+        assert!(samples.iter().all(|[left, right]| left == right));
+        for decoded_frame in samples.chunks(1024) {
+            let peak = decoded_frame
+                .iter()
+                .map(|[sample, _]| sample.unsigned_abs())
+                .max();
+            assert!(peak.is_some_and(|peak| (700..750).contains(&peak)));
+        }
+    }
+}
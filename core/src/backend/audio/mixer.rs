@@ -321,6 +321,8 @@ impl AudioMixer {
             )?),
             #[cfg(feature = "mp3")]
             AudioCompression::Mp3 => Box::new(decoders::Mp3Decoder::new_seekable(data)?),
+            #[cfg(feature = "aac")]
+            AudioCompression::Aac => Box::new(decoders::AacDecoder::new_seekable(data)?),
             #[cfg(feature = "nellymoser")]
             AudioCompression::Nellymoser => Box::new(decoders::NellymoserDecoder::new(
                 data,
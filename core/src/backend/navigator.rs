@@ -1,6 +1,7 @@
 //! Browser-related platform functions
 
 use crate::loader::Error;
+use crate::player::ParseEnumError;
 use crate::socket::{ConnectionState, SocketAction, SocketHandle};
 use crate::string::WStr;
 use async_channel::{Receiver, Sender};
@@ -14,6 +15,7 @@ use std::future::Future;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
+use std::str::FromStr;
 use std::time::Duration;
 use swf::avm1::types::SendVarsMethod;
 use url::{ParseError, Url};
@@ -55,6 +57,20 @@ pub enum SocketMode {
     Ask,
 }
 
+impl FromStr for SocketMode {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let socket_mode = match s.to_ascii_lowercase().as_str() {
+            "allow" => SocketMode::Allow,
+            "deny" => SocketMode::Deny,
+            "ask" => SocketMode::Ask,
+            _ => return Err(ParseEnumError),
+        };
+        Ok(socket_mode)
+    }
+}
+
 /// The handling mode of links opening a new website.
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
@@ -1,5 +1,26 @@
 use std::collections::HashMap;
 
+/// Whether a shared object of a given size may be stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuotaCheck {
+    /// The shared object fits within its domain's quota.
+    Allowed,
+
+    /// The shared object doesn't fit. Flash Player would ask the user for more space,
+    /// so `flush` reports that the request is pending.
+    Exceeded,
+
+    /// The domain may not store any data at all.
+    Denied,
+}
+
+/// Returns the domain that a shared object's name belongs to.
+///
+/// Names are of the form `host/path/to/movie.swf/name`, so this is the host.
+pub fn domain_of(name: &str) -> &str {
+    name.split('/').next().unwrap_or_default()
+}
+
 pub trait StorageBackend {
     fn get(&self, name: &str) -> Option<Vec<u8>>;
 
@@ -10,11 +31,57 @@ pub trait StorageBackend {
     }
 
     fn remove_key(&mut self, name: &str);
+
+    /// Lists the names of all stored shared objects.
+    fn list_keys(&self) -> Vec<String>;
+
+    /// The number of bytes a domain may store, or `None` if it may store any amount.
+    ///
+    /// Quotas are only enforced once the user has set one with `set_domain_quota`.
+    fn domain_quota(&self, _domain: &str) -> Option<usize> {
+        None
+    }
+
+    /// Changes the number of bytes a domain may store, or lifts its limit if `quota` is `None`.
+    ///
+    /// Returns `false` if this backend can't store quotas.
+    fn set_domain_quota(&mut self, _domain: &str, _quota: Option<usize>) -> bool {
+        false
+    }
+
+    /// The number of bytes currently stored by a domain.
+    fn domain_usage(&self, domain: &str) -> usize {
+        self.list_keys()
+            .iter()
+            .filter(|name| domain_of(name) == domain)
+            .filter_map(|name| self.get_size(name))
+            .sum()
+    }
+
+    /// Checks whether `name` may be replaced with `size` bytes of data.
+    fn check_quota(&self, name: &str, size: usize) -> QuotaCheck {
+        let domain = domain_of(name);
+        let Some(quota) = self.domain_quota(domain) else {
+            return QuotaCheck::Allowed;
+        };
+        if quota == 0 {
+            return QuotaCheck::Denied;
+        }
+
+        let existing = self.get_size(name).unwrap_or_default();
+        let usage = self.domain_usage(domain).saturating_sub(existing);
+        if usage + size > quota {
+            QuotaCheck::Exceeded
+        } else {
+            QuotaCheck::Allowed
+        }
+    }
 }
 
 #[derive(Default)]
 pub struct MemoryStorageBackend {
     map: HashMap<String, Vec<u8>>,
+    quotas: HashMap<String, usize>,
 }
 
 impl MemoryStorageBackend {
@@ -33,7 +100,29 @@ impl StorageBackend for MemoryStorageBackend {
         true
     }
 
+    fn get_size(&self, name: &str) -> Option<usize> {
+        self.map.get(name).map(|x| x.len())
+    }
+
     fn remove_key(&mut self, name: &str) {
         self.map.remove(name);
     }
+
+    fn list_keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.map.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    fn domain_quota(&self, domain: &str) -> Option<usize> {
+        self.quotas.get(domain).copied()
+    }
+
+    fn set_domain_quota(&mut self, domain: &str, quota: Option<usize>) -> bool {
+        match quota {
+            Some(quota) => self.quotas.insert(domain.to_string(), quota),
+            None => self.quotas.remove(domain),
+        };
+        true
+    }
 }
@@ -71,12 +71,58 @@ pub trait UiBackend: Downcast {
     /// Changes the mouse cursor image.
     fn set_mouse_cursor(&mut self, cursor: MouseCursor);
 
+    /// Registers a bitmap cursor under the given name, replacing any cursor
+    /// previously registered with that name.
+    ///
+    /// The cursor is displayed once `set_mouse_cursor` is called with
+    /// `MouseCursor::Custom` and the same name.
+    /// Backends that can't display custom cursors don't need to implement this.
+    fn register_mouse_cursor(&mut self, _name: String, _cursor: CustomMouseCursor) {}
+
+    /// Removes a cursor registered with `register_mouse_cursor`.
+    fn unregister_mouse_cursor(&mut self, _name: &str) {}
+
     /// Get the clipboard content
     fn clipboard_content(&mut self) -> String;
 
     /// Sets the clipboard to the given content.
     fn set_clipboard_content(&mut self, content: String);
 
+    /// Lists the formats that the clipboard currently holds content in.
+    ///
+    /// Backends that only support plain text don't need to implement this.
+    fn clipboard_formats(&mut self) -> Vec<ClipboardFormat> {
+        if self.clipboard_content().is_empty() {
+            vec![]
+        } else {
+            vec![ClipboardFormat::Text]
+        }
+    }
+
+    /// Get the clipboard content in the given format, if there is any.
+    ///
+    /// Backends that only support plain text don't need to implement this.
+    fn clipboard_content_in_format(&mut self, format: ClipboardFormat) -> Option<String> {
+        if format == ClipboardFormat::Text {
+            Some(self.clipboard_content()).filter(|content| !content.is_empty())
+        } else {
+            None
+        }
+    }
+
+    /// Replaces the clipboard with the given content, which may be in several formats.
+    ///
+    /// Backends that only support plain text don't need to implement this;
+    /// by default, only the `Text` content is kept.
+    fn set_clipboard_content_in_formats(&mut self, content: Vec<(ClipboardFormat, String)>) {
+        let text = content
+            .into_iter()
+            .find(|(format, _)| *format == ClipboardFormat::Text)
+            .map(|(_, content)| content)
+            .unwrap_or_default();
+        self.set_clipboard_content(text);
+    }
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError>;
 
     /// Displays a message about an error during root movie download.
@@ -130,7 +176,7 @@ impl_downcast!(UiBackend);
 
 /// A mouse cursor icon displayed by the Flash Player.
 /// Communicated from the core to the UI backend via `UiBackend::set_mouse_cursor`.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum MouseCursor {
     /// The default arrow icon.
     /// Equivalent to AS3 `MouseCursor.ARROW`.
@@ -147,6 +193,53 @@ pub enum MouseCursor {
     /// The grabby-dragging hand icon.
     /// Equivalent to AS3 `MouseCursor.HAND`.
     Grab,
+
+    /// A bitmap cursor registered with `UiBackend::register_mouse_cursor`.
+    /// Equivalent to a name registered with AS3 `Mouse.registerCursor`.
+    Custom(String),
+}
+
+/// A bitmap cursor registered by a movie.
+/// Equivalent to the AS3 `MouseCursorData` given to `Mouse.registerCursor`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomMouseCursor {
+    /// The width of every frame, in pixels.
+    pub width: u32,
+
+    /// The height of every frame, in pixels.
+    pub height: u32,
+
+    /// The frames of the cursor, as non-premultiplied RGBA pixels.
+    /// Cursors with more than one frame are animated.
+    pub frames: Vec<Vec<u8>>,
+
+    /// The point within a frame that the cursor points at, in pixels.
+    pub hotspot: (u32, u32),
+
+    /// The rate at which the frames of an animated cursor are cycled through,
+    /// in frames per second.
+    pub frame_rate: f64,
+}
+
+/// A format that clipboard content can be stored in.
+/// Equivalent to the AS3 `ClipboardFormats` that Flash Player supports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ClipboardFormat {
+    /// Plain text, as returned by `UiBackend::clipboard_content`.
+    /// Equivalent to AS3 `ClipboardFormats.TEXT_FORMAT`.
+    Text,
+
+    /// HTML markup.
+    /// Equivalent to AS3 `ClipboardFormats.HTML_FORMAT`.
+    Html,
+
+    /// Rich Text Format markup.
+    /// Equivalent to AS3 `ClipboardFormats.RICH_TEXT_FORMAT`.
+    RichText,
+
+    /// A URL.
+    /// Equivalent to AS3 `ClipboardFormats.URL_FORMAT`.
+    Url,
 }
 
 pub struct InputManager {
@@ -837,3 +837,10 @@ impl ThresholdOperation {
         }
     }
 }
+
+/// An image format that a `BitmapData` can be encoded to.
+#[derive(Copy, Clone, Debug)]
+pub enum ImageEncoding {
+    Png { fast_compression: bool },
+    Jpeg { quality: u8 },
+}
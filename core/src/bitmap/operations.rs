@@ -4,7 +4,7 @@ use crate::avm2::vector::VectorStorage;
 use crate::avm2::{Activation, Error, Value as Avm2Value};
 use crate::bitmap::bitmap_data::{
     BitmapData, BitmapDataDrawError, BitmapDataWrapper, ChannelOptions, Color, IBitmapDrawable,
-    LehmerRng, ThresholdOperation,
+    ImageEncoding, LehmerRng, ThresholdOperation,
 };
 use crate::bitmap::turbulence::Turbulence;
 use crate::context::{RenderContext, UpdateContext};
@@ -1691,6 +1691,110 @@ pub fn get_pixels_as_byte_array<'gc>(
     Ok(())
 }
 
+/// Encodes the given region of a BitmapData as an image, writing it to `result`.
+#[allow(clippy::too_many_arguments)]
+pub fn encode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    target: BitmapDataWrapper<'gc>,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+    encoding: ImageEncoding,
+    result: &mut ByteArrayStorage,
+) -> Result<(), Error<'gc>> {
+    let mut region = PixelRegion::for_region_i32(x, y, width, height);
+    region.clamp(target.width(), target.height());
+    if region.width() == 0 || region.height() == 0 {
+        return Ok(());
+    }
+
+    // JPEG has no alpha channel, so it's dropped even from transparent images.
+    let has_alpha = target.transparency() && matches!(encoding, ImageEncoding::Png { .. });
+    let channels = if has_alpha { 4 } else { 3 };
+    let mut pixels = Vec::with_capacity((region.width() * region.height()) as usize * channels);
+    let read = target.read_area(region, activation.context.renderer);
+    for y in region.y_min..region.y_max {
+        for x in region.x_min..region.x_max {
+            let color = read.get_pixel32_raw(x, y).to_un_multiplied_alpha();
+            pixels.extend_from_slice(&[color.red(), color.green(), color.blue()]);
+            if has_alpha {
+                pixels.push(color.alpha());
+            }
+        }
+    }
+    drop(read);
+
+    let mut encoded = Vec::new();
+    match encoding {
+        ImageEncoding::Png { fast_compression } => {
+            let mut encoder = png::Encoder::new(&mut encoded, region.width(), region.height());
+            encoder.set_color(if has_alpha {
+                png::ColorType::Rgba
+            } else {
+                png::ColorType::Rgb
+            });
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.set_compression(if fast_compression {
+                png::Compression::Fast
+            } else {
+                png::Compression::Default
+            });
+            let mut writer = encoder
+                .write_header()
+                .map_err(|e| Error::RustError(e.into()))?;
+            writer
+                .write_image_data(&pixels)
+                .map_err(|e| Error::RustError(e.into()))?;
+            writer.finish().map_err(|e| Error::RustError(e.into()))?;
+        }
+        ImageEncoding::Jpeg { quality } => {
+            // BitmapData dimensions always fit in 16 bits.
+            jpeg_encoder::Encoder::new(&mut encoded, quality.clamp(1, 100))
+                .encode(
+                    &pixels,
+                    region.width() as u16,
+                    region.height() as u16,
+                    jpeg_encoder::ColorType::Rgb,
+                )
+                .map_err(|e| Error::RustError(e.into()))?;
+        }
+    }
+
+    result
+        .write_bytes(&encoded)
+        .map_err(|e| e.to_avm(activation))
+}
+
+/// Counts how often each value of each channel occurs in the given region of a BitmapData.
+///
+/// The counts are returned in red, green, blue, alpha order.
+pub fn histogram<'gc>(
+    target: BitmapDataWrapper<'gc>,
+    renderer: &mut dyn RenderBackend,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+) -> [[f64; 256]; 4] {
+    let mut region = PixelRegion::for_region_i32(x, y, width, height);
+    region.clamp(target.width(), target.height());
+
+    let mut counts = [[0.0; 256]; 4];
+    let read = target.read_area(region, renderer);
+    for y in region.y_min..region.y_max {
+        for x in region.x_min..region.x_max {
+            let color = read.get_pixel32_raw(x, y).to_un_multiplied_alpha();
+            counts[0][color.red() as usize] += 1.0;
+            counts[1][color.green() as usize] += 1.0;
+            counts[2][color.blue() as usize] += 1.0;
+            counts[3][color.alpha() as usize] += 1.0;
+        }
+    }
+
+    counts
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn set_pixels_from_byte_array<'gc>(
     mc: &Mutation<'gc>,
@@ -1,4 +1,6 @@
+use crate::player::ParseEnumError;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 /// Controls whether the content is letterboxed or pillarboxed when the
 /// player's aspect ratio does not match the movie's aspect ratio.
@@ -22,6 +24,20 @@ pub enum Letterbox {
     On,
 }
 
+impl FromStr for Letterbox {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let letterbox = match s.to_ascii_lowercase().as_str() {
+            "off" => Letterbox::Off,
+            "fullscreen" => Letterbox::Fullscreen,
+            "on" => Letterbox::On,
+            _ => return Err(ParseEnumError),
+        };
+        Ok(letterbox)
+    }
+}
+
 /// The networking API access mode of the Ruffle player.
 /// This setting is only used on web.
 #[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
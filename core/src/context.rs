@@ -18,7 +18,7 @@ use crate::backend::{
     log::LogBackend,
     navigator::NavigatorBackend,
     storage::StorageBackend,
-    ui::{InputManager, UiBackend},
+    ui::{InputManager, MouseCursor, UiBackend},
 };
 use crate::context_menu::ContextMenuState;
 use crate::display_object::{EditText, InteractiveObject, MovieClip, SoundTransform, Stage};
@@ -159,6 +159,12 @@ pub struct UpdateContext<'a, 'gc> {
     /// The location of the mouse when it was last over the player.
     pub mouse_position: &'a Point<Twips>,
 
+    /// The cursor chosen for the object that the mouse is currently over.
+    pub mouse_cursor: &'a MouseCursor,
+
+    /// The cursor set by AS3 `Mouse.cursor`, which replaces the automatically chosen cursor.
+    pub mouse_cursor_override: &'a mut Option<MouseCursor>,
+
     /// The object being dragged via a `startDrag` action.
     pub drag_object: &'a mut Option<crate::player::DragObject<'gc>>,
 
@@ -509,6 +515,8 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             mouse_down_object: self.mouse_down_object,
             input: self.input,
             mouse_position: self.mouse_position,
+            mouse_cursor: self.mouse_cursor,
+            mouse_cursor_override: self.mouse_cursor_override,
             drag_object: self.drag_object,
             player: self.player.clone(),
             load_manager: self.load_manager,
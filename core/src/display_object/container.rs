@@ -7,6 +7,7 @@ use crate::avm2::{
 };
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::avm1_button::Avm1Button;
+use crate::display_object::edit_text::EditText;
 use crate::display_object::loader_display::LoaderDisplay;
 use crate::display_object::movie_clip::MovieClip;
 use crate::display_object::stage::Stage;
@@ -123,6 +124,7 @@ pub fn dispatch_added_event<'gc>(
         Avm1Button(Avm1Button<'gc>),
         MovieClip(MovieClip<'gc>),
         LoaderDisplay(LoaderDisplay<'gc>),
+        EditText(EditText<'gc>),
     }
 )]
 pub trait TDisplayObjectContainer<'gc>:
@@ -564,6 +566,7 @@ impl<'gc> From<DisplayObjectContainer<'gc>> for DisplayObject<'gc> {
             DisplayObjectContainer::Avm1Button(o) => DisplayObject::Avm1Button(o),
             DisplayObjectContainer::MovieClip(o) => DisplayObject::MovieClip(o),
             DisplayObjectContainer::LoaderDisplay(o) => DisplayObject::LoaderDisplay(o),
+            DisplayObjectContainer::EditText(o) => DisplayObject::EditText(o),
         }
     }
 }
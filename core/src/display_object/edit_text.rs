@@ -14,6 +14,7 @@ use crate::avm2::{
 };
 use crate::backend::ui::MouseCursor;
 use crate::context::{RenderContext, UpdateContext};
+use crate::display_object::container::ChildContainer;
 use crate::display_object::interactive::{
     InteractiveObject, InteractiveObjectBase, TInteractiveObject,
 };
@@ -22,7 +23,8 @@ use crate::drawing::Drawing;
 use crate::events::{ClipEvent, ClipEventResult, TextControlCode};
 use crate::font::{round_down_to_pixel, FontType, Glyph, TextRenderSettings};
 use crate::html::{
-    BoxBounds, FormatSpans, LayoutBox, LayoutContent, LayoutMetrics, Position, TextFormat,
+    BoxBounds, FormatSpans, LayoutBox, LayoutContent, LayoutMetrics, Position, StyleSheet,
+    TextFormat,
 };
 use crate::prelude::*;
 use crate::string::{utils as string_utils, AvmString, SwfStrExt as _, WStr, WString};
@@ -38,6 +40,7 @@ use ruffle_render::transform::Transform;
 use ruffle_wstr::WStrToUtf8;
 use std::cmp::Ordering;
 use std::collections::VecDeque;
+use std::ops::Range;
 use std::{cell::Ref, cell::RefMut, sync::Arc};
 use swf::ColorTransform;
 use unic_segment::WordBoundIndices;
@@ -95,6 +98,20 @@ pub struct EditTextData<'gc> {
     #[collect(require_static)]
     text_spans: FormatSpans,
 
+    /// The CSS stylesheet applied to HTML text set on this field, if any.
+    #[collect(require_static)]
+    style_sheet: Option<StyleSheet>,
+
+    /// The `StyleSheet` object that `style_sheet` was created from (AVM1 only).
+    style_sheet_object: Option<Avm1Object<'gc>>,
+
+    /// The HTML that the text was last set from while a stylesheet was present.
+    ///
+    /// Styled text can't be raised back to HTML, so this is returned as-is
+    /// by `htmlText` instead.
+    #[collect(require_static)]
+    original_html_text: Option<WString>,
+
     /// The color of the background fill. Only applied when has_border and has_background.
     #[collect(require_static)]
     background_color: Color,
@@ -177,6 +194,10 @@ pub struct EditTextData<'gc> {
     /// Whether this EditText represents an AVM2 TextLine.
     is_tlf: bool,
 
+    /// The children of this text (AVM2 TextLine only), such as the graphics
+    /// of its `GraphicElement`s.
+    container: ChildContainer<'gc>,
+
     /// Restrict what characters the user may input.
     #[collect(require_static)]
     restrict: EditTextRestrict,
@@ -188,6 +209,15 @@ pub struct EditTextData<'gc> {
 }
 
 impl<'gc> EditTextData<'gc> {
+    /// The offset between the matrix translation and the reported position.
+    fn position_offset(&self) -> Point<Twips> {
+        if self.is_tlf {
+            Point::ZERO
+        } else {
+            Point::new(self.bounds.x_min, self.bounds.y_min)
+        }
+    }
+
     fn vertical_scroll_offset(&self) -> Twips {
         if self.scroll > 1 {
             let line_data = &self.line_data;
@@ -269,6 +299,7 @@ impl<'gc> EditText<'gc> {
             FormatSpans::from_html(
                 &text,
                 default_format,
+                None,
                 swf_tag.is_multiline(),
                 swf_movie.version(),
             )
@@ -334,10 +365,13 @@ impl<'gc> EditText<'gc> {
             EditTextData {
                 base,
                 text_spans,
+                style_sheet: None,
+                style_sheet_object: None,
+                original_html_text: None,
                 static_data: gc_arena::Gc::new(
                     context.gc_context,
                     EditTextStatic {
-                        swf: swf_movie,
+                        swf: swf_movie.clone(),
                         id: swf_tag.id(),
                         layout: swf_tag.layout().cloned(),
                         initial_text: swf_tag
@@ -366,6 +400,7 @@ impl<'gc> EditText<'gc> {
                 max_chars: swf_tag.max_length().unwrap_or_default() as i32,
                 mouse_wheel_enabled: true,
                 is_tlf: false,
+                container: ChildContainer::new(swf_movie),
                 restrict: EditTextRestrict::allow_all(),
                 tab_index: None,
             },
@@ -438,6 +473,7 @@ impl<'gc> EditText<'gc> {
         let mut edit_text = self.0.write(context.gc_context);
         let default_format = edit_text.text_spans.default_format().clone();
         edit_text.text_spans = FormatSpans::from_text(text.into(), default_format);
+        edit_text.original_html_text = None;
         drop(edit_text);
 
         self.relayout(context);
@@ -445,7 +481,11 @@ impl<'gc> EditText<'gc> {
 
     pub fn html_text(self) -> WString {
         if self.is_html() {
-            self.0.read().text_spans.to_html()
+            let read = self.0.read();
+            if let Some(html) = &read.original_html_text {
+                return html.clone();
+            }
+            read.text_spans.to_html()
         } else {
             // Non-HTML text fields always return plain text.
             self.text()
@@ -459,9 +499,11 @@ impl<'gc> EditText<'gc> {
             write.text_spans = FormatSpans::from_html(
                 text,
                 default_format,
+                write.style_sheet.as_ref(),
                 write.flags.contains(EditTextFlag::MULTILINE),
                 write.static_data.swf.version(),
             );
+            write.original_html_text = write.style_sheet.is_some().then(|| text.into());
             drop(write);
 
             self.relayout(context);
@@ -470,6 +512,31 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    pub fn style_sheet_object(self) -> Option<Avm1Object<'gc>> {
+        self.0.read().style_sheet_object
+    }
+
+    /// Sets the stylesheet used to style HTML text, and reapplies the current HTML text with it.
+    ///
+    /// `object` is the AVM1 `StyleSheet` object the stylesheet came from, if any.
+    pub fn set_style_sheet(
+        self,
+        style_sheet: Option<StyleSheet>,
+        object: Option<Avm1Object<'gc>>,
+        context: &mut UpdateContext<'_, 'gc>,
+    ) {
+        let html = self.html_text();
+        let mut write = self.0.write(context.gc_context);
+        write.style_sheet = style_sheet;
+        write.style_sheet_object = object;
+        write.original_html_text = None;
+        drop(write);
+
+        if self.is_html() {
+            self.set_html_text(&html, context);
+        }
+    }
+
     pub fn text_length(self) -> usize {
         self.0.read().text_spans.text().len()
     }
@@ -665,6 +732,24 @@ impl<'gc> EditText<'gc> {
         self.0.write(gc_context).is_tlf = is_tlf;
     }
 
+    /// Move the bounds of this text so that the given point of the laid-out
+    /// text lies at the origin of the `EditText`.
+    ///
+    /// This is used by TextLines, whose origin is the start of their baseline.
+    pub fn set_text_origin(self, gc_context: &Mutation<'gc>, origin: Point<Twips>) {
+        let mut edit_text = self.0.write(gc_context);
+        let padding = Twips::from_pixels(EditText::INTERNAL_PADDING);
+        let width = edit_text.bounds.width();
+        let height = edit_text.bounds.height();
+        edit_text.bounds.x_min = -(origin.x + padding);
+        edit_text.bounds.y_min = -(origin.y + padding);
+        edit_text.bounds.set_width(width);
+        edit_text.bounds.set_height(height);
+        drop(edit_text);
+        self.redraw_border(gc_context);
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
     pub fn replace_text(
         self,
         from: usize,
@@ -672,10 +757,10 @@ impl<'gc> EditText<'gc> {
         text: &WStr,
         context: &mut UpdateContext<'_, 'gc>,
     ) {
-        self.0
-            .write(context.gc_context)
-            .text_spans
-            .replace_text(from, to, text, None);
+        let mut write = self.0.write(context.gc_context);
+        write.text_spans.replace_text(from, to, text, None);
+        write.original_html_text = None;
+        drop(write);
         self.relayout(context);
     }
 
@@ -1968,6 +2053,92 @@ impl<'gc> EditText<'gc> {
         Some(text)
     }
 
+    /// Returns the range of text laid out on the given line.
+    ///
+    /// Characters that are not part of any text box, such as tabs and line
+    /// breaks, are only included if they are surrounded by text on the line.
+    pub fn line_range(self, line: usize) -> Option<Range<usize>> {
+        let read = self.0.read();
+        let line = read.line_data.get(line).copied()?;
+
+        let mut range: Option<Range<usize>> = None;
+        for layout_box in read.layout.iter() {
+            if layout_box.bounds().offset_y() < line.offset
+                || layout_box.bounds().extent_y() > line.extent
+            {
+                continue;
+            }
+
+            if let LayoutContent::Text { start, end, .. } = layout_box.content() {
+                range = Some(match range {
+                    Some(range) => range.start.min(*start)..range.end.max(*end),
+                    None => *start..*end,
+                });
+            }
+        }
+
+        range
+    }
+
+    /// Returns the position of every character that was laid out as part of a
+    /// text box, in the order they appear in the layout.
+    ///
+    /// Positions are relative to the origin of the laid-out text.
+    pub fn char_layouts(self) -> Vec<CharLayout> {
+        let read = self.0.read();
+        let text = read.text_spans.displayed_text();
+
+        let mut char_layouts = Vec::new();
+        for layout_box in read.layout.iter() {
+            let LayoutContent::Text { start, .. } = layout_box.content() else {
+                continue;
+            };
+            let Some((box_text, _tf, font, params, _color)) = layout_box.as_renderable_text(text)
+            else {
+                continue;
+            };
+
+            let origin = layout_box.bounds().origin();
+            let ascent = font.get_baseline_for_height(params.height());
+            let descent = font.get_descent_for_height(params.height());
+
+            // Characters without a glyph are skipped by `evaluate`, so they
+            // are given no advance.
+            let mut glyphs = Vec::new();
+            font.evaluate(
+                box_text,
+                Default::default(),
+                params,
+                |pos, _transform, _glyph, advance, x| glyphs.push((pos, x, advance)),
+            );
+            let mut glyphs = glyphs.into_iter().peekable();
+
+            let mut x = Twips::ZERO;
+            for (pos, _) in box_text.char_indices() {
+                let mut advance = Twips::ZERO;
+                if let Some(&(glyph_pos, glyph_x, glyph_advance)) = glyphs.peek() {
+                    if glyph_pos == pos {
+                        glyphs.next();
+                        x = glyph_x;
+                        advance = glyph_advance;
+                    }
+                }
+
+                char_layouts.push(CharLayout {
+                    index: start + pos,
+                    x: origin.x() + x,
+                    advance,
+                    baseline: origin.y() + ascent,
+                    ascent,
+                    descent,
+                });
+                x += advance;
+            }
+        }
+
+        char_layouts
+    }
+
     fn execute_avm1_asfunction(
         self,
         context: &mut UpdateContext<'_, 'gc>,
@@ -2079,6 +2250,25 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
             self.construct_as_avm2_object(context, (*self).into());
             self.on_construction_complete(context);
         }
+
+        for child in self.iter_render_list() {
+            child.construct_frame(context);
+        }
+    }
+
+    fn enter_frame(&self, context: &mut UpdateContext<'_, 'gc>) {
+        let skip_frame = self.base().should_skip_next_enter_frame();
+        for child in self.iter_render_list() {
+            // See MovieClip::enter_frame for an explanation of this.
+            if skip_frame {
+                child
+                    .base_mut(context.gc_context)
+                    .set_skip_next_enter_frame(true);
+            }
+            child.enter_frame(context);
+        }
+        self.base_mut(context.gc_context)
+            .set_skip_next_enter_frame(false);
     }
 
     fn run_frame_avm1(&self, _context: &mut UpdateContext) {
@@ -2093,6 +2283,14 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         Some(self.into())
     }
 
+    fn as_container(self) -> Option<DisplayObjectContainer<'gc>> {
+        if self.0.read().is_tlf {
+            Some(self.into())
+        } else {
+            None
+        }
+    }
+
     fn post_instantiation(
         &self,
         context: &mut UpdateContext<'_, 'gc>,
@@ -2152,15 +2350,16 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
     }
 
     // The returned position x and y of a text field is offset by the text bounds.
+    // TextLines are positioned by their baseline instead, see `set_text_origin`.
     fn x(&self) -> Twips {
         let edit_text = self.0.read();
-        let offset = edit_text.bounds.x_min;
+        let offset = edit_text.position_offset().x;
         edit_text.base.base.x() + offset
     }
 
     fn set_x(&self, gc_context: &Mutation<'gc>, x: Twips) {
         let mut edit_text = self.0.write(gc_context);
-        let offset = edit_text.bounds.x_min;
+        let offset = edit_text.position_offset().x;
         edit_text.base.base.set_x(x - offset);
         drop(edit_text);
         self.redraw_border(gc_context);
@@ -2168,13 +2367,13 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
 
     fn y(&self) -> Twips {
         let edit_text = self.0.read();
-        let offset = edit_text.bounds.y_min;
+        let offset = edit_text.position_offset().y;
         edit_text.base.base.y() + offset
     }
 
     fn set_y(&self, gc_context: &Mutation<'gc>, y: Twips) {
         let mut edit_text = self.0.write(gc_context);
-        let offset = edit_text.bounds.y_min;
+        let offset = edit_text.position_offset().y;
         edit_text.base.base.set_y(y - offset);
         drop(edit_text);
         self.redraw_border(gc_context);
@@ -2286,6 +2485,12 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         context.commands.pop_mask();
 
         context.transform_stack.pop();
+
+        // The children of a TextLine may extend past the text, so they aren't masked.
+        if edit_text.is_tlf {
+            drop(edit_text);
+            self.render_children(context);
+        }
     }
 
     fn allow_as_mask(&self) -> bool {
@@ -2475,13 +2680,24 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         &self,
         context: &mut UpdateContext<'_, 'gc>,
         point: Point<Twips>,
-        _require_button_mode: bool,
+        require_button_mode: bool,
     ) -> Avm2MousePick<'gc> {
         // Don't do anything if run in an AVM1 context.
         if !self.as_displayobject().movie().is_action_script_3() {
             return Avm2MousePick::Miss;
         }
 
+        // The children of a TextLine are on top of its text.
+        for child in self.iter_render_list().rev() {
+            let Some(child) = child.as_interactive() else {
+                continue;
+            };
+            let res = child.mouse_pick_avm2(context, point, require_button_mode);
+            if let Avm2MousePick::Hit(_) = res {
+                return res.combine_with_parent((*self).into());
+            }
+        }
+
         // The text is hovered if the mouse is over any child nodes.
         if self.visible() && self.hit_test_shape(context, point, HitTestOptions::MOUSE_PICK) {
             // Note - for mouse-enabled selectable text, we consider this to be a hit (which
@@ -2511,6 +2727,16 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
     }
 }
 
+impl<'gc> TDisplayObjectContainer<'gc> for EditText<'gc> {
+    fn raw_container(&self) -> Ref<'_, ChildContainer<'gc>> {
+        Ref::map(self.0.read(), |this| &this.container)
+    }
+
+    fn raw_container_mut(&self, gc_context: &Mutation<'gc>) -> RefMut<'_, ChildContainer<'gc>> {
+        RefMut::map(self.0.write(gc_context), |this| &mut this.container)
+    }
+}
+
 bitflags::bitflags! {
     #[derive(Clone, Copy)]
     struct EditTextFlag: u16 {
@@ -2552,6 +2778,24 @@ pub struct TextSelection {
     blink_epoch: DateTime<Utc>,
 }
 
+/// The position of a single laid-out character, as returned by
+/// `EditText::char_layouts`.
+#[derive(Copy, Clone, Debug)]
+pub struct CharLayout {
+    /// The index of the character within the text.
+    pub index: usize,
+    /// The x-coordinate of the left edge of the character.
+    pub x: Twips,
+    /// How far the character advances the cursor.
+    pub advance: Twips,
+    /// The y-coordinate of the baseline the character sits on.
+    pub baseline: Twips,
+    /// How far the font extends above the baseline.
+    pub ascent: Twips,
+    /// How far the font extends below the baseline.
+    pub descent: Twips,
+}
+
 /// Information about the start and end y-coordinates of a given line of text
 #[derive(Copy, Clone, Debug)]
 pub struct LineData {
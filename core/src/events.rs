@@ -1,5 +1,7 @@
 use crate::display_object::InteractiveObject;
+use crate::player::ParseEnumError;
 use serde::Deserialize;
+use std::str::FromStr;
 use swf::ClipEventFlag;
 
 #[derive(Debug, Clone, Copy)]
@@ -513,6 +515,19 @@ impl KeyCode {
     }
 }
 
+impl FromStr for KeyCode {
+    type Err = ParseEnumError;
+
+    /// Parses a key name such as `a`, `key1`, `up` or `numpad-enter`, ignoring case and dashes.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.replace('-', "");
+        (0..=u8::MAX)
+            .filter_map(KeyCode::from_u8)
+            .find(|key_code| format!("{key_code:?}").eq_ignore_ascii_case(&name))
+            .ok_or(ParseEnumError)
+    }
+}
+
 /// Subset of `KeyCode` that contains only mouse buttons.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum MouseButton {
@@ -701,3 +716,29 @@ pub enum GamepadButton {
     DPadLeft,
     DPadRight,
 }
+
+impl FromStr for GamepadButton {
+    type Err = ParseEnumError;
+
+    /// Parses a button name such as `south` or `d-pad-up`, ignoring case and dashes.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let button = match s.replace('-', "").to_ascii_lowercase().as_str() {
+            "south" => GamepadButton::South,
+            "east" => GamepadButton::East,
+            "north" => GamepadButton::North,
+            "west" => GamepadButton::West,
+            "lefttrigger" => GamepadButton::LeftTrigger,
+            "lefttrigger2" => GamepadButton::LeftTrigger2,
+            "righttrigger" => GamepadButton::RightTrigger,
+            "righttrigger2" => GamepadButton::RightTrigger2,
+            "select" => GamepadButton::Select,
+            "start" => GamepadButton::Start,
+            "dpadup" => GamepadButton::DPadUp,
+            "dpaddown" => GamepadButton::DPadDown,
+            "dpadleft" => GamepadButton::DPadLeft,
+            "dpadright" => GamepadButton::DPadRight,
+            _ => return Err(ParseEnumError),
+        };
+        Ok(button)
+    }
+}
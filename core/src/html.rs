@@ -3,11 +3,13 @@
 mod dimensions;
 mod iterators;
 mod layout;
+mod style_sheet;
 mod text_format;
 
 pub use dimensions::BoxBounds;
 pub use dimensions::Position;
 pub use layout::{LayoutBox, LayoutContent, LayoutMetrics};
+pub use style_sheet::{parse_css, transform_style, CssStyle, StyleSheet, CSS_PROPERTIES};
 pub use text_format::{FormatSpans, TextFormat, TextSpan};
 
 #[cfg(test)]
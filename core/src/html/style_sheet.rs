@@ -0,0 +1,355 @@
+//! CSS style sheets, as used by `TextField.styleSheet`
+
+use crate::html::TextFormat;
+use crate::string::{Integer, WStr, WString};
+use gc_arena::Collect;
+
+/// The declarations of a single style, as `(property, value)` pairs.
+///
+/// Property names are stored in their ActionScript form, i.e. `fontSize`
+/// rather than `font-size`. Values are kept as written in the CSS source.
+pub type CssStyle = Vec<(WString, WString)>;
+
+/// The ActionScript names of every CSS property that Flash Player understands.
+pub const CSS_PROPERTIES: &[&[u8]] = &[
+    b"color",
+    b"display",
+    b"fontFamily",
+    b"fontSize",
+    b"fontStyle",
+    b"fontWeight",
+    b"kerning",
+    b"leading",
+    b"letterSpacing",
+    b"marginLeft",
+    b"marginRight",
+    b"textAlign",
+    b"textDecoration",
+    b"textIndent",
+];
+
+/// How an element is laid out, as set by the CSS `display` property.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CssDisplay {
+    /// The element is part of the surrounding line.
+    Inline,
+
+    /// The element ends with a line break, like a `<p>` tag.
+    Block,
+
+    /// The element and its contents are not displayed at all.
+    None,
+}
+
+/// A set of named styles which can be applied to HTML text.
+///
+/// Style names are either tag names (`p`, `a:link`) or class names (`.title`),
+/// and are case-insensitive.
+#[derive(Clone, Debug, Default, Collect)]
+#[collect(require_static)]
+pub struct StyleSheet {
+    styles: Vec<(WString, CssStyle)>,
+}
+
+impl StyleSheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes all styles.
+    pub fn clear(&mut self) {
+        self.styles.clear();
+    }
+
+    /// Iterates over the (lowercase) names of all styles, in the order they were defined.
+    pub fn style_names(&self) -> impl Iterator<Item = &WStr> {
+        self.styles.iter().map(|(name, _)| name.as_wstr())
+    }
+
+    pub fn style(&self, name: &WStr) -> Option<&CssStyle> {
+        self.styles
+            .iter()
+            .find(|(existing, _)| existing.eq_ignore_case(name))
+            .map(|(_, style)| style)
+    }
+
+    /// Replaces the style with the given name, or removes it if `style` is `None`.
+    pub fn set_style(&mut self, name: &WStr, style: Option<CssStyle>) {
+        let index = self
+            .styles
+            .iter()
+            .position(|(existing, _)| existing.eq_ignore_case(name));
+        match (index, style) {
+            (Some(index), Some(style)) => self.styles[index].1 = style,
+            (Some(index), None) => {
+                self.styles.remove(index);
+            }
+            (None, Some(style)) => self.styles.push((name.to_ascii_lowercase(), style)),
+            (None, None) => {}
+        }
+    }
+
+    /// Parses `css` and adds all styles defined in it.
+    ///
+    /// Returns `false` (without adding any styles) if the CSS is malformed.
+    pub fn parse_css(&mut self, css: &WStr) -> bool {
+        match parse_css(css) {
+            Some(styles) => {
+                for (name, style) in styles {
+                    self.set_style(&name, Some(style));
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Resolves the format and display mode to apply to an HTML element,
+    /// or `None` if no style applies to it.
+    ///
+    /// Tag styles are applied first, followed by the style of the `class` attribute.
+    pub fn element_style(
+        &self,
+        tag_name: &WStr,
+        class: Option<&WStr>,
+    ) -> Option<(TextFormat, Option<CssDisplay>)> {
+        let mut styles = vec![self.style(tag_name)];
+        if tag_name.eq_ignore_case(WStr::from_units(b"a")) {
+            styles.push(self.style(WStr::from_units(b"a:link")));
+        }
+        if let Some(class) = class {
+            let mut class_name = WString::from_unit(b'.'.into());
+            class_name.push_str(class);
+            styles.push(self.style(&class_name));
+        }
+
+        let mut result = None;
+        for style in styles.into_iter().flatten() {
+            let (format, display) = result.unwrap_or((TextFormat::default(), None));
+            result = Some((
+                transform_style(style).mix_with(format),
+                css_display(style).or(display),
+            ));
+        }
+        result
+    }
+}
+
+/// Converts a CSS property name to its ActionScript form, i.e. `font-size` to `fontSize`.
+pub fn css_property_name(name: &WStr) -> WString {
+    let mut result = WString::new();
+    let mut capitalize = false;
+    for unit in name.iter() {
+        if unit == u16::from(b'-') {
+            capitalize = true;
+        } else if capitalize && unit < 0x80 {
+            result.push_byte((unit as u8).to_ascii_uppercase());
+            capitalize = false;
+        } else {
+            result.push(unit);
+            capitalize = false;
+        }
+    }
+    result
+}
+
+/// Replaces every `/* comment */` in `css` with a space.
+fn strip_comments(css: &WStr) -> WString {
+    let mut result = WString::new();
+    let mut rest = css;
+    while let Some(start) = rest.find(WStr::from_units(b"/*")) {
+        result.push_str(&rest[..start]);
+        result.push_byte(b' ');
+        rest = match rest[start + 2..].find(WStr::from_units(b"*/")) {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => WStr::empty(),
+        };
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Parses a CSS style sheet into a list of named styles.
+///
+/// Only simple selectors are supported, optionally separated by commas.
+/// Returns `None` if the CSS is malformed.
+pub fn parse_css(css: &WStr) -> Option<Vec<(WString, CssStyle)>> {
+    let css = strip_comments(css);
+    let mut styles: Vec<(WString, CssStyle)> = Vec::new();
+    let mut rest = css.trim();
+    while !rest.is_empty() {
+        let open = rest.find(b'{')?;
+        let close = open + 1 + rest[open + 1..].find(b'}')?;
+        let selectors = &rest[..open];
+
+        let mut style = CssStyle::new();
+        for declaration in rest[open + 1..close].split(b';') {
+            if declaration.trim().is_empty() {
+                continue;
+            }
+            let (name, value) = declaration.split_once(b':')?;
+            let name = css_property_name(name.trim());
+            let value = value.trim().into();
+            set_property(&mut style, name, value);
+        }
+
+        for selector in selectors.split(b',') {
+            let selector = selector.trim();
+            if selector.is_empty() {
+                return None;
+            }
+            let selector = selector.to_ascii_lowercase();
+            // Repeated selectors are merged, with later declarations taking precedence.
+            if let Some((_, existing)) = styles.iter_mut().find(|(name, _)| *name == selector) {
+                for (name, value) in &style {
+                    set_property(existing, name.clone(), value.clone());
+                }
+            } else {
+                styles.push((selector, style.clone()));
+            }
+        }
+
+        rest = rest[close + 1..].trim_start();
+    }
+    Some(styles)
+}
+
+fn set_property(style: &mut CssStyle, name: WString, value: WString) {
+    if let Some((_, existing)) = style.iter_mut().find(|(key, _)| *key == name) {
+        *existing = value;
+    } else {
+        style.push((name, value));
+    }
+}
+
+/// Parses the leading number of a CSS value, ignoring any unit (such as `px`) after it.
+fn css_number(value: &WStr) -> Option<f64> {
+    let value = value.trim();
+    let end = value
+        .iter()
+        .enumerate()
+        .position(|(i, c)| {
+            let is_sign = i == 0 && (c == u16::from(b'-') || c == u16::from(b'+'));
+            let is_number =
+                c == u16::from(b'.') || (u16::from(b'0')..=u16::from(b'9')).contains(&c);
+            !(is_sign || is_number)
+        })
+        .unwrap_or(value.len());
+    value[..end].parse().ok()
+}
+
+/// Converts a CSS `font-family` value into a font name usable by a `TextFormat`.
+///
+/// Only the first font of the list is used, and generic families are mapped
+/// to their equivalent device fonts.
+fn css_font_family(value: &WStr) -> WString {
+    let family = value
+        .split(b',')
+        .next()
+        .unwrap_or(value)
+        .trim()
+        .trim_matches(|c| c == u16::from(b'"') || c == u16::from(b'\''));
+    let device_font: &[u8] = if family.eq_ignore_case(WStr::from_units(b"sans-serif")) {
+        b"_sans"
+    } else if family.eq_ignore_case(WStr::from_units(b"serif")) {
+        b"_serif"
+    } else if family.eq_ignore_case(WStr::from_units(b"monospace"))
+        || family.eq_ignore_case(WStr::from_units(b"mono"))
+    {
+        b"_typewriter"
+    } else {
+        return family.into();
+    };
+    WStr::from_units(device_font).into()
+}
+
+/// Converts a style into a `TextFormat`, as done by `StyleSheet.transform`.
+///
+/// Unknown properties and malformed values are ignored.
+pub fn transform_style(style: &[(WString, WString)]) -> TextFormat {
+    let mut format = TextFormat::default();
+    for (name, value) in style {
+        let value = value.trim();
+        let is = |expected: &[u8]| value.eq_ignore_case(WStr::from_units(expected));
+        match &name.as_wstr().to_utf8_lossy()[..] {
+            "color" => {
+                if let Some(Ok(color)) = value
+                    .strip_prefix(b'#')
+                    .map(|hex| u32::from_wstr_radix(hex, 16))
+                {
+                    format.color = Some(swf::Color::from_rgba(color));
+                }
+            }
+            "fontFamily" => format.font = Some(css_font_family(value)),
+            "fontSize" => {
+                if let Some(size) = css_number(value) {
+                    format.size = Some(size.trunc());
+                }
+            }
+            "fontStyle" => {
+                if is(b"italic") {
+                    format.italic = Some(true);
+                } else if is(b"normal") {
+                    format.italic = Some(false);
+                }
+            }
+            "fontWeight" => {
+                if is(b"bold") {
+                    format.bold = Some(true);
+                } else if is(b"normal") {
+                    format.bold = Some(false);
+                }
+            }
+            "kerning" => {
+                if is(b"true") {
+                    format.kerning = Some(true);
+                } else if is(b"false") {
+                    format.kerning = Some(false);
+                }
+            }
+            "leading" => format.leading = css_number(value),
+            "letterSpacing" => format.letter_spacing = css_number(value),
+            "marginLeft" => format.left_margin = css_number(value),
+            "marginRight" => format.right_margin = css_number(value),
+            "textIndent" => format.indent = css_number(value),
+            "textAlign" => {
+                if is(b"left") {
+                    format.align = Some(swf::TextAlign::Left);
+                } else if is(b"center") {
+                    format.align = Some(swf::TextAlign::Center);
+                } else if is(b"right") {
+                    format.align = Some(swf::TextAlign::Right);
+                } else if is(b"justify") {
+                    format.align = Some(swf::TextAlign::Justify);
+                }
+            }
+            "textDecoration" => {
+                if is(b"underline") {
+                    format.underline = Some(true);
+                } else if is(b"none") {
+                    format.underline = Some(false);
+                }
+            }
+            _ => {}
+        }
+    }
+    format
+}
+
+/// Returns the display mode set by a style, if any.
+pub fn css_display(style: &[(WString, WString)]) -> Option<CssDisplay> {
+    let (_, value) = style
+        .iter()
+        .rev()
+        .find(|(name, _)| *name == WStr::from_units(b"display"))?;
+    let value = value.trim();
+    if value.eq_ignore_case(WStr::from_units(b"inline")) {
+        Some(CssDisplay::Inline)
+    } else if value.eq_ignore_case(WStr::from_units(b"block")) {
+        Some(CssDisplay::Block)
+    } else if value.eq_ignore_case(WStr::from_units(b"none")) {
+        Some(CssDisplay::None)
+    } else {
+        None
+    }
+}
@@ -1,6 +1,7 @@
 //! Tests for HTML module
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
+use crate::html::style_sheet::{transform_style, StyleSheet};
 use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
 use crate::string::{WStr, WString};
 use swf::{Rectangle, Twips};
@@ -868,3 +869,90 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn stylesheet_parse_css() {
+    let mut style_sheet = StyleSheet::new();
+    let css = WStr::from_units(
+        b"/* heading */ H1, .Title { font-size: 24px; font-weight: bold }\n\
+          h1 { color: #FF0000; }",
+    );
+    assert!(style_sheet.parse_css(css));
+
+    let names: Vec<_> = style_sheet.style_names().map(WString::from).collect();
+    assert_eq!(
+        names,
+        vec![WString::from_utf8("h1"), WString::from_utf8(".title")]
+    );
+
+    let h1 = style_sheet.style(WStr::from_units(b"H1")).unwrap();
+    assert_eq!(
+        h1,
+        &vec![
+            (WString::from_utf8("fontSize"), WString::from_utf8("24px")),
+            (WString::from_utf8("fontWeight"), WString::from_utf8("bold")),
+            (WString::from_utf8("color"), WString::from_utf8("#FF0000")),
+        ]
+    );
+}
+
+#[test]
+fn stylesheet_parse_css_malformed() {
+    let mut style_sheet = StyleSheet::new();
+    assert!(!style_sheet.parse_css(WStr::from_units(b"p { color: #FF0000; ")));
+    assert!(!style_sheet.parse_css(WStr::from_units(b"p { color }")));
+    assert_eq!(style_sheet.style_names().count(), 0);
+}
+
+#[test]
+fn stylesheet_transform_style() {
+    let style = vec![
+        (WString::from_utf8("color"), WString::from_utf8("#00FF00")),
+        (
+            WString::from_utf8("fontFamily"),
+            WString::from_utf8("sans-serif, Arial"),
+        ),
+        (WString::from_utf8("fontSize"), WString::from_utf8("12.7px")),
+        (
+            WString::from_utf8("textAlign"),
+            WString::from_utf8("center"),
+        ),
+        (
+            WString::from_utf8("textDecoration"),
+            WString::from_utf8("underline"),
+        ),
+        (WString::from_utf8("unknown"), WString::from_utf8("value")),
+    ];
+    let format = transform_style(&style);
+
+    assert_eq!(format.color, Some(swf::Color::from_rgba(0x00FF00)));
+    assert_eq!(format.font, Some(WString::from_utf8("_sans")));
+    assert_eq!(format.size, Some(12.0));
+    assert_eq!(format.align, Some(swf::TextAlign::Center));
+    assert_eq!(format.underline, Some(true));
+    assert_eq!(format.bold, None);
+}
+
+#[test]
+fn formatspans_from_html_with_stylesheet() {
+    let mut style_sheet = StyleSheet::new();
+    assert!(style_sheet.parse_css(WStr::from_units(
+        b"p { font-size: 20 } .hidden { display: none } .red { color: #FF0000 }",
+    )));
+
+    let spans = FormatSpans::from_html(
+        WStr::from_units(b"<p>a<span class=\"hidden\">b</span><span class=\"red\">c</span></p>"),
+        TextFormat::default(),
+        Some(&style_sheet),
+        true,
+        8,
+    );
+
+    assert_eq!(spans.text(), WStr::from_units(b"ac\n"));
+    let format = spans.get_text_format(0, 1);
+    assert_eq!(format.size, Some(20.0));
+    assert_eq!(format.color, None);
+    let format = spans.get_text_format(1, 2);
+    assert_eq!(format.size, Some(20.0));
+    assert_eq!(format.color, Some(swf::Color::from_rgba(0xFF0000)));
+}
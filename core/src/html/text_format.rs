@@ -2,6 +2,7 @@
 
 use crate::context::UpdateContext;
 use crate::html::iterators::TextSpanIter;
+use crate::html::style_sheet::{CssDisplay, StyleSheet};
 use crate::string::{Integer, SwfStrExt as _, Units, WStr, WString};
 use crate::tag_utils::SwfMovie;
 use gc_arena::Collect;
@@ -594,13 +595,13 @@ impl FormatSpans {
 
     /// Lower an HTML tree into text-span representation.
     ///
-    /// This is the "legacy" implementation of this process: it only looks for
-    /// a handful of presentational attributes in the HTML tree to generate
-    /// styling. There's also a `lower_from_css` that respects both
-    /// presentational markup and CSS stylesheets.
+    /// Styling is generated from a handful of presentational attributes in the
+    /// HTML tree and, if given, from the tag and class styles of a CSS
+    /// stylesheet, which take precedence over the presentational attributes.
     pub fn from_html(
         html: &WStr,
         default_format: TextFormat,
+        style_sheet: Option<&StyleSheet>,
         is_multiline: bool,
         swf_version: u8,
     ) -> Self {
@@ -609,6 +610,8 @@ impl FormatSpans {
         let is_multiline = is_multiline || swf_version <= 6;
 
         let mut format_stack = vec![default_format.clone()];
+        // The CSS display mode of every element in `format_stack` (except the default format).
+        let mut display_stack: Vec<Option<CssDisplay>> = Vec::new();
         let mut text = WString::new();
         let mut spans: Vec<TextSpan> = Vec::new();
 
@@ -836,11 +839,25 @@ impl FormatSpans {
                         }
                         _ => {}
                     }
+                    let mut display = None;
+                    if let Some(style_sheet) = style_sheet {
+                        let class = attribute(b"class");
+                        if let Some((style, style_display)) =
+                            style_sheet.element_style(WStr::from_units(tag_name), class.as_deref())
+                        {
+                            format = style.mix_with(format);
+                            display = style_display;
+                        }
+                    }
                     opened_starts.push(opened_buffer.len());
                     opened_buffer.extend(tag_name);
                     format_stack.push(format);
+                    display_stack.push(display);
                 }
                 Ok(Event::Text(e)) if !e.is_empty() => 'text: {
+                    if display_stack.contains(&Some(CssDisplay::None)) {
+                        break 'text;
+                    }
                     let e = decode_to_wstr(&e.into_inner());
                     let e = process_html_entity(&e).unwrap_or(e);
                     let format = format_stack.last().unwrap().clone();
@@ -911,9 +928,20 @@ impl FormatSpans {
                             let tf = format_stack.last().unwrap();
                             last_closed_font = Some(TextSpanFont::with_format(tf));
                         }
+                        _ if is_multiline
+                            && display_stack.last() == Some(&Some(CssDisplay::Block))
+                            && !display_stack.contains(&Some(CssDisplay::None)) =>
+                        {
+                            text.push_byte(HTML_NEWLINE);
+                            spans.push(TextSpan::with_length_and_format(
+                                1,
+                                format_stack.last().unwrap(),
+                            ));
+                        }
                         _ => {}
                     }
                     format_stack.pop();
+                    display_stack.pop();
                 }
                 Ok(Event::Eof) => break,
                 Err(e) => {
@@ -5,6 +5,7 @@ use crate::avm1::{Attribute, Avm1};
 use crate::avm1::{ExecutionReason, NativeObject};
 use crate::avm1::{Object, SoundObject, TObject, Value};
 use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::globals::flash::net::url_stream;
 use crate::avm2::object::{
     ByteArrayObject, EventObject as Avm2EventObject, FileReferenceObject, LoaderStream,
     TObject as _,
@@ -24,7 +25,7 @@ use crate::display_object::{
 use crate::events::ClipEvent;
 use crate::frame_lifecycle::catchup_display_object_to_frame;
 use crate::limits::ExecutionLimit;
-use crate::player::{Player, PostFrameCallback};
+use crate::player::{ParseEnumError, Player, PostFrameCallback};
 use crate::streams::NetStream;
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
@@ -36,6 +37,7 @@ use ruffle_render::utils::{determine_jpeg_tag_format, JpegTagFormat};
 use slotmap::{new_key_type, SlotMap};
 use std::borrow::Borrow;
 use std::fmt;
+use std::str::FromStr;
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 use swf::read::{extract_swz, read_compression_type};
@@ -74,6 +76,20 @@ pub enum LoadBehavior {
     Blocking,
 }
 
+impl FromStr for LoadBehavior {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let load_behavior = match s.to_ascii_lowercase().as_str() {
+            "streaming" => LoadBehavior::Streaming,
+            "delayed" => LoadBehavior::Delayed,
+            "blocking" => LoadBehavior::Blocking,
+            _ => return Err(ParseEnumError),
+        };
+        Ok(load_behavior)
+    }
+}
+
 /// Enumeration of all content types that `Loader` can handle.
 ///
 /// This is a superset of `JpegTagFormat`.
@@ -156,6 +172,9 @@ pub enum Error {
     #[error("Non-data loader spawned as data loader")]
     NotLoadDataLoader,
 
+    #[error("Non-URLStream loader spawned as URLStream loader")]
+    NotUrlStreamLoader,
+
     #[error("Non-sound loader spawned as sound loader")]
     NotSoundLoader,
 
@@ -259,6 +278,7 @@ impl<'gc> LoadManager<'gc> {
             | Loader::Form { self_handle, .. }
             | Loader::LoadVars { self_handle, .. }
             | Loader::LoadURLLoader { self_handle, .. }
+            | Loader::LoadURLStream { self_handle, .. }
             | Loader::SoundAvm1 { self_handle, .. }
             | Loader::SoundAvm2 { self_handle, .. }
             | Loader::NetStream { self_handle, .. }
@@ -435,6 +455,38 @@ impl<'gc> LoadManager<'gc> {
         loader.load_url_loader(player, request, data_format)
     }
 
+    /// Kick off a streaming data load into a `URLStream`,
+    /// cancelling any load that is already in progress for it.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_data_into_url_stream(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: Avm2Object<'gc>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        self.cancel_url_stream(target_object);
+        let loader = Loader::LoadURLStream {
+            self_handle: None,
+            target_object,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.url_stream_loader(player, request)
+    }
+
+    /// Cancel any load in progress into the given `URLStream`.
+    ///
+    /// The load's async process will stop once it next receives data.
+    pub fn cancel_url_stream(&mut self, target: Avm2Object<'gc>) {
+        self.0.retain(|_, loader| match loader {
+            Loader::LoadURLStream { target_object, .. } => {
+                !Avm2Object::ptr_eq(*target_object, target)
+            }
+            _ => true,
+        });
+    }
+
     /// Kick off an AVM1 audio load.
     ///
     /// Returns the loader's async process, which you will need to spawn.
@@ -718,6 +770,17 @@ pub enum Loader<'gc> {
         target_object: Avm2Object<'gc>,
     },
 
+    /// Loader that is streaming data into a `URLStream`.
+    /// Data is appended to the stream as each chunk arrives.
+    LoadURLStream {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<LoaderHandle>,
+
+        /// The target `URLStream` to load data into.
+        target_object: Avm2Object<'gc>,
+    },
+
     /// Loader that is loading an MP3 into an AVM1 Sound object.
     SoundAvm1 {
         /// The handle to refer to this loader instance.
@@ -1459,6 +1522,177 @@ impl<'gc> Loader<'gc> {
         })
     }
 
+    /// Creates a future for a LoadURLStream load call.
+    fn url_stream_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::LoadURLStream { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotUrlStreamLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        fn get_target<'gc>(
+            uc: &UpdateContext<'_, 'gc>,
+            handle: LoaderHandle,
+        ) -> Result<Avm2Object<'gc>, Error> {
+            match uc.load_manager.get_loader(handle) {
+                Some(&Loader::LoadURLStream { target_object, .. }) => Ok(target_object),
+                None => Err(Error::Cancelled),
+                _ => Err(Error::NotUrlStreamLoader),
+            }
+        }
+
+        fn dispatch_http_status<'gc>(
+            activation: &mut Avm2Activation<'_, 'gc>,
+            target: Avm2Object<'gc>,
+            status: u16,
+            redirected: bool,
+        ) -> Result<(), Error> {
+            let http_status_evt = activation
+                .avm2()
+                .classes()
+                .httpstatusevent
+                .construct(
+                    activation,
+                    &[
+                        "httpStatus".into(),
+                        false.into(),
+                        false.into(),
+                        status.into(),
+                        redirected.into(),
+                    ],
+                )
+                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+            Avm2::dispatch_event(&mut activation.context, http_status_evt, target);
+            Ok(())
+        }
+
+        fn dispatch_io_error<'gc>(
+            activation: &mut Avm2Activation<'_, 'gc>,
+            target: Avm2Object<'gc>,
+        ) -> Result<(), Error> {
+            let _ = url_stream::set_connected(activation, target, false);
+
+            // FIXME - Match the exact error message generated by Flash
+            let io_error_evt = activation
+                .avm2()
+                .classes()
+                .ioerrorevent
+                .construct(
+                    activation,
+                    &[
+                        "ioError".into(),
+                        false.into(),
+                        false.into(),
+                        "Error #2032: Stream Error".into(),
+                        2032.into(),
+                    ],
+                )
+                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+            Avm2::dispatch_event(&mut activation.context, io_error_evt, target);
+            Ok(())
+        }
+
+        Box::pin(async move {
+            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let mut response = match fetch.await {
+                Ok(response) => response,
+                Err(response) => {
+                    return player.lock().unwrap().update(|uc| {
+                        let target = get_target(uc, handle)?;
+                        let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+
+                        let (status_code, redirected) =
+                            if let Error::HttpNotOk(_, status_code, redirected, _) = response.error
+                            {
+                                (status_code, redirected)
+                            } else {
+                                (0, false)
+                            };
+                        dispatch_http_status(&mut activation, target, status_code, redirected)?;
+                        dispatch_io_error(&mut activation, target)
+                    });
+                }
+            };
+
+            let status = response.status();
+            let redirected = response.redirected();
+            // Flash reports a total of 0 bytes when the length isn't known.
+            let bytes_total = response.expected_length().ok().flatten().unwrap_or(0) as usize;
+
+            player.lock().unwrap().update(|uc| {
+                let target = get_target(uc, handle)?;
+                let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+
+                let open_evt = Avm2EventObject::bare_default_event(&mut activation.context, "open");
+                Avm2::dispatch_event(&mut activation.context, open_evt, target);
+                dispatch_http_status(&mut activation, target, status, redirected)
+            })?;
+
+            let mut bytes_loaded = 0;
+            loop {
+                let chunk = response.next_chunk().await;
+                let is_end = !matches!(chunk, Ok(Some(_)));
+
+                player.lock().unwrap().update(|uc| {
+                    let target = get_target(uc, handle)?;
+                    let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+
+                    match chunk {
+                        Ok(Some(data)) => {
+                            bytes_loaded += data.len();
+                            url_stream::append_data(&mut activation, target, &data)
+                                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+                            let progress_evt = activation
+                                .avm2()
+                                .classes()
+                                .progressevent
+                                .construct(
+                                    &mut activation,
+                                    &[
+                                        "progress".into(),
+                                        false.into(),
+                                        false.into(),
+                                        bytes_loaded.into(),
+                                        bytes_total.into(),
+                                    ],
+                                )
+                                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                            Avm2::dispatch_event(&mut activation.context, progress_evt, target);
+                        }
+                        Ok(None) => {
+                            let complete_evt = Avm2EventObject::bare_default_event(
+                                &mut activation.context,
+                                "complete",
+                            );
+                            Avm2::dispatch_event(&mut activation.context, complete_evt, target);
+                        }
+                        Err(e) => {
+                            tracing::error!("Error while streaming into URLStream: {e}");
+                            dispatch_io_error(&mut activation, target)?;
+                        }
+                    }
+                    Ok::<_, Error>(())
+                })?;
+
+                if is_end {
+                    break;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
     /// Creates a future for a Sound load call.
     fn sound_loader_avm1(
         &mut self,
@@ -1,3 +1,11 @@
+mod collate;
+mod data;
+mod format;
+
+pub use collate::CollatorOptions;
+pub use data::{resolve_locale, LocaleData, LOCALES};
+pub use format::{format_date_time, NumberFormat};
+
 use chrono::{DateTime, FixedOffset, Local, Offset, TimeZone, Utc};
 
 // For tests, we emulate being in Nepal with a local time of 2001-02-03 at 04:05:06.
@@ -0,0 +1,220 @@
+//! String comparison for `flash.globalization.Collator`
+
+use std::cmp::Ordering;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// The differences between strings that a `Collator` ignores.
+///
+/// Comparisons follow the general shape of the Unicode Collation Algorithm: strings are first
+/// compared by their base letters, then by their diacritics, and then by their case. The base
+/// letters are ordered by code point, so no bundled locale has its own tailored ordering.
+#[derive(Debug, Default)]
+pub struct CollatorOptions {
+    pub ignore_case: bool,
+
+    /// Treat fullwidth forms of ASCII characters as their ASCII counterparts.
+    pub ignore_character_width: bool,
+
+    pub ignore_diacritics: bool,
+
+    /// Treat katakana as the hiragana with the same sound.
+    pub ignore_kana_type: bool,
+
+    /// Ignore everything that isn't a letter or a digit, such as spaces and punctuation.
+    pub ignore_symbols: bool,
+
+    /// Compare runs of digits by their numeric value.
+    pub numeric_comparison: bool,
+}
+
+/// A single unit of a string that is being compared.
+#[derive(Debug)]
+enum Element {
+    /// A run of digits, without leading zeros. Only used for numeric comparison.
+    Number(String),
+
+    Char {
+        /// The lowercase form of the character, without any diacritics.
+        base: char,
+
+        /// The combining diacritics that follow the character.
+        marks: Vec<char>,
+
+        is_uppercase: bool,
+    },
+}
+
+impl Element {
+    fn compare_base(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Element::Number(a), Element::Number(b)) => {
+                a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+            }
+            // Numbers are sorted where their digits would be.
+            (Element::Number(_), Element::Char { base, .. }) => '0'.cmp(base),
+            (Element::Char { base, .. }, Element::Number(_)) => base.cmp(&'0'),
+            (Element::Char { base: a, .. }, Element::Char { base: b, .. }) => a.cmp(b),
+        }
+    }
+
+    fn marks(&self) -> &[char] {
+        match self {
+            Element::Number(_) => &[],
+            Element::Char { marks, .. } => marks,
+        }
+    }
+
+    fn is_uppercase(&self) -> bool {
+        match self {
+            Element::Number(_) => false,
+            Element::Char { is_uppercase, .. } => *is_uppercase,
+        }
+    }
+}
+
+impl CollatorOptions {
+    /// Compares two strings, ignoring the differences chosen by these options.
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        let a = self.elements(a);
+        let b = self.elements(b);
+
+        let by_base = compare_each(&a, &b, Element::compare_base);
+        if by_base != Ordering::Equal || a.len() != b.len() {
+            return by_base.then(a.len().cmp(&b.len()));
+        }
+
+        if !self.ignore_diacritics {
+            let by_marks = compare_each(&a, &b, |a, b| a.marks().cmp(b.marks()));
+            if by_marks != Ordering::Equal {
+                return by_marks;
+            }
+        }
+
+        if !self.ignore_case {
+            // Lowercase letters are sorted first.
+            return compare_each(&a, &b, |a, b| a.is_uppercase().cmp(&b.is_uppercase()));
+        }
+
+        Ordering::Equal
+    }
+
+    fn elements(&self, string: &str) -> Vec<Element> {
+        let folded = string.chars().map(|c| self.fold(c));
+        let mut elements: Vec<Element> = Vec::new();
+        for c in folded.nfd() {
+            if is_combining_mark(c) {
+                if let Some(Element::Char { marks, .. }) = elements.last_mut() {
+                    marks.push(c);
+                    continue;
+                }
+            }
+
+            if self.numeric_comparison && c.is_ascii_digit() {
+                if let Some(Element::Number(digits)) = elements.last_mut() {
+                    if !(digits.is_empty() && c == '0') {
+                        digits.push(c);
+                    }
+                    continue;
+                }
+                let digits = if c == '0' {
+                    String::new()
+                } else {
+                    c.to_string()
+                };
+                elements.push(Element::Number(digits));
+                continue;
+            }
+
+            if self.ignore_symbols && !c.is_alphanumeric() && !is_combining_mark(c) {
+                continue;
+            }
+
+            let base = c.to_lowercase().next().unwrap_or(c);
+            elements.push(Element::Char {
+                base,
+                marks: Vec::new(),
+                is_uppercase: base != c,
+            });
+        }
+        elements
+    }
+
+    /// Replaces a character with the one it's treated as.
+    fn fold(&self, c: char) -> char {
+        let fold_offset = match c {
+            // Fullwidth forms of ASCII.
+            '\u{FF01}'..='\u{FF5E}' if self.ignore_character_width => 0xFEE0,
+            '\u{3000}' if self.ignore_character_width => return ' ',
+            // Katakana that have a hiragana counterpart.
+            '\u{30A1}'..='\u{30F6}' if self.ignore_kana_type => 0x60,
+            _ => return c,
+        };
+        char::from_u32(c as u32 - fold_offset).unwrap_or(c)
+    }
+}
+
+/// Compares the elements of two strings pairwise, returning the first difference.
+fn compare_each(
+    a: &[Element],
+    b: &[Element],
+    compare: impl Fn(&Element, &Element) -> Ordering,
+) -> Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| compare(a, b))
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(options: &CollatorOptions, strings: &[&str]) -> Vec<String> {
+        let mut strings: Vec<String> = strings.iter().map(|s| s.to_string()).collect();
+        strings.sort_by(|a, b| options.compare(a, b));
+        strings
+    }
+
+    #[test]
+    fn sorting_order() {
+        let options = CollatorOptions::default();
+        assert_eq!(
+            sorted(&options, &["b", "B", "ä", "A", "a", "ab", "Ä"]),
+            ["a", "A", "ä", "Ä", "ab", "b", "B"]
+        );
+        assert_eq!(options.compare("resume", "résumé"), Ordering::Less);
+        assert_eq!(options.compare("item10", "item9"), Ordering::Less);
+    }
+
+    #[test]
+    fn matching_options() {
+        let options = CollatorOptions {
+            ignore_case: true,
+            ignore_character_width: true,
+            ignore_diacritics: true,
+            ignore_kana_type: true,
+            ignore_symbols: true,
+            numeric_comparison: false,
+        };
+        assert_eq!(options.compare("Résumé", "resume"), Ordering::Equal);
+        assert_eq!(options.compare("ＡＢＣ", "abc"), Ordering::Equal);
+        assert_eq!(options.compare("カタカナ", "かたかな"), Ordering::Equal);
+        assert_eq!(options.compare("e-mail!", "email"), Ordering::Equal);
+        assert_eq!(options.compare("apple", "apricot"), Ordering::Less);
+    }
+
+    #[test]
+    fn numeric_comparison() {
+        let options = CollatorOptions {
+            numeric_comparison: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            sorted(&options, &["item10", "item9", "item009", "item1", "item"]),
+            ["item", "item1", "item9", "item009", "item10"]
+        );
+        assert_eq!(options.compare("2 apples", "10 apples"), Ordering::Less);
+    }
+}
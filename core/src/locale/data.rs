@@ -0,0 +1,667 @@
+//! Formatting conventions of the locales bundled with Ruffle
+
+/// The formatting conventions of a single locale, as used by `flash.globalization`.
+#[derive(Debug)]
+pub struct LocaleData {
+    /// The locale ID, such as `en-US`.
+    pub name: &'static str,
+
+    pub decimal_separator: &'static str,
+    pub grouping_separator: &'static str,
+
+    /// The sizes of digit groups, in the format of `NumberFormatter.groupingPattern`.
+    pub grouping_pattern: &'static str,
+
+    /// One of the formats accepted by `NumberFormatter.negativeNumberFormat`.
+    pub negative_number_format: u32,
+
+    pub currency_iso_code: &'static str,
+    pub currency_symbol: &'static str,
+    pub currency_fractional_digits: u32,
+
+    /// One of the formats accepted by `CurrencyFormatter.positiveCurrencyFormat`.
+    pub positive_currency_format: u32,
+
+    /// One of the formats accepted by `CurrencyFormatter.negativeCurrencyFormat`.
+    pub negative_currency_format: u32,
+
+    /// Month names, as used when they stand on their own.
+    pub month_names: [&'static str; 12],
+
+    /// Month names, as used within a formatted date.
+    /// Only differs from `month_names` in languages that decline them.
+    pub format_month_names: Option<[&'static str; 12]>,
+
+    pub month_abbreviations: [&'static str; 12],
+
+    /// Weekday names, starting on Sunday.
+    pub weekday_names: [&'static str; 7],
+    pub weekday_abbreviations: [&'static str; 7],
+
+    /// The first day of the week, where 0 is Sunday.
+    pub first_weekday: u32,
+
+    pub am_pm: [&'static str; 2],
+
+    /// Date patterns for the long, medium and short `DateTimeStyle`s.
+    pub date_patterns: [&'static str; 3],
+
+    /// Time patterns for the long, medium and short `DateTimeStyle`s.
+    pub time_patterns: [&'static str; 3],
+}
+
+impl LocaleData {
+    pub fn language(&self) -> &'static str {
+        self.name.split('-').next().unwrap_or(self.name)
+    }
+
+    pub fn format_month_names(&self) -> &[&'static str; 12] {
+        self.format_month_names
+            .as_ref()
+            .unwrap_or(&self.month_names)
+    }
+}
+
+const ENGLISH_MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const ENGLISH_MONTH_ABBREVIATIONS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+const ENGLISH_WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+const ENGLISH_WEEKDAY_ABBREVIATIONS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+const NUMBERED_MONTHS_JA_ZH: [&str; 12] = [
+    "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+];
+
+/// All bundled locales. The first one is used as the final fallback.
+pub static LOCALES: &[LocaleData] = &[
+    LocaleData {
+        name: "en-US",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "USD",
+        currency_symbol: "$",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: ENGLISH_MONTHS,
+        format_month_names: None,
+        month_abbreviations: ENGLISH_MONTH_ABBREVIATIONS,
+        weekday_names: ENGLISH_WEEKDAYS,
+        weekday_abbreviations: ENGLISH_WEEKDAY_ABBREVIATIONS,
+        first_weekday: 0,
+        am_pm: ["AM", "PM"],
+        date_patterns: ["EEEE, MMMM d, yyyy", "MMM d, yyyy", "M/d/yyyy"],
+        time_patterns: ["h:mm:ss a", "h:mm:ss a", "h:mm a"],
+    },
+    LocaleData {
+        name: "en-GB",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "GBP",
+        currency_symbol: "£",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: ENGLISH_MONTHS,
+        format_month_names: None,
+        month_abbreviations: ENGLISH_MONTH_ABBREVIATIONS,
+        weekday_names: ENGLISH_WEEKDAYS,
+        weekday_abbreviations: ENGLISH_WEEKDAY_ABBREVIATIONS,
+        first_weekday: 1,
+        am_pm: ["am", "pm"],
+        date_patterns: ["EEEE, d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "de-DE",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        format_month_names: None,
+        month_abbreviations: [
+            "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+        ],
+        weekday_names: [
+            "Sonntag",
+            "Montag",
+            "Dienstag",
+            "Mittwoch",
+            "Donnerstag",
+            "Freitag",
+            "Samstag",
+        ],
+        weekday_abbreviations: ["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"],
+        first_weekday: 1,
+        am_pm: ["AM", "PM"],
+        date_patterns: ["EEEE, d. MMMM yyyy", "dd.MM.yyyy", "dd.MM.yy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "fr-FR",
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        format_month_names: None,
+        month_abbreviations: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+        weekday_names: [
+            "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+        ],
+        weekday_abbreviations: ["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."],
+        first_weekday: 1,
+        am_pm: ["AM", "PM"],
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "es-ES",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        format_month_names: None,
+        month_abbreviations: [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+        ],
+        weekday_names: [
+            "domingo",
+            "lunes",
+            "martes",
+            "miércoles",
+            "jueves",
+            "viernes",
+            "sábado",
+        ],
+        weekday_abbreviations: ["dom", "lun", "mar", "mié", "jue", "vie", "sáb"],
+        first_weekday: 1,
+        am_pm: ["a. m.", "p. m."],
+        date_patterns: ["EEEE, d 'de' MMMM 'de' yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+    },
+    LocaleData {
+        name: "it-IT",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ],
+        format_month_names: None,
+        month_abbreviations: [
+            "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+        ],
+        weekday_names: [
+            "domenica",
+            "lunedì",
+            "martedì",
+            "mercoledì",
+            "giovedì",
+            "venerdì",
+            "sabato",
+        ],
+        weekday_abbreviations: ["dom", "lun", "mar", "mer", "gio", "ven", "sab"],
+        first_weekday: 1,
+        am_pm: ["AM", "PM"],
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "pt-BR",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "BRL",
+        currency_symbol: "R$",
+        currency_fractional_digits: 2,
+        positive_currency_format: 2,
+        negative_currency_format: 9,
+        month_names: [
+            "janeiro",
+            "fevereiro",
+            "março",
+            "abril",
+            "maio",
+            "junho",
+            "julho",
+            "agosto",
+            "setembro",
+            "outubro",
+            "novembro",
+            "dezembro",
+        ],
+        format_month_names: None,
+        month_abbreviations: [
+            "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez",
+        ],
+        weekday_names: [
+            "domingo",
+            "segunda-feira",
+            "terça-feira",
+            "quarta-feira",
+            "quinta-feira",
+            "sexta-feira",
+            "sábado",
+        ],
+        weekday_abbreviations: ["dom", "seg", "ter", "qua", "qui", "sex", "sáb"],
+        first_weekday: 0,
+        am_pm: ["AM", "PM"],
+        date_patterns: [
+            "EEEE, d 'de' MMMM 'de' yyyy",
+            "d 'de' MMM 'de' yyyy",
+            "dd/MM/yyyy",
+        ],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "nl-NL",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 2,
+        negative_currency_format: 12,
+        month_names: [
+            "januari",
+            "februari",
+            "maart",
+            "april",
+            "mei",
+            "juni",
+            "juli",
+            "augustus",
+            "september",
+            "oktober",
+            "november",
+            "december",
+        ],
+        format_month_names: None,
+        month_abbreviations: [
+            "jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+        ],
+        weekday_names: [
+            "zondag",
+            "maandag",
+            "dinsdag",
+            "woensdag",
+            "donderdag",
+            "vrijdag",
+            "zaterdag",
+        ],
+        weekday_abbreviations: ["zo", "ma", "di", "wo", "do", "vr", "za"],
+        first_weekday: 1,
+        am_pm: ["a.m.", "p.m."],
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "dd-MM-yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "ru-RU",
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "RUB",
+        currency_symbol: "₽",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "январь",
+            "февраль",
+            "март",
+            "апрель",
+            "май",
+            "июнь",
+            "июль",
+            "август",
+            "сентябрь",
+            "октябрь",
+            "ноябрь",
+            "декабрь",
+        ],
+        format_month_names: Some([
+            "января",
+            "февраля",
+            "марта",
+            "апреля",
+            "мая",
+            "июня",
+            "июля",
+            "августа",
+            "сентября",
+            "октября",
+            "ноября",
+            "декабря",
+        ]),
+        month_abbreviations: [
+            "янв.",
+            "февр.",
+            "март",
+            "апр.",
+            "май",
+            "июнь",
+            "июль",
+            "авг.",
+            "сент.",
+            "окт.",
+            "нояб.",
+            "дек.",
+        ],
+        weekday_names: [
+            "воскресенье",
+            "понедельник",
+            "вторник",
+            "среда",
+            "четверг",
+            "пятница",
+            "суббота",
+        ],
+        weekday_abbreviations: ["вс", "пн", "вт", "ср", "чт", "пт", "сб"],
+        first_weekday: 1,
+        am_pm: ["AM", "PM"],
+        date_patterns: ["EEEE, d MMMM yyyy 'г'.", "d MMM yyyy 'г'.", "dd.MM.yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "pl-PL",
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "PLN",
+        currency_symbol: "zł",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "styczeń",
+            "luty",
+            "marzec",
+            "kwiecień",
+            "maj",
+            "czerwiec",
+            "lipiec",
+            "sierpień",
+            "wrzesień",
+            "październik",
+            "listopad",
+            "grudzień",
+        ],
+        format_month_names: Some([
+            "stycznia",
+            "lutego",
+            "marca",
+            "kwietnia",
+            "maja",
+            "czerwca",
+            "lipca",
+            "sierpnia",
+            "września",
+            "października",
+            "listopada",
+            "grudnia",
+        ]),
+        month_abbreviations: [
+            "sty", "lut", "mar", "kwi", "maj", "cze", "lip", "sie", "wrz", "paź", "lis", "gru",
+        ],
+        weekday_names: [
+            "niedziela",
+            "poniedziałek",
+            "wtorek",
+            "środa",
+            "czwartek",
+            "piątek",
+            "sobota",
+        ],
+        weekday_abbreviations: ["niedz.", "pon.", "wt.", "śr.", "czw.", "pt.", "sob."],
+        first_weekday: 1,
+        am_pm: ["AM", "PM"],
+        date_patterns: ["EEEE, d MMMM yyyy", "d MMM yyyy", "dd.MM.yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "ja-JP",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "JPY",
+        currency_symbol: "￥",
+        currency_fractional_digits: 0,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: NUMBERED_MONTHS_JA_ZH,
+        format_month_names: None,
+        month_abbreviations: NUMBERED_MONTHS_JA_ZH,
+        weekday_names: [
+            "日曜日",
+            "月曜日",
+            "火曜日",
+            "水曜日",
+            "木曜日",
+            "金曜日",
+            "土曜日",
+        ],
+        weekday_abbreviations: ["日", "月", "火", "水", "木", "金", "土"],
+        first_weekday: 0,
+        am_pm: ["午前", "午後"],
+        date_patterns: ["yyyy年M月d日EEEE", "yyyy/MM/dd", "yyyy/MM/dd"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+    },
+    LocaleData {
+        name: "zh-CN",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "CNY",
+        currency_symbol: "¥",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: [
+            "一月",
+            "二月",
+            "三月",
+            "四月",
+            "五月",
+            "六月",
+            "七月",
+            "八月",
+            "九月",
+            "十月",
+            "十一月",
+            "十二月",
+        ],
+        format_month_names: None,
+        month_abbreviations: NUMBERED_MONTHS_JA_ZH,
+        weekday_names: [
+            "星期日",
+            "星期一",
+            "星期二",
+            "星期三",
+            "星期四",
+            "星期五",
+            "星期六",
+        ],
+        weekday_abbreviations: ["周日", "周一", "周二", "周三", "周四", "周五", "周六"],
+        first_weekday: 1,
+        am_pm: ["上午", "下午"],
+        date_patterns: ["yyyy年M月d日EEEE", "yyyy年M月d日", "yyyy/M/d"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "ko-KR",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "KRW",
+        currency_symbol: "₩",
+        currency_fractional_digits: 0,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: [
+            "1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월", "12월",
+        ],
+        format_month_names: None,
+        month_abbreviations: [
+            "1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월", "12월",
+        ],
+        weekday_names: [
+            "일요일",
+            "월요일",
+            "화요일",
+            "수요일",
+            "목요일",
+            "금요일",
+            "토요일",
+        ],
+        weekday_abbreviations: ["일", "월", "화", "수", "목", "금", "토"],
+        first_weekday: 0,
+        am_pm: ["오전", "오후"],
+        date_patterns: ["yyyy년 M월 d일 EEEE", "yyyy. M. d.", "yy. M. d."],
+        time_patterns: ["a h:mm:ss", "a h:mm:ss", "a h:mm"],
+    },
+];
+
+/// Returns the bundled locale with exactly the given name, ignoring case.
+pub fn find_locale(name: &str) -> Option<&'static LocaleData> {
+    LOCALES
+        .iter()
+        .find(|locale| locale.name.eq_ignore_ascii_case(name))
+}
+
+/// Picks the bundled locale that best matches a requested locale ID.
+///
+/// `i-default` requests the user's language, as reported by `default_locale`.
+/// Otherwise, an exact match is preferred, followed by a locale with the same language.
+/// If neither exists, `en-US` is used.
+pub fn resolve_locale(requested: &str, default_locale: &str) -> &'static LocaleData {
+    // Strip keywords such as `@collation=phonebook`, and normalize `en_US` to `en-US`.
+    let requested = requested.split('@').next().unwrap_or_default();
+    let mut requested = requested.replace('_', "-");
+    if requested.eq_ignore_ascii_case("i-default") {
+        requested = default_locale.replace('_', "-");
+    }
+
+    if let Some(locale) = find_locale(&requested) {
+        return locale;
+    }
+
+    let language = requested.split('-').next().unwrap_or_default();
+    LOCALES
+        .iter()
+        .find(|locale| locale.language().eq_ignore_ascii_case(language))
+        .unwrap_or(&LOCALES[0])
+}
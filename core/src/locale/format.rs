@@ -0,0 +1,587 @@
+//! Locale-aware number, currency and date formatting, following `flash.globalization`
+
+use super::data::LocaleData;
+use chrono::{DateTime, Datelike, FixedOffset, Timelike};
+
+/// The largest number of fractional digits that will be formatted.
+const MAX_FRACTIONAL_DIGITS: u32 = 20;
+
+/// The options shared by `NumberFormatter` and `CurrencyFormatter`.
+#[derive(Clone, Debug)]
+pub struct NumberFormat {
+    pub decimal_separator: String,
+    pub grouping_separator: String,
+    pub grouping_pattern: String,
+    pub fractional_digits: u32,
+    pub leading_zero: bool,
+    pub trailing_zeros: bool,
+    pub use_grouping: bool,
+    pub negative_symbol: String,
+}
+
+/// A number found by [`NumberFormat::parse`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParsedNumber {
+    pub value: f64,
+
+    /// The UTF-16 index of the first character of the number, including any sign.
+    pub start_index: usize,
+
+    /// The UTF-16 index just after the last character of the number.
+    pub end_index: usize,
+}
+
+impl NumberFormat {
+    /// Formats a number, using one of the `NumberFormatter.negativeNumberFormat` values.
+    pub fn format_number(&self, value: f64, negative_number_format: u32) -> String {
+        if !value.is_finite() {
+            return format_non_finite(value);
+        }
+
+        let number = self.format_digits(value);
+        if !is_negative(value, &number) {
+            return number;
+        }
+
+        let symbol = &self.negative_symbol;
+        match negative_number_format {
+            0 => format!("({number})"),
+            2 => format!("{symbol} {number}"),
+            3 => format!("{number}{symbol}"),
+            4 => format!("{number} {symbol}"),
+            _ => format!("{symbol}{number}"),
+        }
+    }
+
+    /// Formats an amount of currency, using the `CurrencyFormatter`
+    /// `positiveCurrencyFormat` and `negativeCurrencyFormat` values.
+    pub fn format_currency(
+        &self,
+        value: f64,
+        currency: &str,
+        positive_currency_format: u32,
+        negative_currency_format: u32,
+    ) -> String {
+        if !value.is_finite() {
+            return format_non_finite(value);
+        }
+
+        let number = self.format_digits(value);
+        let template = if is_negative(value, &number) {
+            match negative_currency_format {
+                0 => "(¤n)",
+                2 => "¤-n",
+                3 => "¤n-",
+                4 => "(n¤)",
+                5 => "-n¤",
+                6 => "n-¤",
+                7 => "n¤-",
+                8 => "-n ¤",
+                9 => "-¤ n",
+                10 => "n ¤-",
+                11 => "¤ n-",
+                12 => "¤ -n",
+                13 => "n- ¤",
+                14 => "(¤ n)",
+                15 => "(n ¤)",
+                _ => "-¤n",
+            }
+        } else {
+            match positive_currency_format {
+                1 => "n¤",
+                2 => "¤ n",
+                3 => "n ¤",
+                _ => "¤n",
+            }
+        };
+
+        let mut result = String::new();
+        for c in template.chars() {
+            match c {
+                '¤' => result.push_str(currency),
+                'n' => result.push_str(&number),
+                '-' => result.push_str(&self.negative_symbol),
+                c => result.push(c),
+            }
+        }
+        result
+    }
+
+    /// Formats the absolute value of a finite number, without any sign.
+    fn format_digits(&self, value: f64) -> String {
+        let digits = self.fractional_digits.min(MAX_FRACTIONAL_DIGITS) as usize;
+        let formatted = format!("{:.*}", digits, value.abs());
+        let (integer, fraction) = formatted
+            .split_once('.')
+            .unwrap_or((formatted.as_str(), ""));
+
+        let fraction = if self.trailing_zeros {
+            fraction
+        } else {
+            fraction.trim_end_matches('0')
+        };
+        let integer = if integer == "0" && !self.leading_zero && !fraction.is_empty() {
+            ""
+        } else {
+            integer
+        };
+
+        let mut result = if self.use_grouping {
+            self.group_digits(integer)
+        } else {
+            integer.to_string()
+        };
+        if !fraction.is_empty() {
+            result.push_str(&self.decimal_separator);
+            result.push_str(fraction);
+        }
+        result
+    }
+
+    /// Inserts grouping separators into a string of ASCII digits,
+    /// according to the grouping pattern.
+    fn group_digits(&self, digits: &str) -> String {
+        let Some((sizes, repeat)) = parse_grouping_pattern(&self.grouping_pattern) else {
+            return digits.to_string();
+        };
+
+        let mut groups = Vec::new();
+        let mut rest = digits;
+        for i in 0.. {
+            let size = match sizes.get(i) {
+                Some(&size) => size,
+                None if repeat => sizes[sizes.len() - 1],
+                None => break,
+            };
+            if size == 0 || rest.len() <= size {
+                break;
+            }
+            let (head, tail) = rest.split_at(rest.len() - size);
+            groups.push(tail);
+            rest = head;
+        }
+        groups.push(rest);
+        groups.reverse();
+        groups.join(&self.grouping_separator)
+    }
+
+    /// Finds the first number in a string, as done by `NumberFormatter.parse`.
+    ///
+    /// The number may be surrounded by other text, and may be negative in any of the
+    /// `NumberFormatter.negativeNumberFormat` styles.
+    pub fn parse(&self, input: &str) -> Option<ParsedNumber> {
+        let chars: Vec<char> = input.chars().collect();
+        let negative_symbol: Vec<char> = self.negative_symbol.chars().collect();
+        let decimal_separator: Vec<char> = self.decimal_separator.chars().collect();
+        let grouping_separator: Vec<char> = self.grouping_separator.chars().collect();
+
+        let starts_with = |at: usize, needle: &[char]| {
+            !needle.is_empty() && chars.get(at..at + needle.len()) == Some(needle)
+        };
+        let is_digit_at = |at: usize| chars.get(at).is_some_and(|c| c.is_ascii_digit());
+        let skip_spaces = |mut at: usize| {
+            while chars.get(at).is_some_and(|c| c.is_whitespace()) {
+                at += 1;
+            }
+            at
+        };
+        // Whether a number's digits begin at the given index.
+        let digits_at = |at: usize| {
+            is_digit_at(at)
+                || (starts_with(at, &decimal_separator)
+                    && is_digit_at(at + decimal_separator.len()))
+        };
+
+        for start in 0..chars.len() {
+            let mut negative = false;
+            let mut parenthesized = false;
+            let mut pos = start;
+            if chars[start] == '(' && digits_at(start + 1) {
+                parenthesized = true;
+                pos += 1;
+            } else if starts_with(start, &negative_symbol) {
+                let after_sign = skip_spaces(start + negative_symbol.len());
+                if digits_at(after_sign) {
+                    negative = true;
+                    pos = after_sign;
+                }
+            }
+            if pos == start && !digits_at(start) {
+                continue;
+            }
+
+            let mut number = String::new();
+            let mut seen_decimal = false;
+            loop {
+                if is_digit_at(pos) {
+                    number.push(chars[pos]);
+                    pos += 1;
+                } else if !seen_decimal
+                    && starts_with(pos, &decimal_separator)
+                    && is_digit_at(pos + decimal_separator.len())
+                {
+                    seen_decimal = true;
+                    number.push('.');
+                    pos += decimal_separator.len();
+                } else if !seen_decimal
+                    && !number.is_empty()
+                    && starts_with(pos, &grouping_separator)
+                    && is_digit_at(pos + grouping_separator.len())
+                {
+                    pos += grouping_separator.len();
+                } else {
+                    break;
+                }
+            }
+
+            if parenthesized {
+                if chars.get(pos) == Some(&')') {
+                    negative = true;
+                    pos += 1;
+                } else {
+                    // The opening parenthesis isn't part of the number after all.
+                    continue;
+                }
+            } else if !negative {
+                let after_number = skip_spaces(pos);
+                if starts_with(after_number, &negative_symbol) {
+                    negative = true;
+                    pos = after_number + negative_symbol.len();
+                }
+            }
+
+            let value: f64 = number.parse().ok()?;
+            return Some(ParsedNumber {
+                value: if negative { -value } else { value },
+                start_index: utf16_len(&chars[..start]),
+                end_index: utf16_len(&chars[..pos]),
+            });
+        }
+
+        None
+    }
+
+    /// Parses a string that must consist of only a number and surrounding whitespace,
+    /// as done by `NumberFormatter.parseNumber`.
+    pub fn parse_exact(&self, input: &str) -> Option<f64> {
+        let parsed = self.parse(input)?;
+        let units: Vec<u16> = input.encode_utf16().collect();
+        let is_blank = |units: &[u16]| {
+            char::decode_utf16(units.iter().copied()).all(|c| c.is_ok_and(char::is_whitespace))
+        };
+        if is_blank(&units[..parsed.start_index]) && is_blank(&units[parsed.end_index..]) {
+            Some(parsed.value)
+        } else {
+            None
+        }
+    }
+
+    /// Parses an amount of currency, as done by `CurrencyFormatter.parse`.
+    ///
+    /// Returns the amount, and the currency symbol or ISO code that was found (if any).
+    pub fn parse_currency(
+        &self,
+        input: &str,
+        currency_symbol: &str,
+        currency_iso_code: &str,
+    ) -> Option<(f64, String)> {
+        let found = [currency_symbol, currency_iso_code]
+            .into_iter()
+            .find(|currency| !currency.is_empty() && input.contains(currency));
+        let (number, currency) = match found {
+            Some(currency) => (input.replacen(currency, "", 1), currency.to_string()),
+            None => (input.to_string(), String::new()),
+        };
+        self.parse_exact(&number).map(|value| (value, currency))
+    }
+}
+
+/// Parses a `groupingPattern` such as `3;2;*` into group sizes
+/// and whether the last size repeats.
+fn parse_grouping_pattern(pattern: &str) -> Option<(Vec<usize>, bool)> {
+    let mut sizes = Vec::new();
+    let mut repeat = false;
+    for part in pattern.split(';') {
+        if repeat {
+            // `*` may only appear at the end.
+            return None;
+        }
+        if part == "*" {
+            repeat = true;
+        } else {
+            sizes.push(part.parse().ok()?);
+        }
+    }
+    if sizes.is_empty() {
+        None
+    } else {
+        Some((sizes, repeat))
+    }
+}
+
+fn is_negative(value: f64, formatted: &str) -> bool {
+    value < 0.0 && formatted.bytes().any(|b| matches!(b, b'1'..=b'9'))
+}
+
+fn format_non_finite(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value > 0.0 {
+        "Infinity".to_string()
+    } else {
+        "-Infinity".to_string()
+    }
+}
+
+fn utf16_len(chars: &[char]) -> usize {
+    chars.iter().map(|c| c.len_utf16()).sum()
+}
+
+/// Formats a date with a `DateTimeFormatter` pattern, such as `EEEE, MMMM d, yyyy`.
+///
+/// Letters are replaced by date fields, repeated to pick their width;
+/// text within single quotes is copied as-is.
+pub fn format_date_time(
+    locale: &LocaleData,
+    pattern: &str,
+    date: &DateTime<FixedOffset>,
+) -> String {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut result = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c == '\'' {
+            if chars.get(i + 1) == Some(&'\'') {
+                result.push('\'');
+                i += 2;
+                continue;
+            }
+            i += 1;
+            while i < chars.len() {
+                if chars[i] == '\'' {
+                    if chars.get(i + 1) == Some(&'\'') {
+                        result.push('\'');
+                        i += 2;
+                        continue;
+                    }
+                    break;
+                }
+                result.push(chars[i]);
+                i += 1;
+            }
+            i += 1;
+            continue;
+        }
+
+        if !c.is_ascii_alphabetic() {
+            result.push(c);
+            i += 1;
+            continue;
+        }
+
+        let mut count = 1;
+        while chars.get(i + count) == Some(&c) {
+            count += 1;
+        }
+        i += count;
+
+        let number = |value: u32| format!("{value:0count$}");
+        let month = date.month0() as usize;
+        let weekday = date.weekday().num_days_from_sunday() as usize;
+        let hour = date.hour();
+
+        match c {
+            'G' => result.push_str(if date.year() > 0 { "AD" } else { "BC" }),
+            'y' => {
+                let year = date.year().unsigned_abs();
+                if count == 2 {
+                    result.push_str(&format!("{:02}", year % 100));
+                } else {
+                    result.push_str(&number(year));
+                }
+            }
+            'M' => match count {
+                1 | 2 => result.push_str(&number(date.month())),
+                3 => result.push_str(locale.month_abbreviations[month]),
+                4 => result.push_str(locale.format_month_names()[month]),
+                _ => result.extend(locale.month_names[month].chars().next()),
+            },
+            'd' => result.push_str(&number(date.day())),
+            'D' => result.push_str(&number(date.ordinal())),
+            'E' => match count {
+                1..=3 => result.push_str(locale.weekday_abbreviations[weekday]),
+                4 => result.push_str(locale.weekday_names[weekday]),
+                _ => result.extend(locale.weekday_names[weekday].chars().next()),
+            },
+            'Q' => result.push_str(&number(month as u32 / 3 + 1)),
+            'a' => result.push_str(locale.am_pm[(hour >= 12) as usize]),
+            'h' => result.push_str(&number((hour + 11) % 12 + 1)),
+            'H' => result.push_str(&number(hour)),
+            'K' => result.push_str(&number(hour % 12)),
+            'k' => result.push_str(&number(if hour == 0 { 24 } else { hour })),
+            'm' => result.push_str(&number(date.minute())),
+            's' => result.push_str(&number(date.second())),
+            'S' => {
+                let millis = format!("{:03}", date.timestamp_subsec_millis().min(999));
+                let digits: String = millis
+                    .chars()
+                    .chain(std::iter::repeat('0'))
+                    .take(count)
+                    .collect();
+                result.push_str(&digits);
+            }
+            'z' | 'Z' | 'v' => {
+                let offset = date.offset().local_minus_utc();
+                let sign = if offset < 0 { '-' } else { '+' };
+                let offset = offset.unsigned_abs() / 60;
+                let (hours, minutes) = (offset / 60, offset % 60);
+                if c == 'Z' {
+                    result.push_str(&format!("{sign}{hours:02}{minutes:02}"));
+                } else {
+                    result.push_str(&format!("GMT{sign}{hours:02}:{minutes:02}"));
+                }
+            }
+            _ => {
+                for _ in 0..count {
+                    result.push(c);
+                }
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locale::data::find_locale;
+    use chrono::TimeZone;
+
+    /// The default options of a `NumberFormatter` for the given locale.
+    fn number_format(locale: &str) -> NumberFormat {
+        let locale = find_locale(locale).unwrap();
+        NumberFormat {
+            decimal_separator: locale.decimal_separator.to_string(),
+            grouping_separator: locale.grouping_separator.to_string(),
+            grouping_pattern: locale.grouping_pattern.to_string(),
+            fractional_digits: 2,
+            leading_zero: true,
+            trailing_zeros: false,
+            use_grouping: true,
+            negative_symbol: "-".to_string(),
+        }
+    }
+
+    #[test]
+    fn format_number_grouping() {
+        let format = number_format("en-US");
+        assert_eq!(format.format_number(1234567.891, 1), "1,234,567.89");
+        assert_eq!(format.format_number(-1234.5, 1), "-1,234.5");
+        assert_eq!(format.format_number(-1234.5, 0), "(1,234.5)");
+        assert_eq!(format.format_number(-0.001, 1), "0");
+        assert_eq!(format.format_number(f64::NAN, 1), "NaN");
+
+        let format = NumberFormat {
+            grouping_pattern: "3;2;*".to_string(),
+            trailing_zeros: true,
+            ..number_format("en-US")
+        };
+        assert_eq!(format.format_number(123456789.0, 1), "12,34,56,789.00");
+
+        let format = NumberFormat {
+            grouping_pattern: "3".to_string(),
+            leading_zero: false,
+            ..number_format("en-US")
+        };
+        assert_eq!(format.format_number(1234567.0, 1), "1234,567");
+        assert_eq!(format.format_number(0.25, 1), ".25");
+
+        let format = number_format("de-DE");
+        assert_eq!(format.format_number(1234567.891, 1), "1.234.567,89");
+    }
+
+    #[test]
+    fn format_currency() {
+        let locale = find_locale("de-DE").unwrap();
+        let format = number_format("de-DE");
+        assert_eq!(
+            format.format_currency(-1234.5, locale.currency_symbol, 3, 8),
+            "-1.234,5 €"
+        );
+        assert_eq!(
+            format.format_currency(1234.5, locale.currency_iso_code, 3, 8),
+            "1.234,5 EUR"
+        );
+
+        let format = number_format("en-US");
+        assert_eq!(format.format_currency(-5.0, "$", 0, 0), "($5)");
+        assert_eq!(format.format_currency(-5.0, "$", 0, 12), "$ -5");
+    }
+
+    #[test]
+    fn parse_number() {
+        let format = number_format("en-US");
+        assert_eq!(
+            format.parse("Total: 1,234.5 items"),
+            Some(ParsedNumber {
+                value: 1234.5,
+                start_index: 7,
+                end_index: 14,
+            })
+        );
+        assert_eq!(
+            format.parse("(42)"),
+            Some(ParsedNumber {
+                value: -42.0,
+                start_index: 0,
+                end_index: 4,
+            })
+        );
+        assert_eq!(format.parse("12 -").map(|p| p.value), Some(-12.0));
+        assert_eq!(format.parse("no digits"), None);
+
+        assert_eq!(format.parse_exact(" -3.25 "), Some(-3.25));
+        assert_eq!(format.parse_exact("3.25 apples"), None);
+
+        let format = number_format("fr-FR");
+        assert_eq!(format.parse_exact("1\u{a0}234,5"), Some(1234.5));
+        assert_eq!(
+            format.parse_currency("-1\u{a0}234,5 €", "€", "EUR"),
+            Some((-1234.5, "€".to_string()))
+        );
+    }
+
+    #[test]
+    fn format_date_patterns() {
+        let date = FixedOffset::east_opt(20700)
+            .unwrap()
+            .with_ymd_and_hms(2001, 2, 3, 16, 5, 6)
+            .unwrap();
+
+        let en_us = find_locale("en-US").unwrap();
+        assert_eq!(
+            format_date_time(en_us, "EEEE, MMMM d, yyyy h:mm:ss a", &date),
+            "Saturday, February 3, 2001 4:05:06 PM"
+        );
+        assert_eq!(
+            format_date_time(en_us, "yy-MM-dd'T'HH:mm 'o''clock' Z", &date),
+            "01-02-03T16:05 o'clock +0545"
+        );
+
+        let ru_ru = find_locale("ru-RU").unwrap();
+        assert_eq!(
+            format_date_time(ru_ru, ru_ru.date_patterns[0], &date),
+            "суббота, 3 февраля 2001 г."
+        );
+
+        let ja_jp = find_locale("ja-JP").unwrap();
+        assert_eq!(
+            format_date_time(ja_jp, ja_jp.date_patterns[0], &date),
+            "2001年2月3日土曜日"
+        );
+    }
+}
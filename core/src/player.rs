@@ -7,7 +7,8 @@ use crate::avm1::VariableDumper;
 use crate::avm1::{Activation, ActivationIdentifier};
 use crate::avm1::{TObject, Value};
 use crate::avm2::{
-    object::TObject as _, Activation as Avm2Activation, Avm2, CallStack, Object as Avm2Object,
+    object::TObject as _, Activation as Avm2Activation, Avm2, CallStack, EventObject as Avm2EventObject,
+    Object as Avm2Object,
 };
 use crate::backend::ui::FontDefinition;
 use crate::backend::{
@@ -30,7 +31,9 @@ use crate::display_object::{
     TInteractiveObject, WindowMode,
 };
 use crate::events::GamepadButton;
-use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, MouseButton, PlayerEvent};
+use crate::events::{
+    ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, MouseButton, PlayerEvent, TextControlCode,
+};
 use crate::external::{ExternalInterface, ExternalInterfaceProvider, NullFsCommandProvider};
 use crate::external::{FsCommandProvider, Value as ExternalValue};
 use crate::frame_lifecycle::{run_all_phases_avm2, FramePhase};
@@ -315,6 +318,9 @@ pub struct Player {
     mouse_cursor: MouseCursor,
     mouse_cursor_needs_check: bool,
 
+    /// The cursor set by AS3 `Mouse.cursor`, displayed instead of `mouse_cursor`.
+    mouse_cursor_override: Option<MouseCursor>,
+
     system: SystemProperties,
 
     page_url: Option<String>,
@@ -861,6 +867,27 @@ impl Player {
     /// 7. The AVM1 action queue is drained.
     /// 8. Mouse state is updated. This triggers button rollovers, which are a
     ///    second wave of event processing.
+    /// Dispatches `Event.PASTE` to the focused object, or the stage if nothing has focus.
+    ///
+    /// Its listeners are the only code that may read `Clipboard.generalClipboard`.
+    fn dispatch_avm2_paste_event(context: &mut UpdateContext<'_, '_>) {
+        let target = context
+            .focus_tracker
+            .get()
+            .unwrap_or_else(|| context.stage.into());
+        if !target.movie().is_action_script_3() {
+            return;
+        }
+        let Avm2Value::Object(target) = target.object2() else {
+            return;
+        };
+
+        let event = Avm2EventObject::bare_event(context, "paste", true, false);
+        context.avm2.is_in_paste_event = true;
+        Avm2::dispatch_event(context, event, target);
+        context.avm2.is_in_paste_event = false;
+    }
+
     pub fn handle_event(&mut self, event: PlayerEvent) {
         // Optionally transform gamepad button events into key events.
         let event = match event {
@@ -1049,6 +1076,13 @@ impl Player {
                 }
             }
 
+            if let PlayerEvent::TextControl {
+                code: TextControlCode::Paste,
+            } = event
+            {
+                Self::dispatch_avm2_paste_event(context);
+            }
+
             // keyPress events take precedence over text input.
             if !key_press_handled {
                 if let PlayerEvent::TextInput { codepoint } = event {
@@ -1252,7 +1286,7 @@ impl Player {
 
     /// Updates the hover state of buttons.
     fn update_mouse_state(&mut self, is_mouse_button_changed: bool, is_mouse_moved: bool) -> bool {
-        let mut new_cursor = self.mouse_cursor;
+        let mut new_cursor = self.mouse_cursor.clone();
         let mut mouse_cursor_needs_check = self.mouse_cursor_needs_check;
         let mouse_in_stage = self.mouse_in_stage();
 
@@ -1499,8 +1533,10 @@ impl Player {
 
         // Update mouse cursor if it has changed.
         if new_cursor != self.mouse_cursor {
+            if self.mouse_cursor_override.is_none() {
+                self.ui.set_mouse_cursor(new_cursor.clone());
+            }
             self.mouse_cursor = new_cursor;
-            self.ui.set_mouse_cursor(new_cursor)
         }
         self.mouse_cursor_needs_check = mouse_cursor_needs_check;
 
@@ -1887,6 +1923,8 @@ impl Player {
                 mouse_down_object: mouse_pressed_object,
                 input: &self.input,
                 mouse_position: &self.mouse_position,
+                mouse_cursor: &self.mouse_cursor,
+                mouse_cursor_override: &mut self.mouse_cursor_override,
                 drag_object,
                 player: self.self_reference.clone(),
                 load_manager,
@@ -2088,6 +2126,11 @@ impl Player {
         &self.log
     }
 
+    /// The stubs that have been encountered while running this player.
+    pub fn stub_tracker(&self) -> &StubCollection {
+        &self.stub_tracker
+    }
+
     pub fn max_execution_duration(&self) -> Duration {
         self.max_execution_duration
     }
@@ -2541,6 +2584,7 @@ impl PlayerBuilder {
                 mouse_position: Point::ZERO,
                 mouse_cursor: MouseCursor::Arrow,
                 mouse_cursor_needs_check: false,
+                mouse_cursor_override: None,
 
                 // Misc. state
                 rng: SmallRng::seed_from_u64(get_current_date_time().timestamp_millis() as u64),
@@ -21,18 +21,24 @@ use crate::loader::Error;
 use crate::string::AvmString;
 use crate::vminterface::AvmObject;
 use flv_rs::{
-    AudioData as FlvAudioData, AudioDataType as FlvAudioDataType, Error as FlvError, FlvReader,
-    FrameType as FlvFrameType, Header as FlvHeader, ScriptData as FlvScriptData,
-    SoundFormat as FlvSoundFormat, SoundRate as FlvSoundRate, SoundSize as FlvSoundSize,
-    SoundType as FlvSoundType, Tag as FlvTag, TagData as FlvTagData, Value as FlvValue,
-    VideoData as FlvVideoData, VideoPacket as FlvVideoPacket,
+    AudioData as FlvAudioData, AudioDataType as FlvAudioDataType, CodecId as FlvCodecId,
+    Error as FlvError, FlvReader, FrameType as FlvFrameType, Header as FlvHeader,
+    ScriptData as FlvScriptData, SoundFormat as FlvSoundFormat, SoundRate as FlvSoundRate,
+    SoundSize as FlvSoundSize, SoundType as FlvSoundType, Tag as FlvTag, TagData as FlvTagData,
+    Value as FlvValue, Variable as FlvVariable, VideoData as FlvVideoData,
+    VideoPacket as FlvVideoPacket,
 };
 use gc_arena::{Collect, GcCell, Mutation};
+use mp4_rs::{
+    Error as Mp4Error, Movie as Mp4Movie, SampleEntry as Mp4SampleEntry, SampleRef as Mp4SampleRef,
+    Track as Mp4Track, TrackKind as Mp4TrackKind,
+};
 use ruffle_render::bitmap::BitmapInfo;
 use ruffle_video::frame::EncodedFrame;
 use ruffle_video::VideoStreamHandle;
 use std::cmp::max;
 use std::io::{Seek, SeekFrom};
+use std::sync::Arc;
 use swf::{AudioCompression, SoundFormat, VideoCodec, VideoDeblocking};
 use thiserror::Error;
 use url::Url;
@@ -176,6 +182,49 @@ pub enum NetStreamType {
         /// frame IDs ourselves for various API related purposes.
         frame_id: u32,
     },
+
+    /// The stream is an MP4, or one of its relatives such as F4V and M4A.
+    Mp4 {
+        /// The movie box, which is parsed in full before playback starts.
+        movie: Arc<Mp4Movie>,
+
+        /// The samples of the played tracks, in the order they are decoded.
+        samples: Vec<Mp4SampleRef>,
+
+        /// The index of the next sample to process.
+        next_sample: usize,
+
+        /// The index of the first sample that has never been processed.
+        preload_sample: usize,
+
+        /// The index of the played video track, if any.
+        video_track: Option<usize>,
+
+        /// The currently playing video track's stream instance.
+        video_stream: Option<VideoStreamHandle>,
+
+        /// The index of the last processed frame.
+        frame_id: u32,
+
+        /// Whether `onMetaData` was already called for this movie.
+        sent_metadata: bool,
+    },
+}
+
+/// An action that controls how a `NetStream` handles the data given to it in
+/// data generation mode, corresponding to AS3's `NetStreamAppendBytesAction`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppendBytesAction {
+    /// Discard the buffer and expect a new FLV, starting with its header.
+    ResetBegin,
+
+    /// Discard the buffer and expect the current FLV to continue from the
+    /// start of a tag, such as after a seek.
+    ResetSeek,
+
+    /// Indicate that no further data will be appended, so the stream should
+    /// stop once the buffer is played out.
+    EndSequence,
 }
 
 #[derive(Clone, Debug, Collect)]
@@ -209,6 +258,10 @@ pub struct NetStreamData<'gc> {
     #[collect(require_static)]
     stream_type: Option<NetStreamType>,
 
+    /// Whether sniffing the container format of the buffer failed, in which
+    /// case the stream is not processed until new data replaces the buffer.
+    sniff_failed: bool,
+
     /// The current seek offset in the stream in milliseconds.
     stream_time: f64,
 
@@ -238,6 +291,10 @@ pub struct NetStreamData<'gc> {
     #[collect(require_static)]
     audio_stream: Option<(Substream, SoundStreamInfo)>,
 
+    /// The most recent AAC sequence header, which is needed to decode every
+    /// audio stream started after it, including those started after a seek.
+    audio_codec_config: Option<Vec<u8>>,
+
     /// The currently playing sound stream
     #[collect(require_static)]
     sound_instance: Option<SoundInstanceHandle>,
@@ -247,6 +304,14 @@ pub struct NetStreamData<'gc> {
 
     /// True if the stream should play when ticked.
     playing: bool,
+
+    /// True if the stream is in data generation mode, where the buffer is
+    /// filled by `append_bytes` rather than by downloading a URL.
+    data_generation: bool,
+
+    /// True if the stream time should jump to the timestamp of the next tag,
+    /// which is the case after the buffer was reset for a seek.
+    sync_time_to_next_tag: bool,
 }
 
 impl<'gc> NetStream<'gc> {
@@ -260,6 +325,7 @@ impl<'gc> NetStream<'gc> {
                 offset: 0,
                 preload_offset: 0,
                 stream_type: None,
+                sniff_failed: false,
                 stream_time: 0.0,
                 queued_seek_time: None,
                 last_decoded_bitmap: None,
@@ -267,9 +333,12 @@ impl<'gc> NetStream<'gc> {
                 avm2_client: None,
                 url: None,
                 audio_stream: None,
+                audio_codec_config: None,
                 sound_instance: None,
                 attached_to: None,
                 playing: false,
+                data_generation: false,
+                sync_time_to_next_tag: false,
                 expected_length: Some(0),
             },
         ))
@@ -311,9 +380,41 @@ impl<'gc> NetStream<'gc> {
         write.offset = 0;
         write.preload_offset = 0;
         write.stream_type = None;
+        write.sniff_failed = false;
         write.stream_time = 0.0;
         write.queued_seek_time = None;
         write.audio_stream = None;
+        write.audio_codec_config = None;
+        write.sound_instance = None;
+        write.sync_time_to_next_tag = false;
+        write.expected_length = Some(0);
+    }
+
+    /// Discard all data in the buffer, but keep the state of the FLV being
+    /// played so that it can continue from data appended afterwards.
+    ///
+    /// This is only meaningful in data generation mode, as a downloaded
+    /// stream cannot be refilled.
+    fn flush_buffer(self, context: &mut UpdateContext<'_, 'gc>) {
+        let mut write = self.0.write(context.gc_context);
+
+        if let Some(instance) = write.sound_instance {
+            context.audio.stop_sound(instance);
+            context.audio_manager.stop_sound(context.audio, instance);
+        }
+
+        if matches!(write.stream_type, Some(NetStreamType::Flv { .. })) {
+            // FLV tags are parsed along with the size of the tag before them,
+            // which appended data doesn't start with after a flush.
+            write.buffer = Buffer::from(vec![0; 4]);
+        } else {
+            write.buffer = Buffer::new();
+            write.stream_type = None;
+            write.sniff_failed = false;
+        }
+        write.offset = 0;
+        write.preload_offset = 0;
+        write.audio_stream = None;
         write.sound_instance = None;
         write.expected_length = Some(0);
     }
@@ -358,6 +459,48 @@ impl<'gc> NetStream<'gc> {
         self.0.write(context.gc_context).expected_length = None;
     }
 
+    /// Append script-supplied data to the buffer in data generation mode.
+    ///
+    /// Data is ignored if the stream is not in data generation mode, or if
+    /// the end of the sequence was already signalled.
+    pub fn append_bytes(self, context: &mut UpdateContext<'_, 'gc>, data: &[u8]) {
+        let mut write = self.0.write(context.gc_context);
+        if !write.data_generation || write.expected_length.is_none() {
+            return;
+        }
+
+        write.buffer.extend_from_slice(data);
+        drop(write);
+
+        StreamManager::activate(context, self);
+    }
+
+    /// Change how data appended in data generation mode is handled.
+    pub fn append_bytes_action(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        action: AppendBytesAction,
+    ) {
+        if !self.0.read().data_generation {
+            return;
+        }
+
+        match action {
+            AppendBytesAction::ResetBegin => {
+                self.reset_buffer(context);
+            }
+            AppendBytesAction::ResetSeek => {
+                self.flush_buffer(context);
+                self.0.write(context.gc_context).sync_time_to_next_tag = true;
+            }
+            AppendBytesAction::EndSequence => {
+                self.finish_buffer(context);
+            }
+        }
+
+        StreamManager::activate(context, self);
+    }
+
     pub fn report_error(self, _error: Error) {
         //TODO: Report an `asyncError` to AVM1 or 2.
     }
@@ -422,6 +565,14 @@ impl<'gc> NetStream<'gc> {
             vec![("code", "NetStream.Seek.Notify"), ("level", "status")],
         );
 
+        // In data generation mode, the script is responsible for supplying
+        // the data at the new position, so all we do is drop the old data.
+        if self.0.read().data_generation {
+            self.flush_buffer(context);
+            self.0.write(context.gc_context).stream_time = offset;
+            return;
+        }
+
         // Ensure the container stream type is known before continuing.
         if self.0.read().stream_type.is_none() && !self.sniff_stream_type(context) {
             return;
@@ -514,6 +665,27 @@ impl<'gc> NetStream<'gc> {
                 .expect("FLV reader stream position") as usize;
         }
 
+        if let Some(NetStreamType::Mp4 {
+            movie,
+            samples,
+            next_sample,
+            video_track,
+            ..
+        }) = &mut write.stream_type
+        {
+            // Playback has to start from a keyframe, so snap to the last one
+            // before the requested time.
+            let time = video_track
+                .and_then(|index| {
+                    let track = &movie.tracks[index];
+                    let keyframe = track.sync_sample_before(offset)?;
+                    Some(track.time_to_ms(track.samples[keyframe].decode_time))
+                })
+                .unwrap_or(offset);
+            *next_sample = samples.partition_point(|sample| sample.time < time);
+            write.stream_time = time;
+        }
+
         drop(write);
 
         if let Some(AvmObject::Avm2(_)) = self.0.read().avm_object {
@@ -531,8 +703,8 @@ impl<'gc> NetStream<'gc> {
     /// Start playing media from this NetStream.
     ///
     /// If `name` is specified, this will also trigger streaming download of
-    /// the given resource. Otherwise, the stream will play whatever data is
-    /// available in the buffer.
+    /// the given resource. Otherwise, the stream enters data generation mode,
+    /// and will play whatever data is given to it with `append_bytes`.
     pub fn play(self, context: &mut UpdateContext<'_, 'gc>, name: Option<AvmString<'gc>>) {
         if let Some(name) = name {
             let request = if let Ok(stream_url) =
@@ -545,11 +717,18 @@ impl<'gc> NetStream<'gc> {
             let mut write = self.0.write(context.gc_context);
             write.url = Some(request.url().to_string());
             write.preload_offset = 0;
+            write.sniff_failed = false;
+            write.data_generation = false;
             let future = context
                 .load_manager
                 .load_netstream(context.player.clone(), self, request);
 
             context.navigator.spawn_future(future);
+        } else {
+            self.reset_buffer(context);
+            let mut write = self.0.write(context.gc_context);
+            write.url = None;
+            write.data_generation = true;
         }
 
         self.0.write(context.gc_context).playing = true;
@@ -638,9 +817,16 @@ impl<'gc> NetStream<'gc> {
         audio_data: FlvAudioData<'_>,
     ) -> Result<(), NetstreamError> {
         let data = match audio_data.data {
-            FlvAudioDataType::Raw(data)
-            | FlvAudioDataType::AacSequenceHeader(data)
-            | FlvAudioDataType::AacRaw(data) => slice.to_subslice(data),
+            FlvAudioDataType::Raw(data) | FlvAudioDataType::AacRaw(data) => slice.to_subslice(data),
+            FlvAudioDataType::AacSequenceHeader(config) => {
+                // The sequence header configures the decoder rather than
+                // holding audio, so it's kept aside for the next stream.
+                write.audio_codec_config = Some(config.to_vec());
+                if let Some((_substream, sound_stream_info)) = &mut write.audio_stream {
+                    sound_stream_info.codec_config = Some(config.to_vec());
+                }
+                return Ok(());
+            }
         };
         let substream = match &mut write.audio_stream {
             Some((substream, _sound_stream_info)) => {
@@ -677,7 +863,7 @@ impl<'gc> NetStream<'gc> {
                         FlvSoundFormat::Nellymoser => AudioCompression::Nellymoser,
                         FlvSoundFormat::G711ALawPCM => return Err(NetstreamError::UnknownCodec),
                         FlvSoundFormat::G711MuLawPCM => return Err(NetstreamError::UnknownCodec),
-                        FlvSoundFormat::Aac => return Err(NetstreamError::UnknownCodec),
+                        FlvSoundFormat::Aac => AudioCompression::Aac,
                         FlvSoundFormat::Speex => AudioCompression::Speex,
                         FlvSoundFormat::MP38kHz => AudioCompression::Mp3,
                         FlvSoundFormat::DeviceSpecific => return Err(NetstreamError::UnknownCodec),
@@ -704,6 +890,7 @@ impl<'gc> NetStream<'gc> {
                     stream_format: swf_format,
                     num_samples_per_block: 0,
                     latency_seek: 0,
+                    codec_config: write.audio_codec_config.clone(),
                 };
 
                 *audio_stream = Some((substream, sound_stream_head));
@@ -791,10 +978,7 @@ impl<'gc> NetStream<'gc> {
         let slice = write.buffer.to_full_slice();
         let buffer = slice.data();
 
-        // A nonzero preload offset indicates that we tried and failed to
-        // sniff the container format, so in that case do not process the
-        // stream anymore.
-        if write.preload_offset > 0 {
+        if write.sniff_failed {
             return false;
         }
 
@@ -816,16 +1000,32 @@ impl<'gc> NetStream<'gc> {
                     Err(e) => {
                         //TODO: Fire an error event to AS & stop playing too
                         tracing::error!("FLV header parsing failed: {}", e);
-                        write.preload_offset = 3;
+                        write.sniff_failed = true;
                         false
                     }
                 }
             }
+            // An MP4 is recognised by the type of its first box, which
+            // follows the size of the box.
+            Some(_) if buffer.len() < 8 && write.expected_length.is_some() => false,
+            Some(_) if mp4_rs::is_mp4(&buffer) => match Mp4Movie::parse(&buffer) {
+                Ok(movie) => {
+                    write.stream_type = Some(Self::mp4_stream_type(context, &mut write, movie));
+                    true
+                }
+                Err(Mp4Error::EndOfData) => false,
+                Err(e) => {
+                    //TODO: Fire an error event to AS & stop playing too
+                    tracing::error!("MP4 parsing failed: {}", e);
+                    write.sniff_failed = true;
+                    false
+                }
+            },
             Some(magic) => {
                 //Unrecognized signature
                 //TODO: Fire an error event to AS & stop playing too
                 tracing::error!("Unrecognized file signature: {:?}", magic);
-                write.preload_offset = 3;
+                write.sniff_failed = true;
                 if let Some(url) = &write.url {
                     if url.is_empty() {
                         return false;
@@ -849,6 +1049,137 @@ impl<'gc> NetStream<'gc> {
         }
     }
 
+    /// Choose the tracks of an MP4 to play, and prepare their decoders.
+    ///
+    /// `write` must be an active borrow of the current `NetStream`.
+    fn mp4_stream_type(
+        context: &mut UpdateContext<'_, 'gc>,
+        write: &mut NetStreamData<'gc>,
+        movie: Mp4Movie,
+    ) -> NetStreamType {
+        let supported_track = |kind| {
+            let index = movie.first_track(kind)?;
+            let sample_entry = &movie.tracks[index].sample_entry;
+            match (kind, sample_entry) {
+                (Mp4TrackKind::Video, Mp4SampleEntry::Avc { .. })
+                | (Mp4TrackKind::Audio, Mp4SampleEntry::Aac { .. } | Mp4SampleEntry::Mp3 { .. }) => {
+                    Some(index)
+                }
+                _ => {
+                    tracing::error!(
+                        "MP4 track has unsupported codec {}",
+                        String::from_utf8_lossy(mp4_sample_type(sample_entry))
+                    );
+                    None
+                }
+            }
+        };
+        let video_track = supported_track(Mp4TrackKind::Video);
+        let audio_track = supported_track(Mp4TrackKind::Audio);
+
+        let video_stream =
+            video_track.and_then(|index| Self::mp4_video_stream(context, &movie.tracks[index]));
+        let video_track = video_track.filter(|_| video_stream.is_some());
+
+        if let Some(Mp4SampleEntry::Aac { config, .. }) =
+            audio_track.map(|index| &movie.tracks[index].sample_entry)
+        {
+            if !config.is_empty() {
+                write.audio_codec_config = Some(config.clone());
+            }
+        }
+
+        let tracks: Vec<usize> = video_track.into_iter().chain(audio_track).collect();
+        NetStreamType::Mp4 {
+            samples: movie.interleave(&tracks),
+            movie: Arc::new(movie),
+            next_sample: 0,
+            preload_sample: 0,
+            video_track,
+            video_stream,
+            frame_id: 0,
+            sent_metadata: false,
+        }
+    }
+
+    /// Register the video stream of an MP4 video track and configure its
+    /// decoder.
+    fn mp4_video_stream(
+        context: &mut UpdateContext<'_, 'gc>,
+        track: &Mp4Track,
+    ) -> Option<VideoStreamHandle> {
+        let Mp4SampleEntry::Avc {
+            width,
+            height,
+            config,
+        } = &track.sample_entry
+        else {
+            return None;
+        };
+
+        let video_handle = match context.video.register_video_stream(
+            track.samples.len() as u32,
+            (*width, *height),
+            VideoCodec::H264,
+            VideoDeblocking::UseVideoPacketValue,
+        ) {
+            Ok(video_handle) => video_handle,
+            Err(e) => {
+                tracing::error!("Got error when registering MP4 video stream: {}", e);
+                return None;
+            }
+        };
+
+        if let Err(e) = context
+            .video
+            .configure_video_stream_decoder(video_handle, config)
+        {
+            tracing::error!("Configuring MP4 video decoder failed: {}", e);
+        }
+
+        Some(video_handle)
+    }
+
+    /// Get the video stream of an FLV, registering one if necessary.
+    ///
+    /// `write` must be an active borrow of the current `NetStream`.
+    fn flv_video_stream(
+        context: &mut UpdateContext<'_, 'gc>,
+        write: &mut NetStreamData<'gc>,
+        maybe_video_handle: Option<VideoStreamHandle>,
+        codec: VideoCodec,
+    ) -> Option<VideoStreamHandle> {
+        if let Some(video_handle) = maybe_video_handle {
+            return Some(video_handle);
+        }
+
+        //Some movies don't actually have metadata, so let's register a
+        //dummy stream just in case. All the actual data in the registration
+        //is lies, of course.
+        match context.video.register_video_stream(
+            1,
+            (8, 8),
+            codec,
+            VideoDeblocking::UseVideoPacketValue,
+        ) {
+            Ok(new_handle) => {
+                match &mut write.stream_type {
+                    Some(NetStreamType::Flv { video_stream, .. })
+                    | Some(NetStreamType::Mp4 { video_stream, .. }) => {
+                        *video_stream = Some(new_handle)
+                    }
+                    _ => unreachable!(),
+                }
+
+                Some(new_handle)
+            }
+            Err(e) => {
+                tracing::error!("Got error when registering FLV video stream: {}", e);
+                None
+            }
+        }
+    }
+
     /// Process a parsed FLV video tag.
     ///
     /// `write` must be an active borrow of the current `NetStream`. `slice`
@@ -869,6 +1200,11 @@ impl<'gc> NetStream<'gc> {
                 video_stream,
                 frame_id,
                 ..
+            })
+            | Some(NetStreamType::Mp4 {
+                video_stream,
+                frame_id,
+                ..
             }) => (video_stream, frame_id),
             _ => unreachable!(),
         };
@@ -885,38 +1221,21 @@ impl<'gc> NetStream<'gc> {
                     vadjust: _,
                     mut data,
                 },
+            )
+            // TODO: Frames are shown in decoding order, the composition time
+            // offset of B-frames is ignored.
+            | (
+                maybe_video_handle,
+                Some(codec),
+                FlvVideoPacket::AvcNalu {
+                    composition_time_offset: _,
+                    mut data,
+                },
             ) => {
-                //Some movies don't actually have metadata, so let's register a
-                //dummy stream just in case. All the actual data in the registration
-                //is lies, of course.
-                let video_handle = match maybe_video_handle {
-                    Some(stream) => stream,
-                    None => {
-                        match context.video.register_video_stream(
-                            1,
-                            (8, 8),
-                            codec,
-                            VideoDeblocking::UseVideoPacketValue,
-                        ) {
-                            Ok(new_handle) => {
-                                match &mut write.stream_type {
-                                    Some(NetStreamType::Flv { video_stream, .. }) => {
-                                        *video_stream = Some(new_handle)
-                                    }
-                                    _ => unreachable!(),
-                                }
-
-                                new_handle
-                            }
-                            Err(e) => {
-                                tracing::error!(
-                                    "Got error when registering FLV video stream: {}",
-                                    e
-                                );
-                                return; //TODO: This originally breaks and halts tag processing
-                            }
-                        }
-                    }
+                let Some(video_handle) =
+                    Self::flv_video_stream(context, write, maybe_video_handle, codec)
+                else {
+                    return; //TODO: This originally breaks and halts tag processing
                 };
 
                 if codec == VideoCodec::ScreenVideo || codec == VideoCodec::ScreenVideoV2 {
@@ -968,14 +1287,22 @@ impl<'gc> NetStream<'gc> {
             (_, _, FlvVideoPacket::CommandFrame(_command)) => {
                 tracing::warn!("Stub: FLV command frame processing")
             }
-            (_, _, FlvVideoPacket::AvcSequenceHeader(_data)) => {
-                tracing::warn!("Stub: FLV AVC/H.264 Sequence Header processing")
-            }
-            (_, _, FlvVideoPacket::AvcNalu { .. }) => {
-                tracing::warn!("Stub: FLV AVC/H.264 NALU processing")
+            (maybe_video_handle, Some(codec), FlvVideoPacket::AvcSequenceHeader(data)) => {
+                let Some(video_handle) =
+                    Self::flv_video_stream(context, write, maybe_video_handle, codec)
+                else {
+                    return;
+                };
+
+                if let Err(e) = context
+                    .video
+                    .configure_video_stream_decoder(video_handle, data)
+                {
+                    tracing::error!("Configuring FLV video decoder failed: {}", e);
+                }
             }
             (_, _, FlvVideoPacket::AvcEndOfSequence) => {
-                tracing::warn!("Stub: FLV AVC/H.264 End of Sequence processing")
+                // Every NALU is decoded as soon as it arrives, so there's nothing left to do.
             }
             (_, None, _) => {
                 tracing::error!(
@@ -988,6 +1315,9 @@ impl<'gc> NetStream<'gc> {
         match &mut write.stream_type {
             Some(NetStreamType::Flv {
                 ref mut frame_id, ..
+            })
+            | Some(NetStreamType::Mp4 {
+                ref mut frame_id, ..
             }) => *frame_id += 1,
             _ => unreachable!(),
         };
@@ -1107,7 +1437,7 @@ impl<'gc> NetStream<'gc> {
         let slice = write.buffer.to_full_slice();
         let buffer = slice.data();
 
-        let max_time = write.stream_time + dt;
+        let mut max_time = write.stream_time + dt;
         let mut buffer_underrun = false;
         let mut error = false;
         let mut max_lookahead_audio_tags = 5;
@@ -1136,6 +1466,14 @@ impl<'gc> NetStream<'gc> {
                 }
 
                 let tag = tag.expect("valid tag");
+                if write.sync_time_to_next_tag {
+                    // Data appended after a seek carries its original
+                    // timestamps, which playback resumes from.
+                    write.sync_time_to_next_tag = false;
+                    write.stream_time = tag.timestamp as f64;
+                    max_time = write.stream_time + dt;
+                }
+
                 is_lookahead_tag = tag.timestamp as f64 >= max_time; //FLV timestamps are also ms
                 if is_lookahead_tag && max_lookahead_audio_tags == 0 {
                     break;
@@ -1182,6 +1520,120 @@ impl<'gc> NetStream<'gc> {
                     write.preload_offset = max(write.offset, write.preload_offset);
                 }
             }
+        } else if let Some(NetStreamType::Mp4 {
+            movie,
+            sent_metadata,
+            next_sample,
+            ..
+        }) = &mut write.stream_type
+        {
+            let mut index = *next_sample;
+            if !*sent_metadata {
+                *sent_metadata = true;
+                let movie = movie.clone();
+                let avm_object = write.avm_object;
+                drop(write);
+                // Any errors while trying to lookup or call AVM2 properties are silently swallowed.
+                let _ = self.handle_script_data(
+                    avm_object,
+                    context,
+                    b"onMetaData",
+                    mp4_metadata(&movie),
+                );
+                write = self.0.write(context.gc_context);
+            }
+
+            loop {
+                let Some(NetStreamType::Mp4 {
+                    movie,
+                    samples,
+                    preload_sample,
+                    ..
+                }) = &write.stream_type
+                else {
+                    unreachable!()
+                };
+                let Some(&sample_ref) = samples.get(index) else {
+                    // No samples are left to play.
+                    if !is_lookahead_tag {
+                        buffer_underrun = true;
+                    }
+                    break;
+                };
+                let tag_needs_preloading = index >= *preload_sample;
+                let movie = movie.clone();
+                let track = &movie.tracks[sample_ref.track];
+                let sample = &track.samples[sample_ref.sample];
+
+                is_lookahead_tag = sample_ref.time >= max_time;
+                if is_lookahead_tag && max_lookahead_audio_tags == 0 {
+                    break;
+                }
+
+                let range = usize::try_from(sample.offset)
+                    .ok()
+                    .and_then(|start| Some(start..start.checked_add(sample.size as usize)?));
+                let Some(data) = range.and_then(|range| buffer.get(range)) else {
+                    // The sample hasn't been downloaded yet.
+                    if !is_lookahead_tag {
+                        buffer_underrun = true;
+                    }
+                    break;
+                };
+
+                match track.kind {
+                    Mp4TrackKind::Audio => {
+                        if is_lookahead_tag {
+                            max_lookahead_audio_tags -= 1;
+                        }
+
+                        if let Some(audio_data) = mp4_audio_data(&track.sample_entry, data) {
+                            if let Err(e) = self.flv_audio_tag(&mut write, &slice, audio_data) {
+                                //TODO: Fire an error event at AS.
+                                tracing::error!("Error committing sound stream: {}", e);
+                            }
+                        }
+                    }
+                    Mp4TrackKind::Video if !is_lookahead_tag => {
+                        let video_data = FlvVideoData {
+                            frame_type: if sample.is_sync {
+                                FlvFrameType::Keyframe
+                            } else {
+                                FlvFrameType::Interframe
+                            },
+                            codec_id: FlvCodecId::Avc,
+                            data: FlvVideoPacket::AvcNalu {
+                                composition_time_offset: (f64::from(sample.composition_offset)
+                                    * 1000.0
+                                    / f64::from(track.timescale.max(1)))
+                                    as i32,
+                                data,
+                            },
+                        };
+                        self.flv_video_tag(
+                            context,
+                            &mut write,
+                            &slice,
+                            video_data,
+                            tag_needs_preloading,
+                        );
+                    }
+                    _ => {}
+                }
+
+                index += 1;
+                if !is_lookahead_tag {
+                    if let Some(NetStreamType::Mp4 {
+                        next_sample,
+                        preload_sample,
+                        ..
+                    }) = &mut write.stream_type
+                    {
+                        *next_sample = index;
+                        *preload_sample = max(index, *preload_sample);
+                    }
+                }
+            }
         }
 
         write.stream_time = max_time;
@@ -1327,3 +1779,162 @@ impl<'gc> NetStream<'gc> {
         Ok(())
     }
 }
+
+/// The type of the sample entry of an MP4 track, which identifies its codec.
+fn mp4_sample_type(sample_entry: &Mp4SampleEntry) -> &[u8] {
+    match sample_entry {
+        Mp4SampleEntry::Avc { .. } => b"avc1",
+        Mp4SampleEntry::Aac { .. } => b"mp4a",
+        Mp4SampleEntry::Mp3 { .. } => b".mp3",
+        Mp4SampleEntry::Unknown(sample_type) => sample_type,
+    }
+}
+
+/// Wrap a sample of an MP4 audio track as if it was the data of an FLV audio
+/// tag, so that it can be streamed the same way.
+fn mp4_audio_data<'a>(sample_entry: &Mp4SampleEntry, data: &'a [u8]) -> Option<FlvAudioData<'a>> {
+    let (format, channels, data) = match sample_entry {
+        Mp4SampleEntry::Aac { channels, .. } => (
+            FlvSoundFormat::Aac,
+            *channels,
+            FlvAudioDataType::AacRaw(data),
+        ),
+        Mp4SampleEntry::Mp3 { channels, .. } => {
+            (FlvSoundFormat::MP3, *channels, FlvAudioDataType::Raw(data))
+        }
+        _ => return None,
+    };
+
+    Some(FlvAudioData {
+        format,
+        // The actual sample rate is read from the audio data by the decoder.
+        rate: FlvSoundRate::R44_000,
+        size: FlvSoundSize::Bits16,
+        sound_type: if channels > 1 {
+            FlvSoundType::Stereo
+        } else {
+            FlvSoundType::Mono
+        },
+        data,
+    })
+}
+
+/// Build the `onMetaData` object of an MP4 from its movie box, with the
+/// properties that Flash Player provides for F4V files.
+fn mp4_metadata(movie: &Mp4Movie) -> FlvValue<'_> {
+    fn variable<'a>(name: &'a [u8], data: FlvValue<'a>) -> FlvVariable<'a> {
+        FlvVariable { name, data }
+    }
+
+    let mut variables = vec![
+        variable(b"duration", FlvValue::Number(movie.duration_ms() / 1000.0)),
+        variable(
+            b"moovposition",
+            FlvValue::Number(movie.moov_position as f64),
+        ),
+    ];
+
+    if let Some(index) = movie.first_track(Mp4TrackKind::Video) {
+        let track = &movie.tracks[index];
+        variables.push(variable(b"width", FlvValue::Number(track.width)));
+        variables.push(variable(b"height", FlvValue::Number(track.height)));
+        variables.push(variable(
+            b"videocodecid",
+            FlvValue::String(mp4_sample_type(&track.sample_entry)),
+        ));
+        if track.duration > 0 {
+            let frame_rate = track.samples.len() as f64 * 1000.0 / track.time_to_ms(track.duration);
+            variables.push(variable(b"videoframerate", FlvValue::Number(frame_rate)));
+        }
+        if let Mp4SampleEntry::Avc { config, .. } = &track.sample_entry {
+            if let (Some(profile), Some(level)) = (config.get(1), config.get(3)) {
+                variables.push(variable(b"avcprofile", FlvValue::Number((*profile).into())));
+                variables.push(variable(b"avclevel", FlvValue::Number((*level).into())));
+            }
+        }
+
+        let seek_points = track
+            .samples
+            .iter()
+            .filter(|sample| sample.is_sync)
+            .map(|sample| {
+                FlvValue::Object(vec![
+                    variable(
+                        b"time",
+                        FlvValue::Number(track.time_to_ms(sample.decode_time) / 1000.0),
+                    ),
+                    variable(b"offset", FlvValue::Number(sample.offset as f64)),
+                ])
+            })
+            .collect();
+        variables.push(variable(b"seekpoints", FlvValue::StrictArray(seek_points)));
+    }
+
+    if let Some(index) = movie.first_track(Mp4TrackKind::Audio) {
+        let track = &movie.tracks[index];
+        variables.push(variable(
+            b"audiocodecid",
+            FlvValue::String(mp4_sample_type(&track.sample_entry)),
+        ));
+        match &track.sample_entry {
+            Mp4SampleEntry::Aac {
+                channels,
+                sample_rate,
+                config,
+            } => {
+                variables.push(variable(
+                    b"audiosamplerate",
+                    FlvValue::Number((*sample_rate).into()),
+                ));
+                variables.push(variable(
+                    b"audiochannels",
+                    FlvValue::Number((*channels).into()),
+                ));
+                if let Some(first_byte) = config.first() {
+                    // The audio object type is in the top five bits.
+                    variables.push(variable(
+                        b"aacaot",
+                        FlvValue::Number((first_byte >> 3).into()),
+                    ));
+                }
+            }
+            Mp4SampleEntry::Mp3 {
+                channels,
+                sample_rate,
+            } => {
+                variables.push(variable(
+                    b"audiosamplerate",
+                    FlvValue::Number((*sample_rate).into()),
+                ));
+                variables.push(variable(
+                    b"audiochannels",
+                    FlvValue::Number((*channels).into()),
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    let track_info = movie
+        .tracks
+        .iter()
+        .map(|track| {
+            let sample_description = FlvValue::Object(vec![variable(
+                b"sampletype",
+                FlvValue::String(mp4_sample_type(&track.sample_entry)),
+            )]);
+            FlvValue::Object(vec![
+                variable(b"length", FlvValue::Number(track.duration as f64)),
+                variable(b"timescale", FlvValue::Number(track.timescale.into())),
+                variable(b"language", FlvValue::String(&track.language)),
+                variable(
+                    b"sampledescription",
+                    FlvValue::StrictArray(vec![sample_description]),
+                ),
+            ])
+        })
+        .collect();
+    variables.push(variable(b"trackinfo", FlvValue::StrictArray(track_info)));
+
+    FlvValue::EcmaArray(variables)
+}
@@ -20,4 +20,5 @@ log-filename-pattern-with-timestamp = With Timestamp
 
 storage-backend = Save-File Location
 storage-backend-disk = Disk
-storage-backend-memory = Memory
\ No newline at end of file
+storage-backend-memory = Memory
+storage-backend-single-file = Single File
\ No newline at end of file
@@ -8,9 +8,10 @@ use rfd::{
 };
 use ruffle_core::backend::navigator::OpenURLMode;
 use ruffle_core::backend::ui::{
-    DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter, FontDefinition,
-    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend,
+    CustomMouseCursor, DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter,
+    FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor, UiBackend,
 };
+use std::collections::HashMap;
 use std::rc::Rc;
 use tracing::error;
 use url::Url;
@@ -121,6 +122,12 @@ pub struct DesktopUiBackend {
     clipboard: Clipboard,
     preferences: GlobalPreferences,
     preferred_cursor: MouseCursor,
+    /// Cursors registered by the movie, along with the generation they were
+    /// registered in.
+    custom_cursors: HashMap<String, (u64, CustomMouseCursor)>,
+    /// Incremented whenever a cursor is registered, so that the GUI can tell
+    /// when the cursor it has drawn is out of date without comparing bitmaps.
+    custom_cursor_generation: u64,
     open_url_mode: OpenURLMode,
     font_database: Rc<fontdb::Database>,
     /// Is a dialog currently open
@@ -149,6 +156,8 @@ impl DesktopUiBackend {
             clipboard,
             preferences,
             preferred_cursor: MouseCursor::Arrow,
+            custom_cursors: HashMap::new(),
+            custom_cursor_generation: 0,
             open_url_mode,
             dialog_open: false,
             font_database,
@@ -157,16 +166,33 @@ impl DesktopUiBackend {
 
     pub fn cursor(&self) -> egui::CursorIcon {
         if self.cursor_visible {
-            match self.preferred_cursor {
+            match &self.preferred_cursor {
                 MouseCursor::Arrow => egui::CursorIcon::Default,
                 MouseCursor::Hand => egui::CursorIcon::PointingHand,
                 MouseCursor::IBeam => egui::CursorIcon::Text,
                 MouseCursor::Grab => egui::CursorIcon::Grab,
+                // Custom cursors are drawn by the GUI instead of the system.
+                MouseCursor::Custom(name) if self.custom_cursors.contains_key(name) => {
+                    egui::CursorIcon::None
+                }
+                MouseCursor::Custom(_) => egui::CursorIcon::Default,
             }
         } else {
             egui::CursorIcon::None
         }
     }
+
+    /// The custom cursor that should be drawn in place of the system cursor, if any,
+    /// along with the generation it was registered in.
+    pub fn custom_cursor(&self) -> Option<(u64, &CustomMouseCursor)> {
+        match &self.preferred_cursor {
+            MouseCursor::Custom(name) if self.cursor_visible => self
+                .custom_cursors
+                .get(name)
+                .map(|(generation, cursor)| (*generation, cursor)),
+            _ => None,
+        }
+    }
 }
 
 const DOWNLOAD_FAILED_MESSAGE: &str = "Ruffle failed to open or download this file.";
@@ -184,6 +210,16 @@ impl UiBackend for DesktopUiBackend {
         self.preferred_cursor = cursor;
     }
 
+    fn register_mouse_cursor(&mut self, name: String, cursor: CustomMouseCursor) {
+        self.custom_cursor_generation += 1;
+        self.custom_cursors
+            .insert(name, (self.custom_cursor_generation, cursor));
+    }
+
+    fn unregister_mouse_cursor(&mut self, name: &str) {
+        self.custom_cursors.remove(name);
+    }
+
     fn clipboard_content(&mut self) -> String {
         self.clipboard.get().unwrap_or_default()
     }
@@ -70,17 +70,18 @@ pub struct Opt {
     pub height: Option<f64>,
 
     /// Maximum number of seconds a script can run before scripting is disabled.
-    #[clap(long, short, default_value = "Infinity")]
-    pub max_execution_duration: f64,
+    /// The default is no limit.
+    #[clap(long, short)]
+    pub max_execution_duration: Option<f64>,
 
     /// Base directory or URL used to resolve all relative path statements in the SWF file.
     /// The default is the current directory.
     #[clap(long)]
     pub base: Option<Url>,
 
-    /// Default quality of the movie.
-    #[clap(long, short, default_value = "high")]
-    pub quality: StageQuality,
+    /// Default quality of the movie. The default is "high".
+    #[clap(long, short)]
+    pub quality: Option<StageQuality>,
 
     /// The alignment of the stage.
     #[clap(long, short)]
@@ -90,9 +91,9 @@ pub struct Opt {
     #[clap(long, action)]
     pub force_align: bool,
 
-    /// The scale mode of the stage.
-    #[clap(long, short, default_value = "show-all")]
-    pub scale: StageScaleMode,
+    /// The scale mode of the stage. The default is "show-all".
+    #[clap(long, short)]
+    pub scale: Option<StageScaleMode>,
 
     /// Audio volume as a number between 0 (muted) and 1 (full volume). Default is 1.
     #[clap(long, short)]
@@ -109,10 +110,23 @@ pub struct Opt {
 
     /// Location to store save data for games.
     ///
-    /// This option has no effect if `storage` is not `disk`.
+    /// This option has no effect if `storage` is `memory`.
+    /// With `single-file` storage, save data is kept in a `.zip` file next to this directory.
     #[clap(long, default_value_os_t=get_default_save_directory())]
     pub save_directory: std::path::PathBuf,
 
+    /// Limit how much save data a domain may store, in kilobytes.
+    /// This can be repeated multiple times, for example --storage-quota example.com=100.
+    ///
+    /// Limits are remembered with the save data. Use `unlimited` to lift a limit again.
+    /// Domains without a limit may store any amount of data.
+    #[clap(
+        long,
+        value_parser(parse_storage_quota),
+        value_name = "DOMAIN>=<KILOBYTES"
+    )]
+    pub storage_quota: Vec<(String, Option<usize>)>,
+
     /// Location of a directory to store Ruffle configuration.
     #[clap(long, default_value_os_t=get_default_config_directory())]
     pub config: std::path::PathBuf,
@@ -125,9 +139,9 @@ pub struct Opt {
     #[clap(long = "socket-allow", number_of_values = 1, action = clap::ArgAction::Append)]
     pub socket_allow: Vec<String>,
 
-    /// Define how to deal with TCP Socket connections.
-    #[clap(long = "tcp-connections", default_value = "ask")]
-    pub tcp_connections: SocketMode,
+    /// Define how to deal with TCP Socket connections. The default is "ask".
+    #[clap(long = "tcp-connections")]
+    pub tcp_connections: Option<SocketMode>,
 
     /// Replace all embedded HTTP URLs with HTTPS.
     #[clap(long, action)]
@@ -140,12 +154,12 @@ pub struct Opt {
     #[clap(long, action)]
     pub timedemo: bool,
 
-    #[clap(long, default_value = "streaming")]
-    pub load_behavior: LoadBehavior,
+    #[clap(long)]
+    pub load_behavior: Option<LoadBehavior>,
 
-    /// Specify how Ruffle should handle areas outside the movie stage.
-    #[clap(long, default_value = "on")]
-    pub letterbox: Letterbox,
+    /// Specify how Ruffle should handle areas outside the movie stage. The default is "on".
+    #[clap(long)]
+    pub letterbox: Option<Letterbox>,
 
     /// Spoofs the root SWF URL provided to ActionScript.
     #[clap(long, value_parser)]
@@ -155,9 +169,9 @@ pub struct Opt {
     #[clap(long)]
     pub player_version: Option<u8>,
 
-    /// The runtime to emulate (Flash Player or Adobe AIR)
-    #[clap(long, default_value = "flash-player")]
-    pub player_runtime: PlayerRuntime,
+    /// The runtime to emulate (Flash Player or Adobe AIR). The default is "flash-player".
+    #[clap(long)]
+    pub player_runtime: Option<PlayerRuntime>,
 
     /// Set and lock the player's frame rate, overriding the movie's frame rate.
     #[clap(long)]
@@ -252,6 +266,21 @@ fn parse_gamepad_button(mapping: &str) -> Result<(GamepadButton, KeyCode), Error
     Ok((button, key_code))
 }
 
+fn parse_storage_quota(quota: &str) -> Result<(String, Option<usize>), Error> {
+    let (domain, kilobytes) = quota
+        .split_once('=')
+        .ok_or_else(|| anyhow!("invalid <domain>=<kilobytes>: no `=` found in `{quota}`"))?;
+    let quota = if kilobytes == "unlimited" {
+        None
+    } else {
+        let kilobytes: usize = kilobytes
+            .parse()
+            .map_err(|err| anyhow!("Could not parse <kilobytes>: {err}"))?;
+        Some(kilobytes * 1024)
+    };
+    Ok((domain.to_owned(), quota))
+}
+
 impl Opt {
     #[cfg(feature = "render_trace")]
     pub fn trace_path(&self) -> Option<&Path> {
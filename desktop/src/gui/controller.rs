@@ -7,6 +7,7 @@ use crate::preferences::GlobalPreferences;
 use anyhow::anyhow;
 use egui::{Context, ViewportId};
 use fontdb::{Database, Family, Query, Source};
+use ruffle_core::backend::ui::CustomMouseCursor;
 use ruffle_core::Player;
 use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
 use ruffle_render_wgpu::descriptors::Descriptors;
@@ -39,6 +40,79 @@ pub struct GuiController {
     size: PhysicalSize<u32>,
     /// If this is set, we should not render the main menu.
     no_gui: bool,
+    /// The textures of the custom cursor that was drawn last.
+    custom_cursor: Option<CustomCursorTextures>,
+}
+
+/// A custom cursor uploaded to egui.
+///
+/// winit 0.29 can't use bitmaps as system cursors, so custom cursors are
+/// drawn on top of everything else in place of a hidden system cursor.
+// TODO: Use `winit::window::CustomCursor` instead once we're on winit 0.30,
+// which needs an egui-winit release that supports it.
+struct CustomCursorTextures {
+    /// The generation the cursor was registered in, see `DesktopUiBackend::custom_cursor`.
+    generation: u64,
+    size: egui::Vec2,
+    hotspot: egui::Vec2,
+    frame_rate: f64,
+    frames: Vec<egui::TextureHandle>,
+    created: Instant,
+}
+
+impl CustomCursorTextures {
+    fn new(context: &egui::Context, generation: u64, cursor: &CustomMouseCursor) -> Self {
+        let size = [cursor.width as usize, cursor.height as usize];
+        let frames = cursor
+            .frames
+            .iter()
+            .enumerate()
+            .map(|(i, pixels)| {
+                context.load_texture(
+                    format!("custom_cursor_{i}"),
+                    egui::ColorImage::from_rgba_unmultiplied(size, pixels),
+                    egui::TextureOptions::NEAREST,
+                )
+            })
+            .collect();
+        Self {
+            generation,
+            size: egui::vec2(cursor.width as f32, cursor.height as f32),
+            hotspot: egui::vec2(cursor.hotspot.0 as f32, cursor.hotspot.1 as f32),
+            frame_rate: cursor.frame_rate,
+            frames,
+            created: Instant::now(),
+        }
+    }
+
+    /// Draws the current frame of the cursor at the pointer position.
+    fn draw(&self, context: &egui::Context) {
+        let Some(pointer) = context.pointer_latest_pos() else {
+            return;
+        };
+
+        let mut frame = 0;
+        if self.frames.len() > 1 && self.frame_rate > 0.0 {
+            let elapsed = self.created.elapsed().as_secs_f64();
+            frame = (elapsed * self.frame_rate) as usize % self.frames.len();
+            context.request_repaint_after(Duration::from_secs_f64(1.0 / self.frame_rate));
+        }
+
+        // Cursors are drawn at their pixel size, regardless of the UI scale.
+        let pixels_per_point = context.pixels_per_point();
+        let hotspot = self.hotspot / pixels_per_point;
+        let size = self.size / pixels_per_point;
+        let painter = context.layer_painter(egui::LayerId::new(
+            egui::Order::Tooltip,
+            egui::Id::new("custom_cursor"),
+        ));
+        painter.image(
+            self.frames[frame].id(),
+            egui::Rect::from_min_size(pointer - hotspot, size),
+            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+            egui::Color32::WHITE,
+        );
+    }
 }
 
 impl GuiController {
@@ -134,6 +208,7 @@ impl GuiController {
             movie_view_renderer,
             size,
             no_gui,
+            custom_cursor: None,
         })
     }
 
@@ -242,6 +317,31 @@ impl GuiController {
                     0.0
                 },
             );
+
+            // The system cursor is hidden whenever a custom cursor is active, so
+            // this must be drawn even when the menu isn't (with `--no-gui` or in
+            // fullscreen).
+            let custom_cursor = player.as_deref().and_then(|player| {
+                player
+                    .ui()
+                    .downcast_ref::<DesktopUiBackend>()
+                    .unwrap_or_else(|| panic!("UI Backend should be DesktopUiBackend"))
+                    .custom_cursor()
+            });
+            match custom_cursor {
+                Some((generation, cursor)) if !context.wants_pointer_input() => {
+                    if !matches!(&self.custom_cursor, Some(textures) if textures.generation == generation)
+                    {
+                        self.custom_cursor =
+                            Some(CustomCursorTextures::new(context, generation, cursor));
+                    }
+                    if let Some(textures) = &self.custom_cursor {
+                        textures.draw(context);
+                    }
+                }
+                Some(_) => {}
+                None => self.custom_cursor = None,
+            }
         });
         self.repaint_after = full_output
             .viewport_output
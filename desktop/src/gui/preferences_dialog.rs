@@ -303,6 +303,11 @@ impl PreferencesDialog {
                         StorageBackend::Memory,
                         storage_backend_name(locale, StorageBackend::Memory),
                     );
+                    ui.selectable_value(
+                        &mut self.storage_backend,
+                        StorageBackend::SingleFile,
+                        storage_backend_name(locale, StorageBackend::SingleFile),
+                    );
                 });
 
             if self.storage_backend != previous {
@@ -375,6 +380,7 @@ fn storage_backend_name(locale: &LanguageIdentifier, backend: StorageBackend) ->
     match backend {
         StorageBackend::Disk => text(locale, "storage-backend-disk"),
         StorageBackend::Memory => text(locale, "storage-backend-memory"),
+        StorageBackend::SingleFile => text(locale, "storage-backend-single-file"),
     }
 }
 
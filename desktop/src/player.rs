@@ -2,6 +2,7 @@ use crate::backends::{
     CpalAudioBackend, DesktopExternalInterfaceProvider, DesktopFSCommandProvider, DesktopUiBackend,
     RfdNavigatorInterface,
 };
+use crate::cli::Opt;
 use crate::custom_event::RuffleEvent;
 use crate::gui::MovieView;
 use crate::preferences::GlobalPreferences;
@@ -19,6 +20,7 @@ use ruffle_frontend_utils::backends::navigator::ExternalNavigatorBackend;
 use ruffle_frontend_utils::bundle::source::BundleSourceError;
 use ruffle_frontend_utils::bundle::{Bundle, BundleError};
 use ruffle_frontend_utils::content::PlayingContent;
+use ruffle_frontend_utils::player_options::PlayerOptions as BundlePlayerOptions;
 use ruffle_render::backend::RenderBackend;
 use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::backend::WgpuRenderBackend;
@@ -52,6 +54,7 @@ pub struct PlayerOptions {
     pub fullscreen: bool,
     pub load_behavior: LoadBehavior,
     pub save_directory: PathBuf,
+    pub storage_quotas: Vec<(String, Option<usize>)>,
     pub letterbox: Letterbox,
     pub spoof_url: Option<Url>,
     pub player_version: u8,
@@ -67,33 +70,114 @@ impl From<&GlobalPreferences> for PlayerOptions {
     fn from(value: &GlobalPreferences) -> Self {
         Self {
             parameters: value.cli.parameters().collect(),
-            max_execution_duration: value.cli.max_execution_duration,
+            max_execution_duration: value.cli.max_execution_duration.unwrap_or(f64::INFINITY),
             base: value.cli.base.clone(),
-            quality: value.cli.quality,
+            quality: value.cli.quality.unwrap_or(StageQuality::High),
             align: value.cli.align.unwrap_or_default(),
             force_align: value.cli.force_align,
-            scale: value.cli.scale,
+            scale: value.cli.scale.unwrap_or(StageScaleMode::ShowAll),
             force_scale: value.cli.force_scale,
             proxy: value.cli.proxy.clone(),
             upgrade_to_https: value.cli.upgrade_to_https,
             fullscreen: value.cli.fullscreen,
-            load_behavior: value.cli.load_behavior,
+            load_behavior: value.cli.load_behavior.unwrap_or(LoadBehavior::Streaming),
             save_directory: value.cli.save_directory.clone(),
-            letterbox: value.cli.letterbox,
+            storage_quotas: value.cli.storage_quota.clone(),
+            letterbox: value.cli.letterbox.unwrap_or(Letterbox::On),
             spoof_url: value.cli.spoof_url.clone(),
             player_version: value.cli.player_version.unwrap_or(32),
-            player_runtime: value.cli.player_runtime,
+            player_runtime: value
+                .cli
+                .player_runtime
+                .unwrap_or(PlayerRuntime::FlashPlayer),
             frame_rate: value.cli.frame_rate,
             open_url_mode: value.cli.open_url_mode,
             dummy_external_interface: value.cli.dummy_external_interface,
             socket_allowed: HashSet::from_iter(value.cli.socket_allow.iter().cloned()),
-            tcp_connections: value.cli.tcp_connections,
+            tcp_connections: value.cli.tcp_connections.unwrap_or(SocketMode::Ask),
             gamepad_button_mapping: HashMap::from_iter(value.cli.gamepad_button.iter().cloned()),
             avm2_optimizer_enabled: !value.cli.no_avm2_optimizer,
         }
     }
 }
 
+impl PlayerOptions {
+    /// Fills in any options that weren't given on the command line with those that a bundle
+    /// specifies in its `[player]` table.
+    ///
+    /// A bundle can't loosen the network sandbox on its own: it may ask for TCP connections
+    /// to be denied, but never allowed without asking the user.
+    fn with_bundle_options(mut self, bundle: &BundlePlayerOptions, cli: &Opt) -> Self {
+        fn or_bundle<T: Clone>(cli: &Option<T>, bundle: &Option<T>, current: T) -> T {
+            match (cli, bundle) {
+                (None, Some(value)) => value.clone(),
+                _ => current,
+            }
+        }
+
+        let mut parameters: Vec<_> = bundle
+            .parameters
+            .iter()
+            .filter(|(key, _)| !self.parameters.iter().any(|(cli_key, _)| cli_key == key))
+            .cloned()
+            .collect();
+        parameters.append(&mut self.parameters);
+        self.parameters = parameters;
+
+        self.max_execution_duration = or_bundle(
+            &cli.max_execution_duration,
+            &bundle.max_execution_duration,
+            self.max_execution_duration,
+        );
+        self.quality = or_bundle(&cli.quality, &bundle.quality, self.quality);
+        self.align = or_bundle(&cli.align, &bundle.align, self.align);
+        self.scale = or_bundle(&cli.scale, &bundle.scale, self.scale);
+        self.load_behavior = or_bundle(
+            &cli.load_behavior,
+            &bundle.load_behavior,
+            self.load_behavior,
+        );
+        self.letterbox = or_bundle(&cli.letterbox, &bundle.letterbox, self.letterbox);
+        self.player_version = or_bundle(
+            &cli.player_version,
+            &bundle.player_version,
+            self.player_version,
+        );
+        self.player_runtime = or_bundle(
+            &cli.player_runtime,
+            &bundle.player_runtime,
+            self.player_runtime,
+        );
+        if cli.base.is_none() {
+            self.base = bundle.base.clone().or(self.base);
+        }
+        if cli.spoof_url.is_none() {
+            self.spoof_url = bundle.spoof_url.clone().or(self.spoof_url);
+        }
+        if cli.frame_rate.is_none() {
+            self.frame_rate = bundle.frame_rate.or(self.frame_rate);
+        }
+
+        // Flags can only be turned on from the command line, so a bundle may only turn them on too.
+        self.force_align |= bundle.force_align.unwrap_or_default();
+        self.force_scale |= bundle.force_scale.unwrap_or_default();
+        self.upgrade_to_https |= bundle.upgrade_to_https.unwrap_or_default();
+        self.dummy_external_interface |= bundle.dummy_external_interface.unwrap_or_default();
+
+        if cli.tcp_connections.is_none() {
+            self.tcp_connections = match bundle.tcp_connections {
+                Some(SocketMode::Deny) => SocketMode::Deny,
+                Some(SocketMode::Ask) | Some(SocketMode::Allow) => SocketMode::Ask,
+                None => self.tcp_connections,
+            };
+        }
+        for (button, key) in &bundle.gamepad_button_mapping {
+            self.gamepad_button_mapping.entry(*button).or_insert(*key);
+        }
+        self
+    }
+}
+
 #[derive(Clone)]
 struct WinitWaker(EventLoopProxy<RuffleEvent>);
 
@@ -125,6 +209,7 @@ impl ActivePlayer {
         preferences: GlobalPreferences,
     ) -> Self {
         let mut builder = PlayerBuilder::new();
+        let mut opt = opt.clone();
 
         match CpalAudioBackend::new(&preferences) {
             Ok(audio) => {
@@ -149,7 +234,9 @@ impl ActivePlayer {
                                 tracing::warn!("{warning}");
                             }
                         }
-                        content = PlayingContent::Bundle(movie_url.clone(), bundle);
+                        opt =
+                            opt.with_bundle_options(&bundle.information().player, &preferences.cli);
+                        content = PlayingContent::Bundle(movie_url.clone(), Box::new(bundle));
                     }
                     Err(BundleError::BundleDoesntExist)
                     | Err(BundleError::InvalidSource(BundleSourceError::UnknownSource)) => {
@@ -207,7 +294,7 @@ impl ActivePlayer {
         builder = builder
             .with_navigator(navigator)
             .with_renderer(renderer)
-            .with_storage(preferences.storage_backend().create_backend(opt))
+            .with_storage(preferences.storage_backend().create_backend(&opt))
             .with_fs_commands(Box::new(DesktopFSCommandProvider {
                 event_loop: event_loop.clone(),
                 window: window.clone(),
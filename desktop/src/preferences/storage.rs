@@ -1,6 +1,6 @@
 use crate::player::PlayerOptions;
 use ruffle_core::backend::storage::MemoryStorageBackend;
-use ruffle_frontend_utils::backends::storage::DiskStorageBackend;
+use ruffle_frontend_utils::backends::storage::{DiskStorageBackend, SingleFileStorageBackend};
 use std::str::FromStr;
 
 #[derive(clap::ValueEnum, Copy, Clone, PartialEq, Eq, Debug, Default)]
@@ -8,6 +8,7 @@ pub enum StorageBackend {
     #[default]
     Disk,
     Memory,
+    SingleFile,
 }
 
 impl FromStr for StorageBackend {
@@ -17,6 +18,7 @@ impl FromStr for StorageBackend {
         match s {
             "disk" => Ok(StorageBackend::Disk),
             "memory" => Ok(StorageBackend::Memory),
+            "single-file" => Ok(StorageBackend::SingleFile),
             _ => Err(()),
         }
     }
@@ -27,6 +29,7 @@ impl StorageBackend {
         match self {
             StorageBackend::Disk => "disk",
             StorageBackend::Memory => "memory",
+            StorageBackend::SingleFile => "single-file",
         }
     }
 
@@ -34,9 +37,25 @@ impl StorageBackend {
         &self,
         opt: &PlayerOptions,
     ) -> Box<dyn ruffle_core::backend::storage::StorageBackend> {
-        match self {
+        let mut backend: Box<dyn ruffle_core::backend::storage::StorageBackend> = match self {
             StorageBackend::Disk => Box::new(DiskStorageBackend::new(opt.save_directory.clone())),
             StorageBackend::Memory => Box::new(MemoryStorageBackend::new()),
+            StorageBackend::SingleFile => {
+                let path = opt.save_directory.with_extension("zip");
+                let is_new = !path.exists();
+                let mut backend = SingleFileStorageBackend::new(path);
+                if is_new && opt.save_directory.exists() {
+                    // Bring along anything saved while using the disk backend.
+                    backend.import_from(&DiskStorageBackend::new(opt.save_directory.clone()));
+                }
+                Box::new(backend)
+            }
+        };
+        for (domain, quota) in &opt.storage_quotas {
+            if !backend.set_domain_quota(domain, *quota) {
+                tracing::warn!("Couldn't set the storage quota of {domain}");
+            }
         }
+        backend
     }
 }
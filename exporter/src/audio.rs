@@ -0,0 +1,82 @@
+use anyhow::Result;
+use ruffle_core::backend::audio::{
+    swf, AudioBackend, AudioMixer, DecodeError, RegisterError, SoundHandle, SoundInstanceHandle,
+    SoundStreamInfo, SoundTransform,
+};
+use ruffle_core::impl_audio_mixer_backend;
+use std::fs::File;
+use std::io::{BufWriter, Seek, Write};
+use std::path::Path;
+
+/// An audio backend that records the output of the mixer instead of playing it.
+///
+/// The exporter doesn't run the player in real time, so samples are pulled
+/// from the mixer explicitly once per frame via `capture_frame`.
+pub struct CaptureAudioBackend {
+    mixer: AudioMixer,
+    frame_rate: f64,
+    /// Fractional sample frames carried over from previous frames, so that
+    /// the recording doesn't drift at frame rates that don't evenly divide the sample rate.
+    remainder: f64,
+    samples: Vec<i16>,
+}
+
+impl CaptureAudioBackend {
+    pub const NUM_CHANNELS: u8 = 2;
+    pub const SAMPLE_RATE: u32 = 44100;
+
+    pub fn new(frame_rate: f64) -> Self {
+        Self {
+            mixer: AudioMixer::new(Self::NUM_CHANNELS, Self::SAMPLE_RATE),
+            frame_rate,
+            remainder: 0.0,
+            samples: vec![],
+        }
+    }
+
+    /// Mixes one frame's worth of audio and returns the resulting interleaved samples.
+    pub fn capture_frame(&mut self) -> &[i16] {
+        let exact = f64::from(Self::SAMPLE_RATE) / self.frame_rate + self.remainder;
+        let sample_frames = exact.floor();
+        self.remainder = exact - sample_frames;
+
+        let len = sample_frames as usize * usize::from(Self::NUM_CHANNELS);
+        self.samples.clear();
+        self.samples.resize(len, 0);
+        self.mixer.mix::<i16>(&mut self.samples);
+        &self.samples
+    }
+}
+
+impl AudioBackend for CaptureAudioBackend {
+    impl_audio_mixer_backend!(mixer);
+    fn play(&mut self) {}
+    fn pause(&mut self) {}
+
+    fn set_frame_rate(&mut self, frame_rate: f64) {
+        self.frame_rate = frame_rate;
+    }
+}
+
+/// Writes interleaved 16-bit stereo samples to a WAV file.
+pub fn write_wav(path: &Path, samples: &[i16]) -> Result<()> {
+    write_wav_to(BufWriter::new(File::create(path)?), samples)
+}
+
+/// Writes interleaved 16-bit stereo samples as WAV data to `writer`.
+pub fn write_wav_to(writer: impl Write + Seek, samples: &[i16]) -> Result<()> {
+    let spec = hound::WavSpec {
+        channels: CaptureAudioBackend::NUM_CHANNELS.into(),
+        sample_rate: CaptureAudioBackend::SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::new(writer, spec)?;
+    let mut sample_writer = writer.get_i16_writer(samples.len() as u32);
+    for &sample in samples {
+        sample_writer.write_sample(sample);
+    }
+    sample_writer.flush()?;
+    writer.finalize()?;
+    Ok(())
+}
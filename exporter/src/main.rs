@@ -1,6 +1,10 @@
+mod audio;
+mod output;
+
+use crate::audio::{write_wav, write_wav_to, CaptureAudioBackend};
+use crate::output::{write_animation, Capture, OutputFormat};
 use anyhow::{anyhow, Result};
-use clap::Parser;
-use image::RgbaImage;
+use clap::{Args, Parser, Subcommand};
 use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
 use ruffle_core::limits::ExecutionLimit;
@@ -34,7 +38,51 @@ struct SizeOpt {
 }
 
 #[derive(Parser, Debug)]
-#[clap(name = "Ruffle Exporter", author, version)]
+#[clap(
+    name = "Ruffle Exporter",
+    author,
+    version,
+    args_conflicts_with_subcommands = true
+)]
+struct Cli {
+    #[clap(subcommand)]
+    command: Option<Command>,
+
+    #[clap(flatten)]
+    capture: Option<Opt>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Render only the audio of a movie to a WAV file. This needs neither a GPU nor an audio device.
+    Audio(AudioOpt),
+}
+
+#[derive(Args, Debug)]
+struct AudioOpt {
+    /// The file to record the audio of
+    #[clap(name = "swf")]
+    swf: PathBuf,
+
+    /// The WAV file to store the audio in.
+    /// Defaults to the name of the swf + ".wav", or stdout if given "-".
+    #[clap(name = "output")]
+    output_path: Option<PathBuf>,
+
+    /// Number of frames to record. Defaults to the number of frames in the movie's header.
+    #[clap(short = 'f', long = "frames")]
+    frames: Option<u32>,
+
+    /// Number of frames to skip
+    #[clap(long = "skipframes", default_value = "0")]
+    skipframes: u32,
+
+    /// Don't show a progress bar
+    #[clap(short, long, action)]
+    silent: bool,
+}
+
+#[derive(Args, Debug)]
 struct Opt {
     /// The file or directory of files to export frames from
     #[clap(name = "swf")]
@@ -44,6 +92,7 @@ struct Opt {
     /// The default value will either be:
     /// - If given one swf and one frame, the name of the swf + ".png"
     /// - If given one swf and multiple frames, the name of the swf as a directory
+    /// - If given one swf and an animated format, the name of the swf + the format's extension
     /// - If given multiple swfs, this field is required.
     #[clap(name = "output")]
     output_path: Option<PathBuf>,
@@ -81,21 +130,31 @@ struct Opt {
     /// Skip unsupported movie types (currently AVM 2)
     #[clap(long, action)]
     skip_unsupported: bool,
+
+    /// The format to store captured frames in.
+    /// Animated formats store every frame of a movie in a single file.
+    #[clap(long, default_value = "png")]
+    format: OutputFormat,
+
+    /// Also capture the audio of each movie, in sync with the captured frames.
+    /// This is stored in the video itself for WebM, and as a WAV file next to the output otherwise.
+    #[clap(long, action)]
+    audio: bool,
 }
 
-/// Captures a screenshot. The resulting image uses straight alpha
+/// Captures a screenshot of `swf_path`, using the capture settings of `opt`.
+/// The resulting image uses straight alpha
 fn take_screenshot(
     descriptors: Arc<Descriptors>,
     swf_path: &Path,
-    frames: u32,
-    skipframes: u32,
+    opt: &Opt,
     progress: &Option<ProgressBar>,
-    size: SizeOpt,
-    skip_unsupported: bool,
-) -> Result<Vec<RgbaImage>> {
+) -> Result<Capture> {
+    let size = opt.size;
+    let capture_audio = opt.audio;
     let movie = SwfMovie::from_path(swf_path, None).map_err(|e| anyhow!(e.to_string()))?;
 
-    if movie.is_action_script_3() && skip_unsupported {
+    if movie.is_action_script_3() && opt.skip_unsupported {
         return Err(anyhow!("Skipping unsupported movie"));
     }
 
@@ -111,18 +170,25 @@ fn take_screenshot(
         .unwrap_or_else(|| movie.height().to_pixels());
     let height = (height * size.scale).round() as u32;
 
+    let frame_rate = movie.frame_rate().to_f64();
+
     let target = TextureTarget::new(&descriptors.device, (width, height))
         .map_err(|e| anyhow!(e.to_string()))?;
-    let player = PlayerBuilder::new()
+    let mut builder = PlayerBuilder::new()
         .with_renderer(
             WgpuRenderBackend::new(descriptors, target).map_err(|e| anyhow!(e.to_string()))?,
         )
         .with_movie(movie)
-        .with_viewport_dimensions(width, height, size.scale)
-        .build();
+        .with_viewport_dimensions(width, height, size.scale);
+    if capture_audio {
+        builder = builder.with_audio(CaptureAudioBackend::new(frame_rate));
+    }
+    let player = builder.build();
 
     let mut result = Vec::new();
-    let totalframes = frames + skipframes;
+    let mut audio = Vec::new();
+    let skipframes = opt.skipframes;
+    let totalframes = opt.frames + skipframes;
 
     for i in 0..totalframes {
         if let Some(progress) = &progress {
@@ -136,6 +202,17 @@ fn take_screenshot(
         player.lock().unwrap().preload(&mut ExecutionLimit::none());
 
         player.lock().unwrap().run_frame();
+        if capture_audio {
+            let mut player = player.lock().unwrap();
+            let backend = player
+                .audio_mut()
+                .downcast_mut::<CaptureAudioBackend>()
+                .unwrap();
+            let samples = backend.capture_frame();
+            if i >= skipframes {
+                audio.extend_from_slice(samples);
+            }
+        }
         if i >= skipframes {
             let image = || {
                 player.lock().unwrap().render();
@@ -164,7 +241,102 @@ fn take_screenshot(
             progress.inc(1);
         }
     }
-    Ok(result)
+
+    let frame_rate = player.lock().unwrap().frame_rate();
+    Ok(Capture {
+        frames: result,
+        frame_rate,
+        audio: capture_audio.then_some(audio),
+    })
+}
+
+/// Runs a movie without rendering it, returning the interleaved 16-bit stereo audio of each frame.
+fn record_audio(
+    swf_path: &Path,
+    frames: Option<u32>,
+    skipframes: u32,
+    progress: &Option<ProgressBar>,
+) -> Result<Vec<i16>> {
+    let movie = SwfMovie::from_path(swf_path, None).map_err(|e| anyhow!(e.to_string()))?;
+    let frames = frames.unwrap_or_else(|| movie.num_frames().into());
+    if let Some(progress) = &progress {
+        progress.set_length(frames.into());
+    }
+
+    let player = PlayerBuilder::new()
+        .with_audio(CaptureAudioBackend::new(movie.frame_rate().to_f64()))
+        .with_movie(movie)
+        .build();
+
+    let mut audio = Vec::new();
+    for i in 0..frames + skipframes {
+        let mut player = player.lock().unwrap();
+        player.preload(&mut ExecutionLimit::none());
+        player.run_frame();
+        let backend = player
+            .audio_mut()
+            .downcast_mut::<CaptureAudioBackend>()
+            .unwrap();
+        let samples = backend.capture_frame();
+        if i >= skipframes {
+            audio.extend_from_slice(samples);
+            if let Some(progress) = &progress {
+                progress.inc(1);
+            }
+        }
+    }
+    Ok(audio)
+}
+
+fn capture_audio(opt: &AudioOpt) -> Result<()> {
+    let output = opt.output_path.clone().unwrap_or_else(|| {
+        let mut result = PathBuf::new();
+        result.set_file_name(opt.swf.file_stem().unwrap());
+        result.set_extension("wav");
+        result
+    });
+
+    let progress = if !opt.silent {
+        let progress = ProgressBar::new(0);
+        progress.set_style(
+            ProgressStyle::with_template(
+                "[{elapsed_precise}] {bar:40.cyan/blue} [{eta_precise}] {pos:>7}/{len:7} {msg}",
+            )
+            .unwrap()
+            .progress_chars("##-"),
+        );
+        progress.set_message(opt.swf.file_stem().unwrap().to_string_lossy().into_owned());
+        Some(progress)
+    } else {
+        None
+    };
+
+    let audio = record_audio(&opt.swf, opt.frames, opt.skipframes, &progress)?;
+    if output == Path::new("-") {
+        let mut bytes = Vec::new();
+        write_wav_to(io::Cursor::new(&mut bytes), &audio)?;
+        io::stdout()
+            .write_all(bytes.as_slice())
+            .expect("Writing to stdout failed");
+    } else {
+        write_wav(&output, &audio)?;
+    }
+
+    let seconds = audio.len() as f64
+        / f64::from(CaptureAudioBackend::SAMPLE_RATE)
+        / f64::from(CaptureAudioBackend::NUM_CHANNELS);
+    let message = format!(
+        "Saved {seconds:.2}s of audio from {} to {}",
+        opt.swf.to_string_lossy(),
+        output.to_string_lossy()
+    );
+    if let Some(progress) = progress {
+        progress.finish_with_message(message);
+    } else if output != Path::new("-") {
+        println!("{message}");
+    }
+
+    Ok(())
 }
 
 fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
@@ -201,13 +373,13 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
         result.set_file_name(opt.swf.file_stem().unwrap());
-        if opt.frames == 1 {
-            result.set_extension("png");
+        if opt.frames == 1 || opt.format.is_animated() {
+            result.set_extension(opt.format.extension());
         }
         result
     });
 
-    if opt.frames > 1 {
+    if opt.frames > 1 && !opt.format.is_animated() {
         let _ = create_dir_all(&output);
     }
 
@@ -225,23 +397,21 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
         None
     };
 
-    let frames = take_screenshot(
-        descriptors,
-        &opt.swf,
-        opt.frames,
-        opt.skipframes,
-        &progress,
-        opt.size,
-        opt.skip_unsupported,
-    )?;
+    let capture = take_screenshot(descriptors, &opt.swf, opt, &progress)?;
+    let frames = &capture.frames;
 
     if let Some(progress) = &progress {
         progress.set_message(opt.swf.file_stem().unwrap().to_string_lossy().into_owned());
     }
 
-    if frames.len() == 1 {
+    if opt.format.is_animated() {
+        write_animation(&capture, opt.format, &output)?;
+    } else if frames.len() == 1 {
         let image = frames.first().unwrap();
         if opt.output_path == Some(PathBuf::from("-")) {
+            if opt.audio {
+                return Err(anyhow!("Audio can't be written alongside stdout output"));
+            }
             let mut bytes: Vec<u8> = Vec::new();
             image
                 .write_to(&mut io::Cursor::new(&mut bytes), image::ImageFormat::Png)
@@ -251,6 +421,9 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
                 .expect("Writing to stdout failed");
         } else {
             image.save(&output)?;
+            if let Some(audio) = &capture.audio {
+                write_wav(&output.with_extension("wav"), audio)?;
+            }
         }
     } else {
         for (frame, image) in frames.iter().enumerate() {
@@ -258,6 +431,9 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
             path.push(format!("{frame}.png"));
             image.save(&path)?;
         }
+        if let Some(audio) = &capture.audio {
+            write_wav(&output.join("audio.wav"), audio)?;
+        }
     }
 
     let message = if frames.len() == 1 {
@@ -319,22 +495,23 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
                     .into_owned(),
             );
         }
-        if let Ok(frames) = take_screenshot(
-            descriptors.clone(),
-            file.path(),
-            opt.frames,
-            opt.skipframes,
-            &progress,
-            opt.size,
-            opt.skip_unsupported,
-        ) {
+        if let Ok(capture) = take_screenshot(descriptors.clone(), file.path(), opt, &progress) {
+            let frames = &capture.frames;
             let mut relative_path = file
                 .path()
                 .strip_prefix(&opt.swf)
                 .unwrap_or_else(|_| file.path())
                 .to_path_buf();
 
-            if frames.len() == 1 {
+            if opt.format.is_animated() {
+                let mut destination: PathBuf = (&output).into();
+                relative_path.set_extension(opt.format.extension());
+                destination.push(relative_path);
+                if let Some(parent) = destination.parent() {
+                    let _ = create_dir_all(parent);
+                }
+                write_animation(&capture, opt.format, &destination)?;
+            } else if frames.len() == 1 {
                 let mut destination: PathBuf = (&output).into();
                 relative_path.set_extension("png");
                 destination.push(relative_path);
@@ -342,6 +519,9 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
                     let _ = create_dir_all(parent);
                 }
                 frames.first().unwrap().save(&destination)?;
+                if let Some(audio) = &capture.audio {
+                    write_wav(&destination.with_extension("wav"), audio)?;
+                }
             } else {
                 let mut parent: PathBuf = (&output).into();
                 relative_path.set_extension("");
@@ -352,6 +532,9 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
                     destination.push(format!("{frame}.png"));
                     image.save(&destination)?;
                 }
+                if let Some(audio) = &capture.audio {
+                    write_wav(&parent.join("audio.wav"), audio)?;
+                }
             }
         }
 
@@ -398,7 +581,13 @@ fn trace_path(_opt: &Opt) -> Option<&Path> {
 }
 
 fn main() -> Result<()> {
-    let opt: Opt = Opt::parse();
+    let cli = Cli::parse();
+    let opt = match cli.command {
+        Some(Command::Audio(opt)) => return capture_audio(&opt),
+        None => cli
+            .capture
+            .expect("Capture options are required without a subcommand"),
+    };
     let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
         backends: opt.graphics.into(),
         ..Default::default()
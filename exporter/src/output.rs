@@ -0,0 +1,222 @@
+use crate::audio::write_wav;
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use image::RgbaImage;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+#[derive(ValueEnum, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// One PNG image per frame
+    Png,
+    /// A single animated PNG
+    Apng,
+    /// A single animated GIF
+    Gif,
+    /// A VP9/Opus WebM video. Requires `ffmpeg` to be available on the PATH.
+    Webm,
+    /// Raw YUV4MPEG2 video, suitable for piping into other encoders
+    Y4m,
+}
+
+impl OutputFormat {
+    /// Whether this format stores every captured frame in a single file.
+    pub fn is_animated(self) -> bool {
+        self != OutputFormat::Png
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Png | OutputFormat::Apng => "png",
+            OutputFormat::Gif => "gif",
+            OutputFormat::Webm => "webm",
+            OutputFormat::Y4m => "y4m",
+        }
+    }
+}
+
+/// The result of capturing a movie.
+pub struct Capture {
+    pub frames: Vec<RgbaImage>,
+    /// The frame rate the movie was captured at, in FPS.
+    pub frame_rate: f64,
+    /// Interleaved 16-bit stereo samples for the captured frames, if audio was requested.
+    pub audio: Option<Vec<i16>>,
+}
+
+/// Encodes all frames of `capture` into a single animated file at `path`.
+/// A path of `-` writes to stdout instead.
+///
+/// If the format can't hold an audio track, the audio is written to a WAV file next to it.
+pub fn write_animation(capture: &Capture, format: OutputFormat, path: &Path) -> Result<()> {
+    let to_stdout = path == Path::new("-");
+    if to_stdout && capture.audio.is_some() {
+        return Err(anyhow!("Audio can't be written alongside stdout output"));
+    }
+    if format == OutputFormat::Webm {
+        if to_stdout {
+            return Err(anyhow!("WebM output can't be written to stdout"));
+        }
+        return write_webm(capture, path);
+    }
+
+    let writer: Box<dyn Write> = if to_stdout {
+        Box::new(io::stdout().lock())
+    } else {
+        Box::new(File::create(path)?)
+    };
+    let writer = BufWriter::new(writer);
+    match format {
+        OutputFormat::Apng => write_apng(capture, writer)?,
+        OutputFormat::Gif => write_gif(capture, writer)?,
+        OutputFormat::Y4m => write_y4m(capture, writer)?,
+        OutputFormat::Png | OutputFormat::Webm => unreachable!(),
+    }
+
+    if let Some(audio) = &capture.audio {
+        write_wav(&path.with_extension("wav"), audio)?;
+    }
+    Ok(())
+}
+
+fn dimensions(capture: &Capture) -> Result<(u32, u32)> {
+    capture
+        .frames
+        .first()
+        .map(|frame| frame.dimensions())
+        .ok_or_else(|| anyhow!("No frames were captured"))
+}
+
+fn write_apng(capture: &Capture, writer: impl Write) -> Result<()> {
+    let (width, height) = dimensions(capture)?;
+    let mut encoder = png::Encoder::new(writer, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_animated(capture.frames.len() as u32, 0)?;
+    // The frame delay is `1 / frame_rate` seconds, stored as a fraction of two u16s.
+    encoder.set_frame_delay(100, (capture.frame_rate * 100.0).round().max(1.0) as u16)?;
+    let mut writer = encoder.write_header()?;
+    for frame in &capture.frames {
+        writer.write_image_data(frame.as_raw())?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+fn write_gif(capture: &Capture, writer: impl Write) -> Result<()> {
+    let (width, height) = dimensions(capture)?;
+    let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(anyhow!("{width}x{height} is too large for a GIF"));
+    };
+    let mut encoder = gif::Encoder::new(writer, width, height, &[])?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+
+    // GIF delays are in whole centiseconds, so carry the rounding error
+    // between frames to keep the overall timing correct.
+    let frame_time = 100.0 / capture.frame_rate;
+    let mut elapsed = 0.0;
+    let mut delayed = 0;
+    for frame in &capture.frames {
+        elapsed += frame_time;
+        let delay = (elapsed.round() as u64 - delayed) as u16;
+        delayed += u64::from(delay);
+
+        let mut pixels = frame.as_raw().clone();
+        let mut frame = gif::Frame::from_rgba_speed(width, height, &mut pixels, 10);
+        frame.delay = delay;
+        encoder.write_frame(&frame)?;
+    }
+    Ok(())
+}
+
+/// Writes a YUV4MPEG2 stream using BT.601 limited range 4:4:4 planes.
+/// Transparent pixels are composited onto black.
+fn write_y4m(capture: &Capture, mut writer: impl Write) -> Result<()> {
+    let (width, height) = dimensions(capture)?;
+    let (rate_num, rate_den) = frame_rate_ratio(capture.frame_rate);
+    writeln!(
+        writer,
+        "YUV4MPEG2 W{width} H{height} F{rate_num}:{rate_den} Ip A1:1 C444"
+    )?;
+
+    let plane_len = width as usize * height as usize;
+    let mut planes = vec![0u8; plane_len * 3];
+    for frame in &capture.frames {
+        let (y_plane, rest) = planes.split_at_mut(plane_len);
+        let (u_plane, v_plane) = rest.split_at_mut(plane_len);
+        for (i, pixel) in frame.pixels().enumerate() {
+            let [r, g, b, a] = pixel.0.map(i32::from);
+            let (r, g, b) = (r * a / 255, g * a / 255, b * a / 255);
+            y_plane[i] = (((66 * r + 129 * g + 25 * b + 128) >> 8) + 16) as u8;
+            u_plane[i] = (((-38 * r - 74 * g + 112 * b + 128) >> 8) + 128) as u8;
+            v_plane[i] = (((112 * r - 94 * g - 18 * b + 128) >> 8) + 128) as u8;
+        }
+        writer.write_all(b"FRAME\n")?;
+        writer.write_all(&planes)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn frame_rate_ratio(frame_rate: f64) -> (u64, u64) {
+    fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 {
+            a
+        } else {
+            gcd(b, a % b)
+        }
+    }
+    let num = (frame_rate * 1000.0).round().max(1.0) as u64;
+    let divisor = gcd(num, 1000);
+    (num / divisor, 1000 / divisor)
+}
+
+/// Encodes a WebM by piping a y4m stream (and a temporary WAV file) into `ffmpeg`.
+fn write_webm(capture: &Capture, path: &Path) -> Result<()> {
+    let audio_path = capture
+        .audio
+        .as_ref()
+        .map(|audio| -> Result<PathBuf> {
+            let audio_path = path.with_extension("tmp.wav");
+            write_wav(&audio_path, audio)?;
+            Ok(audio_path)
+        })
+        .transpose()?;
+
+    let mut command = Command::new("ffmpeg");
+    command.args(["-y", "-loglevel", "error", "-f", "yuv4mpegpipe", "-i", "-"]);
+    if let Some(audio_path) = &audio_path {
+        command.arg("-i").arg(audio_path);
+        command.args(["-c:a", "libopus"]);
+    }
+    command.args(["-c:v", "libvpx-vp9", "-pix_fmt", "yuv420p"]);
+    command.args(["-b:v", "0", "-crf", "30"]);
+    command.arg(path);
+
+    let result = (|| -> Result<()> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|e| anyhow!("Couldn't run ffmpeg, is it installed? {e}"))?;
+        let stdin = child.stdin.take().expect("stdin was piped");
+        // Stdin is closed once this returns, even on error, so ffmpeg always gets to exit.
+        // A failed write usually means ffmpeg quit early, and its exit status says why.
+        let written = write_y4m(capture, BufWriter::new(stdin));
+        let status = child.wait()?;
+        match written {
+            Err(e) if !status.success() => Err(anyhow!(
+                "ffmpeg exited with {status} while frames were written to it: {e}"
+            )),
+            Err(e) => Err(e.context("Couldn't write frames to ffmpeg")),
+            Ok(()) if !status.success() => Err(anyhow!("ffmpeg exited with {status}")),
+            Ok(()) => Ok(()),
+        }
+    })();
+
+    if let Some(audio_path) = audio_path {
+        let _ = std::fs::remove_file(audio_path);
+    }
+    result
+}
@@ -51,6 +51,9 @@ pub enum Error {
     #[error("the FLV contains a tag with unknown type {0}")]
     UnknownTagType(u8),
 
+    #[error("the FLV cannot be written as a value is too large for its field")]
+    ValueTooLarge,
+
     #[error("IO error ({0}, {1})")]
     IoError(IoErrorKind, String),
 }
@@ -80,6 +83,7 @@ impl PartialEq for Error {
             (Self::UnknownVideoCommandType(s), Self::UnknownVideoCommandType(o)) => s == o,
             (Self::UnknownAvcPacketType(s), Self::UnknownAvcPacketType(o)) => s == o,
             (Self::UnknownTagType(s), Self::UnknownTagType(o)) => s == o,
+            (Self::ValueTooLarge, Self::ValueTooLarge) => true,
             (Self::IoError(sk, ss), Self::IoError(ok, os)) => sk == ok && ss == os,
             _ => false,
         }
@@ -1,7 +1,8 @@
 use crate::error::Error;
 use crate::reader::FlvReader;
+use crate::writer::FlvWriter;
 use bitflags::bitflags;
-use std::io::{Seek, SeekFrom};
+use std::io::{Seek, SeekFrom, Write};
 
 bitflags! {
     #[derive(PartialEq, Eq, Debug, Clone, Copy)]
@@ -56,12 +57,27 @@ impl Header {
             }
         }
     }
+
+    /// Write an FLV header.
+    ///
+    /// If the data offset leaves room after the header, it is padded with
+    /// zeroes so that the first tag starts at the data offset.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        writer.write_u24(0x464C56)?;
+        writer.write_u8(self.version)?;
+        writer.write_u8(self.type_flags.bits())?;
+        writer.write_u32(self.data_offset)?;
+
+        let padding = self.data_offset.saturating_sub(9);
+        writer.write(&vec![0; padding as usize])
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::header::{Header, TypeFlags};
     use crate::reader::FlvReader;
+    use crate::writer::FlvWriter;
 
     #[test]
     fn read_header() {
@@ -77,4 +93,49 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn write_header() {
+        let header = Header {
+            version: 1,
+            type_flags: TypeFlags::HAS_AUDIO | TypeFlags::HAS_VIDEO,
+            data_offset: 9,
+        };
+        let mut writer = FlvWriter::new(vec![]);
+        header.write(&mut writer).unwrap();
+
+        let data = writer.finish().unwrap();
+        assert_eq!(
+            data,
+            [0x46, 0x4C, 0x56, 0x01, 0x05, 0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00, 0x00]
+        );
+        assert_eq!(
+            Header::parse(&mut FlvReader::from_source(&data)),
+            Ok(header)
+        );
+    }
+
+    #[test]
+    fn write_header_padded() {
+        let header = Header {
+            version: 1,
+            type_flags: TypeFlags::HAS_VIDEO,
+            data_offset: 11,
+        };
+        let mut writer = FlvWriter::new(vec![]);
+        header.write(&mut writer).unwrap();
+
+        let data = writer.finish().unwrap();
+        assert_eq!(
+            data,
+            [
+                0x46, 0x4C, 0x56, 0x01, 0x04, 0x00, 0x00, 0x00, 0x0B, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00
+            ]
+        );
+        assert_eq!(
+            Header::parse(&mut FlvReader::from_source(&data)),
+            Ok(header)
+        );
+    }
 }
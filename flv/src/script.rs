@@ -1,6 +1,7 @@
 use crate::error::Error;
 use crate::reader::FlvReader;
-use std::io::Seek;
+use crate::writer::FlvWriter;
+use std::io::{Seek, Write};
 
 fn parse_string<'a>(reader: &mut FlvReader<'a>, is_long_string: bool) -> Result<&'a [u8], Error> {
     let length = if is_long_string {
@@ -12,6 +13,33 @@ fn parse_string<'a>(reader: &mut FlvReader<'a>, is_long_string: bool) -> Result<
     reader.read(length as usize)
 }
 
+fn write_string<W: Write>(
+    writer: &mut FlvWriter<W>,
+    string: &[u8],
+    is_long_string: bool,
+) -> Result<(), Error> {
+    if is_long_string {
+        writer.write_u32(string.len().try_into().map_err(|_| Error::ValueTooLarge)?)?;
+    } else {
+        writer.write_u16(string.len().try_into().map_err(|_| Error::ValueTooLarge)?)?;
+    }
+
+    writer.write(string)
+}
+
+/// Write the variables of an object or ECMA array, followed by the object
+/// terminator.
+fn write_variables<W: Write>(
+    writer: &mut FlvWriter<W>,
+    variables: &[Variable<'_>],
+) -> Result<(), Error> {
+    for variable in variables {
+        variable.write(writer)?;
+    }
+
+    writer.write_u24(9)
+}
+
 #[repr(u8)]
 #[derive(PartialEq, Debug, Clone)]
 pub enum Value<'a> {
@@ -98,6 +126,71 @@ impl<'a> Value<'a> {
             _ => Err(Error::UnknownValueType),
         }
     }
+
+    /// Write a script value.
+    ///
+    /// ECMA arrays are written with their actual length as the length hint.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        match self {
+            Self::Number(value) => {
+                writer.write_u8(0)?;
+                writer.write_f64(*value)
+            }
+            Self::Boolean(value) => {
+                writer.write_u8(1)?;
+                writer.write_u8(*value as u8)
+            }
+            Self::String(string) => {
+                writer.write_u8(2)?;
+                write_string(writer, string, false)
+            }
+            Self::Object(variables) => {
+                writer.write_u8(3)?;
+                write_variables(writer, variables)
+            }
+            Self::MovieClip(path) => {
+                writer.write_u8(4)?;
+                write_string(writer, path, false)
+            }
+            Self::Null => writer.write_u8(5),
+            Self::Undefined => writer.write_u8(6),
+            Self::Reference(index) => {
+                writer.write_u8(7)?;
+                writer.write_u16(*index)
+            }
+            Self::EcmaArray(variables) => {
+                writer.write_u8(8)?;
+                writer.write_u32(
+                    variables
+                        .len()
+                        .try_into()
+                        .map_err(|_| Error::ValueTooLarge)?,
+                )?;
+                write_variables(writer, variables)
+            }
+            Self::StrictArray(values) => {
+                writer.write_u8(10)?;
+                writer.write_u32(values.len().try_into().map_err(|_| Error::ValueTooLarge)?)?;
+                for value in values {
+                    value.write(writer)?;
+                }
+
+                Ok(())
+            }
+            Self::Date {
+                unix_time,
+                local_offset,
+            } => {
+                writer.write_u8(11)?;
+                writer.write_f64(*unix_time)?;
+                writer.write_i16(*local_offset)
+            }
+            Self::LongString(string) => {
+                writer.write_u8(12)?;
+                write_string(writer, string, true)
+            }
+        }
+    }
 }
 
 /// An individual object in a ScriptData tag.
@@ -118,6 +211,11 @@ impl<'a> Variable<'a> {
             data: Value::parse(reader)?,
         })
     }
+
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        write_string(writer, self.name, false)?;
+        self.data.write(writer)
+    }
 }
 
 #[derive(PartialEq, Debug, Clone)]
@@ -149,12 +247,28 @@ impl<'a> ScriptData<'a> {
             vars.push(Variable::parse(reader)?);
         }
     }
+
+    /// Write a script data structure.
+    ///
+    /// The variables are preceded by the string value type marker that `parse`
+    /// skips over. No terminator is written, as it is commonly elided from
+    /// script data blocks.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        writer.write_u8(2)?;
+        for variable in &self.0 {
+            variable.write(writer)?;
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::error::Error;
     use crate::reader::FlvReader;
-    use crate::script::{parse_string, ScriptData, Value, Variable};
+    use crate::script::{parse_string, write_string, ScriptData, Value, Variable};
+    use crate::writer::FlvWriter;
 
     #[test]
     fn read_string() {
@@ -386,4 +500,98 @@ mod tests {
             ]))
         );
     }
+
+    #[test]
+    fn write_string_short() {
+        let mut writer = FlvWriter::new(vec![]);
+        write_string(&mut writer, &[0x01, 0x02, 0x03], false).unwrap();
+
+        assert_eq!(
+            writer.finish(),
+            Ok(vec![0x00, 0x03, 0x01, 0x02, 0x03, 0x00, 0x00, 0x00, 0x00])
+        );
+    }
+
+    #[test]
+    fn write_string_too_long() {
+        let mut writer = FlvWriter::new(vec![]);
+
+        assert_eq!(
+            write_string(&mut writer, &[0; 0x10000], false),
+            Err(Error::ValueTooLarge)
+        );
+    }
+
+    #[test]
+    fn write_values() {
+        let values: &[&[u8]] = &[
+            &[0x00, 0x40, 0x28, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a],
+            &[0x01, 0x01],
+            &[0x02, 0x00, 0x03, 0x01, 0x02, 0x03],
+            &[0x04, 0x00, 0x03, 0x01, 0x02, 0x03],
+            &[0x0C, 0x00, 0x00, 0x00, 0x03, 0x01, 0x02, 0x03],
+            &[0x05],
+            &[0x06],
+            &[0x07, 0x24, 0x38],
+            &[
+                0x0B, 0x40, 0x28, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a, 0xFF, 0xFE,
+            ],
+            &[
+                0x03, 0x00, 0x03, 0x01, 0x02, 0x03, 0x06, 0x00, 0x03, 0x01, 0x02, 0x03, 0x05, 0x00,
+                0x00, 0x09,
+            ],
+            &[
+                0x08, 0x00, 0x00, 0x00, 0x02, 0x00, 0x03, 0x01, 0x02, 0x03, 0x06, 0x00, 0x03, 0x01,
+                0x02, 0x03, 0x05, 0x00, 0x00, 0x09,
+            ],
+            &[0x0A, 0x00, 0x00, 0x00, 0x02, 0x06, 0x05],
+        ];
+
+        for data in values {
+            let value = Value::parse(&mut FlvReader::from_source(data)).unwrap();
+            let mut buf = vec![];
+            value.write(&mut FlvWriter::new(&mut buf)).unwrap();
+
+            assert_eq!(&buf, data, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn write_value_ecmaarray_length_hint() {
+        let data = [
+            0x08, 0x00, 0x00, 0x0F, 0x02, 0x00, 0x03, 0x01, 0x02, 0x03, 0x06, 0x00, 0x03, 0x01,
+            0x02, 0x03, 0x05, 0x00, 0x00, 0x09,
+        ];
+        let value = Value::parse(&mut FlvReader::from_source(&data)).unwrap();
+        let mut buf = vec![];
+        value.write(&mut FlvWriter::new(&mut buf)).unwrap();
+
+        assert_eq!(buf[1..5], [0x00, 0x00, 0x00, 0x02]);
+        assert_eq!(Value::parse(&mut FlvReader::from_source(&buf)), Ok(value));
+    }
+
+    #[test]
+    fn write_scriptdata() {
+        let script_data = ScriptData(vec![
+            Variable {
+                name: &[0x01, 0x02, 0x03],
+                data: Value::Undefined,
+            },
+            Variable {
+                name: &[0x01, 0x02, 0x03],
+                data: Value::Null,
+            },
+        ]);
+        let mut buf = vec![];
+        script_data.write(&mut FlvWriter::new(&mut buf)).unwrap();
+
+        assert_eq!(
+            buf,
+            [0x02, 0x00, 0x03, 0x01, 0x02, 0x03, 0x06, 0x00, 0x03, 0x01, 0x02, 0x03, 0x05]
+        );
+        assert_eq!(
+            ScriptData::parse(&mut FlvReader::from_source(&buf), buf.len() as u32),
+            Ok(script_data)
+        );
+    }
 }
@@ -1,6 +1,7 @@
 use crate::error::Error;
+use crate::writer::FlvWriter;
 use crate::FlvReader;
-use std::io::Seek;
+use std::io::{Seek, Write};
 
 #[repr(u8)]
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
@@ -167,6 +168,31 @@ impl<'a> AudioData<'a> {
             data,
         })
     }
+
+    /// Write an audio data structure.
+    ///
+    /// AAC packets are written with their packet type; all other audio data is
+    /// written as-is after the header.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        writer.write_u8(
+            (self.format as u8) << 4
+                | (self.rate as u8) << 2
+                | (self.size as u8) << 1
+                | self.sound_type as u8,
+        )?;
+
+        match self.data {
+            AudioDataType::Raw(data) => writer.write(data),
+            AudioDataType::AacSequenceHeader(data) => {
+                writer.write_u8(0)?;
+                writer.write(data)
+            }
+            AudioDataType::AacRaw(data) => {
+                writer.write_u8(1)?;
+                writer.write(data)
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -174,6 +200,7 @@ mod tests {
     use crate::error::Error;
     use crate::reader::FlvReader;
     use crate::sound::{AudioData, AudioDataType, SoundFormat, SoundRate, SoundSize, SoundType};
+    use crate::writer::FlvWriter;
 
     #[test]
     fn read_audiodata() {
@@ -247,4 +274,58 @@ mod tests {
             Err(Error::UnknownAacPacketType(2))
         );
     }
+
+    #[test]
+    fn write_audiodata() {
+        let audio_data = AudioData {
+            format: SoundFormat::Speex,
+            rate: SoundRate::R44_000,
+            size: SoundSize::Bits16,
+            sound_type: SoundType::Stereo,
+            data: AudioDataType::Raw(&[0x12, 0x34, 0x56, 0x78]),
+        };
+        let mut buf = vec![];
+        audio_data.write(&mut FlvWriter::new(&mut buf)).unwrap();
+
+        assert_eq!(buf, [0xBF, 0x12, 0x34, 0x56, 0x78]);
+        assert_eq!(
+            AudioData::parse(&mut FlvReader::from_source(&buf), buf.len() as u32),
+            Ok(audio_data)
+        );
+    }
+
+    #[test]
+    fn write_audiodata_aac() {
+        for (audio_data, expected) in [
+            (
+                AudioData {
+                    format: SoundFormat::Aac,
+                    rate: SoundRate::R44_000,
+                    size: SoundSize::Bits16,
+                    sound_type: SoundType::Stereo,
+                    data: AudioDataType::AacSequenceHeader(&[0x12, 0x10]),
+                },
+                [0xAF, 0x00, 0x12, 0x10],
+            ),
+            (
+                AudioData {
+                    format: SoundFormat::Aac,
+                    rate: SoundRate::R22_000,
+                    size: SoundSize::Bits8,
+                    sound_type: SoundType::Mono,
+                    data: AudioDataType::AacRaw(&[0x21, 0x1C]),
+                },
+                [0xA8, 0x01, 0x21, 0x1C],
+            ),
+        ] {
+            let mut buf = vec![];
+            audio_data.write(&mut FlvWriter::new(&mut buf)).unwrap();
+
+            assert_eq!(buf, expected);
+            assert_eq!(
+                AudioData::parse(&mut FlvReader::from_source(&buf), buf.len() as u32),
+                Ok(audio_data)
+            );
+        }
+    }
 }
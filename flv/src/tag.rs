@@ -3,8 +3,9 @@ use crate::reader::FlvReader;
 use crate::script::ScriptData;
 use crate::sound::AudioData;
 use crate::video::VideoData;
+use crate::writer::FlvWriter;
 
-use std::io::{Seek, SeekFrom};
+use std::io::{Seek, SeekFrom, Write};
 
 #[repr(u8)]
 #[derive(PartialEq, Debug, Clone)]
@@ -20,6 +21,29 @@ pub enum TagData<'a> {
     Invalid(Error),
 }
 
+impl TagData<'_> {
+    /// Write the data of a tag, not including the tag header.
+    ///
+    /// Invalid tag data cannot be written, and yields its contained error.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        match self {
+            TagData::Audio(data) => data.write(writer),
+            TagData::Video(data) => data.write(writer),
+            TagData::Script(data) => data.write(writer),
+            TagData::Invalid(e) => Err(e.clone()),
+        }
+    }
+
+    fn tag_type(&self) -> u8 {
+        match self {
+            TagData::Audio(_) => 8,
+            TagData::Video(_) => 9,
+            TagData::Script(_) => 18,
+            TagData::Invalid(_) => unreachable!("invalid tag data cannot be written"),
+        }
+    }
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub struct Tag<'a> {
     pub timestamp: i32,
@@ -114,6 +138,38 @@ impl<'a> Tag<'a> {
         }
     }
 
+    /// Write a single FLV tag structure.
+    ///
+    /// This is the inverse of `parse`: the back pointer to the previous tag
+    /// written by the writer is written first, followed by the tag itself.
+    /// Once all tags have been written, `FlvWriter::finish` writes the back
+    /// pointer to the last tag.
+    ///
+    /// If the tag data is invalid or too large, an error is returned and
+    /// nothing is written.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        let mut body = FlvWriter::new(vec![]);
+        self.data.write(&mut body)?;
+        let body = body.into_inner();
+
+        let data_size: u32 = body.len().try_into().map_err(|_| Error::ValueTooLarge)?;
+        if data_size > 0xFFFFFF || self.stream_id > 0xFFFFFF {
+            return Err(Error::ValueTooLarge);
+        }
+
+        let timestamp = self.timestamp as u32;
+        writer.write_u32(writer.previous_tag_size())?;
+        writer.write_u8(self.data.tag_type())?;
+        writer.write_u24(data_size)?;
+        writer.write_u24(timestamp & 0xFFFFFF)?;
+        writer.write_u8((timestamp >> 24) as u8)?;
+        writer.write_u24(self.stream_id)?;
+        writer.write(&body)?;
+        writer.set_previous_tag_size(11 + data_size);
+
+        Ok(())
+    }
+
     /// Skip back to the prior tag in the FLV.
     ///
     /// FLV files are constructed as a list of tags. Back pointers to prior
@@ -141,11 +197,14 @@ impl<'a> Tag<'a> {
 #[cfg(test)]
 mod tests {
     use crate::error::Error;
+    use crate::header::{Header, TypeFlags};
     use crate::reader::FlvReader;
     use crate::script::{ScriptData, Value, Variable};
     use crate::sound::{AudioData, AudioDataType, SoundFormat, SoundRate, SoundSize, SoundType};
     use crate::tag::{Tag, TagData};
     use crate::video::{CodecId, FrameType, VideoData, VideoPacket};
+    use crate::writer::FlvWriter;
+    use std::io::{Seek, SeekFrom};
 
     #[test]
     fn read_tag_sounddata() {
@@ -332,4 +391,140 @@ mod tests {
             })
         )
     }
+
+    #[test]
+    fn write_tag_sounddata() {
+        let tag = Tag {
+            timestamp: 0,
+            stream_id: 0x5000,
+            data: TagData::Audio(AudioData {
+                format: SoundFormat::Speex,
+                rate: SoundRate::R44_000,
+                size: SoundSize::Bits16,
+                sound_type: SoundType::Stereo,
+                data: AudioDataType::Raw(&[0x12, 0x34, 0x56, 0x78]),
+            }),
+        };
+        let mut buf = vec![];
+        let mut writer = FlvWriter::new(&mut buf);
+        tag.write(&mut writer).unwrap();
+
+        assert_eq!(writer.previous_tag_size(), 16);
+        assert_eq!(
+            buf,
+            [
+                0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00, 0x00, 0x00, 0x50,
+                0x00, 0xBF, 0x12, 0x34, 0x56, 0x78,
+            ]
+        );
+        assert_eq!(Tag::parse(&mut FlvReader::from_source(&buf)), Ok(tag));
+    }
+
+    #[test]
+    fn write_tag_extended_timestamp() {
+        let tag = Tag {
+            timestamp: 0x12345678,
+            stream_id: 0,
+            data: TagData::Video(VideoData {
+                frame_type: FrameType::Keyframe,
+                codec_id: CodecId::SorensonH263,
+                data: VideoPacket::Data(&[0x12, 0x34, 0x56, 0x78]),
+            }),
+        };
+        let mut buf = vec![];
+        tag.write(&mut FlvWriter::new(&mut buf)).unwrap();
+
+        assert_eq!(
+            buf,
+            [
+                0x00, 0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x05, 0x34, 0x56, 0x78, 0x12, 0x00, 0x00,
+                0x00, 0x12, 0x12, 0x34, 0x56, 0x78,
+            ]
+        );
+        assert_eq!(Tag::parse(&mut FlvReader::from_source(&buf)), Ok(tag));
+    }
+
+    #[test]
+    fn write_tag_invalid() {
+        let tag = Tag {
+            timestamp: 0,
+            stream_id: 0,
+            data: TagData::Invalid(Error::UnknownTagType(0x0C)),
+        };
+        let mut buf = vec![];
+
+        assert_eq!(
+            tag.write(&mut FlvWriter::new(&mut buf)),
+            Err(Error::UnknownTagType(0x0C))
+        );
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn write_flv_roundtrip() {
+        let header = Header {
+            version: 1,
+            type_flags: TypeFlags::HAS_AUDIO | TypeFlags::HAS_VIDEO,
+            data_offset: 9,
+        };
+        let tags = [
+            Tag {
+                timestamp: 0,
+                stream_id: 0,
+                data: TagData::Script(ScriptData(vec![Variable {
+                    name: b"onMetaData",
+                    data: Value::EcmaArray(vec![Variable {
+                        name: b"duration",
+                        data: Value::Number(1.5),
+                    }]),
+                }])),
+            },
+            Tag {
+                timestamp: 0,
+                stream_id: 0,
+                data: TagData::Video(VideoData {
+                    frame_type: FrameType::Keyframe,
+                    codec_id: CodecId::SorensonH263,
+                    data: VideoPacket::Data(&[0x12, 0x34, 0x56, 0x78]),
+                }),
+            },
+            Tag {
+                timestamp: 33,
+                stream_id: 0,
+                data: TagData::Audio(AudioData {
+                    format: SoundFormat::MP3,
+                    rate: SoundRate::R44_000,
+                    size: SoundSize::Bits16,
+                    sound_type: SoundType::Stereo,
+                    data: AudioDataType::Raw(&[0xFF, 0xFB, 0x90, 0x00]),
+                }),
+            },
+        ];
+
+        let mut writer = FlvWriter::new(vec![]);
+        header.write(&mut writer).unwrap();
+        for tag in &tags {
+            tag.write(&mut writer).unwrap();
+        }
+        let last_tag_size = writer.previous_tag_size();
+        let buf = writer.finish().unwrap();
+
+        assert_eq!(buf[buf.len() - 4..], last_tag_size.to_be_bytes());
+
+        let mut reader = FlvReader::from_source(&buf);
+        assert_eq!(Header::parse(&mut reader), Ok(header));
+        for tag in &tags {
+            assert_eq!(Tag::parse(&mut reader).as_ref(), Ok(tag));
+        }
+        assert_eq!(Tag::parse(&mut reader), Err(Error::EndOfData));
+
+        // The back pointers written should allow walking the tags in reverse.
+        for tag in tags.iter().rev() {
+            Tag::skip_back(&mut reader).unwrap();
+            let position = reader.stream_position().unwrap();
+            assert_eq!(Tag::parse(&mut reader).as_ref(), Ok(tag));
+            reader.seek(SeekFrom::Start(position)).unwrap();
+        }
+        assert_eq!(Tag::skip_back(&mut reader), Err(Error::EndOfData));
+    }
 }
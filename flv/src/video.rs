@@ -1,6 +1,7 @@
 use crate::error::Error;
 use crate::reader::FlvReader;
-use std::io::Seek;
+use crate::writer::FlvWriter;
+use std::io::{Seek, Write};
 
 #[repr(u8)]
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
@@ -161,13 +162,52 @@ impl<'a> VideoData<'a> {
             data: packet,
         })
     }
+
+    /// Write a video data structure.
+    ///
+    /// AVC composition time offsets must fit in a signed 24-bit integer, or
+    /// `ValueTooLarge` is returned.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        writer.write_u8((self.frame_type as u8) << 4 | self.codec_id as u8)?;
+
+        match self.data {
+            VideoPacket::Data(data) => writer.write(data),
+            VideoPacket::Vp6Data {
+                hadjust,
+                vadjust,
+                data,
+            } => {
+                writer.write_u8((vadjust & 0x0F) << 4 | (hadjust & 0x0F))?;
+                writer.write(data)
+            }
+            VideoPacket::AvcSequenceHeader(data) => {
+                writer.write(&[0, 0, 0, 0])?;
+                writer.write(data)
+            }
+            VideoPacket::AvcNalu {
+                composition_time_offset,
+                data,
+            } => {
+                if !(-0x800000..0x800000).contains(&composition_time_offset) {
+                    return Err(Error::ValueTooLarge);
+                }
+
+                writer.write_u8(1)?;
+                writer.write(&composition_time_offset.to_be_bytes()[1..])?;
+                writer.write(data)
+            }
+            VideoPacket::AvcEndOfSequence => writer.write(&[2, 0, 0, 0]),
+            VideoPacket::CommandFrame(command) => writer.write_u8(command as u8),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::error::Error;
     use crate::reader::FlvReader;
-    use crate::video::{CodecId, FrameType, VideoData, VideoPacket};
+    use crate::video::{CodecId, CommandFrame, FrameType, VideoData, VideoPacket};
+    use crate::writer::FlvWriter;
 
     #[test]
     fn read_videodata() {
@@ -324,4 +364,56 @@ mod tests {
             Err(Error::UnknownAvcPacketType(0xFF))
         );
     }
+
+    #[test]
+    fn write_videodata() {
+        let packets: &[&[u8]] = &[
+            &[0x12, 0x12, 0x34, 0x56, 0x78],
+            &[0x14, 0x37, 0x12, 0x34, 0x56, 0x78],
+            &[0x17, 0x00, 0x00, 0x00, 0x00, 0x12, 0x34, 0x56, 0x78],
+            &[0x27, 0x01, 0xFF, 0xFF, 0xFE, 0x12, 0x34, 0x56, 0x78],
+            &[0x17, 0x02, 0x00, 0x00, 0x00],
+            &[0x52, 0x01],
+        ];
+
+        for data in packets {
+            let video_data =
+                VideoData::parse(&mut FlvReader::from_source(data), data.len() as u32).unwrap();
+            let mut buf = vec![];
+            video_data.write(&mut FlvWriter::new(&mut buf)).unwrap();
+
+            assert_eq!(&buf, data, "{video_data:?}");
+        }
+    }
+
+    #[test]
+    fn write_videodata_command_frame() {
+        let video_data = VideoData {
+            frame_type: FrameType::CommandFrame,
+            codec_id: CodecId::Avc,
+            data: VideoPacket::CommandFrame(CommandFrame::StartOfClientSideSeek),
+        };
+        let mut buf = vec![];
+        video_data.write(&mut FlvWriter::new(&mut buf)).unwrap();
+
+        assert_eq!(buf, [0x57, 0x00]);
+    }
+
+    #[test]
+    fn write_videodata_avc_offset_too_large() {
+        let video_data = VideoData {
+            frame_type: FrameType::Interframe,
+            codec_id: CodecId::Avc,
+            data: VideoPacket::AvcNalu {
+                composition_time_offset: 0x800000,
+                data: &[],
+            },
+        };
+        let mut buf = vec![];
+
+        assert_eq!(
+            video_data.write(&mut FlvWriter::new(&mut buf)),
+            Err(Error::ValueTooLarge)
+        );
+    }
 }
@@ -0,0 +1,112 @@
+use crate::error::Error as FlvError;
+use std::io::Write;
+
+/// A writer that allows muxing an FLV container.
+pub struct FlvWriter<W: Write> {
+    output: W,
+
+    /// The size of the last tag written, which is written ahead of the next
+    /// tag as its back pointer.
+    previous_tag_size: u32,
+}
+
+impl<W: Write> FlvWriter<W> {
+    pub fn new(output: W) -> Self {
+        FlvWriter {
+            output,
+            previous_tag_size: 0,
+        }
+    }
+
+    /// Finish writing the FLV and return the output.
+    ///
+    /// This writes the back pointer to the last tag, which terminates the
+    /// list of tags.
+    pub fn finish(mut self) -> Result<W, FlvError> {
+        self.write_u32(self.previous_tag_size)?;
+        Ok(self.output)
+    }
+
+    /// Return the output without writing a final back pointer.
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+
+    /// The size of the last tag written, including its header.
+    pub fn previous_tag_size(&self) -> u32 {
+        self.previous_tag_size
+    }
+
+    pub(crate) fn set_previous_tag_size(&mut self, size: u32) {
+        self.previous_tag_size = size;
+    }
+
+    /// Write a buffer of bytes to the output.
+    pub fn write(&mut self, data: &[u8]) -> Result<(), FlvError> {
+        Ok(self.output.write_all(data)?)
+    }
+
+    pub fn write_u8(&mut self, value: u8) -> Result<(), FlvError> {
+        self.write(&[value])
+    }
+
+    pub fn write_u16(&mut self, value: u16) -> Result<(), FlvError> {
+        self.write(&value.to_be_bytes())
+    }
+
+    pub fn write_i16(&mut self, value: i16) -> Result<(), FlvError> {
+        self.write(&value.to_be_bytes())
+    }
+
+    /// Write the low 24 bits of a value.
+    ///
+    /// If the value doesn't fit in 24 bits, `ValueTooLarge` is returned and
+    /// nothing is written.
+    pub fn write_u24(&mut self, value: u32) -> Result<(), FlvError> {
+        if value > 0xFFFFFF {
+            return Err(FlvError::ValueTooLarge);
+        }
+
+        self.write(&value.to_be_bytes()[1..])
+    }
+
+    pub fn write_u32(&mut self, value: u32) -> Result<(), FlvError> {
+        self.write(&value.to_be_bytes())
+    }
+
+    pub fn write_f64(&mut self, value: f64) -> Result<(), FlvError> {
+        self.write(&value.to_be_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::writer::FlvWriter;
+
+    #[test]
+    fn write_integers() {
+        let mut writer = FlvWriter::new(vec![]);
+        writer.write_u8(0x12).unwrap();
+        writer.write_u16(0x3456).unwrap();
+        writer.write_i16(-2).unwrap();
+        writer.write_u24(0x789ABC).unwrap();
+        writer.write_u32(0xDEF01234).unwrap();
+
+        assert_eq!(
+            writer.finish(),
+            Ok(vec![
+                0x12, 0x34, 0x56, 0xFF, 0xFE, 0x78, 0x9A, 0xBC, 0xDE, 0xF0, 0x12, 0x34, 0x00, 0x00,
+                0x00, 0x00
+            ])
+        );
+    }
+
+    #[test]
+    fn write_u24_too_large() {
+        let mut writer = FlvWriter::new(vec![]);
+
+        assert_eq!(writer.write_u24(0x1000000), Err(Error::ValueTooLarge));
+        assert_eq!(writer.finish(), Ok(vec![0x00, 0x00, 0x00, 0x00]));
+    }
+}
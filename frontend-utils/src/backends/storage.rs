@@ -1,14 +1,23 @@
 use ruffle_core::backend::storage::StorageBackend;
+use std::collections::BTreeMap;
 use std::fs;
 use std::fs::File;
-use std::io::Write;
+use std::io::{Read, Write};
 use std::path::{Component, Path, PathBuf};
+use toml_edit::{value, DocumentMut};
+use zip::result::ZipError;
+use zip::write::FileOptions;
+use zip::{ZipArchive, ZipWriter};
 
 pub struct DiskStorageBackend {
     shared_objects_path: PathBuf,
+    quotas: BTreeMap<String, usize>,
 }
 
 impl DiskStorageBackend {
+    /// Custom domain quotas are kept in this file, next to the shared objects.
+    const QUOTAS_FILE: &'static str = "quotas.toml";
+
     pub fn new(shared_objects_path: PathBuf) -> Self {
         // Create a base dir if one doesn't exist yet
         if !shared_objects_path.exists() {
@@ -18,8 +27,14 @@ impl DiskStorageBackend {
             }
         }
 
+        let quotas = match fs::read_to_string(shared_objects_path.join(Self::QUOTAS_FILE)) {
+            Ok(quotas) => parse_quotas(&quotas),
+            Err(_) => BTreeMap::new(),
+        };
+
         DiskStorageBackend {
             shared_objects_path,
+            quotas,
         }
     }
 
@@ -31,6 +46,23 @@ impl DiskStorageBackend {
     fn get_shared_object_path(&self, name: &str) -> PathBuf {
         self.shared_objects_path.join(format!("{name}.sol"))
     }
+
+    /// Adds the names of all `.sol` files within `dir` to `keys`.
+    fn collect_keys(dir: &Path, prefix: &str, keys: &mut Vec<String>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let file_name = entry.file_name();
+            let file_name = file_name.to_string_lossy();
+            let path = entry.path();
+            if path.is_dir() {
+                Self::collect_keys(&path, &format!("{prefix}{file_name}/"), keys);
+            } else if let Some(name) = file_name.strip_suffix(".sol") {
+                keys.push(format!("{prefix}{name}"));
+            }
+        }
+    }
 }
 
 impl StorageBackend for DiskStorageBackend {
@@ -85,4 +117,373 @@ impl StorageBackend for DiskStorageBackend {
         }
         let _ = fs::remove_file(path);
     }
+
+    fn get_size(&self, name: &str) -> Option<usize> {
+        let path = self.get_shared_object_path(name);
+        if !Self::is_path_allowed(&path) {
+            return None;
+        }
+        fs::metadata(path)
+            .ok()
+            .map(|metadata| metadata.len() as usize)
+    }
+
+    fn list_keys(&self) -> Vec<String> {
+        let mut keys = Vec::new();
+        Self::collect_keys(&self.shared_objects_path, "", &mut keys);
+        keys.sort();
+        keys
+    }
+
+    fn domain_quota(&self, domain: &str) -> Option<usize> {
+        self.quotas.get(domain).copied()
+    }
+
+    fn set_domain_quota(&mut self, domain: &str, quota: Option<usize>) -> bool {
+        set_quota(&mut self.quotas, domain, quota);
+        let path = self.shared_objects_path.join(Self::QUOTAS_FILE);
+        if let Err(r) = fs::write(path, write_quotas(&self.quotas)) {
+            tracing::warn!("Unable to save storage quotas {:?}", r);
+            return false;
+        }
+        true
+    }
+}
+
+/// Stores every shared object in a single zip archive.
+///
+/// Shared objects are stored as `<name>.sol` entries, the same layout that
+/// [`DiskStorageBackend`] uses on disk, so the archive can be inspected with any zip tool
+/// and extracted into a save directory. Custom domain quotas are kept in a `quotas.toml` entry.
+///
+/// The archive is rewritten on every change. It's written to a temporary file first,
+/// which then replaces the previous archive, so it's never left half-written.
+pub struct SingleFileStorageBackend {
+    path: PathBuf,
+    shared_objects: BTreeMap<String, Vec<u8>>,
+    quotas: BTreeMap<String, usize>,
+}
+
+impl SingleFileStorageBackend {
+    const QUOTAS_ENTRY: &'static str = "quotas.toml";
+
+    /// Opens the archive at `path`, or starts an empty one if it doesn't exist yet.
+    pub fn new(path: PathBuf) -> Self {
+        let mut backend = Self {
+            path,
+            shared_objects: BTreeMap::new(),
+            quotas: BTreeMap::new(),
+        };
+        if backend.path.exists() {
+            if let Err(e) = backend.load() {
+                tracing::warn!("Unable to read storage file {:?}: {}", backend.path, e);
+            }
+        }
+        backend
+    }
+
+    /// Whether no shared objects are stored.
+    pub fn is_empty(&self) -> bool {
+        self.shared_objects.is_empty()
+    }
+
+    /// Copies all shared objects from another backend, such as a [`DiskStorageBackend`].
+    ///
+    /// Shared objects that are already stored here are kept as they are.
+    pub fn import_from(&mut self, other: &dyn StorageBackend) -> bool {
+        for name in other.list_keys() {
+            if self.shared_objects.contains_key(&name) {
+                continue;
+            }
+            if let Some(data) = other.get(&name) {
+                self.shared_objects.insert(name, data);
+            }
+        }
+        self.save()
+    }
+
+    fn load(&mut self) -> Result<(), ZipError> {
+        let mut archive = ZipArchive::new(File::open(&self.path)?)?;
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)?;
+            if entry.is_dir() {
+                continue;
+            }
+            let entry_name = entry.name().to_string();
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data)?;
+
+            if entry_name == Self::QUOTAS_ENTRY {
+                self.quotas = parse_quotas(&String::from_utf8_lossy(&data));
+            } else if let Some(name) = entry_name.strip_suffix(".sol") {
+                self.shared_objects.insert(name.to_string(), data);
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the archive, returning whether it succeeded.
+    fn save(&self) -> bool {
+        if let Some(parent_dir) = self.path.parent() {
+            if !parent_dir.exists() {
+                if let Err(r) = fs::create_dir_all(parent_dir) {
+                    tracing::warn!("Unable to create storage dir {}", r);
+                    return false;
+                }
+            }
+        }
+
+        let mut temp_path = self.path.clone().into_os_string();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+
+        let result = self
+            .write_archive(&temp_path)
+            .and_then(|_| fs::rename(&temp_path, &self.path).map_err(ZipError::from));
+        if let Err(r) = result {
+            tracing::warn!("Unable to save storage file {:?}: {}", self.path, r);
+            let _ = fs::remove_file(&temp_path);
+            return false;
+        }
+        true
+    }
+
+    fn write_archive(&self, path: &Path) -> Result<(), ZipError> {
+        let mut writer = ZipWriter::new(File::create(path)?);
+        let options = FileOptions::default();
+        for (name, data) in &self.shared_objects {
+            writer.start_file(format!("{name}.sol"), options)?;
+            writer.write_all(data)?;
+        }
+        if !self.quotas.is_empty() {
+            writer.start_file(Self::QUOTAS_ENTRY, options)?;
+            writer.write_all(write_quotas(&self.quotas).as_bytes())?;
+        }
+        writer.finish()?.sync_all()?;
+        Ok(())
+    }
+}
+
+impl StorageBackend for SingleFileStorageBackend {
+    fn get(&self, name: &str) -> Option<Vec<u8>> {
+        self.shared_objects.get(name).cloned()
+    }
+
+    fn put(&mut self, name: &str, value: &[u8]) -> bool {
+        let previous = self.shared_objects.insert(name.to_string(), value.to_vec());
+        if self.save() {
+            return true;
+        }
+
+        // Keep what's in memory consistent with what's on disk.
+        match previous {
+            Some(previous) => self.shared_objects.insert(name.to_string(), previous),
+            None => self.shared_objects.remove(name),
+        };
+        false
+    }
+
+    fn get_size(&self, name: &str) -> Option<usize> {
+        self.shared_objects.get(name).map(|data| data.len())
+    }
+
+    fn remove_key(&mut self, name: &str) {
+        if self.shared_objects.remove(name).is_some() {
+            self.save();
+        }
+    }
+
+    fn list_keys(&self) -> Vec<String> {
+        self.shared_objects.keys().cloned().collect()
+    }
+
+    fn domain_quota(&self, domain: &str) -> Option<usize> {
+        self.quotas.get(domain).copied()
+    }
+
+    fn set_domain_quota(&mut self, domain: &str, quota: Option<usize>) -> bool {
+        set_quota(&mut self.quotas, domain, quota);
+        self.save()
+    }
+}
+
+fn set_quota(quotas: &mut BTreeMap<String, usize>, domain: &str, quota: Option<usize>) {
+    match quota {
+        Some(quota) => quotas.insert(domain.to_string(), quota),
+        None => quotas.remove(domain),
+    };
+}
+
+fn parse_quotas(input: &str) -> BTreeMap<String, usize> {
+    let document = match input.parse::<DocumentMut>() {
+        Ok(document) => document,
+        Err(e) => {
+            tracing::warn!("Unable to parse storage quotas: {}", e);
+            return BTreeMap::new();
+        }
+    };
+    document
+        .iter()
+        .filter_map(|(domain, quota)| {
+            let quota = quota.as_integer()?.try_into().ok()?;
+            Some((domain.to_string(), quota))
+        })
+        .collect()
+}
+
+fn write_quotas(quotas: &BTreeMap<String, usize>) -> String {
+    let mut document = DocumentMut::new();
+    for (domain, quota) in quotas {
+        document[domain.as_str()] = value(*quota as i64);
+    }
+    document.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_core::backend::storage::{MemoryStorageBackend, QuotaCheck};
+    use tempfile::tempdir;
+
+    #[test]
+    fn single_file_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("SharedObjects.zip");
+
+        let mut backend = SingleFileStorageBackend::new(path.clone());
+        assert!(backend.is_empty());
+        assert!(backend.put("localhost/game.swf/save", b"first"));
+        assert!(backend.put("example.com/other.swf/#nested/save", b"second"));
+        assert!(backend.set_domain_quota("example.com", Some(1000)));
+        backend.remove_key("missing");
+
+        let mut backend = SingleFileStorageBackend::new(path.clone());
+        assert_eq!(
+            backend.list_keys(),
+            vec![
+                "example.com/other.swf/#nested/save".to_string(),
+                "localhost/game.swf/save".to_string(),
+            ]
+        );
+        assert_eq!(
+            backend.get("localhost/game.swf/save"),
+            Some(b"first".to_vec())
+        );
+        assert_eq!(backend.domain_quota("example.com"), Some(1000));
+        assert_eq!(backend.domain_quota("localhost"), None);
+        assert_eq!(backend.domain_usage("example.com"), 6);
+
+        backend.remove_key("localhost/game.swf/save");
+        let backend = SingleFileStorageBackend::new(path);
+        assert_eq!(backend.get("localhost/game.swf/save"), None);
+        assert!(!dir.path().join("SharedObjects.zip.tmp").exists());
+    }
+
+    #[test]
+    fn single_file_import_from_disk() {
+        let dir = tempdir().unwrap();
+        let mut disk = DiskStorageBackend::new(dir.path().join("SharedObjects"));
+        assert!(disk.put("localhost/game.swf/save", b"from disk"));
+        assert!(disk.put("localhost/game.swf/other", b"also from disk"));
+        assert_eq!(
+            disk.list_keys(),
+            vec![
+                "localhost/game.swf/other".to_string(),
+                "localhost/game.swf/save".to_string(),
+            ]
+        );
+
+        let mut backend = SingleFileStorageBackend::new(dir.path().join("SharedObjects.zip"));
+        assert!(backend.put("localhost/game.swf/save", b"newer"));
+        assert!(backend.import_from(&disk));
+        assert_eq!(
+            backend.get("localhost/game.swf/save"),
+            Some(b"newer".to_vec())
+        );
+        assert_eq!(
+            backend.get("localhost/game.swf/other"),
+            Some(b"also from disk".to_vec())
+        );
+    }
+
+    #[test]
+    fn quota_check() {
+        let dir = tempdir().unwrap();
+        let mut backend = SingleFileStorageBackend::new(dir.path().join("SharedObjects.zip"));
+        assert!(backend.set_domain_quota("localhost", Some(10)));
+        assert!(backend.put("localhost/game.swf/save", b"12345678"));
+
+        // Replacing a shared object only counts its new size.
+        assert_eq!(
+            backend.check_quota("localhost/game.swf/save", 10),
+            QuotaCheck::Allowed
+        );
+        assert_eq!(
+            backend.check_quota("localhost/game.swf/other", 3),
+            QuotaCheck::Exceeded
+        );
+        assert_eq!(
+            backend.check_quota("example.com/game.swf/save", 3),
+            QuotaCheck::Allowed
+        );
+
+        assert!(backend.set_domain_quota("localhost", Some(0)));
+        assert_eq!(
+            backend.check_quota("localhost/game.swf/save", 1),
+            QuotaCheck::Denied
+        );
+
+        assert!(backend.set_domain_quota("localhost", None));
+        assert_eq!(
+            backend.check_quota("localhost/game.swf/other", 1024 * 1024),
+            QuotaCheck::Allowed
+        );
+    }
+
+    #[test]
+    fn no_quota_by_default() {
+        let dir = tempdir().unwrap();
+        let data = vec![0; 200 * 1024];
+        let backends: Vec<Box<dyn StorageBackend>> = vec![
+            Box::new(MemoryStorageBackend::new()),
+            Box::new(DiskStorageBackend::new(dir.path().join("SharedObjects"))),
+            Box::new(SingleFileStorageBackend::new(
+                dir.path().join("SharedObjects.zip"),
+            )),
+        ];
+        for mut backend in backends {
+            assert!(backend.put("localhost/game.swf/first", &data));
+            assert_eq!(backend.domain_quota("localhost"), None);
+            assert_eq!(
+                backend.check_quota("localhost/game.swf/second", data.len()),
+                QuotaCheck::Allowed
+            );
+            assert!(backend.put("localhost/game.swf/second", &data));
+        }
+    }
+
+    #[test]
+    fn disk_quota_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("SharedObjects");
+
+        let mut backend = DiskStorageBackend::new(path.clone());
+        assert!(backend.set_domain_quota("localhost", Some(10)));
+        assert!(backend.set_domain_quota("example.com", Some(20)));
+        assert!(backend.set_domain_quota("example.com", None));
+        assert!(backend.put("localhost/game.swf/save", b"12345678"));
+
+        let backend = DiskStorageBackend::new(path);
+        assert_eq!(backend.domain_quota("localhost"), Some(10));
+        assert_eq!(backend.domain_quota("example.com"), None);
+        assert_eq!(backend.domain_usage("localhost"), 8);
+        assert_eq!(
+            backend.list_keys(),
+            vec!["localhost/game.swf/save".to_string()]
+        );
+        assert_eq!(
+            backend.check_quota("localhost/game.swf/other", 3),
+            QuotaCheck::Exceeded
+        );
+    }
 }
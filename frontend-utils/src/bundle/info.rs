@@ -1,4 +1,5 @@
 use crate::parse::{DocumentHolder, ParseContext, ParseDetails, ReadExt};
+use crate::player_options::{read_player_options, PlayerOptions};
 use toml_edit::DocumentMut;
 use url::Url;
 
@@ -23,6 +24,7 @@ pub enum BundleInformationParseError {
 pub struct BundleInformation {
     pub name: String,
     pub url: Url,
+    pub player: PlayerOptions,
 }
 
 impl BundleInformation {
@@ -33,7 +35,7 @@ impl BundleInformation {
 
         let mut cx = ParseContext::default();
 
-        let result = document
+        let (name, url) = document
             .get_table_like(&mut cx, "bundle", |cx, bundle| {
                 let Some(name) = bundle.parse_from_str(cx, "name") else {
                     return Err(BundleInformationParseError::InvalidName);
@@ -41,9 +43,13 @@ impl BundleInformation {
                 let Some(url) = bundle.parse_from_str(cx, "url") else {
                     return Err(BundleInformationParseError::InvalidUrl);
                 };
-                Ok(BundleInformation { name, url })
+                Ok((name, url))
             })
             .unwrap_or(Err(BundleInformationParseError::InvalidBundleSection))?;
+        let player = document
+            .get_table_like(&mut cx, "player", read_player_options)
+            .unwrap_or_default();
+        let result = BundleInformation { name, url, player };
 
         Ok(ParseDetails {
             result: DocumentHolder::new(result, document),
@@ -55,6 +61,13 @@ impl BundleInformation {
 #[cfg(test)]
 mod test {
     use crate::bundle::info::{BundleInformation, BundleInformationParseError};
+    use crate::player_options::PlayerOptions;
+    use ruffle_core::backend::navigator::SocketMode;
+    use ruffle_core::config::Letterbox;
+    use ruffle_core::events::{GamepadButton, KeyCode};
+    use ruffle_core::{LoadBehavior, PlayerRuntime, StageAlign, StageScaleMode};
+    use ruffle_render::quality::StageQuality;
+    use std::collections::HashMap;
     use url::Url;
 
     fn read(input: &str) -> Result<(BundleInformation, Vec<String>), BundleInformationParseError> {
@@ -154,9 +167,121 @@ mod test {
                 BundleInformation {
                     name: "Cool Game!".to_string(),
                     url: Url::parse("file:///game.swf").unwrap(),
+                    player: Default::default(),
                 },
                 vec![]
             ))
         )
     }
+
+    #[test]
+    fn player_options() {
+        assert_eq!(
+            read(
+                r#"
+                [bundle]
+                name = "Cool Game!"
+                url = "file:///game.swf"
+
+                [player]
+                max_execution_duration = 30.0
+                base = "https://example.com/"
+                quality = "low"
+                align = "TL"
+                force_align = true
+                scale = "noScale"
+                force_scale = false
+                upgrade_to_https = true
+                load_behavior = "delayed"
+                letterbox = "fullscreen"
+                spoof_url = "https://example.com/game.swf"
+                player_version = 9
+                player_runtime = "air"
+                frame_rate = 60.0
+                dummy_external_interface = true
+                tcp_connections = "deny"
+
+                [player.parameters]
+                level = "1"
+
+                [player.gamepad_buttons]
+                d-pad-up = "up"
+                south = "space"
+                "#
+            ),
+            Ok((
+                BundleInformation {
+                    name: "Cool Game!".to_string(),
+                    url: Url::parse("file:///game.swf").unwrap(),
+                    player: PlayerOptions {
+                        parameters: vec![("level".to_string(), "1".to_string())],
+                        max_execution_duration: Some(30.0),
+                        base: Some(Url::parse("https://example.com/").unwrap()),
+                        quality: Some(StageQuality::Low),
+                        align: Some(StageAlign::TOP | StageAlign::LEFT),
+                        force_align: Some(true),
+                        scale: Some(StageScaleMode::NoScale),
+                        force_scale: Some(false),
+                        upgrade_to_https: Some(true),
+                        load_behavior: Some(LoadBehavior::Delayed),
+                        letterbox: Some(Letterbox::Fullscreen),
+                        spoof_url: Some(Url::parse("https://example.com/game.swf").unwrap()),
+                        player_version: Some(9),
+                        player_runtime: Some(PlayerRuntime::AIR),
+                        frame_rate: Some(60.0),
+                        dummy_external_interface: Some(true),
+                        tcp_connections: Some(SocketMode::Deny),
+                        gamepad_button_mapping: HashMap::from([
+                            (GamepadButton::DPadUp, KeyCode::Up),
+                            (GamepadButton::South, KeyCode::Space),
+                        ]),
+                    },
+                },
+                vec![]
+            ))
+        )
+    }
+
+    #[test]
+    fn invalid_player_options() {
+        assert_eq!(
+            read(
+                r#"
+                [bundle]
+                name = "Cool Game!"
+                url = "file:///game.swf"
+
+                [player]
+                quality = "ultra"
+                force_scale = "yes"
+                player_version = 300
+
+                [player.parameters]
+                level = 1
+
+                [player.gamepad_buttons]
+                jump = "space"
+                south = "nothing"
+                "#
+            ),
+            Ok((
+                BundleInformation {
+                    name: "Cool Game!".to_string(),
+                    url: Url::parse("file:///game.swf").unwrap(),
+                    player: Default::default(),
+                },
+                vec![
+                    "Invalid player.quality: unsupported value \"ultra\"".to_string(),
+                    "Invalid player.force_scale: expected boolean but found string".to_string(),
+                    "Invalid player.player_version: unsupported value 300".to_string(),
+                    "Invalid player.parameters.level: expected string but found integer"
+                        .to_string(),
+                    "Invalid player.gamepad_buttons: unsupported gamepad button \"jump\""
+                        .to_string(),
+                    "Invalid player.gamepad_buttons.south: unsupported value \"nothing\""
+                        .to_string(),
+                ]
+            ))
+        )
+    }
 }
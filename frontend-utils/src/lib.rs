@@ -1,6 +1,7 @@
 pub mod bookmarks;
 pub mod bundle;
 pub mod parse;
+pub mod player_options;
 pub mod write;
 
 pub mod backends;
@@ -226,6 +226,29 @@ pub trait ReadExt<'a> {
         res
     }
 
+    fn get_integer(&'a self, cx: &mut ParseContext, key: &'static str) -> Option<i64> {
+        cx.push_key(key);
+
+        let res = if let Some(item) = self.get_impl(key) {
+            if let Some(value) = item.as_integer() {
+                Some(value)
+            } else {
+                cx.add_warning(format!(
+                    "Invalid {}: expected integer but found {}",
+                    cx.path(),
+                    item.type_name()
+                ));
+                None
+            }
+        } else {
+            None
+        };
+
+        cx.pop_key();
+
+        res
+    }
+
     fn get_float(&'a self, cx: &mut ParseContext, key: &'static str) -> Option<f64> {
         cx.push_key(key);
 
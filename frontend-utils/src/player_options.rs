@@ -0,0 +1,112 @@
+use crate::parse::{ParseContext, ReadExt};
+use ruffle_core::backend::navigator::SocketMode;
+use ruffle_core::config::Letterbox;
+use ruffle_core::events::{GamepadButton, KeyCode};
+use ruffle_core::{LoadBehavior, PlayerRuntime, StageAlign, StageScaleMode};
+use ruffle_render::quality::StageQuality;
+use std::collections::HashMap;
+use toml_edit::TableLike;
+use url::Url;
+
+/// Options for a Player, as given by a `[player]` table.
+///
+/// Every value is optional, and a missing value means that the frontend's own setting should be used.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlayerOptions {
+    pub parameters: Vec<(String, String)>,
+    pub max_execution_duration: Option<f64>,
+    pub base: Option<Url>,
+    pub quality: Option<StageQuality>,
+    pub align: Option<StageAlign>,
+    pub force_align: Option<bool>,
+    pub scale: Option<StageScaleMode>,
+    pub force_scale: Option<bool>,
+    pub upgrade_to_https: Option<bool>,
+    pub load_behavior: Option<LoadBehavior>,
+    pub letterbox: Option<Letterbox>,
+    pub spoof_url: Option<Url>,
+    pub player_version: Option<u8>,
+    pub player_runtime: Option<PlayerRuntime>,
+    pub frame_rate: Option<f64>,
+    pub dummy_external_interface: Option<bool>,
+    pub tcp_connections: Option<SocketMode>,
+    pub gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
+}
+
+pub fn read_player_options(cx: &mut ParseContext, table: &dyn TableLike) -> PlayerOptions {
+    let mut result = PlayerOptions {
+        max_execution_duration: table.get_float(cx, "max_execution_duration"),
+        base: table.parse_from_str(cx, "base"),
+        quality: table.parse_from_str(cx, "quality"),
+        align: table.parse_from_str(cx, "align"),
+        force_align: table.get_bool(cx, "force_align"),
+        scale: table.parse_from_str(cx, "scale"),
+        force_scale: table.get_bool(cx, "force_scale"),
+        upgrade_to_https: table.get_bool(cx, "upgrade_to_https"),
+        load_behavior: table.parse_from_str(cx, "load_behavior"),
+        letterbox: table.parse_from_str(cx, "letterbox"),
+        spoof_url: table.parse_from_str(cx, "spoof_url"),
+        player_runtime: table.parse_from_str(cx, "player_runtime"),
+        frame_rate: table.get_float(cx, "frame_rate"),
+        dummy_external_interface: table.get_bool(cx, "dummy_external_interface"),
+        tcp_connections: table.parse_from_str(cx, "tcp_connections"),
+        ..Default::default()
+    };
+
+    if let Some(version) = table.get_integer(cx, "player_version") {
+        if let Ok(version) = u8::try_from(version) {
+            result.player_version = Some(version);
+        } else {
+            cx.push_key("player_version");
+            cx.add_warning(format!(
+                "Invalid {}: unsupported value {version}",
+                cx.path()
+            ));
+            cx.pop_key();
+        }
+    }
+
+    table.get_table_like(cx, "parameters", |cx, parameters| {
+        for (key, value) in parameters.iter() {
+            if let Some(value) = value.as_str() {
+                result.parameters.push((key.to_string(), value.to_string()));
+            } else {
+                cx.add_warning(format!(
+                    "Invalid {}.{key}: expected string but found {}",
+                    cx.path(),
+                    value.type_name()
+                ));
+            }
+        }
+    });
+
+    table.get_table_like(cx, "gamepad_buttons", |cx, buttons| {
+        for (button_name, key) in buttons.iter() {
+            let Ok(button) = button_name.parse::<GamepadButton>() else {
+                cx.add_warning(format!(
+                    "Invalid {}: unsupported gamepad button {button_name:?}",
+                    cx.path()
+                ));
+                continue;
+            };
+            let Some(key_name) = key.as_str() else {
+                cx.add_warning(format!(
+                    "Invalid {}.{button_name}: expected string but found {}",
+                    cx.path(),
+                    key.type_name()
+                ));
+                continue;
+            };
+            if let Ok(key_code) = key_name.parse::<KeyCode>() {
+                result.gamepad_button_mapping.insert(button, key_code);
+            } else {
+                cx.add_warning(format!(
+                    "Invalid {}.{button_name}: unsupported value {key_name:?}",
+                    cx.path()
+                ));
+            }
+        }
+    });
+
+    result
+}
@@ -0,0 +1,28 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum Error {
+    #[error("the MP4 parser ran out of data")]
+    EndOfData,
+
+    #[error("the MP4 cannot be read as its length exceeds the maximum memory size for this architecture")]
+    PointerTooBig,
+
+    #[error("the data stream does not start with an MP4 box")]
+    WrongMagic,
+
+    #[error("the MP4 contains a box with invalid size {0}")]
+    InvalidBoxSize(u64),
+
+    #[error("the MP4 contains a '{}' box that is too short", String::from_utf8_lossy(.0))]
+    ShortBox([u8; 4]),
+
+    #[error("the MP4 contains a '{}' box with unsupported version {1}", String::from_utf8_lossy(.0))]
+    UnsupportedBoxVersion([u8; 4], u8),
+
+    #[error("the MP4 contains a track without a '{}' box", String::from_utf8_lossy(.0))]
+    MissingBox([u8; 4]),
+
+    #[error("the MP4 contains a track with an inconsistent sample table")]
+    InvalidSampleTable,
+}
@@ -0,0 +1,15 @@
+//! A demuxer for the ISO base media file format (MP4, F4V, M4A, ...).
+//!
+//! Only what's needed to play a progressively downloaded file is parsed: the
+//! movie box, with the codec configuration and sample table of every track.
+//! The samples themselves are left in the file and referred to by offset.
+
+mod error;
+mod movie;
+mod reader;
+mod sample;
+
+pub use error::Error;
+pub use movie::{is_mp4, Movie, SampleEntry, Track, TrackKind};
+pub use reader::Mp4Reader;
+pub use sample::{Sample, SampleRef};
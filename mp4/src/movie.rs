@@ -0,0 +1,675 @@
+use crate::error::Error;
+use crate::reader::Mp4Reader;
+use crate::sample::{Sample, SampleRef, SampleSizes, SampleTables};
+
+/// What kind of media a track holds, from its handler type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrackKind {
+    Video,
+    Audio,
+    Other([u8; 4]),
+}
+
+/// The codec of a track and its configuration, from the first sample entry
+/// of its sample description box.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SampleEntry {
+    /// H.264 video, with its `AVCDecoderConfigurationRecord`.
+    Avc {
+        width: u16,
+        height: u16,
+        config: Vec<u8>,
+    },
+
+    /// AAC audio, with its `AudioSpecificConfig`.
+    Aac {
+        channels: u16,
+        sample_rate: u32,
+        config: Vec<u8>,
+    },
+
+    /// MP3 audio.
+    Mp3 { channels: u16, sample_rate: u32 },
+
+    /// Any other codec, identified by the type of its sample entry.
+    Unknown([u8; 4]),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Track {
+    pub id: u32,
+    pub kind: TrackKind,
+
+    /// The number of time units per second used by the samples of this track.
+    pub timescale: u32,
+
+    /// The duration of the track, in units of `timescale`.
+    pub duration: u64,
+
+    /// The ISO 639-2/T language code of the track.
+    pub language: [u8; 3],
+
+    /// The presentation size of the track, for visual tracks.
+    pub width: f64,
+    pub height: f64,
+
+    pub sample_entry: SampleEntry,
+    pub samples: Vec<Sample>,
+}
+
+impl Track {
+    /// Converts a time in units of this track's timescale to milliseconds.
+    pub fn time_to_ms(&self, time: u64) -> f64 {
+        if self.timescale == 0 {
+            return 0.0;
+        }
+        time as f64 * 1000.0 / self.timescale as f64
+    }
+
+    /// The index of the last sync sample decoded at or before `time`
+    /// milliseconds.
+    pub fn sync_sample_before(&self, time: f64) -> Option<usize> {
+        self.samples
+            .iter()
+            .rposition(|s| s.is_sync && self.time_to_ms(s.decode_time) <= time)
+    }
+}
+
+/// The contents of the movie box of an MP4 file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Movie {
+    /// The offset of the movie box within the file.
+    pub moov_position: u64,
+
+    /// The number of time units per second used by `duration`.
+    pub timescale: u32,
+
+    /// The duration of the longest track, in units of `timescale`.
+    pub duration: u64,
+
+    pub tracks: Vec<Track>,
+}
+
+/// Whether `data` looks like the start of an MP4 file.
+pub fn is_mp4(data: &[u8]) -> bool {
+    matches!(
+        data.get(4..8),
+        Some(b"ftyp" | b"moov" | b"mdat" | b"free" | b"skip" | b"wide")
+    )
+}
+
+impl Movie {
+    /// Parse the movie box of an MP4 file.
+    ///
+    /// Files may store their movie box after the media data, so this returns
+    /// `EndOfData` until the whole movie box is available.
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        if !is_mp4(data) {
+            return Err(if data.len() < 8 {
+                Error::EndOfData
+            } else {
+                Error::WrongMagic
+            });
+        }
+
+        let mut reader = Mp4Reader::from_source(data);
+        loop {
+            let position = reader.position();
+            let (kind, body) = reader.read_box()?;
+            if kind == *b"moov" {
+                return parse_moov(body, position as u64);
+            }
+        }
+    }
+
+    /// The duration of the movie, in milliseconds.
+    pub fn duration_ms(&self) -> f64 {
+        if self.timescale == 0 {
+            return 0.0;
+        }
+        self.duration as f64 * 1000.0 / self.timescale as f64
+    }
+
+    /// The index of the first track of the given kind that has any samples.
+    pub fn first_track(&self, kind: TrackKind) -> Option<usize> {
+        self.tracks
+            .iter()
+            .position(|t| t.kind == kind && !t.samples.is_empty())
+    }
+
+    /// All samples of the given tracks, in the order they are to be decoded.
+    ///
+    /// Samples decoded at the same time are ordered by their position in the
+    /// file.
+    pub fn interleave(&self, tracks: &[usize]) -> Vec<SampleRef> {
+        let mut samples: Vec<SampleRef> = tracks
+            .iter()
+            .flat_map(|&track_index| {
+                let track = &self.tracks[track_index];
+                track
+                    .samples
+                    .iter()
+                    .enumerate()
+                    .map(move |(sample_index, sample)| SampleRef {
+                        track: track_index,
+                        sample: sample_index,
+                        time: track.time_to_ms(sample.decode_time),
+                    })
+            })
+            .collect();
+        samples.sort_by(|a, b| {
+            a.time.total_cmp(&b.time).then_with(|| {
+                let a = &self.tracks[a.track].samples[a.sample];
+                let b = &self.tracks[b.track].samples[b.sample];
+                a.offset.cmp(&b.offset)
+            })
+        });
+        samples
+    }
+}
+
+/// Parse the body of a box with `parse`, treating running out of data as the
+/// box being malformed, as the whole box is known to be available.
+fn parse_body<'a, T>(
+    kind: &[u8; 4],
+    body: &'a [u8],
+    parse: impl FnOnce(&mut Mp4Reader<'a>) -> Result<T, Error>,
+) -> Result<T, Error> {
+    parse(&mut Mp4Reader::from_source(body)).map_err(|e| match e {
+        Error::EndOfData => Error::ShortBox(*kind),
+        e => e,
+    })
+}
+
+/// Call `visit` with the type and body of every child box of a container box.
+fn for_each_child<'a>(
+    kind: &[u8; 4],
+    body: &'a [u8],
+    mut visit: impl FnMut(&[u8; 4], &'a [u8]) -> Result<(), Error>,
+) -> Result<(), Error> {
+    parse_body(kind, body, |reader| {
+        while !reader.is_empty() {
+            let (child_kind, child_body) = reader.read_box()?;
+            visit(&child_kind, child_body)?;
+        }
+        Ok(())
+    })
+}
+
+/// Find the body of the first child box of the given type.
+fn find_child<'a>(
+    kind: &[u8; 4],
+    body: &'a [u8],
+    child_kind: &[u8; 4],
+) -> Result<Option<&'a [u8]>, Error> {
+    let mut found = None;
+    for_each_child(kind, body, |kind, body| {
+        if kind == child_kind && found.is_none() {
+            found = Some(body);
+        }
+        Ok(())
+    })?;
+    Ok(found)
+}
+
+/// Read the version and flags of a full box.
+fn read_full_box_header(reader: &mut Mp4Reader<'_>) -> Result<(u8, u32), Error> {
+    let version = reader.read_u8()?;
+    let flags = reader.read_u24()?;
+    Ok((version, flags))
+}
+
+/// Read a table of entries prefixed by their count.
+fn read_entries<'a, T>(
+    reader: &mut Mp4Reader<'a>,
+    mut read_entry: impl FnMut(&mut Mp4Reader<'a>) -> Result<T, Error>,
+) -> Result<Vec<T>, Error> {
+    let count = reader.read_u32()? as usize;
+    // Don't trust the count with the allocation, every entry is at least a byte.
+    let mut entries = Vec::with_capacity(count.min(reader.remaining()));
+    for _ in 0..count {
+        entries.push(read_entry(reader)?);
+    }
+    Ok(entries)
+}
+
+fn parse_moov(body: &[u8], moov_position: u64) -> Result<Movie, Error> {
+    let mut movie = Movie {
+        moov_position,
+        timescale: 0,
+        duration: 0,
+        tracks: Vec::new(),
+    };
+
+    for_each_child(b"moov", body, |kind, body| {
+        match kind {
+            b"mvhd" => parse_body(kind, body, |reader| {
+                let (version, _flags) = read_full_box_header(reader)?;
+                reader.read_versioned(version)?; // Creation time
+                reader.read_versioned(version)?; // Modification time
+                movie.timescale = reader.read_u32()?;
+                movie.duration = reader.read_versioned(version)?;
+                Ok(())
+            })?,
+            b"trak" => movie.tracks.push(parse_trak(body)?),
+            _ => {}
+        }
+        Ok(())
+    })?;
+
+    Ok(movie)
+}
+
+fn parse_trak(body: &[u8]) -> Result<Track, Error> {
+    let mut track = Track {
+        id: 0,
+        kind: TrackKind::Other([0; 4]),
+        timescale: 0,
+        duration: 0,
+        language: *b"und",
+        width: 0.0,
+        height: 0.0,
+        sample_entry: SampleEntry::Unknown([0; 4]),
+        samples: Vec::new(),
+    };
+
+    let tkhd = find_child(b"trak", body, b"tkhd")?.ok_or(Error::MissingBox(*b"tkhd"))?;
+    parse_body(b"tkhd", tkhd, |reader| {
+        let (version, _flags) = read_full_box_header(reader)?;
+        reader.read_versioned(version)?; // Creation time
+        reader.read_versioned(version)?; // Modification time
+        track.id = reader.read_u32()?;
+        reader.skip(4)?; // Reserved
+        reader.read_versioned(version)?; // Duration, in the movie's timescale
+        reader.skip(52)?; // Reserved, layer, alternate group, volume, matrix
+        track.width = f64::from(reader.read_u32()?) / 65536.0;
+        track.height = f64::from(reader.read_u32()?) / 65536.0;
+        Ok(())
+    })?;
+
+    let mdia = find_child(b"trak", body, b"mdia")?.ok_or(Error::MissingBox(*b"mdia"))?;
+    let mut stbl = None;
+    for_each_child(b"mdia", mdia, |kind, body| {
+        match kind {
+            b"mdhd" => parse_body(kind, body, |reader| {
+                let (version, _flags) = read_full_box_header(reader)?;
+                reader.read_versioned(version)?; // Creation time
+                reader.read_versioned(version)?; // Modification time
+                track.timescale = reader.read_u32()?;
+                track.duration = reader.read_versioned(version)?;
+                // Three letters, packed into five bits each.
+                let language = reader.read_u16()?;
+                track.language = [10, 5, 0].map(|shift| ((language >> shift) & 0x1F) as u8 + 0x60);
+                Ok(())
+            })?,
+            b"hdlr" => parse_body(kind, body, |reader| {
+                read_full_box_header(reader)?;
+                reader.skip(4)?; // Pre-defined
+                track.kind = match &reader.read_fourcc()? {
+                    b"vide" => TrackKind::Video,
+                    b"soun" => TrackKind::Audio,
+                    other => TrackKind::Other(*other),
+                };
+                Ok(())
+            })?,
+            b"minf" => stbl = find_child(kind, body, b"stbl")?,
+            _ => {}
+        }
+        Ok(())
+    })?;
+
+    let stbl = stbl.ok_or(Error::MissingBox(*b"stbl"))?;
+    let mut tables = SampleTables::default();
+    for_each_child(b"stbl", stbl, |kind, body| {
+        parse_body(kind, body, |reader| {
+            match kind {
+                b"stsd" => {
+                    read_full_box_header(reader)?;
+                    if reader.read_u32()? > 0 {
+                        let (entry_kind, entry) = reader.read_box()?;
+                        track.sample_entry = parse_sample_entry(&entry_kind, entry)?;
+                    }
+                }
+                b"stts" => {
+                    read_full_box_header(reader)?;
+                    tables.time_to_sample =
+                        read_entries(reader, |r| Ok((r.read_u32()?, r.read_u32()?)))?;
+                }
+                b"ctts" => {
+                    // Version 0 offsets are unsigned, but are commonly written as
+                    // signed, which is the same for all sensible offsets.
+                    read_full_box_header(reader)?;
+                    tables.composition_offsets =
+                        read_entries(reader, |r| Ok((r.read_u32()?, r.read_u32()? as i32)))?;
+                }
+                b"stsc" => {
+                    read_full_box_header(reader)?;
+                    tables.sample_to_chunk = read_entries(reader, |r| {
+                        let first_chunk = r.read_u32()?;
+                        let samples_per_chunk = r.read_u32()?;
+                        r.read_u32()?; // Sample description index
+                        Ok((first_chunk, samples_per_chunk))
+                    })?;
+                }
+                b"stsz" => {
+                    read_full_box_header(reader)?;
+                    let size = reader.read_u32()?;
+                    tables.sample_sizes = if size == 0 {
+                        SampleSizes::Varying(read_entries(reader, |r| r.read_u32())?)
+                    } else {
+                        let count = reader.read_u32()?;
+                        SampleSizes::Constant { size, count }
+                    };
+                }
+                b"stco" => {
+                    read_full_box_header(reader)?;
+                    tables.chunk_offsets = read_entries(reader, |r| Ok(r.read_u32()?.into()))?;
+                }
+                b"co64" => {
+                    read_full_box_header(reader)?;
+                    tables.chunk_offsets = read_entries(reader, |r| r.read_u64())?;
+                }
+                b"stss" => {
+                    read_full_box_header(reader)?;
+                    tables.sync_samples = Some(read_entries(reader, |r| r.read_u32())?);
+                }
+                _ => {}
+            }
+            Ok(())
+        })
+    })?;
+    track.samples = tables.into_samples()?;
+
+    Ok(track)
+}
+
+fn parse_sample_entry(kind: &[u8; 4], body: &[u8]) -> Result<SampleEntry, Error> {
+    parse_body(kind, body, |reader| {
+        reader.skip(8)?; // Reserved, data reference index
+
+        match kind {
+            b"avc1" | b"avc3" => {
+                reader.skip(16)?; // Pre-defined, reserved
+                let width = reader.read_u16()?;
+                let height = reader.read_u16()?;
+                reader.skip(50)?; // Resolution, frame count, compressor name, depth
+
+                // `avc3` streams carry their parameter sets in band instead.
+                let config = find_child(kind, reader.read_to_end(), b"avcC")?;
+                Ok(SampleEntry::Avc {
+                    width,
+                    height,
+                    config: config.unwrap_or_default().to_vec(),
+                })
+            }
+            b"mp4a" | b".mp3" => {
+                // QuickTime sound descriptions have extra fields in later versions.
+                let version = reader.read_u16()?;
+                reader.skip(6)?; // Revision level, vendor
+                let channels = reader.read_u16()?;
+                reader.skip(6)?; // Sample size, compression ID, packet size
+                let sample_rate = reader.read_u32()? >> 16;
+                match version {
+                    1 => reader.skip(16)?,
+                    2 => reader.skip(36)?,
+                    _ => {}
+                }
+
+                let children = reader.read_to_end();
+                let esds = match find_child(kind, children, b"esds")? {
+                    Some(esds) => Some(esds),
+                    None => match find_child(kind, children, b"wave")? {
+                        Some(wave) => find_child(b"wave", wave, b"esds")?,
+                        None => None,
+                    },
+                };
+                let (object_type, config) = match esds {
+                    Some(esds) => parse_esds(esds)?,
+                    None => (0, &[][..]),
+                };
+
+                // MPEG-1 and MPEG-2 audio, which is to say MP3.
+                if kind == b".mp3" || object_type == 0x69 || object_type == 0x6B {
+                    Ok(SampleEntry::Mp3 {
+                        channels,
+                        sample_rate,
+                    })
+                } else {
+                    Ok(SampleEntry::Aac {
+                        channels,
+                        sample_rate,
+                        config: config.to_vec(),
+                    })
+                }
+            }
+            other => Ok(SampleEntry::Unknown(*other)),
+        }
+    })
+}
+
+/// Read an MPEG-4 descriptor, returning its tag and body.
+fn read_descriptor<'a>(reader: &mut Mp4Reader<'a>) -> Result<(u8, &'a [u8]), Error> {
+    let tag = reader.read_u8()?;
+    let mut len = 0;
+    for _ in 0..4 {
+        let byte = reader.read_u8()?;
+        len = (len << 7) | usize::from(byte & 0x7F);
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    Ok((tag, reader.read(len)?))
+}
+
+/// Parse an elementary stream descriptor box, returning the object type of
+/// the stream and its decoder specific info.
+fn parse_esds(body: &[u8]) -> Result<(u8, &[u8]), Error> {
+    const ES_DESCRIPTOR: u8 = 3;
+    const DECODER_CONFIG_DESCRIPTOR: u8 = 4;
+    const DECODER_SPECIFIC_INFO: u8 = 5;
+
+    parse_body(b"esds", body, |reader| {
+        read_full_box_header(reader)?;
+        let (tag, es) = read_descriptor(reader)?;
+        if tag != ES_DESCRIPTOR {
+            return Ok((0, &[][..]));
+        }
+
+        let mut reader = Mp4Reader::from_source(es);
+        reader.skip(2)?; // ES ID
+        let flags = reader.read_u8()?;
+        if flags & 0x80 != 0 {
+            reader.skip(2)?; // Depends on ES ID
+        }
+        if flags & 0x40 != 0 {
+            let len = reader.read_u8()?;
+            reader.skip(len.into())?; // URL
+        }
+        if flags & 0x20 != 0 {
+            reader.skip(2)?; // OCR ES ID
+        }
+
+        while !reader.is_empty() {
+            let (tag, config) = read_descriptor(&mut reader)?;
+            if tag != DECODER_CONFIG_DESCRIPTOR {
+                continue;
+            }
+
+            let mut reader = Mp4Reader::from_source(config);
+            let object_type = reader.read_u8()?;
+            reader.skip(12)?; // Stream type, buffer size, bitrates
+            while !reader.is_empty() {
+                let (tag, info) = read_descriptor(&mut reader)?;
+                if tag == DECODER_SPECIFIC_INFO {
+                    return Ok((object_type, info));
+                }
+            }
+            return Ok((object_type, &[][..]));
+        }
+        Ok((0, &[][..]))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::movie::{Movie, SampleEntry, TrackKind};
+    use crate::sample::Sample;
+
+    /// Build a box out of its type and body.
+    fn mp4_box(kind: &[u8; 4], body: &[&[u8]]) -> Vec<u8> {
+        let body = body.concat();
+        let mut data = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(kind);
+        data.extend_from_slice(&body);
+        data
+    }
+
+    /// Build a full box out of its type, version and body.
+    fn full_box(kind: &[u8; 4], version: u8, body: &[&[u8]]) -> Vec<u8> {
+        let mut full_body = vec![version, 0, 0, 0];
+        full_body.extend_from_slice(&body.concat());
+        mp4_box(kind, &[&full_body])
+    }
+
+    fn u32s(values: &[u32]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_be_bytes()).collect()
+    }
+
+    /// Build an audio track with three samples in one chunk.
+    fn audio_track(mdat_offset: u32) -> Vec<u8> {
+        audio_track_with_tables(mdat_offset, &[1, 1, 3, 1], &[0, 3, 5, 6, 7])
+    }
+
+    /// Build an audio track with the given `stsc` and `stsz` box bodies.
+    fn audio_track_with_tables(mdat_offset: u32, stsc: &[u32], stsz: &[u32]) -> Vec<u8> {
+        let mut mp4a = vec![0; 8];
+        mp4a.extend_from_slice(&[0; 8]); // Version, revision level, vendor
+        mp4a.extend_from_slice(&[0, 2, 0, 16, 0, 0, 0, 0]); // Stereo, 16 bits
+        mp4a.extend_from_slice(&u32s(&[44100 << 16]));
+        let esds = full_box(
+            b"esds",
+            0,
+            &[&[
+                3, 22, 0, 1, 0, // ES descriptor
+                4, 17, 0x40, 0x15, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, // Decoder config
+                5, 2, 0x12, 0x10, // Decoder specific info
+            ]],
+        );
+        let mp4a = mp4_box(b"mp4a", &[&mp4a, &esds]);
+
+        let mut tkhd = u32s(&[0, 0, 2, 0, 0]);
+        tkhd.extend_from_slice(&[0; 52]);
+        tkhd.extend_from_slice(&u32s(&[0, 0]));
+
+        let stbl = mp4_box(
+            b"stbl",
+            &[
+                &full_box(b"stsd", 0, &[&u32s(&[1]), &mp4a]),
+                &full_box(b"stts", 0, &[&u32s(&[1, 3, 1024])]),
+                &full_box(b"stsc", 0, &[&u32s(stsc)]),
+                &full_box(b"stsz", 0, &[&u32s(stsz)]),
+                &full_box(b"stco", 0, &[&u32s(&[1, mdat_offset])]),
+            ],
+        );
+        mp4_box(
+            b"trak",
+            &[
+                &full_box(b"tkhd", 0, &[&tkhd]),
+                &mp4_box(
+                    b"mdia",
+                    &[
+                        // English, as "eng" packed into five bits per letter.
+                        &full_box(
+                            b"mdhd",
+                            0,
+                            &[&u32s(&[0, 0, 44100, 3072]), &[0x15, 0xC7, 0, 0]],
+                        ),
+                        &full_box(b"hdlr", 0, &[&u32s(&[0]), b"soun", &[0; 12], &[0]]),
+                        &mp4_box(b"minf", &[&stbl]),
+                    ],
+                ),
+            ],
+        )
+    }
+
+    fn movie_box(track: &[u8]) -> Vec<u8> {
+        let mvhd = full_box(b"mvhd", 0, &[&u32s(&[0, 0, 1000, 70]), &[0; 80]]);
+        mp4_box(b"moov", &[&mvhd, track])
+    }
+
+    #[test]
+    fn parse_movie() {
+        let ftyp = mp4_box(b"ftyp", &[b"isom", &[0, 0, 2, 0], b"isom"]);
+        let mdat = mp4_box(b"mdat", &[&[0; 18]]);
+        let moov = movie_box(&audio_track((ftyp.len() + 8) as u32));
+        let data = [ftyp.clone(), mdat.clone(), moov].concat();
+
+        let movie = Movie::parse(&data).unwrap();
+        assert_eq!(movie.moov_position, (ftyp.len() + mdat.len()) as u64);
+        assert_eq!(movie.duration_ms(), 70.0);
+        assert_eq!(movie.tracks.len(), 1);
+
+        let track = &movie.tracks[0];
+        assert_eq!(track.id, 2);
+        assert_eq!(track.kind, TrackKind::Audio);
+        assert_eq!(track.language, *b"eng");
+        assert_eq!(
+            track.sample_entry,
+            SampleEntry::Aac {
+                channels: 2,
+                sample_rate: 44100,
+                config: vec![0x12, 0x10],
+            }
+        );
+        assert_eq!(
+            track.samples[2],
+            Sample {
+                offset: ftyp.len() as u64 + 8 + 11,
+                size: 7,
+                decode_time: 2048,
+                composition_offset: 0,
+                is_sync: true,
+            }
+        );
+        assert_eq!(movie.first_track(TrackKind::Audio), Some(0));
+        assert_eq!(movie.first_track(TrackKind::Video), None);
+
+        let interleaved = movie.interleave(&[0]);
+        assert_eq!(interleaved.len(), 3);
+        assert!((interleaved[1].time - 1024.0 / 44.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn huge_constant_sample_count() {
+        // A few bytes claiming billions of 1-byte samples in a single chunk.
+        let ftyp = mp4_box(b"ftyp", &[b"isom", &[0, 0, 2, 0], b"isom"]);
+        let moov = movie_box(&audio_track_with_tables(
+            0,
+            &[1, 1, u32::MAX, 1],
+            &[1, u32::MAX],
+        ));
+        let data = [ftyp, moov].concat();
+
+        assert_eq!(Movie::parse(&data), Err(Error::InvalidSampleTable));
+    }
+
+    #[test]
+    fn movie_after_partial_media_data() {
+        let ftyp = mp4_box(b"ftyp", &[b"isom", &[0, 0, 2, 0], b"isom"]);
+        let mdat = mp4_box(b"mdat", &[&[0; 18]]);
+        let data = [ftyp, mdat].concat();
+
+        assert_eq!(Movie::parse(&data[..30]), Err(Error::EndOfData));
+        assert_eq!(Movie::parse(&data), Err(Error::EndOfData));
+    }
+
+    #[test]
+    fn wrong_magic() {
+        assert_eq!(Movie::parse(b"FLV\x01"), Err(Error::EndOfData));
+        assert_eq!(
+            Movie::parse(b"FLV\x01\x05\0\0\0\x09"),
+            Err(Error::WrongMagic)
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use crate::error::Error;
+
+/// A reader over the boxes and fields of an MP4 file.
+pub struct Mp4Reader<'a> {
+    source: &'a [u8],
+
+    position: usize,
+}
+
+impl<'a> Mp4Reader<'a> {
+    pub fn from_source(source: &'a [u8]) -> Self {
+        Mp4Reader {
+            source,
+            position: 0,
+        }
+    }
+
+    /// The current position of the reader within its source.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Whether all of the source has been read.
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// The number of bytes left to read.
+    pub fn remaining(&self) -> usize {
+        self.source.len().saturating_sub(self.position)
+    }
+
+    /// Read a certain number of bytes from the buffer.
+    ///
+    /// If the requested number of bytes are not available, `EndOfData` is
+    /// returned and the position of the reader is left unchanged.
+    pub fn read(&mut self, count: usize) -> Result<&'a [u8], Error> {
+        let start = self.position;
+        let end = self
+            .position
+            .checked_add(count)
+            .ok_or(Error::PointerTooBig)?;
+        if end > self.source.len() {
+            return Err(Error::EndOfData);
+        }
+
+        self.position = end;
+
+        Ok(&self.source[start..end])
+    }
+
+    /// Read all remaining bytes of the buffer.
+    pub fn read_to_end(&mut self) -> &'a [u8] {
+        let rest = self.source.get(self.position..).unwrap_or_default();
+        self.position = self.source.len();
+        rest
+    }
+
+    pub fn skip(&mut self, count: usize) -> Result<(), Error> {
+        self.read(count).map(|_| ())
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.read(1)?[0])
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, Error> {
+        Ok(u16::from_be_bytes(
+            self.read(2)?.try_into().expect("two bytes"),
+        ))
+    }
+
+    pub fn read_u24(&mut self) -> Result<u32, Error> {
+        let bytes = self.read(3)?;
+        Ok(u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]))
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_be_bytes(
+            self.read(4)?.try_into().expect("four bytes"),
+        ))
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, Error> {
+        Ok(u64::from_be_bytes(
+            self.read(8)?.try_into().expect("eight bytes"),
+        ))
+    }
+
+    pub fn read_fourcc(&mut self) -> Result<[u8; 4], Error> {
+        Ok(self.read(4)?.try_into().expect("four bytes"))
+    }
+
+    /// Read a 32-bit value for version 0 boxes, or a 64-bit value otherwise.
+    pub fn read_versioned(&mut self, version: u8) -> Result<u64, Error> {
+        if version == 0 {
+            Ok(self.read_u32()?.into())
+        } else {
+            self.read_u64()
+        }
+    }
+
+    /// Read the header of the next box, returning its type, the offset of the
+    /// box within the source, and its size including the header.
+    ///
+    /// A box that claims to extend to the end of the file is given the size of
+    /// the remaining source. The body of the box isn't read.
+    pub fn read_box_header(&mut self) -> Result<([u8; 4], usize, u64), Error> {
+        let start = self.position;
+        let header = (|| {
+            let size = self.read_u32()?;
+            let kind = self.read_fourcc()?;
+            let size = match size {
+                0 => (self.source.len() - start) as u64,
+                1 => self.read_u64()?,
+                size => size.into(),
+            };
+            Ok((kind, start, size))
+        })();
+
+        let header = match header {
+            Ok((_, _, size)) if size < (self.position - start) as u64 => {
+                Err(Error::InvalidBoxSize(size))
+            }
+            header => header,
+        };
+        if header.is_err() {
+            self.position = start;
+        }
+        header
+    }
+
+    /// Read the next box, returning its type and its body.
+    ///
+    /// If the whole box isn't available yet, `EndOfData` is returned and the
+    /// position of the reader is left unchanged.
+    pub fn read_box(&mut self) -> Result<([u8; 4], &'a [u8]), Error> {
+        let (kind, start, size) = self.read_box_header()?;
+        let header_len = self.position - start;
+        let size = usize::try_from(size).map_err(|_| Error::PointerTooBig)?;
+        match self.read(size - header_len) {
+            Ok(body) => Ok((kind, body)),
+            Err(e) => {
+                self.position = start;
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::reader::Mp4Reader;
+
+    #[test]
+    fn read_boxes() {
+        let data = [
+            0, 0, 0, 10, b'f', b'r', b'e', b'e', 1, 2, // free
+            0, 0, 0, 1, b'm', b'd', b'a', b't', 0, 0, 0, 0, 0, 0, 0, 17, 3, // mdat
+            0, 0, 0, 0, b's', b'k', b'i', b'p', 4, 5, // skip
+        ];
+        let mut reader = Mp4Reader::from_source(&data);
+
+        assert_eq!(reader.read_box(), Ok((*b"free", &[1, 2][..])));
+        assert_eq!(reader.read_box(), Ok((*b"mdat", &[3][..])));
+        assert_eq!(reader.read_box(), Ok((*b"skip", &[4, 5][..])));
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn read_partial_box() {
+        let data = [0, 0, 0, 12, b'm', b'o', b'o', b'v', 1, 2];
+        let mut reader = Mp4Reader::from_source(&data);
+
+        assert_eq!(reader.read_box(), Err(Error::EndOfData));
+        assert_eq!(reader.position(), 0);
+    }
+
+    #[test]
+    fn read_invalid_box() {
+        let data = [0, 0, 0, 4, b'f', b'r', b'e', b'e'];
+        let mut reader = Mp4Reader::from_source(&data);
+
+        assert_eq!(reader.read_box(), Err(Error::InvalidBoxSize(4)));
+    }
+}
@@ -0,0 +1,247 @@
+use crate::error::Error;
+
+/// The most samples a track may have.
+///
+/// A table of constant sample sizes only stores their count, so without this a
+/// few bytes of a malicious file could make us allocate billions of samples.
+/// This is more than three days of 60 FPS video.
+const MAX_SAMPLES: usize = 1 << 24;
+
+/// A single sample of a track, such as a video frame or a packet of audio.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sample {
+    /// The offset of the sample's data from the start of the file.
+    pub offset: u64,
+
+    /// The size of the sample's data.
+    pub size: u32,
+
+    /// When the sample is decoded, in units of the track's timescale.
+    pub decode_time: u64,
+
+    /// How much later than its decode time the sample is presented, in units
+    /// of the track's timescale.
+    pub composition_offset: i32,
+
+    /// Whether the sample can be decoded without any of the samples before it.
+    pub is_sync: bool,
+}
+
+/// A reference to a sample of one of the tracks of a movie.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SampleRef {
+    /// The index of the track in the movie.
+    pub track: usize,
+
+    /// The index of the sample in the track.
+    pub sample: usize,
+
+    /// When the sample is decoded, in milliseconds.
+    pub time: f64,
+}
+
+/// The tables of a sample table box that describe where and when each sample is.
+#[derive(Default)]
+pub(crate) struct SampleTables {
+    /// Runs of samples with the same duration, from the `stts` box.
+    pub time_to_sample: Vec<(u32, u32)>,
+
+    /// Runs of samples with the same composition offset, from the `ctts` box.
+    pub composition_offsets: Vec<(u32, i32)>,
+
+    /// The number of samples in each chunk, starting from the given chunk
+    /// number, from the `stsc` box.
+    pub sample_to_chunk: Vec<(u32, u32)>,
+
+    /// The size of every sample, from the `stsz` box.
+    pub sample_sizes: SampleSizes,
+
+    /// The offset of every chunk, from the `stco` or `co64` box.
+    pub chunk_offsets: Vec<u64>,
+
+    /// The 1-based numbers of the sync samples, from the `stss` box. If
+    /// there's no such box, all samples are sync samples.
+    pub sync_samples: Option<Vec<u32>>,
+}
+
+/// The sizes of the samples of a track.
+pub(crate) enum SampleSizes {
+    /// All samples have the same size.
+    Constant { size: u32, count: u32 },
+
+    /// Every sample has its own size.
+    Varying(Vec<u32>),
+}
+
+impl SampleSizes {
+    fn len(&self) -> usize {
+        match self {
+            SampleSizes::Constant { count, .. } => *count as usize,
+            SampleSizes::Varying(sizes) => sizes.len(),
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<u32> {
+        match self {
+            SampleSizes::Constant { size, count } => (index < *count as usize).then_some(*size),
+            SampleSizes::Varying(sizes) => sizes.get(index).copied(),
+        }
+    }
+}
+
+impl Default for SampleSizes {
+    fn default() -> Self {
+        SampleSizes::Varying(Vec::new())
+    }
+}
+
+/// Expands runs of `(count, value)` pairs.
+fn runs<T: Copy>(runs: &[(u32, T)]) -> impl Iterator<Item = T> + '_ {
+    runs.iter()
+        .flat_map(|&(count, value)| std::iter::repeat_n(value, count as usize))
+}
+
+impl SampleTables {
+    /// Combines the tables into a list of every sample of the track.
+    pub fn into_samples(self) -> Result<Vec<Sample>, Error> {
+        let count = self.sample_sizes.len();
+        if count > MAX_SAMPLES {
+            return Err(Error::InvalidSampleTable);
+        }
+        let mut samples = Vec::new();
+
+        let mut sample_to_chunk = self.sample_to_chunk.iter().peekable();
+        let mut samples_per_chunk = 0;
+        for (chunk_number, &chunk_offset) in (1..).zip(&self.chunk_offsets) {
+            while let Some(&&(first_chunk, samples_in_chunk)) = sample_to_chunk.peek() {
+                if first_chunk > chunk_number {
+                    break;
+                }
+                samples_per_chunk = samples_in_chunk;
+                sample_to_chunk.next();
+            }
+
+            let mut offset = chunk_offset;
+            for _ in 0..samples_per_chunk {
+                let Some(size) = self.sample_sizes.get(samples.len()) else {
+                    break;
+                };
+                samples.push(Sample {
+                    offset,
+                    size,
+                    decode_time: 0,
+                    composition_offset: 0,
+                    is_sync: self.sync_samples.is_none(),
+                });
+                offset = offset
+                    .checked_add(u64::from(size))
+                    .ok_or(Error::InvalidSampleTable)?;
+            }
+        }
+        if samples.len() != count {
+            return Err(Error::InvalidSampleTable);
+        }
+
+        let mut durations = runs(&self.time_to_sample);
+        let mut decode_time = 0u64;
+        for sample in &mut samples {
+            sample.decode_time = decode_time;
+            let duration = durations.next().ok_or(Error::InvalidSampleTable)?;
+            decode_time = decode_time
+                .checked_add(u64::from(duration))
+                .ok_or(Error::InvalidSampleTable)?;
+        }
+
+        for (sample, offset) in samples.iter_mut().zip(runs(&self.composition_offsets)) {
+            sample.composition_offset = offset;
+        }
+
+        for number in self.sync_samples.iter().flatten() {
+            if let Some(sample) = (*number as usize)
+                .checked_sub(1)
+                .and_then(|index| samples.get_mut(index))
+            {
+                sample.is_sync = true;
+            }
+        }
+
+        Ok(samples)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::sample::{Sample, SampleSizes, SampleTables};
+
+    #[test]
+    fn combine_tables() {
+        let tables = SampleTables {
+            time_to_sample: vec![(2, 100), (2, 50)],
+            composition_offsets: vec![(1, 0), (1, 200)],
+            sample_to_chunk: vec![(1, 3), (2, 1)],
+            sample_sizes: SampleSizes::Varying(vec![10, 20, 30, 40]),
+            chunk_offsets: vec![1000, 2000],
+            sync_samples: Some(vec![1, 4]),
+        };
+
+        let sample = |offset, size, decode_time, composition_offset, is_sync| Sample {
+            offset,
+            size,
+            decode_time,
+            composition_offset,
+            is_sync,
+        };
+        assert_eq!(
+            tables.into_samples(),
+            Ok(vec![
+                sample(1000, 10, 0, 0, true),
+                sample(1010, 20, 100, 200, false),
+                sample(1030, 30, 200, 0, false),
+                sample(2000, 40, 250, 0, true),
+            ])
+        );
+    }
+
+    #[test]
+    fn missing_chunks() {
+        let tables = SampleTables {
+            time_to_sample: vec![(3, 100)],
+            sample_to_chunk: vec![(1, 2)],
+            sample_sizes: SampleSizes::Constant { size: 10, count: 3 },
+            chunk_offsets: vec![1000],
+            ..Default::default()
+        };
+
+        assert_eq!(tables.into_samples(), Err(Error::InvalidSampleTable));
+    }
+
+    #[test]
+    fn too_many_samples() {
+        let tables = SampleTables {
+            time_to_sample: vec![(u32::MAX, 1)],
+            sample_to_chunk: vec![(1, u32::MAX)],
+            sample_sizes: SampleSizes::Constant {
+                size: 1,
+                count: u32::MAX,
+            },
+            chunk_offsets: vec![0],
+            ..Default::default()
+        };
+
+        assert_eq!(tables.into_samples(), Err(Error::InvalidSampleTable));
+    }
+
+    #[test]
+    fn sample_offset_overflow() {
+        let tables = SampleTables {
+            time_to_sample: vec![(2, 100)],
+            sample_to_chunk: vec![(1, 2)],
+            sample_sizes: SampleSizes::Constant { size: 10, count: 2 },
+            chunk_offsets: vec![u64::MAX - 5],
+            ..Default::default()
+        };
+
+        assert_eq!(tables.into_samples(), Err(Error::InvalidSampleTable));
+    }
+}
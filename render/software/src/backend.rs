@@ -0,0 +1,470 @@
+use crate::commands::CommandRenderer;
+use crate::filters;
+use crate::mesh::ShapeData;
+use crate::surface::Surface;
+use ruffle_render::backend::{
+    BitmapCacheEntry, Context3D, Context3DProfile, PixelBenderOutput, PixelBenderTarget,
+    RenderBackend, ShapeHandle, ViewportDimensions,
+};
+use ruffle_render::bitmap::{
+    Bitmap, BitmapHandle, BitmapHandleImpl, BitmapSource, PixelRegion, RgbaBufRead, SyncHandle,
+};
+use ruffle_render::commands::CommandList;
+use ruffle_render::error::Error;
+use ruffle_render::filters::Filter;
+use ruffle_render::pixel_bender::{
+    PixelBenderShader, PixelBenderShaderArgument, PixelBenderShaderHandle,
+};
+use ruffle_render::quality::StageQuality;
+use ruffle_render::shape_utils::DistilledShape;
+use ruffle_render::tessellator::ShapeTessellator;
+use std::borrow::Cow;
+use std::sync::{Arc, Mutex, MutexGuard};
+use swf::Color;
+
+/// A bitmap texture, stored as premultiplied RGBA8.
+#[derive(Debug)]
+pub struct SoftwareBitmap {
+    width: u32,
+    height: u32,
+    pixels: Mutex<Vec<u8>>,
+}
+
+impl BitmapHandleImpl for SoftwareBitmap {}
+
+impl SoftwareBitmap {
+    fn new(width: u32, height: u32, pixels: Vec<u8>) -> Self {
+        Self {
+            width,
+            height,
+            pixels: Mutex::new(pixels),
+        }
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn pixels(&self) -> MutexGuard<'_, Vec<u8>> {
+        self.pixels
+            .lock()
+            .expect("Software bitmap lock should never be poisoned")
+    }
+
+    fn to_surface(&self) -> Surface {
+        Surface::from_data(self.width, self.height, self.pixels().clone())
+    }
+}
+
+pub(crate) fn as_bitmap(handle: &BitmapHandle) -> &SoftwareBitmap {
+    <dyn BitmapHandleImpl>::downcast_ref(&*handle.0)
+        .expect("Bitmap handle must be a SoftwareBitmap")
+}
+
+/// A copy of the pixels of a region that was just rendered to.
+#[derive(Debug)]
+struct SoftwareSyncHandle {
+    pixels: Vec<u8>,
+    row_bytes: u32,
+}
+
+impl SyncHandle for SoftwareSyncHandle {}
+
+/// A render backend that rasterizes everything on the CPU.
+///
+/// It needs no GPU or windowing system and always produces the same pixels
+/// for the same input, which makes it suited to headless tools and tests.
+pub struct SoftwareRenderBackend {
+    surface: Surface,
+    viewport_dimensions: ViewportDimensions,
+    quality: StageQuality,
+    tessellator: ShapeTessellator,
+}
+
+impl SoftwareRenderBackend {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            surface: Surface::new(width, height),
+            viewport_dimensions: ViewportDimensions {
+                width,
+                height,
+                scale_factor: 1.0,
+            },
+            quality: StageQuality::default(),
+            tessellator: ShapeTessellator::new(),
+        }
+    }
+
+    /// Returns the most recently submitted frame, with straight (non-premultiplied) alpha.
+    pub fn capture_frame(&self) -> image::RgbaImage {
+        let mut bytes = self.surface.data().to_vec();
+        ruffle_render::utils::unmultiply_alpha_rgba(&mut bytes);
+        image::RgbaImage::from_raw(self.surface.width(), self.surface.height(), bytes)
+            .expect("Surface buffer must be a valid RgbaImage")
+    }
+
+    /// Renders `commands` on top of the current contents of `target`.
+    fn render_to_bitmap(
+        &self,
+        target: &SoftwareBitmap,
+        clear: Option<Color>,
+        commands: CommandList,
+        quality: StageQuality,
+    ) -> Surface {
+        let mut surface = target.to_surface();
+        if let Some(clear) = clear {
+            surface.clear(clear);
+        }
+        commands.execute(&mut CommandRenderer::new(&mut surface, quality));
+        surface
+    }
+}
+
+impl RenderBackend for SoftwareRenderBackend {
+    fn viewport_dimensions(&self) -> ViewportDimensions {
+        self.viewport_dimensions
+    }
+
+    fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+        self.viewport_dimensions = dimensions;
+        self.surface = Surface::new(dimensions.width, dimensions.height);
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        let mesh = self.tessellator.tessellate_shape(shape, bitmap_source);
+        ShapeHandle(Arc::new(ShapeData::from_mesh(mesh, bitmap_source, self)))
+    }
+
+    fn render_offscreen(
+        &mut self,
+        handle: BitmapHandle,
+        commands: CommandList,
+        quality: StageQuality,
+        bounds: PixelRegion,
+    ) -> Option<Box<dyn SyncHandle>> {
+        let target = as_bitmap(&handle);
+        let rendered = self.render_to_bitmap(target, None, commands, quality);
+
+        // Only the requested region is synced back, so leave the rest untouched.
+        let mut pixels = target.pixels();
+        let mut surface =
+            Surface::from_data(target.width, target.height, std::mem::take(&mut pixels));
+        surface.copy_region_from(&rendered, bounds);
+        let mut region = bounds;
+        region.clamp(target.width, target.height);
+        let sync = SoftwareSyncHandle {
+            pixels: surface.copy_region(region),
+            row_bytes: region.width() * 4,
+        };
+        *pixels = surface.into_data();
+        Some(Box::new(sync))
+    }
+
+    fn apply_filter(
+        &mut self,
+        source: BitmapHandle,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        destination: BitmapHandle,
+        dest_point: (u32, u32),
+        filter: Filter,
+    ) -> Option<Box<dyn SyncHandle>> {
+        let input = as_bitmap(&source).to_surface().extract_area(
+            source_point,
+            source_size.0,
+            source_size.1,
+        );
+        let output = filters::apply(&input, &filter)?;
+
+        let destination = as_bitmap(&destination);
+        let mut pixels = destination.pixels();
+        let mut surface = Surface::from_data(
+            destination.width,
+            destination.height,
+            std::mem::take(&mut pixels),
+        );
+        surface.replace_area(&output, dest_point);
+        let sync = SoftwareSyncHandle {
+            pixels: surface.data().to_vec(),
+            row_bytes: destination.width * 4,
+        };
+        *pixels = surface.into_data();
+        Some(Box::new(sync))
+    }
+
+    fn is_filter_supported(&self, filter: &Filter) -> bool {
+        filters::is_supported(filter)
+    }
+
+    fn is_offscreen_supported(&self) -> bool {
+        true
+    }
+
+    fn submit_frame(
+        &mut self,
+        clear: Color,
+        commands: CommandList,
+        cache_entries: Vec<BitmapCacheEntry>,
+    ) {
+        for entry in cache_entries {
+            let target = as_bitmap(&entry.handle);
+            let mut surface =
+                self.render_to_bitmap(target, Some(entry.clear), entry.commands, self.quality);
+            for filter in &entry.filters {
+                match filters::apply(&surface, filter) {
+                    Some(filtered) => surface = filtered,
+                    None => tracing::warn!("Unsupported cached filter {:?}", filter),
+                }
+            }
+            *target.pixels() = surface.into_data();
+        }
+
+        self.surface.clear(clear);
+        commands.execute(&mut CommandRenderer::new(&mut self.surface, self.quality));
+    }
+
+    fn create_empty_texture(&mut self, width: u32, height: u32) -> Result<BitmapHandle, Error> {
+        if width == 0 || height == 0 {
+            return Err(Error::InvalidSize);
+        }
+        let pixels = vec![0; width as usize * height as usize * 4];
+        Ok(BitmapHandle(Arc::new(SoftwareBitmap::new(
+            width, height, pixels,
+        ))))
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapHandle, Error> {
+        let bitmap = bitmap.to_rgba();
+        Ok(BitmapHandle(Arc::new(SoftwareBitmap::new(
+            bitmap.width(),
+            bitmap.height(),
+            bitmap.data().to_vec(),
+        ))))
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: &BitmapHandle,
+        bitmap: Bitmap,
+        region: PixelRegion,
+    ) -> Result<(), Error> {
+        let texture = as_bitmap(handle);
+        let bitmap = bitmap.to_rgba();
+        if bitmap.width() != texture.width || bitmap.height() != texture.height {
+            return Err(Error::Unimplemented(
+                "Resizing a software bitmap through update_texture".into(),
+            ));
+        }
+        let source = Surface::from_data(bitmap.width(), bitmap.height(), bitmap.data().to_vec());
+        let mut pixels = texture.pixels();
+        let mut surface =
+            Surface::from_data(texture.width, texture.height, std::mem::take(&mut pixels));
+        surface.copy_region_from(&source, region);
+        *pixels = surface.into_data();
+        Ok(())
+    }
+
+    fn create_context3d(
+        &mut self,
+        _profile: Context3DProfile,
+    ) -> Result<Box<dyn Context3D>, Error> {
+        Err(Error::Unimplemented("createContext3D".into()))
+    }
+
+    fn context3d_present(&mut self, _context: &mut dyn Context3D) -> Result<(), Error> {
+        Err(Error::Unimplemented("Context3D.present".into()))
+    }
+
+    fn debug_info(&self) -> Cow<'static, str> {
+        Cow::Borrowed("Renderer: Software")
+    }
+
+    fn name(&self) -> &'static str {
+        "software"
+    }
+
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+    }
+
+    fn compile_pixelbender_shader(
+        &mut self,
+        _shader: PixelBenderShader,
+    ) -> Result<PixelBenderShaderHandle, Error> {
+        Err(Error::Unimplemented(
+            "Pixel bender shader compilation".into(),
+        ))
+    }
+
+    fn run_pixelbender_shader(
+        &mut self,
+        _handle: PixelBenderShaderHandle,
+        _arguments: &[PixelBenderShaderArgument],
+        _target: &PixelBenderTarget,
+    ) -> Result<PixelBenderOutput, Error> {
+        Err(Error::Unimplemented("Pixel bender shader".into()))
+    }
+
+    fn resolve_sync_handle(
+        &mut self,
+        handle: Box<dyn SyncHandle>,
+        with_rgba: RgbaBufRead,
+    ) -> Result<(), Error> {
+        let handle = handle
+            .downcast::<SoftwareSyncHandle>()
+            .map_err(|_| Error::Unimplemented("Foreign sync handle".into()))?;
+        with_rgba(&handle.pixels, handle.row_bytes);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_render::commands::{CommandHandler, RenderBlendMode};
+    use ruffle_render::matrix::Matrix;
+    use swf::{BlendMode, Twips};
+
+    fn rect_matrix(x: f64, y: f64, width: f32, height: f32) -> Matrix {
+        Matrix {
+            a: width,
+            d: height,
+            tx: Twips::from_pixels(x),
+            ty: Twips::from_pixels(y),
+            ..Matrix::IDENTITY
+        }
+    }
+
+    #[test]
+    fn draws_rectangles() {
+        let mut backend = SoftwareRenderBackend::new(4, 4);
+        let mut commands = CommandList::new();
+        commands.draw_rect(Color::RED, rect_matrix(1.0, 1.0, 2.0, 2.0));
+        backend.submit_frame(Color::WHITE, commands, vec![]);
+
+        let image = backend.capture_frame();
+        assert_eq!(image.get_pixel(0, 0).0, [255, 255, 255, 255]);
+        assert_eq!(image.get_pixel(1, 1).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(2, 2).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(3, 3).0, [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn masks_clip_content() {
+        let mut backend = SoftwareRenderBackend::new(4, 4);
+        let mut commands = CommandList::new();
+        commands.push_mask();
+        commands.draw_rect(Color::WHITE, rect_matrix(0.0, 0.0, 2.0, 4.0));
+        commands.activate_mask();
+        commands.draw_rect(Color::BLUE, rect_matrix(0.0, 0.0, 4.0, 4.0));
+        commands.deactivate_mask();
+        commands.draw_rect(Color::WHITE, rect_matrix(0.0, 0.0, 2.0, 4.0));
+        commands.pop_mask();
+        backend.submit_frame(Color::BLACK, commands, vec![]);
+
+        let image = backend.capture_frame();
+        assert_eq!(image.get_pixel(1, 2).0, [0, 0, 255, 255]);
+        assert_eq!(image.get_pixel(3, 2).0, [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn alpha_blend_clears_outside_layer() {
+        let mut backend = SoftwareRenderBackend::new(4, 4);
+        let mut commands = CommandList::new();
+        commands.draw_rect(Color::RED, rect_matrix(0.0, 0.0, 4.0, 4.0));
+        let mut layer = CommandList::new();
+        layer.draw_rect(Color::WHITE, rect_matrix(0.0, 0.0, 2.0, 4.0));
+        commands.blend(layer, RenderBlendMode::Builtin(BlendMode::Alpha));
+        backend.submit_frame(Color::WHITE, commands, vec![]);
+
+        let image = backend.capture_frame();
+        assert_eq!(image.get_pixel(1, 2).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(3, 2).0, [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn erase_blend_keeps_outside_layer() {
+        let mut backend = SoftwareRenderBackend::new(4, 4);
+        let mut commands = CommandList::new();
+        commands.draw_rect(Color::RED, rect_matrix(0.0, 0.0, 4.0, 4.0));
+        let mut layer = CommandList::new();
+        layer.draw_rect(Color::WHITE, rect_matrix(0.0, 0.0, 2.0, 4.0));
+        commands.blend(layer, RenderBlendMode::Builtin(BlendMode::Erase));
+        backend.submit_frame(Color::WHITE, commands, vec![]);
+
+        let image = backend.capture_frame();
+        assert_eq!(image.get_pixel(1, 2).0, [0, 0, 0, 0]);
+        assert_eq!(image.get_pixel(3, 2).0, [255, 0, 0, 255]);
+    }
+
+    fn masked_blend(mode: BlendMode) -> image::RgbaImage {
+        let mut backend = SoftwareRenderBackend::new(4, 4);
+        let mut commands = CommandList::new();
+        commands.draw_rect(Color::RED, rect_matrix(0.0, 0.0, 4.0, 4.0));
+        commands.push_mask();
+        commands.draw_rect(Color::WHITE, rect_matrix(0.0, 0.0, 4.0, 2.0));
+        commands.activate_mask();
+        let mut layer = CommandList::new();
+        layer.draw_rect(Color::WHITE, rect_matrix(0.0, 0.0, 2.0, 4.0));
+        commands.blend(layer, RenderBlendMode::Builtin(mode));
+        commands.deactivate_mask();
+        commands.draw_rect(Color::WHITE, rect_matrix(0.0, 0.0, 4.0, 2.0));
+        commands.pop_mask();
+        backend.submit_frame(Color::WHITE, commands, vec![]);
+        backend.capture_frame()
+    }
+
+    #[test]
+    fn masked_alpha_blend_keeps_outside_mask() {
+        let image = masked_blend(BlendMode::Alpha);
+        assert_eq!(image.get_pixel(1, 1).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(3, 1).0, [0, 0, 0, 0]);
+        assert_eq!(image.get_pixel(1, 3).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(3, 3).0, [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn masked_erase_blend_keeps_outside_mask() {
+        let image = masked_blend(BlendMode::Erase);
+        assert_eq!(image.get_pixel(1, 1).0, [0, 0, 0, 0]);
+        assert_eq!(image.get_pixel(3, 1).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(1, 3).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(3, 3).0, [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn offscreen_rendering_syncs_region() {
+        let mut backend = SoftwareRenderBackend::new(1, 1);
+        let handle = backend
+            .create_empty_texture(4, 4)
+            .expect("texture creation succeeds");
+        let mut commands = CommandList::new();
+        commands.draw_rect(Color::GREEN, rect_matrix(0.0, 0.0, 4.0, 4.0));
+        let region = PixelRegion::for_region(1, 1, 2, 2);
+        let sync = backend
+            .render_offscreen(handle, commands, StageQuality::Low, region)
+            .expect("offscreen rendering is supported");
+
+        let mut synced = Vec::new();
+        backend
+            .resolve_sync_handle(
+                sync,
+                Box::new(|pixels, row_bytes| {
+                    assert_eq!(row_bytes, 8);
+                    synced.extend_from_slice(pixels);
+                }),
+            )
+            .expect("sync handle resolves");
+        assert_eq!(synced, [0, 255, 0, 255].repeat(4));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::surface::Rgba;
+use swf::BlendMode;
+
+/// Blends a premultiplied `src` layer pixel onto a premultiplied `dst` pixel.
+///
+/// These mirror the blend shaders of the wgpu backend, including Flash's quirks
+/// (such as multiply replacing fully transparent destination pixels outright).
+pub fn blend(mode: BlendMode, src: Rgba, dst: Rgba) -> Rgba {
+    let [sr, sg, sb, sa] = src;
+    let [dr, dg, db, da] = dst;
+    let over_alpha = sa + da * (1.0 - sa);
+    match mode {
+        BlendMode::Normal | BlendMode::Layer => [
+            sr + dr * (1.0 - sa),
+            sg + dg * (1.0 - sa),
+            sb + db * (1.0 - sa),
+            over_alpha,
+        ],
+        BlendMode::Add => [
+            (sr + dr).min(1.0),
+            (sg + dg).min(1.0),
+            (sb + db).min(1.0),
+            over_alpha,
+        ],
+        BlendMode::Subtract => [
+            (dr - sr).max(0.0),
+            (dg - sg).max(0.0),
+            (db - sb).max(0.0),
+            over_alpha,
+        ],
+        BlendMode::Screen => [
+            sr + dr * (1.0 - sr),
+            sg + dg * (1.0 - sg),
+            sb + db * (1.0 - sb),
+            over_alpha,
+        ],
+        BlendMode::Alpha => [dr * sa, dg * sa, db * sa, da * sa],
+        BlendMode::Erase => [
+            dr * (1.0 - sa),
+            dg * (1.0 - sa),
+            db * (1.0 - sa),
+            da * (1.0 - sa),
+        ],
+        BlendMode::Invert => separable(src, dst, |_, d| 1.0 - d),
+        BlendMode::Multiply if da <= 0.0 => src,
+        BlendMode::Multiply => separable(src, dst, |s, d| s * d),
+        BlendMode::Lighten => separable(src, dst, f32::max),
+        BlendMode::Darken => separable(src, dst, f32::min),
+        BlendMode::Difference => separable(src, dst, |s, d| (d - s).abs()),
+        BlendMode::Overlay => separable(src, dst, |s, d| hard_light(d, s)),
+        BlendMode::HardLight => separable(src, dst, hard_light),
+    }
+}
+
+/// Applies a separable blend function `f(src, dst)` to straight-alpha components,
+/// using the standard compositing formula for premultiplied colors.
+fn separable(src: Rgba, dst: Rgba, f: impl Fn(f32, f32) -> f32) -> Rgba {
+    let [_, _, _, sa] = src;
+    let [_, _, _, da] = dst;
+    let channel = |i: usize| {
+        let s = src[i] / sa;
+        let d = if da > 0.0 { dst[i] / da } else { 0.0 };
+        src[i] * (1.0 - da) + dst[i] * (1.0 - sa) + sa * da * f(s, d)
+    };
+    [channel(0), channel(1), channel(2), sa + da * (1.0 - sa)]
+}
+
+fn hard_light(src: f32, dst: f32) -> f32 {
+    if src <= 0.5 {
+        2.0 * src * dst
+    } else {
+        1.0 - 2.0 * (1.0 - dst) * (1.0 - src)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiply_onto_transparent_replaces() {
+        let src = [0.25, 0.5, 0.0, 0.5];
+        assert_eq!(blend(BlendMode::Multiply, src, [0.0; 4]), src);
+    }
+
+    #[test]
+    fn erase_removes_destination() {
+        let out = blend(BlendMode::Erase, [0.0, 0.0, 0.0, 1.0], [1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(out, [0.0; 4]);
+    }
+
+    #[test]
+    fn difference_of_identical_colors_is_black() {
+        let white = [1.0, 1.0, 1.0, 1.0];
+        assert_eq!(
+            blend(BlendMode::Difference, white, white),
+            [0.0, 0.0, 0.0, 1.0]
+        );
+    }
+}
@@ -0,0 +1,394 @@
+use crate::backend::{as_bitmap, SoftwareBitmap};
+use crate::blend::blend;
+use crate::mesh::{apply_matrix, as_shape_data, Draw, Paint};
+use crate::raster::{samples_per_axis, Coverage, Triangle};
+use crate::surface::{lerp, premultiply, scale, unmultiply, Rgba, Surface, TRANSPARENT};
+use ruffle_render::backend::ShapeHandle;
+use ruffle_render::bitmap::{BitmapHandle, PixelSnapping};
+use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::quality::StageQuality;
+use ruffle_render::transform::Transform;
+use swf::{BlendMode, Color, ColorTransform};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum MaskState {
+    DrawContent,
+    DrawMask,
+    ClearMask,
+}
+
+/// Executes a `CommandList` onto a `Surface`.
+pub struct CommandRenderer<'a> {
+    target: &'a mut Surface,
+    quality: StageQuality,
+    samples_per_axis: u32,
+    /// Per-pixel coverage of each active mask, already intersected with the masks below it.
+    masks: Vec<Vec<f32>>,
+    mask_state: MaskState,
+}
+
+impl<'a> CommandRenderer<'a> {
+    pub fn new(target: &'a mut Surface, quality: StageQuality) -> Self {
+        Self {
+            target,
+            quality,
+            samples_per_axis: samples_per_axis(quality),
+            masks: Vec::new(),
+            mask_state: MaskState::DrawContent,
+        }
+    }
+
+    /// The inverse of `matrix`, in pixels rather than twips.
+    fn pixel_inverse(matrix: &Matrix) -> Option<[f32; 6]> {
+        let det = matrix.a * matrix.d - matrix.b * matrix.c;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let tx = matrix.tx.to_pixels() as f32;
+        let ty = matrix.ty.to_pixels() as f32;
+        let a = matrix.d / det;
+        let b = -matrix.b / det;
+        let c = -matrix.c / det;
+        let d = matrix.a / det;
+        Some([a, b, c, d, -(a * tx + c * ty), -(b * tx + d * ty)])
+    }
+
+    fn transform_triangles(matrix: &Matrix, triangles: &[Triangle]) -> Vec<Triangle> {
+        let tx = matrix.tx.to_pixels() as f32;
+        let ty = matrix.ty.to_pixels() as f32;
+        triangles
+            .iter()
+            .map(|triangle| {
+                triangle.map(|(x, y)| {
+                    (
+                        matrix.a * x + matrix.c * y + tx,
+                        matrix.b * x + matrix.d * y + ty,
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn rasterize(&self, triangles: &[Triangle]) -> Option<Coverage> {
+        Coverage::rasterize(
+            triangles,
+            self.samples_per_axis,
+            self.target.width(),
+            self.target.height(),
+        )
+    }
+
+    /// Adds coverage to the mask currently being drawn.
+    fn draw_mask(&mut self, coverage: Coverage) {
+        let width = self.target.width() as usize;
+        if let Some(mask) = self.masks.last_mut() {
+            coverage.for_each(|x, y, c| {
+                let value = &mut mask[y as usize * width + x as usize];
+                *value += c * (1.0 - *value);
+            });
+        }
+    }
+
+    /// Composites content with the given coverage, clipping it to the active mask.
+    fn fill(&mut self, coverage: Coverage, mut shade: impl FnMut(u32, u32) -> Rgba) {
+        let width = self.target.width() as usize;
+        let mask = self.masks.last();
+        let target = &mut *self.target;
+        coverage.for_each(|x, y, c| {
+            let c = match mask {
+                Some(mask) => c * mask[y as usize * width + x as usize],
+                None => c,
+            };
+            if c > 0.0 {
+                target.blend_over(x, y, scale(shade(x, y), c));
+            }
+        });
+    }
+
+    fn render_draw(&mut self, draw: &Draw, transform: &Transform) {
+        match self.mask_state {
+            MaskState::ClearMask => {}
+            MaskState::DrawMask => {
+                let triangles = Self::transform_triangles(
+                    &transform.matrix,
+                    &draw.triangles[..draw.mask_triangle_count.min(draw.triangles.len())],
+                );
+                if let Some(coverage) = self.rasterize(&triangles) {
+                    self.draw_mask(coverage);
+                }
+            }
+            MaskState::DrawContent => {
+                let triangles = Self::transform_triangles(&transform.matrix, &draw.triangles);
+                let color_transform = &transform.color_transform;
+                match &draw.paint {
+                    Paint::Color => {
+                        for run in &draw.runs {
+                            let color = premultiply(color_transform * run.color);
+                            if color[3] <= 0.0 {
+                                continue;
+                            }
+                            if let Some(coverage) = self.rasterize(&triangles[run.start..run.end]) {
+                                self.fill(coverage, |_, _| color);
+                            }
+                        }
+                    }
+                    Paint::Gradient { matrix, gradient } => {
+                        let Some(inverse) = Self::pixel_inverse(&transform.matrix) else {
+                            return;
+                        };
+                        let ramp = gradient.transformed(color_transform);
+                        if let Some(coverage) = self.rasterize(&triangles) {
+                            self.fill(coverage, |x, y| {
+                                let local = apply_inverse(&inverse, x, y);
+                                ramp[gradient.position(apply_matrix(matrix, local))]
+                            });
+                        }
+                    }
+                    Paint::Bitmap {
+                        matrix,
+                        handle,
+                        is_smoothed,
+                        is_repeating,
+                    } => {
+                        let Some(inverse) = Self::pixel_inverse(&transform.matrix) else {
+                            return;
+                        };
+                        let bitmap = as_bitmap(handle);
+                        let pixels = bitmap.pixels();
+                        let smoothed = *is_smoothed && self.quality != StageQuality::Low;
+                        if let Some(coverage) = self.rasterize(&triangles) {
+                            self.fill(coverage, |x, y| {
+                                let local = apply_inverse(&inverse, x, y);
+                                let (u, v) = apply_matrix(matrix, local);
+                                let texel = sample(
+                                    bitmap,
+                                    &pixels,
+                                    u * bitmap.width() as f32,
+                                    v * bitmap.height() as f32,
+                                    smoothed,
+                                    *is_repeating,
+                                );
+                                apply_color_transform(texel, color_transform)
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Draws the unit square transformed by `matrix`, shaded by `shade`.
+    fn render_quad(&mut self, matrix: &Matrix, shade: impl FnMut(u32, u32) -> Rgba) {
+        let quad = [
+            [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)],
+            [(0.0, 0.0), (1.0, 1.0), (0.0, 1.0)],
+        ];
+        let triangles = Self::transform_triangles(matrix, &quad);
+        let Some(coverage) = self.rasterize(&triangles) else {
+            return;
+        };
+        match self.mask_state {
+            MaskState::ClearMask => {}
+            MaskState::DrawMask => self.draw_mask(coverage),
+            MaskState::DrawContent => self.fill(coverage, shade),
+        }
+    }
+}
+
+#[inline]
+fn apply_inverse(inverse: &[f32; 6], x: u32, y: u32) -> (f32, f32) {
+    let (x, y) = (x as f32 + 0.5, y as f32 + 0.5);
+    (
+        inverse[0] * x + inverse[2] * y + inverse[4],
+        inverse[1] * x + inverse[3] * y + inverse[5],
+    )
+}
+
+fn apply_color_transform(color: Rgba, color_transform: &ColorTransform) -> Rgba {
+    if *color_transform == ColorTransform::IDENTITY || color[3] <= 0.0 {
+        return color;
+    }
+    let mult = color_transform.mult_rgba_normalized();
+    let add = color_transform.add_rgba_normalized();
+    let color = unmultiply(color);
+    let channel = |i: usize| (color[i] * mult[i] + add[i]).clamp(0.0, 1.0);
+    let alpha = channel(3);
+    [
+        channel(0) * alpha,
+        channel(1) * alpha,
+        channel(2) * alpha,
+        alpha,
+    ]
+}
+
+/// Samples a premultiplied texel at the given texel-space coordinates.
+fn sample(
+    bitmap: &SoftwareBitmap,
+    pixels: &[u8],
+    u: f32,
+    v: f32,
+    smoothed: bool,
+    repeating: bool,
+) -> Rgba {
+    let width = bitmap.width() as i32;
+    let height = bitmap.height() as i32;
+    if width == 0 || height == 0 || !u.is_finite() || !v.is_finite() {
+        return TRANSPARENT;
+    }
+    let texel = |x: i32, y: i32| -> Rgba {
+        let (x, y) = if repeating {
+            (x.rem_euclid(width), y.rem_euclid(height))
+        } else {
+            (x.clamp(0, width - 1), y.clamp(0, height - 1))
+        };
+        let i = (y as usize * width as usize + x as usize) * 4;
+        match pixels.get(i..i + 4) {
+            Some(p) => [
+                f32::from(p[0]) / 255.0,
+                f32::from(p[1]) / 255.0,
+                f32::from(p[2]) / 255.0,
+                f32::from(p[3]) / 255.0,
+            ],
+            None => TRANSPARENT,
+        }
+    };
+
+    if smoothed {
+        let (u, v) = (u - 0.5, v - 0.5);
+        let (x0, y0) = (u.floor(), v.floor());
+        let (fx, fy) = (u - x0, v - y0);
+        let (x0, y0) = (x0 as i32, y0 as i32);
+        let (a, b, c, d) = (
+            texel(x0, y0),
+            texel(x0 + 1, y0),
+            texel(x0, y0 + 1),
+            texel(x0 + 1, y0 + 1),
+        );
+        let mut out = [0.0; 4];
+        for i in 0..4 {
+            let top = a[i] + (b[i] - a[i]) * fx;
+            let bottom = c[i] + (d[i] - c[i]) * fx;
+            out[i] = top + (bottom - top) * fy;
+        }
+        out
+    } else {
+        texel(u.floor() as i32, v.floor() as i32)
+    }
+}
+
+impl<'a> CommandHandler for CommandRenderer<'a> {
+    fn render_bitmap(
+        &mut self,
+        bitmap: BitmapHandle,
+        transform: Transform,
+        smoothing: bool,
+        pixel_snapping: PixelSnapping,
+    ) {
+        let texture = as_bitmap(&bitmap);
+        let (width, height) = (texture.width(), texture.height());
+        let mut matrix = transform.matrix;
+        pixel_snapping.apply(&mut matrix);
+        let Some(inverse) = Self::pixel_inverse(&matrix) else {
+            return;
+        };
+        let quad = matrix * Matrix::scale(width as f32, height as f32);
+        let pixels = texture.pixels();
+        let smoothed = smoothing && self.quality != StageQuality::Low;
+        let color_transform = transform.color_transform;
+        self.render_quad(&quad, |x, y| {
+            let (u, v) = apply_inverse(&inverse, x, y);
+            let texel = sample(texture, &pixels, u, v, smoothed, false);
+            apply_color_transform(texel, &color_transform)
+        });
+    }
+
+    fn render_stage3d(&mut self, bitmap: BitmapHandle, transform: Transform) {
+        self.render_bitmap(bitmap, transform, false, PixelSnapping::Never);
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: Transform) {
+        for draw in &as_shape_data(&shape).draws {
+            self.render_draw(draw, &transform);
+        }
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: Matrix) {
+        let color = premultiply(color);
+        self.render_quad(&matrix, |_, _| color);
+    }
+
+    fn push_mask(&mut self) {
+        let size = self.target.width() as usize * self.target.height() as usize;
+        self.masks.push(vec![0.0; size]);
+        self.mask_state = MaskState::DrawMask;
+    }
+
+    fn activate_mask(&mut self) {
+        // Content is clipped by every active mask, so bake the parent mask into this one.
+        if let [.., parent, mask] = &mut self.masks[..] {
+            for (value, parent) in mask.iter_mut().zip(parent.iter()) {
+                *value *= parent;
+            }
+        }
+        self.mask_state = MaskState::DrawContent;
+    }
+
+    fn deactivate_mask(&mut self) {
+        self.mask_state = MaskState::ClearMask;
+    }
+
+    fn pop_mask(&mut self) {
+        self.masks.pop();
+        self.mask_state = MaskState::DrawContent;
+    }
+
+    fn blend(&mut self, commands: CommandList, blend_mode: RenderBlendMode) {
+        let blend_mode = match blend_mode {
+            RenderBlendMode::Builtin(BlendMode::Normal) => {
+                commands.execute(self);
+                return;
+            }
+            RenderBlendMode::Builtin(mode) => mode,
+            RenderBlendMode::Shader(_) => {
+                tracing::warn!("Shader blend modes are unsupported by the software renderer");
+                BlendMode::Normal
+            }
+        };
+
+        if self.mask_state != MaskState::DrawContent {
+            // Masks only care about geometry, so there's no need for an intermediate layer.
+            commands.execute(self);
+            return;
+        }
+
+        let mut layer = Surface::new(self.target.width(), self.target.height());
+        commands.execute(&mut CommandRenderer::new(&mut layer, self.quality));
+
+        // A transparent source pixel leaves the destination untouched, except under the
+        // Alpha and Erase modes, which scale the destination by the source's alpha.
+        // Pixels outside of the active mask are always left untouched, and those partially
+        // covered by it are interpolated between the destination and the blended result.
+        let skip_transparent = !matches!(blend_mode, BlendMode::Alpha | BlendMode::Erase);
+        let width = self.target.width();
+        let mask = self.masks.last();
+        for y in 0..self.target.height() {
+            for x in 0..width {
+                let coverage = mask.map_or(1.0, |mask| mask[(y * width + x) as usize]);
+                if coverage <= 0.0 {
+                    continue;
+                }
+                let src = layer.get(x, y);
+                if skip_transparent && src[3] <= 0.0 {
+                    continue;
+                }
+                let dst = self.target.get(x, y);
+                let mut result = blend(blend_mode, src, dst);
+                if coverage < 1.0 {
+                    result = lerp(dst, result, coverage);
+                }
+                self.target.set(x, y, result);
+            }
+        }
+    }
+}
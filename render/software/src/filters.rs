@@ -0,0 +1,237 @@
+use crate::surface::{premultiply, unmultiply, Rgba, Surface, TRANSPARENT};
+use ruffle_render::filters::Filter;
+use swf::{BlurFilter, ColorMatrixFilter, GlowFilter};
+
+/// A floating point copy of a surface, used while filtering to avoid
+/// repeatedly converting between `u8` and `f32`.
+struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<Rgba>,
+}
+
+impl Image {
+    fn from_surface(surface: &Surface) -> Self {
+        let mut pixels = Vec::with_capacity(surface.width() as usize * surface.height() as usize);
+        for y in 0..surface.height() {
+            for x in 0..surface.width() {
+                pixels.push(surface.get(x, y));
+            }
+        }
+        Self {
+            width: surface.width(),
+            height: surface.height(),
+            pixels,
+        }
+    }
+
+    fn to_surface(&self) -> Surface {
+        let mut surface = Surface::new(self.width, self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                surface.set(x, y, self.pixels[(y * self.width + x) as usize]);
+            }
+        }
+        surface
+    }
+
+    #[inline]
+    fn get(&self, x: i32, y: i32) -> Rgba {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            TRANSPARENT
+        } else {
+            self.pixels[(y as u32 * self.width + x as u32) as usize]
+        }
+    }
+}
+
+pub fn is_supported(filter: &Filter) -> bool {
+    matches!(
+        filter,
+        Filter::BlurFilter(_)
+            | Filter::ColorMatrixFilter(_)
+            | Filter::GlowFilter(_)
+            | Filter::DropShadowFilter(_)
+    )
+}
+
+/// Applies `filter` to `source`, returning an output surface of the same size.
+/// Returns `None` if the filter isn't supported by this backend.
+pub fn apply(source: &Surface, filter: &Filter) -> Option<Surface> {
+    let image = Image::from_surface(source);
+    let output = match filter {
+        Filter::BlurFilter(filter) => blur(image, filter),
+        Filter::ColorMatrixFilter(filter) => color_matrix(image, filter),
+        Filter::GlowFilter(filter) => glow(image, filter, (0, 0)),
+        Filter::DropShadowFilter(filter) => {
+            let distance = filter.distance.to_f32();
+            let angle = filter.angle.to_f32();
+            let offset = (
+                (angle.cos() * distance).round() as i32,
+                (angle.sin() * distance).round() as i32,
+            );
+            glow(image, &filter.inner_glow_filter(), offset)
+        }
+        _ => return None,
+    };
+    Some(output.to_surface())
+}
+
+fn blur(mut image: Image, filter: &BlurFilter) -> Image {
+    for _ in 0..filter.num_passes() {
+        image = blur_pass(image, filter.blur_x.to_f32(), true);
+        image = blur_pass(image, filter.blur_y.to_f32(), false);
+    }
+    image
+}
+
+/// A single fractional box blur pass, matching the kernel used by the wgpu backend.
+/// See https://fgiesen.wordpress.com/2012/08/01/fast-blurs-2/
+fn blur_pass(image: Image, strength: f32, horizontal: bool) -> Image {
+    // Full width of the kernel (left edge to right edge)
+    let full_size = strength.min(255.0);
+    if full_size <= 1.0 {
+        return image;
+    }
+    let radius = (full_size - 1.0) / 2.0;
+    let m = radius.ceil() - 1.0;
+    // The weight of the outermost pixels, rounded to imitate Flash's fixed-point maths.
+    let edge_weight = ((radius - m) * 255.0).floor() / 255.0;
+    let m = m as i32;
+
+    let mut pixels = Vec::with_capacity(image.pixels.len());
+    for y in 0..image.height as i32 {
+        for x in 0..image.width as i32 {
+            let sample = |offset: i32| {
+                if horizontal {
+                    image.get(x + offset, y)
+                } else {
+                    image.get(x, y + offset)
+                }
+            };
+            let mut total = [0.0; 4];
+            let mut add = |color: Rgba, weight: f32| {
+                for (total, c) in total.iter_mut().zip(color) {
+                    *total += c * weight;
+                }
+            };
+            add(sample(-m - 1), edge_weight);
+            for offset in -m..=m {
+                add(sample(offset), 1.0);
+            }
+            add(sample(m + 1), edge_weight);
+            pixels.push(total.map(|c| (c / full_size * 255.0).floor() / 255.0));
+        }
+    }
+    Image { pixels, ..image }
+}
+
+fn color_matrix(mut image: Image, filter: &ColorMatrixFilter) -> Image {
+    let m = &filter.matrix;
+    for pixel in &mut image.pixels {
+        let [r, g, b, a] = unmultiply(*pixel);
+        let row = |i: usize| {
+            (m[i] * r + m[i + 1] * g + m[i + 2] * b + m[i + 3] * a + m[i + 4] / 255.0)
+                .clamp(0.0, 1.0)
+        };
+        let alpha = row(15);
+        *pixel = [row(0) * alpha, row(5) * alpha, row(10) * alpha, alpha];
+    }
+    image
+}
+
+fn glow(image: Image, filter: &GlowFilter, offset: (i32, i32)) -> Image {
+    let blurred = blur(
+        Image {
+            width: image.width,
+            height: image.height,
+            pixels: image.pixels.clone(),
+        },
+        &filter.inner_blur_filter(),
+    );
+    let color = premultiply(swf::Color {
+        a: 255,
+        ..filter.color
+    });
+    let color_alpha = f32::from(filter.color.a) / 255.0;
+    let strength = filter.strength.to_f32();
+    let inner = filter.is_inner();
+    let knockout = filter.is_knockout();
+    let composite_source = filter.composite_source();
+
+    let mut pixels = Vec::with_capacity(image.pixels.len());
+    for y in 0..image.height as i32 {
+        for x in 0..image.width as i32 {
+            let dest = image.get(x, y);
+            let blur = blurred.get(x - offset.0, y - offset.1)[3];
+            let out = if inner {
+                let alpha = color_alpha * ((1.0 - blur) * strength).clamp(0.0, 1.0);
+                let glow = color.map(|c| c * alpha * dest[3]);
+                if composite_source && !knockout {
+                    let mut out = glow;
+                    for (out, d) in out.iter_mut().zip(dest) {
+                        *out += d * (1.0 - alpha);
+                    }
+                    out
+                } else {
+                    glow
+                }
+            } else {
+                let alpha = color_alpha * (blur * strength).clamp(0.0, 1.0);
+                if knockout {
+                    color.map(|c| c * alpha * (1.0 - dest[3]))
+                } else if composite_source {
+                    let mut out = color.map(|c| c * alpha * (1.0 - dest[3]));
+                    for (out, d) in out.iter_mut().zip(dest) {
+                        *out += d;
+                    }
+                    out
+                } else {
+                    color.map(|c| c * alpha)
+                }
+            };
+            pixels.push(out);
+        }
+    }
+    Image { pixels, ..image }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::{BlurFilterFlags, Fixed16};
+
+    #[test]
+    fn blur_spreads_and_preserves_energy() {
+        let mut surface = Surface::new(9, 1);
+        surface.set(4, 0, [1.0, 1.0, 1.0, 1.0]);
+        let filter = Filter::BlurFilter(BlurFilter {
+            blur_x: Fixed16::from_f32(3.0),
+            blur_y: Fixed16::ONE,
+            flags: BlurFilterFlags::from_passes(1),
+        });
+        let output = apply(&surface, &filter).expect("blur is supported");
+        assert!(output.get(3, 0)[3] > 0.0);
+        assert!(output.get(5, 0)[3] > 0.0);
+        assert_eq!(output.get(0, 0)[3], 0.0);
+        let total: f32 = (0..9).map(|x| output.get(x, 0)[3]).sum();
+        assert!((total - 1.0).abs() < 0.02, "total alpha was {total}");
+    }
+
+    #[test]
+    fn color_matrix_swaps_channels() {
+        let mut surface = Surface::new(1, 1);
+        surface.set(0, 0, [1.0, 0.0, 0.0, 1.0]);
+        let mut matrix = [0.0; 20];
+        matrix[1] = 1.0; // red <- green
+        matrix[5] = 1.0; // green <- red
+        matrix[12] = 1.0;
+        matrix[18] = 1.0;
+        let output = apply(
+            &surface,
+            &Filter::ColorMatrixFilter(ColorMatrixFilter { matrix }),
+        )
+        .expect("color matrix is supported");
+        assert_eq!(output.data(), &[0, 255, 0, 255]);
+    }
+}
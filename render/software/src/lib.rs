@@ -0,0 +1,17 @@
+//! A pure-Rust CPU implementation of Ruffle's `RenderBackend`.
+//!
+//! Shapes are tessellated with the shared `ruffle_render` tessellator and the
+//! resulting triangles are rasterized with multi-sample anti-aliasing.
+
+#![deny(clippy::unwrap_used)]
+#![allow(clippy::arc_with_non_send_sync)]
+
+pub mod backend;
+mod blend;
+mod commands;
+mod filters;
+mod mesh;
+mod raster;
+mod surface;
+
+pub use backend::SoftwareRenderBackend;
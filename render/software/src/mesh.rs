@@ -0,0 +1,263 @@
+use crate::raster::Triangle;
+use crate::surface::{premultiply, Rgba};
+use ruffle_render::backend::{RenderBackend, ShapeHandle, ShapeHandleImpl};
+use ruffle_render::bitmap::{BitmapHandle, BitmapSource};
+use ruffle_render::shape_utils::GradientType;
+use ruffle_render::tessellator::{Gradient as TessGradient, Mesh, Vertex};
+use swf::{Color, ColorTransform, GradientInterpolation, GradientSpread};
+
+const GRADIENT_SIZE: usize = 256;
+
+/// A tessellated shape, ready to be rasterized.
+#[derive(Debug)]
+pub struct ShapeData {
+    pub draws: Vec<Draw>,
+}
+
+impl ShapeHandleImpl for ShapeData {}
+
+pub fn as_shape_data(handle: &ShapeHandle) -> &ShapeData {
+    <dyn ShapeHandleImpl>::downcast_ref(&*handle.0)
+        .expect("Shape handle must be a software ShapeData")
+}
+
+#[derive(Debug)]
+pub struct Draw {
+    pub paint: Paint,
+    pub triangles: Vec<Triangle>,
+    /// Groups of consecutive triangles sharing the same vertex color.
+    /// Each run is rasterized as a whole, so overlapping triangles within a path don't double up.
+    pub runs: Vec<Run>,
+    /// The number of leading triangles to use when this shape is drawn as a mask.
+    pub mask_triangle_count: usize,
+}
+
+#[derive(Debug)]
+pub struct Run {
+    pub color: Color,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug)]
+pub enum Paint {
+    Color,
+    Gradient {
+        /// Maps shape coordinates to gradient space.
+        matrix: [[f32; 3]; 3],
+        gradient: GradientRamp,
+    },
+    Bitmap {
+        /// Maps shape coordinates to normalized texture coordinates.
+        matrix: [[f32; 3]; 3],
+        handle: BitmapHandle,
+        is_smoothed: bool,
+        is_repeating: bool,
+    },
+}
+
+/// Applies a matrix in the column-major layout produced by the tessellator.
+#[inline]
+pub fn apply_matrix(matrix: &[[f32; 3]; 3], (x, y): (f32, f32)) -> (f32, f32) {
+    (
+        matrix[0][0] * x + matrix[1][0] * y + matrix[2][0],
+        matrix[0][1] * x + matrix[1][1] * y + matrix[2][1],
+    )
+}
+
+impl ShapeData {
+    pub fn from_mesh(
+        mesh: Mesh,
+        bitmap_source: &dyn BitmapSource,
+        backend: &mut dyn RenderBackend,
+    ) -> Self {
+        let mut draws = Vec::with_capacity(mesh.draws.len());
+        for draw in mesh.draws {
+            let paint = match draw.draw_type {
+                ruffle_render::tessellator::DrawType::Color => Paint::Color,
+                ruffle_render::tessellator::DrawType::Gradient { matrix, gradient } => {
+                    Paint::Gradient {
+                        matrix,
+                        gradient: GradientRamp::new(&mesh.gradients[gradient]),
+                    }
+                }
+                ruffle_render::tessellator::DrawType::Bitmap(bitmap) => {
+                    let Some(handle) = bitmap_source.bitmap_handle(bitmap.bitmap_id, backend)
+                    else {
+                        tracing::warn!("Missing bitmap {} in shape fill", bitmap.bitmap_id);
+                        continue;
+                    };
+                    Paint::Bitmap {
+                        matrix: bitmap.matrix,
+                        handle,
+                        is_smoothed: bitmap.is_smoothed,
+                        is_repeating: bitmap.is_repeating,
+                    }
+                }
+            };
+
+            let triangles: Vec<Triangle> = draw
+                .indices
+                .chunks_exact(3)
+                .map(|i| {
+                    let point = |index: u32| {
+                        let vertex: &Vertex = &draw.vertices[index as usize];
+                        (vertex.x, vertex.y)
+                    };
+                    [point(i[0]), point(i[1]), point(i[2])]
+                })
+                .collect();
+
+            let mut runs: Vec<Run> = Vec::new();
+            for (index, indices) in draw.indices.chunks_exact(3).enumerate() {
+                let color = draw.vertices[indices[0] as usize].color;
+                match runs.last_mut() {
+                    Some(run) if run.color == color => run.end = index + 1,
+                    _ => runs.push(Run {
+                        color,
+                        start: index,
+                        end: index + 1,
+                    }),
+                }
+            }
+
+            draws.push(Draw {
+                paint,
+                mask_triangle_count: (draw.mask_index_count / 3) as usize,
+                triangles,
+                runs,
+            });
+        }
+        Self { draws }
+    }
+}
+
+/// A gradient baked into a lookup table.
+#[derive(Debug)]
+pub struct GradientRamp {
+    /// Straight-alpha colors for `t` in `[0, 1]`.
+    colors: Vec<Color>,
+    gradient_type: GradientType,
+    spread: GradientSpread,
+    focal_point: f32,
+}
+
+impl GradientRamp {
+    fn new(gradient: &TessGradient) -> Self {
+        let mut colors = vec![Color::TRANSPARENT; GRADIENT_SIZE];
+        let records = &gradient.records;
+        if !records.is_empty() {
+            let linear = gradient.interpolation == GradientInterpolation::LinearRgb;
+            let to_space = |c: u8| {
+                let c = f32::from(c) / 255.0;
+                if linear {
+                    srgb_to_linear(c)
+                } else {
+                    c
+                }
+            };
+            let from_space = |c: f32| {
+                let c = if linear { linear_to_srgb(c) } else { c };
+                (c.clamp(0.0, 1.0) * 255.0 + 0.5) as u8
+            };
+
+            for (t, color) in colors.iter_mut().enumerate() {
+                let next = records
+                    .iter()
+                    .position(|r| usize::from(r.ratio) >= t)
+                    .unwrap_or(records.len() - 1);
+                let last = next.saturating_sub(1);
+                let (a, b) = (&records[last], &records[next]);
+                let amount = if a.ratio == b.ratio || usize::from(b.ratio) < t {
+                    1.0
+                } else {
+                    (t as f32 - f32::from(a.ratio)) / (f32::from(b.ratio) - f32::from(a.ratio))
+                };
+                let amount = amount.clamp(0.0, 1.0);
+                let lerp = |x: u8, y: u8| {
+                    let x = to_space(x);
+                    x + (to_space(y) - x) * amount
+                };
+                *color = Color {
+                    r: from_space(lerp(a.color.r, b.color.r)),
+                    g: from_space(lerp(a.color.g, b.color.g)),
+                    b: from_space(lerp(a.color.b, b.color.b)),
+                    a: (f32::from(a.color.a)
+                        + (f32::from(b.color.a) - f32::from(a.color.a)) * amount
+                        + 0.5) as u8,
+                };
+            }
+        }
+
+        Self {
+            colors,
+            gradient_type: gradient.gradient_type,
+            spread: gradient.repeat_mode,
+            focal_point: gradient.focal_point.to_f32().clamp(-0.98, 0.98),
+        }
+    }
+
+    /// Bakes a color transform into a premultiplied copy of this ramp.
+    pub fn transformed(&self, color_transform: &ColorTransform) -> Vec<Rgba> {
+        self.colors
+            .iter()
+            .map(|color| premultiply(color_transform * *color))
+            .collect()
+    }
+
+    /// Finds the position along the gradient for the given gradient-space coordinate.
+    pub fn position(&self, (u, v): (f32, f32)) -> usize {
+        let t = match self.gradient_type {
+            GradientType::Linear => u,
+            GradientType::Radial => {
+                let (x, y) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+                (x * x + y * y).sqrt()
+            }
+            GradientType::Focal => {
+                let focal = self.focal_point;
+                let (x, y) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+                let (dx, dy) = (focal - x, -y);
+                let length = (dx * dx + dy * dy).sqrt();
+                if length == 0.0 {
+                    0.0
+                } else {
+                    let (dx, dy) = (dx / length, dy / length);
+                    length / ((1.0 - focal * focal * dy * dy).sqrt() + focal * dx)
+                }
+            }
+        };
+
+        let t = match self.spread {
+            GradientSpread::Pad => t.clamp(0.0, 1.0),
+            GradientSpread::Reflect => {
+                let t = t.abs();
+                if (t as i32) & 1 == 0 {
+                    t.fract()
+                } else {
+                    1.0 - t.fract()
+                }
+            }
+            GradientSpread::Repeat => t - t.floor(),
+        };
+        if t.is_nan() {
+            return 0;
+        }
+        ((t * (GRADIENT_SIZE - 1) as f32).round() as usize).min(GRADIENT_SIZE - 1)
+    }
+}
+
+fn srgb_to_linear(color: f32) -> f32 {
+    if color <= 0.04045 {
+        color / 12.92
+    } else {
+        f32::powf((color + 0.055) / 1.055, 2.4)
+    }
+}
+
+fn linear_to_srgb(color: f32) -> f32 {
+    if color <= 0.0031308 {
+        color * 12.92
+    } else {
+        1.055 * color.powf(1.0 / 2.4) - 0.055
+    }
+}
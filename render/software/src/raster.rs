@@ -0,0 +1,241 @@
+//! Triangle rasterization with multi-sample coverage.
+//!
+//! Vertices are snapped to a fixed-point grid so that edge tests are exact, and
+//! a top-left fill rule guarantees that samples on an edge shared by two triangles
+//! are only ever counted once. This avoids seams between the triangles produced by
+//! the tessellator, while keeping the output fully deterministic.
+
+use ruffle_render::quality::StageQuality;
+
+/// Number of fractional bits used when snapping vertices to the sample grid.
+const SUBSAMPLE_BITS: u32 = 8;
+const SUBSAMPLE_ONE: i64 = 1 << SUBSAMPLE_BITS;
+const SUBSAMPLE_HALF: i64 = SUBSAMPLE_ONE / 2;
+
+/// Keeps snapped coordinates small enough that edge functions can't overflow.
+const COORDINATE_LIMIT: f64 = (1i64 << 28) as f64;
+
+pub type Point = (f32, f32);
+pub type Triangle = [Point; 3];
+
+/// How many samples per axis are taken for every pixel at the given quality.
+pub fn samples_per_axis(quality: StageQuality) -> u32 {
+    match quality {
+        StageQuality::Low => 1,
+        StageQuality::Medium => 2,
+        StageQuality::High | StageQuality::Best => 4,
+        // A `u16` sample mask limits us to 4x4 samples.
+        StageQuality::High8x8
+        | StageQuality::High8x8Linear
+        | StageQuality::High16x16
+        | StageQuality::High16x16Linear => 4,
+    }
+}
+
+/// The sampled coverage of a set of triangles, clipped to a target surface.
+pub struct Coverage {
+    samples_per_axis: u32,
+    x_min: u32,
+    y_min: u32,
+    width: u32,
+    height: u32,
+    /// One bit per sample for every pixel in the bounding box.
+    masks: Vec<u16>,
+}
+
+impl Coverage {
+    /// Rasterizes `triangles` (in pixel coordinates) onto a `clip_width`x`clip_height` target.
+    /// Returns `None` if nothing visible is covered.
+    pub fn rasterize(
+        triangles: &[Triangle],
+        samples_per_axis: u32,
+        clip_width: u32,
+        clip_height: u32,
+    ) -> Option<Self> {
+        let mut bounds: Option<(f32, f32, f32, f32)> = None;
+        for point in triangles.iter().flatten() {
+            if !point.0.is_finite() || !point.1.is_finite() {
+                continue;
+            }
+            bounds = Some(match bounds {
+                None => (point.0, point.1, point.0, point.1),
+                Some((x0, y0, x1, y1)) => (
+                    x0.min(point.0),
+                    y0.min(point.1),
+                    x1.max(point.0),
+                    y1.max(point.1),
+                ),
+            });
+        }
+        let (x0, y0, x1, y1) = bounds?;
+        let x_min = x0.floor().clamp(0.0, clip_width as f32) as u32;
+        let y_min = y0.floor().clamp(0.0, clip_height as f32) as u32;
+        let x_max = x1.ceil().clamp(0.0, clip_width as f32) as u32;
+        let y_max = y1.ceil().clamp(0.0, clip_height as f32) as u32;
+        if x_min >= x_max || y_min >= y_max {
+            return None;
+        }
+
+        let width = x_max - x_min;
+        let height = y_max - y_min;
+        let mut coverage = Self {
+            samples_per_axis,
+            x_min,
+            y_min,
+            width,
+            height,
+            masks: vec![0; width as usize * height as usize],
+        };
+        for triangle in triangles {
+            coverage.fill_triangle(triangle);
+        }
+        Some(coverage)
+    }
+
+    fn fill_triangle(&mut self, triangle: &Triangle) {
+        let n = i64::from(self.samples_per_axis);
+        let snap = |(x, y): Point| -> Option<(i64, i64)> {
+            let scale = (n * SUBSAMPLE_ONE) as f64;
+            let x = f64::from(x) * scale;
+            let y = f64::from(y) * scale;
+            if !x.is_finite() || !y.is_finite() {
+                return None;
+            }
+            Some((
+                x.round().clamp(-COORDINATE_LIMIT, COORDINATE_LIMIT) as i64,
+                y.round().clamp(-COORDINATE_LIMIT, COORDINATE_LIMIT) as i64,
+            ))
+        };
+        let (Some(a), Some(mut b), Some(mut c)) =
+            (snap(triangle[0]), snap(triangle[1]), snap(triangle[2]))
+        else {
+            return;
+        };
+
+        let area = edge(a, b, c);
+        if area == 0 {
+            return;
+        }
+        if area < 0 {
+            std::mem::swap(&mut b, &mut c);
+        }
+
+        // Sample `s` lies at `s * ONE + HALF` in snapped coordinates.
+        let first_sample =
+            |min: i64| -> i64 { -((SUBSAMPLE_HALF - min).div_euclid(SUBSAMPLE_ONE)) };
+        let last_sample = |max: i64| -> i64 { (max - SUBSAMPLE_HALF).div_euclid(SUBSAMPLE_ONE) };
+
+        let clip_x0 = i64::from(self.x_min) * n;
+        let clip_y0 = i64::from(self.y_min) * n;
+        let clip_x1 = i64::from(self.x_min + self.width) * n - 1;
+        let clip_y1 = i64::from(self.y_min + self.height) * n - 1;
+
+        let sx0 = first_sample(a.0.min(b.0).min(c.0)).max(clip_x0);
+        let sy0 = first_sample(a.1.min(b.1).min(c.1)).max(clip_y0);
+        let sx1 = last_sample(a.0.max(b.0).max(c.0)).min(clip_x1);
+        let sy1 = last_sample(a.1.max(b.1).max(c.1)).min(clip_y1);
+
+        let bias_ab = is_top_left(a, b);
+        let bias_bc = is_top_left(b, c);
+        let bias_ca = is_top_left(c, a);
+
+        for sy in sy0..=sy1 {
+            let py = sy * SUBSAMPLE_ONE + SUBSAMPLE_HALF;
+            let row = ((sy / n) - i64::from(self.y_min)) as usize * self.width as usize;
+            let bit_row = (sy % n) * n;
+            for sx in sx0..=sx1 {
+                let p = (sx * SUBSAMPLE_ONE + SUBSAMPLE_HALF, py);
+                if inside(edge(a, b, p), bias_ab)
+                    && inside(edge(b, c, p), bias_bc)
+                    && inside(edge(c, a, p), bias_ca)
+                {
+                    let index = row + ((sx / n) - i64::from(self.x_min)) as usize;
+                    self.masks[index] |= 1 << (bit_row + sx % n);
+                }
+            }
+        }
+    }
+
+    /// Calls `f(x, y, coverage)` for every pixel with non-zero coverage.
+    pub fn for_each(&self, mut f: impl FnMut(u32, u32, f32)) {
+        let total = (self.samples_per_axis * self.samples_per_axis) as f32;
+        for y in 0..self.height {
+            let row = y as usize * self.width as usize;
+            for x in 0..self.width {
+                let mask = self.masks[row + x as usize];
+                if mask != 0 {
+                    f(
+                        self.x_min + x,
+                        self.y_min + y,
+                        mask.count_ones() as f32 / total,
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[inline]
+fn edge(a: (i64, i64), b: (i64, i64), p: (i64, i64)) -> i64 {
+    (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)
+}
+
+/// Decides which of the two triangles sharing the edge `a`-`b` owns samples exactly on it.
+#[inline]
+fn is_top_left(a: (i64, i64), b: (i64, i64)) -> bool {
+    let dx = b.0 - a.0;
+    let dy = b.1 - a.1;
+    dy > 0 || (dy == 0 && dx < 0)
+}
+
+#[inline]
+fn inside(value: i64, top_left: bool) -> bool {
+    value > 0 || (value == 0 && top_left)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn total(coverage: &Coverage) -> f32 {
+        let mut sum = 0.0;
+        coverage.for_each(|_, _, c| sum += c);
+        sum
+    }
+
+    #[test]
+    fn shared_edges_are_counted_once() {
+        // Two triangles forming the square (0, 0) - (4, 4), split along the diagonal.
+        let triangles = [
+            [(0.0, 0.0), (4.0, 0.0), (4.0, 4.0)],
+            [(0.0, 0.0), (4.0, 4.0), (0.0, 4.0)],
+        ];
+        for samples in [1, 2, 4] {
+            let coverage =
+                Coverage::rasterize(&triangles, samples, 10, 10).expect("square is visible");
+            let mut pixels = 0;
+            coverage.for_each(|x, y, c| {
+                assert!(x < 4 && y < 4);
+                assert_eq!(c, 1.0);
+                pixels += 1;
+            });
+            assert_eq!(pixels, 16);
+        }
+    }
+
+    #[test]
+    fn partial_coverage() {
+        let triangles = [[(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]];
+        let coverage = Coverage::rasterize(&triangles, 4, 1, 1).expect("triangle is visible");
+        let sum = total(&coverage);
+        assert!(sum > 0.3 && sum < 0.7, "coverage was {sum}");
+    }
+
+    #[test]
+    fn clipped_to_target() {
+        let triangles = [[(-10.0, -10.0), (20.0, -10.0), (-10.0, 20.0)]];
+        let coverage = Coverage::rasterize(&triangles, 1, 2, 2).expect("triangle is visible");
+        assert_eq!(total(&coverage), 4.0);
+        assert!(Coverage::rasterize(&[[(5.0, 5.0), (6.0, 5.0), (5.0, 6.0)]], 1, 2, 2).is_none());
+    }
+}
@@ -0,0 +1,246 @@
+use ruffle_render::bitmap::PixelRegion;
+use swf::Color;
+
+/// A normalized, premultiplied RGBA color.
+pub type Rgba = [f32; 4];
+
+pub const TRANSPARENT: Rgba = [0.0; 4];
+
+/// Converts a straight-alpha `Color` into a normalized premultiplied color.
+#[inline]
+pub fn premultiply(color: Color) -> Rgba {
+    let a = f32::from(color.a) / 255.0;
+    [
+        f32::from(color.r) / 255.0 * a,
+        f32::from(color.g) / 255.0 * a,
+        f32::from(color.b) / 255.0 * a,
+        a,
+    ]
+}
+
+/// Converts a normalized premultiplied color back into straight alpha.
+#[inline]
+pub fn unmultiply(color: Rgba) -> Rgba {
+    if color[3] <= 0.0 {
+        TRANSPARENT
+    } else {
+        [
+            (color[0] / color[3]).min(1.0),
+            (color[1] / color[3]).min(1.0),
+            (color[2] / color[3]).min(1.0),
+            color[3],
+        ]
+    }
+}
+
+#[inline]
+pub fn scale(color: Rgba, amount: f32) -> Rgba {
+    [
+        color[0] * amount,
+        color[1] * amount,
+        color[2] * amount,
+        color[3] * amount,
+    ]
+}
+
+/// Interpolates from `from` towards `to` by `amount`, where 1.0 gives `to`.
+#[inline]
+pub fn lerp(from: Rgba, to: Rgba, amount: f32) -> Rgba {
+    [
+        from[0] + (to[0] - from[0]) * amount,
+        from[1] + (to[1] - from[1]) * amount,
+        from[2] + (to[2] - from[2]) * amount,
+        from[3] + (to[3] - from[3]) * amount,
+    ]
+}
+
+#[inline]
+fn to_unorm(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0 + 0.5) as u8
+}
+
+/// A CPU-side render target, stored as premultiplied RGBA8.
+#[derive(Clone, Debug)]
+pub struct Surface {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl Surface {
+    /// Creates a fully transparent surface.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            data: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    /// Wraps existing premultiplied RGBA8 pixels.
+    /// `data` is padded or truncated to match the given size.
+    pub fn from_data(width: u32, height: u32, mut data: Vec<u8>) -> Self {
+        data.resize(width as usize * height as usize * 4, 0);
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Fills the entire surface with a single (straight-alpha) color.
+    pub fn clear(&mut self, color: Color) {
+        let color = premultiply(color).map(to_unorm);
+        for pixel in self.data.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&color);
+        }
+    }
+
+    #[inline]
+    fn index(&self, x: u32, y: u32) -> usize {
+        (y as usize * self.width as usize + x as usize) * 4
+    }
+
+    #[inline]
+    pub fn get(&self, x: u32, y: u32) -> Rgba {
+        let i = self.index(x, y);
+        let pixel = &self.data[i..i + 4];
+        [
+            f32::from(pixel[0]) / 255.0,
+            f32::from(pixel[1]) / 255.0,
+            f32::from(pixel[2]) / 255.0,
+            f32::from(pixel[3]) / 255.0,
+        ]
+    }
+
+    #[inline]
+    pub fn set(&mut self, x: u32, y: u32, color: Rgba) {
+        let i = self.index(x, y);
+        // Premultiplied colors can never exceed their own alpha.
+        let a = color[3].clamp(0.0, 1.0);
+        self.data[i] = to_unorm(color[0].min(a));
+        self.data[i + 1] = to_unorm(color[1].min(a));
+        self.data[i + 2] = to_unorm(color[2].min(a));
+        self.data[i + 3] = to_unorm(a);
+    }
+
+    /// Composites a premultiplied color over the given pixel using normal "source-over" blending.
+    #[inline]
+    pub fn blend_over(&mut self, x: u32, y: u32, src: Rgba) {
+        if src[3] <= 0.0 {
+            return;
+        }
+        let dst = self.get(x, y);
+        let inv = 1.0 - src[3];
+        self.set(
+            x,
+            y,
+            [
+                src[0] + dst[0] * inv,
+                src[1] + dst[1] * inv,
+                src[2] + dst[2] * inv,
+                src[3] + dst[3] * inv,
+            ],
+        );
+    }
+
+    /// Copies the pixels of `region` (clamped to this surface) into a tightly packed buffer.
+    pub fn copy_region(&self, mut region: PixelRegion) -> Vec<u8> {
+        region.clamp(self.width, self.height);
+        let row_len = region.width() as usize * 4;
+        let mut out = Vec::with_capacity(row_len * region.height() as usize);
+        for y in region.y_min..region.y_max {
+            let start = self.index(region.x_min, y);
+            out.extend_from_slice(&self.data[start..start + row_len]);
+        }
+        out
+    }
+
+    /// Copies the pixels of `region` from `other`, which must be the same size as this surface.
+    pub fn copy_region_from(&mut self, other: &Surface, mut region: PixelRegion) {
+        debug_assert_eq!((self.width, self.height), (other.width, other.height));
+        region.clamp(self.width, self.height);
+        let row_len = region.width() as usize * 4;
+        for y in region.y_min..region.y_max {
+            let start = self.index(region.x_min, y);
+            self.data[start..start + row_len].copy_from_slice(&other.data[start..start + row_len]);
+        }
+    }
+
+    /// Copies all of `source` into this surface, with its top-left corner placed at `point`.
+    /// Pixels falling outside of this surface are discarded.
+    pub fn replace_area(&mut self, source: &Surface, point: (u32, u32)) {
+        let width = source.width.min(self.width.saturating_sub(point.0));
+        let height = source.height.min(self.height.saturating_sub(point.1));
+        let row_len = width as usize * 4;
+        for y in 0..height {
+            let src = source.index(0, y);
+            let dst = self.index(point.0, point.1 + y);
+            self.data[dst..dst + row_len].copy_from_slice(&source.data[src..src + row_len]);
+        }
+    }
+
+    /// Extracts a `width`x`height` area starting at `point` as a new surface.
+    /// Pixels outside of this surface are transparent.
+    pub fn extract_area(&self, point: (u32, u32), width: u32, height: u32) -> Surface {
+        let mut out = Surface::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let sx = point.0 as i64 + x as i64;
+                let sy = point.1 as i64 + y as i64;
+                if sx < self.width as i64 && sy < self.height as i64 {
+                    let src = self.index(sx as u32, sy as u32);
+                    let dst = out.index(x, y);
+                    out.data[dst..dst + 4].copy_from_slice(&self.data[src..src + 4]);
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blend_over_is_source_over() {
+        let mut surface = Surface::new(1, 1);
+        surface.clear(Color::from_rgb(0x0000FF, 255));
+        surface.blend_over(0, 0, premultiply(Color::from_rgb(0xFF0000, 128)));
+        assert_eq!(surface.data(), &[128, 0, 127, 255]);
+    }
+
+    #[test]
+    fn extract_and_replace_round_trip() {
+        let mut surface = Surface::new(4, 4);
+        surface.set(2, 2, [1.0, 1.0, 1.0, 1.0]);
+        let area = surface.extract_area((2, 2), 3, 3);
+        assert_eq!(area.get(0, 0), [1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(area.get(2, 2), TRANSPARENT);
+
+        let mut other = Surface::new(4, 4);
+        other.replace_area(&area, (1, 1));
+        assert_eq!(other.get(1, 1), [1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(other.get(2, 2), TRANSPARENT);
+    }
+}
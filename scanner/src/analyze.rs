@@ -2,8 +2,12 @@
 
 use crate::cli_options::AnalyzeOpt;
 use crate::file_results::{FileResults, Step};
+use std::collections::HashMap;
 use std::fs::File;
 
+/// How many of the most commonly hit stubs to list.
+const TOP_STUBS: usize = 25;
+
 /// Generate and print statistics related to a scan's results
 pub fn analyze(results: impl Iterator<Item = FileResults>) {
     let mut total = 0;
@@ -13,10 +17,15 @@ pub fn analyze(results: impl Iterator<Item = FileResults>) {
     let mut parse = 0;
     let mut execute = 0;
     let mut complete = 0;
+    let mut stubs: HashMap<String, usize> = HashMap::new();
 
     for result in results {
         total += 1;
 
+        for stub in result.stubs {
+            *stubs.entry(stub).or_default() += 1;
+        }
+
         match result.progress {
             Step::Start => start += 1,
             Step::Read => read += 1,
@@ -48,6 +57,19 @@ pub fn analyze(results: impl Iterator<Item = FileResults>) {
     println!("{execute:>digits$} movies failed to execute");
     println!("{complete:>digits$} movies completed without errors");
     println!();
+
+    if !stubs.is_empty() {
+        let mut stubs: Vec<(String, usize)> = stubs.into_iter().collect();
+        stubs.sort_by(|(a_stub, a_count), (b_stub, b_count)| {
+            b_count.cmp(a_count).then_with(|| a_stub.cmp(b_stub))
+        });
+
+        println!("Most commonly hit stubs:");
+        for (stub, count) in stubs.iter().take(TOP_STUBS) {
+            println!("{count:>digits$} movies hit {stub}");
+        }
+        println!();
+    }
 }
 
 pub fn analyze_main(opt: AnalyzeOpt) -> Result<(), std::io::Error> {
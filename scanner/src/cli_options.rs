@@ -20,6 +20,9 @@ pub enum Mode {
 
     /// Execute a single SWF file and generate a machine-readable report
     ExecuteReport(ExecuteReportOpt),
+
+    /// Compare two previously executed scans and report what changed
+    Compare(CompareOpt),
 }
 
 #[derive(Parser, Debug)]
@@ -50,3 +53,22 @@ pub struct ExecuteReportOpt {
     #[clap(name = "file")]
     pub input_path: PathBuf,
 }
+
+#[derive(Parser, Debug)]
+pub struct CompareOpt {
+    /// The CSV file of the older scan
+    #[clap(name = "before")]
+    pub before_path: PathBuf,
+
+    /// The CSV file of the newer scan
+    #[clap(name = "after")]
+    pub after_path: PathBuf,
+
+    /// The file to store the comparison in JSON format
+    #[clap(long = "json")]
+    pub json_path: Option<PathBuf>,
+
+    /// How many times slower or faster a file must run for its timing to be reported
+    #[clap(long = "time-threshold", default_value_t = 2.0)]
+    pub time_threshold: f64,
+}
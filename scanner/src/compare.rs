@@ -0,0 +1,359 @@
+//! Comparison of two scans
+
+use crate::cli_options::CompareOpt;
+use crate::file_results::{into_hex, FileResults, Step};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+/// Timing changes smaller than this many milliseconds are considered noise.
+const MIN_TIME_DIFFERENCE: u128 = 50;
+
+/// How a single file's results changed between two scans.
+#[derive(Serialize, Debug, Clone)]
+pub struct FileChange {
+    /// The file name in the newer scan.
+    pub name: String,
+
+    /// The SHA256 hash of the SWF file.
+    #[serde(serialize_with = "into_hex")]
+    pub hash: Vec<u8>,
+
+    /// How far the older scan got.
+    pub before: Step,
+
+    /// How far the newer scan got.
+    pub after: Step,
+
+    /// How long the older scan took, in milliseconds.
+    pub before_time: u128,
+
+    /// How long the newer scan took, in milliseconds.
+    pub after_time: u128,
+
+    /// Any errors encountered by the newer scan.
+    pub error: Option<String>,
+}
+
+impl FileChange {
+    fn new(before: &FileResults, after: FileResults) -> Self {
+        FileChange {
+            name: after.name,
+            hash: after.hash,
+            before: before.progress,
+            after: after.progress,
+            before_time: before.testing_time,
+            after_time: after.testing_time,
+            error: after.error,
+        }
+    }
+}
+
+/// Everything that changed between two scans.
+#[derive(Serialize, Debug, Default)]
+pub struct Comparison {
+    /// The number of files present in both scans.
+    pub matched: usize,
+
+    /// Files that are only present in the newer scan.
+    pub added: Vec<String>,
+
+    /// Files that are only present in the older scan.
+    pub removed: Vec<String>,
+
+    /// Files that panicked or crashed the scanner, but didn't before.
+    pub panicked: Vec<FileChange>,
+
+    /// Files that got less far than before.
+    pub regressed: Vec<FileChange>,
+
+    /// Files that got further than before.
+    pub improved: Vec<FileChange>,
+
+    /// Files that got as far as before, but took longer.
+    pub slower: Vec<FileChange>,
+
+    /// Files that got as far as before, but finished sooner.
+    pub faster: Vec<FileChange>,
+}
+
+/// Compare the results of two scans.
+///
+/// Files are matched by their SHA256 hash, so renamed or moved files are still
+/// compared. Files that crashed the scanner have no hash, so those are matched
+/// by name instead.
+pub fn compare(
+    before: impl Iterator<Item = FileResults>,
+    after: impl Iterator<Item = FileResults>,
+    time_threshold: f64,
+) -> Comparison {
+    let before: Vec<FileResults> = before.collect();
+    let mut by_hash = HashMap::new();
+    let mut by_name = HashMap::new();
+    let mut unhashed_by_name = HashMap::new();
+    for (index, result) in before.iter().enumerate() {
+        if result.hash.is_empty() {
+            unhashed_by_name.entry(result.name.clone()).or_insert(index);
+        } else {
+            by_hash.entry(result.hash.clone()).or_insert(index);
+        }
+        by_name.entry(result.name.clone()).or_insert(index);
+    }
+
+    let mut before: Vec<Option<FileResults>> = before.into_iter().map(Some).collect();
+    let mut comparison = Comparison::default();
+    for result in after {
+        let index = if result.hash.is_empty() {
+            by_name.get(&result.name)
+        } else {
+            by_hash
+                .get(&result.hash)
+                .or_else(|| unhashed_by_name.get(&result.name))
+        };
+        let matching = index.and_then(|index| before[*index].take());
+        let Some(previous) = matching else {
+            comparison.added.push(result.name);
+            continue;
+        };
+
+        comparison.matched += 1;
+        let newly_panicked = panicked(&result) && !panicked(&previous);
+        let change = FileChange::new(&previous, result);
+        if newly_panicked {
+            comparison.panicked.push(change);
+        } else if change.after < change.before {
+            comparison.regressed.push(change);
+        } else if change.after > change.before {
+            comparison.improved.push(change);
+        } else if is_slower(change.before_time, change.after_time, time_threshold) {
+            comparison.slower.push(change);
+        } else if is_slower(change.after_time, change.before_time, time_threshold) {
+            comparison.faster.push(change);
+        }
+    }
+
+    comparison.removed = before.into_iter().flatten().map(|r| r.name).collect();
+
+    comparison.added.sort();
+    comparison.removed.sort();
+    for changes in [
+        &mut comparison.panicked,
+        &mut comparison.regressed,
+        &mut comparison.improved,
+        &mut comparison.slower,
+        &mut comparison.faster,
+    ] {
+        changes.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    comparison
+}
+
+/// Whether a file panicked while being scanned, or crashed the scanner entirely.
+///
+/// Panics are caught and recorded with a `PANIC` error at the step they happened in.
+/// A crash of the child process, which can't be caught, is recorded by the scanner
+/// with a `CRASH` error, and leaves the file at the step of its last checkpoint.
+fn panicked(result: &FileResults) -> bool {
+    result
+        .error
+        .as_deref()
+        .is_some_and(|error| error.starts_with("PANIC") || error.starts_with("CRASH"))
+}
+
+/// Whether going from `before` to `after` milliseconds is a significant slowdown.
+fn is_slower(before: u128, after: u128, time_threshold: f64) -> bool {
+    after >= before + MIN_TIME_DIFFERENCE && after as f64 >= before as f64 * time_threshold
+}
+
+/// Print a comparison in a human-readable form.
+pub fn print_comparison(comparison: &Comparison) {
+    println!("Compared {} swf files.", comparison.matched);
+    println!();
+
+    let sections = [
+        (
+            "newly panicked or crashed the scanner",
+            &comparison.panicked,
+        ),
+        ("regressed", &comparison.regressed),
+        ("improved", &comparison.improved),
+    ];
+    for (description, changes) in sections {
+        println!("{} movies {description}", changes.len());
+        for change in changes {
+            print!(
+                "    {}: {:?} -> {:?}",
+                change.name, change.before, change.after
+            );
+            match change.error.as_deref().and_then(|e| e.lines().next()) {
+                Some(error) => println!(" ({error})"),
+                None => println!(),
+            }
+        }
+    }
+
+    let sections = [
+        ("got slower", &comparison.slower),
+        ("got faster", &comparison.faster),
+    ];
+    for (description, changes) in sections {
+        println!("{} movies {description}", changes.len());
+        for change in changes {
+            println!(
+                "    {}: {}ms -> {}ms",
+                change.name, change.before_time, change.after_time
+            );
+        }
+    }
+
+    let sections = [
+        ("only in the newer scan", &comparison.added),
+        ("only in the older scan", &comparison.removed),
+    ];
+    for (description, names) in sections {
+        if !names.is_empty() {
+            println!("{} movies {description}", names.len());
+            for name in names {
+                println!("    {name}");
+            }
+        }
+    }
+    println!();
+}
+
+/// Read the results of a scan, skipping unparsable CSV rows.
+fn read_results(path: &Path) -> Result<Vec<FileResults>, std::io::Error> {
+    let file = File::open(path)?;
+    let reader = csv::Reader::from_reader(file);
+
+    Ok(reader
+        .into_deserialize::<FileResults>()
+        .filter_map(|r| match r {
+            Ok(fr) => Some(fr),
+            Err(e) => {
+                eprintln!("Skipping unparsable row in {}: {e}", path.display());
+                None
+            }
+        })
+        .collect())
+}
+
+pub fn compare_main(opt: CompareOpt) -> Result<(), std::io::Error> {
+    let before = read_results(&opt.before_path)?;
+    let after = read_results(&opt.after_path)?;
+
+    let comparison = compare(before.into_iter(), after.into_iter(), opt.time_threshold);
+    print_comparison(&comparison);
+
+    if let Some(json_path) = opt.json_path {
+        let writer = BufWriter::new(File::create(json_path)?);
+        serde_json::to_writer_pretty(writer, &comparison)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(name: &str, hash: &[u8], progress: Step, error: Option<&str>) -> FileResults {
+        FileResults {
+            hash: hash.to_vec(),
+            progress,
+            testing_time: 100,
+            error: error.map(str::to_string),
+            ..FileResults::new(name)
+        }
+    }
+
+    fn names(changes: &[FileChange]) -> Vec<&str> {
+        changes.iter().map(|change| change.name.as_str()).collect()
+    }
+
+    #[test]
+    fn panics_during_execution() {
+        let before = vec![
+            result("new_panic.swf", b"1", Step::Complete, None),
+            result("old_panic.swf", b"2", Step::Execute, Some("PANIC: old")),
+            result("log_error.swf", b"3", Step::Complete, None),
+            result("fixed_panic.swf", b"4", Step::Execute, Some("PANIC")),
+        ];
+        let after = vec![
+            result("new_panic.swf", b"1", Step::Execute, Some("PANIC: new")),
+            result("old_panic.swf", b"2", Step::Execute, Some("PANIC: old")),
+            result("log_error.swf", b"3", Step::Execute, Some("error")),
+            result("fixed_panic.swf", b"4", Step::Complete, None),
+        ];
+
+        let comparison = compare(before.into_iter(), after.into_iter(), 1.5);
+        assert_eq!(comparison.matched, 4);
+        assert_eq!(names(&comparison.panicked), vec!["new_panic.swf"]);
+        assert_eq!(names(&comparison.regressed), vec!["log_error.swf"]);
+        assert_eq!(names(&comparison.improved), vec!["fixed_panic.swf"]);
+        assert!(comparison.slower.is_empty());
+        assert!(comparison.faster.is_empty());
+    }
+
+    #[test]
+    fn scanner_crashes() {
+        // A crashed child process leaves the results of its last checkpoint behind,
+        // with the scanner's note of how it exited.
+        let crash = "CRASH: signal: 6 (SIGABRT)\n\nthread 'main' has overflowed its stack";
+        let before = vec![
+            result("new_crash.swf", b"1", Step::Complete, None),
+            result("old_crash.swf", b"2", Step::Parse, Some(crash)),
+            result("fixed_crash.swf", b"3", Step::Parse, Some(crash)),
+            result("parse_error.swf", b"4", Step::Complete, None),
+        ];
+        let after = vec![
+            result("new_crash.swf", b"1", Step::Parse, Some(crash)),
+            result("old_crash.swf", b"2", Step::Parse, Some(crash)),
+            result("fixed_crash.swf", b"3", Step::Complete, None),
+            result(
+                "parse_error.swf",
+                b"4",
+                Step::Parse,
+                Some("Parse error: eof"),
+            ),
+        ];
+
+        let comparison = compare(before.into_iter(), after.into_iter(), 1.5);
+        assert_eq!(comparison.matched, 4);
+        assert_eq!(names(&comparison.panicked), vec!["new_crash.swf"]);
+        assert_eq!(names(&comparison.regressed), vec!["parse_error.swf"]);
+        assert_eq!(names(&comparison.improved), vec!["fixed_crash.swf"]);
+    }
+
+    #[test]
+    fn matching_and_timing() {
+        let mut slower = result("slower.swf", b"1", Step::Complete, None);
+        slower.testing_time = 300;
+        let mut noise = result("noise.swf", b"2", Step::Complete, None);
+        noise.testing_time = 60;
+        let before = vec![
+            result("renamed_before.swf", b"3", Step::Complete, None),
+            result("removed.swf", b"4", Step::Complete, None),
+            result("slower.swf", b"1", Step::Complete, None),
+            result("noise.swf", b"2", Step::Complete, None),
+        ];
+        let after = vec![
+            result("renamed_after.swf", b"3", Step::Complete, None),
+            result("added.swf", b"5", Step::Complete, None),
+            slower,
+            noise,
+        ];
+
+        let comparison = compare(before.into_iter(), after.into_iter(), 1.5);
+        assert_eq!(comparison.matched, 3);
+        assert_eq!(comparison.added, vec!["added.swf".to_string()]);
+        assert_eq!(comparison.removed, vec!["removed.swf".to_string()]);
+        assert_eq!(names(&comparison.slower), vec!["slower.swf"]);
+        // A 40ms speedup is within the noise, even though it's past the threshold.
+        assert!(comparison.faster.is_empty());
+        assert!(comparison.panicked.is_empty());
+    }
+}
@@ -10,27 +10,51 @@ use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::PlayerBuilder;
 use sha2::{Digest, Sha256};
 use std::io::{stdout, Write};
-use std::panic::catch_unwind;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::Path;
+use std::sync::PoisonError;
 use std::time::{Duration, Instant};
 
-fn execute_swf(file: &Path) {
-    let base_path = file.parent().unwrap();
-    let executor = NullExecutor::new();
-    let movie = SwfMovie::from_path(file, None).unwrap();
-    let frame_time = 1000.0 / movie.frame_rate().to_f64();
-    let player = PlayerBuilder::new()
-        .with_log(ScanLogBackend::new())
-        .with_navigator(NullNavigatorBackend::with_base_path(base_path, &executor).unwrap())
-        .with_max_execution_duration(Duration::from_secs(300))
-        .with_movie(movie)
-        .build();
-
-    player.lock().unwrap().preload(&mut ExecutionLimit::none());
-
-    player.lock().unwrap().run_frame();
-    player.lock().unwrap().update_timers(frame_time);
-    //executor.poll_all().unwrap();
+/// Runs the movie, returning the stubs that it encountered and whether it finished without panicking.
+///
+/// The player is kept outside of `catch_unwind`, so that the stubs encountered before a panic are
+/// still reported.
+fn execute_swf(file: &Path) -> (Vec<String>, std::thread::Result<()>) {
+    let mut player = None;
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let base_path = file.parent().unwrap();
+        let executor = NullExecutor::new();
+        let movie = SwfMovie::from_path(file, None).unwrap();
+        let frame_time = 1000.0 / movie.frame_rate().to_f64();
+        let player = player.insert(
+            PlayerBuilder::new()
+                .with_log(ScanLogBackend::new())
+                .with_navigator(NullNavigatorBackend::with_base_path(base_path, &executor).unwrap())
+                .with_max_execution_duration(Duration::from_secs(300))
+                .with_movie(movie)
+                .build(),
+        );
+
+        player.lock().unwrap().preload(&mut ExecutionLimit::none());
+
+        player.lock().unwrap().run_frame();
+        player.lock().unwrap().update_timers(frame_time);
+        //executor.poll_all().unwrap();
+    }));
+
+    let mut stubs: Vec<String> = player
+        .map(|player| {
+            // A panic while the player was locked poisons it, but its stubs are still intact.
+            let player = player.lock().unwrap_or_else(PoisonError::into_inner);
+            player
+                .stub_tracker()
+                .iter()
+                .map(|stub| stub.to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    stubs.sort();
+    (stubs, result)
 }
 
 fn checkpoint<W: Write>(
@@ -42,6 +66,8 @@ fn checkpoint<W: Write>(
 
     file_result.testing_time = start.elapsed().as_millis();
     writer.serialize(file_result).unwrap();
+    // Make sure the scanner sees this checkpoint even if the process crashes later.
+    writer.flush()?;
 
     if has_error {
         Err(std::io::Error::new(
@@ -150,17 +176,14 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
     file_result.progress = Step::Execute;
 
     //Run one frame of the movie in Ruffle.
-    if let Err(e) = catch_unwind(|| execute_swf(&file_path)) {
-        match e.downcast::<String>() {
-            Ok(e) => {
-                file_result.error = Some(format!("PANIC: {e}"));
-                checkpoint(&mut file_result, &start, &mut writer)?;
-            }
-            Err(_) => {
-                file_result.error = Some("PANIC".to_string());
-                checkpoint(&mut file_result, &start, &mut writer)?;
-            }
-        }
+    let (stubs, result) = execute_swf(&file_path);
+    file_result.stubs = stubs;
+    // A panic is reported by the final checkpoint, along with anything logged before it.
+    if let Err(e) = result {
+        file_result.error = Some(match e.downcast::<String>() {
+            Ok(e) => format!("PANIC: {e}"),
+            Err(_) => "PANIC".to_string(),
+        });
     }
 
     let errors = LOCAL_LOGGER.with(|log_buffer| {
@@ -169,8 +192,12 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
         log_buffer.borrow_mut().join("\n")
     });
     if !errors.is_empty() {
-        file_result.error = Some(errors);
-    } else {
+        // Keep an earlier panic at the start, so that it's still recognisable as one.
+        file_result.error = Some(match file_result.error.take() {
+            Some(error) => format!("{error}\n{errors}"),
+            None => errors,
+        });
+    } else if file_result.error.is_none() {
         file_result.progress = Step::Complete;
     }
 
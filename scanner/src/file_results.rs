@@ -32,7 +32,9 @@ impl From<swf::Compression> for Compression {
 }
 
 /// A particular step in the scanner process.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+///
+/// Steps are ordered by how far along the scanner got.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Step {
     /// Nothing has been done yet.
     ///
@@ -126,6 +128,15 @@ pub struct FileResults {
     /// The AVM type of the movie.
     #[serde(rename = "AVM Version")]
     pub vm_type: Option<AvmType>,
+
+    /// The unimplemented APIs that were hit while executing the movie.
+    #[serde(
+        rename = "Stubs",
+        serialize_with = "into_lines",
+        deserialize_with = "from_lines",
+        default
+    )]
+    pub stubs: Vec<String>,
 }
 
 impl Default for FileResults {
@@ -153,12 +164,13 @@ impl FileResults {
             use_gpu: None,
             use_network_sandbox: None,
             vm_type: None,
+            stubs: vec![],
         }
     }
 }
 
 /// Formats data as capital hex
-fn into_hex<S>(hash: &[u8], s: S) -> Result<S::Ok, S::Error>
+pub fn into_hex<S>(hash: &[u8], s: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
@@ -207,3 +219,20 @@ where
 
     d.deserialize_str(HexVisitor())
 }
+
+/// Formats a list as newline-separated text
+fn into_lines<S>(lines: &[String], s: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    s.serialize_str(&lines.join("\n"))
+}
+
+/// Parses newline-separated text into a list
+fn from_lines<'de, D>(d: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let text = String::deserialize(d)?;
+    Ok(text.lines().map(|line| line.to_string()).collect())
+}
@@ -1,11 +1,13 @@
 use crate::analyze::analyze_main;
 use crate::cli_options::{Mode, Opt};
+use crate::compare::compare_main;
 use crate::execute::execute_report_main;
 use crate::scan::scan_main;
 use clap::Parser;
 
 mod analyze;
 mod cli_options;
+mod compare;
 mod execute;
 mod file_results;
 mod logging;
@@ -18,6 +20,7 @@ fn main() -> Result<(), std::io::Error> {
     match opt.mode {
         Mode::Scan(scan_opt) => scan_main(scan_opt),
         Mode::Analyze(analyze_opt) => analyze_main(analyze_opt),
+        Mode::Compare(compare_opt) => compare_main(compare_opt),
         Mode::ExecuteReport(exeute_report_opt) => {
             if execute_report_main(exeute_report_opt).is_err() {
                 // Do nothing.
@@ -68,6 +68,7 @@ pub fn scan_file<P: AsRef<OsStr>>(exec_path: P, file: &DirEntry, name: &str) ->
                             use_gpu,
                             use_network_sandbox,
                             vm_type,
+                            stubs,
                         } = child_results;
 
                         file_results.hash = hash;
@@ -85,6 +86,7 @@ pub fn scan_file<P: AsRef<OsStr>>(exec_path: P, file: &DirEntry, name: &str) ->
                         file_results.use_gpu = use_gpu;
                         file_results.use_network_sandbox = use_network_sandbox;
                         file_results.vm_type = vm_type;
+                        file_results.stubs = stubs;
                     }
                     Err(e) => {
                         file_results.error = Some(e.to_string());
@@ -92,6 +94,18 @@ pub fn scan_file<P: AsRef<OsStr>>(exec_path: P, file: &DirEntry, name: &str) ->
                 }
             }
 
+            // The child reports its own errors and panics and always exits successfully,
+            // so anything else means it crashed, e.g. from a stack overflow or an abort.
+            if !output.status.success() {
+                let crash = format!("CRASH: {}", output.status);
+                file_results.error = Some(
+                    file_results
+                        .error
+                        .map(|e| format!("{crash}\n{e}"))
+                        .unwrap_or(crash),
+                );
+            }
+
             if !output.stderr.is_empty() {
                 let panic_error = String::from_utf8_lossy(&output.stderr).into_owned();
                 file_results.error = Some(
@@ -0,0 +1,27 @@
+//! Prints the AVM1 bytecode and ABC files of an SWF file.
+//!
+//! Run `cargo run --example disassembling -- <input>`.
+
+use std::error::Error;
+use swf::Tag;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let path = std::env::args()
+        .nth(1)
+        .ok_or("Usage: disassembling <input>")?;
+    let data = std::fs::read(&path).map_err(|e| format!("Couldn't read {path}: {e}"))?;
+    let swf_buf = swf::decompress_swf(&data[..])?;
+    let swf = swf::parse_swf(&swf_buf)?;
+    let text = swf::avm1::disasm::disassemble_tags(&swf.tags, swf.header.version());
+    print!("{text}");
+    for tag in &swf.tags {
+        let data = match tag {
+            Tag::DoAbc(data) => data,
+            Tag::DoAbc2(do_abc) => do_abc.data,
+            _ => continue,
+        };
+        let abc = swf::avm2::read::Reader::new(data).read()?;
+        print!("{}", swf::avm2::disasm::disassemble(&abc));
+    }
+    Ok(())
+}
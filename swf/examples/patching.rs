@@ -0,0 +1,120 @@
+//! A small tool to patch existing SWF files.
+//!
+//! Run `cargo run --example patching -- <input> <output> [edits...]`, where
+//! the edits are applied in order:
+//!
+//! - `--list`: print the top-level tags after the previous edits
+//! - `--version <VERSION>`
+//! - `--frame-rate <FPS>`
+//! - `--stage-size <WIDTH>x<HEIGHT>`: the stage size in pixels
+//! - `--compression <none|zlib|lzma>`
+//! - `--remove <ID>`: remove a character
+//! - `--remove-tag <INDEX>`: remove the top-level tag at an index
+//! - `--replace <ID>=<FILE>[:<ID>]`: replace a character with one from another SWF
+
+use std::error::Error;
+use swf::{CharacterId, Compression, Fixed8, Rectangle, SwfEditor, Twips};
+
+fn read_swf(path: &str) -> Result<swf::SwfBuf, Box<dyn Error>> {
+    let data = std::fs::read(path).map_err(|e| format!("Couldn't read {path}: {e}"))?;
+    Ok(swf::decompress_swf(&data[..])?)
+}
+
+fn list_tags(editor: &SwfEditor) {
+    for (index, entry) in editor.iter().enumerate() {
+        let name = swf::TagCode::format(entry.code);
+        match editor.character_id(index) {
+            Some(id) => println!("{index}: {name} (id {id}, {} bytes)", entry.length),
+            None => println!("{index}: {name} ({} bytes)", entry.length),
+        }
+    }
+}
+
+/// Copies the tag defining `other_id` in another SWF over the tag defining `id`.
+fn replace_character(
+    editor: &mut SwfEditor,
+    id: CharacterId,
+    path: &str,
+    other_id: CharacterId,
+) -> Result<(), Box<dyn Error>> {
+    let other_buf = read_swf(path)?;
+    let other = SwfEditor::new(&other_buf)?;
+    let index = other
+        .position(other_id)
+        .ok_or_else(|| format!("Character {other_id} is not defined in {path}"))?;
+    let target = editor
+        .position(id)
+        .ok_or_else(|| format!("Character {id} is not defined"))?;
+    editor.replace_raw(id, other.get_raw(index).unwrap_or_default().to_vec())?;
+    editor.set_character_id(target, id)?;
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [input, output, edits @ ..] = &args[..] else {
+        return Err("Usage: patching <input> <output> [edits...]".into());
+    };
+
+    let swf_buf = read_swf(input)?;
+    let mut editor = SwfEditor::new(&swf_buf)?;
+
+    let mut edits = edits.iter();
+    while let Some(edit) = edits.next() {
+        if edit == "--list" {
+            list_tags(&editor);
+            continue;
+        }
+        let value = edits
+            .next()
+            .ok_or_else(|| format!("Missing value for {edit}"))?;
+        match edit.as_str() {
+            "--version" => editor.header.version = value.parse()?,
+            "--frame-rate" => editor.header.frame_rate = Fixed8::from_f32(value.parse()?),
+            "--stage-size" => {
+                let (width, height) = value
+                    .split_once('x')
+                    .ok_or("Stage size must be <WIDTH>x<HEIGHT>")?;
+                editor.header.stage_size = Rectangle {
+                    x_min: Twips::ZERO,
+                    x_max: Twips::from_pixels(width.parse()?),
+                    y_min: Twips::ZERO,
+                    y_max: Twips::from_pixels(height.parse()?),
+                };
+            }
+            "--compression" => {
+                editor.header.compression = match value.as_str() {
+                    "none" => Compression::None,
+                    "zlib" => Compression::Zlib,
+                    "lzma" => Compression::Lzma,
+                    _ => return Err(format!("Unknown compression {value}").into()),
+                }
+            }
+            "--remove" => editor.remove(value.parse()?)?,
+            "--remove-tag" => {
+                let index: usize = value.parse()?;
+                editor
+                    .remove_at(index)
+                    .ok_or_else(|| format!("There is no tag {index}"))?;
+            }
+            "--replace" => {
+                let (id, source) = value
+                    .split_once('=')
+                    .ok_or("Replacement must be <ID>=<FILE>[:<ID>]")?;
+                let id = id.parse()?;
+                let (path, other_id) = match source.rsplit_once(':') {
+                    Some((path, other_id)) if other_id.parse::<CharacterId>().is_ok() => {
+                        (path, other_id.parse()?)
+                    }
+                    _ => (source, id),
+                };
+                replace_character(&mut editor, id, path, other_id)?;
+            }
+            _ => return Err(format!("Unknown edit {edit}").into()),
+        }
+    }
+
+    let file = std::fs::File::create(output)?;
+    editor.write(std::io::BufWriter::new(file))?;
+    Ok(())
+}
@@ -1,4 +1,6 @@
 pub(crate) mod opcode;
+pub mod asm;
+pub mod disasm;
 pub mod read;
 pub mod types;
 pub mod write;
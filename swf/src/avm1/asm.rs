@@ -0,0 +1,772 @@
+//! An assembler for AVM1 bytecode.
+//!
+//! This reads the text format written by `avm1::disasm`. Each line holds a
+//! single action, a label such as `L1:`, or the closing brace of a nested
+//! block. Comments start with `;` and run to the end of the line.
+//!
+//! ```
+//! use swf::avm1::{asm::assemble, disasm::disassemble};
+//!
+//! let text = r#"
+//!     Push "x", 1
+//!     SetVariable
+//! "#;
+//! let bytecode = assemble(text, 10).unwrap();
+//! assert_eq!(assemble(&disassemble(&bytecode, 10), 10).unwrap(), bytecode);
+//! ```
+
+use crate::avm1::{opcode::OpCode, types::*, write::Writer};
+use crate::error::{Error, Result};
+use crate::string::{SwfStr, UTF_8};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::num::NonZeroU8;
+use std::str::FromStr;
+
+/// Assembles a block of AVM1 bytecode from text.
+pub fn assemble(text: &str, version: u8) -> Result<Vec<u8>> {
+    let mut lines = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let tokens = tokenize(line, version).map_err(|message| error(i + 1, message))?;
+        if !tokens.is_empty() {
+            lines.push((i + 1, tokens));
+        }
+    }
+
+    let mut assembler = Assembler {
+        lines: lines.into_iter(),
+        version,
+    };
+    let (data, end) = assembler.block()?;
+    match end {
+        Some((line, _)) => Err(error(line, "Unexpected '}'")),
+        None => Ok(data),
+    }
+}
+
+fn error(line: usize, message: impl Display) -> Error {
+    Error::invalid_data(format!("Line {line}: {message}"))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Str(Vec<u8>),
+    Comma,
+    Pipe,
+    Equals,
+    OpenBrace,
+    CloseBrace,
+}
+
+fn tokenize(line: &str, version: u8) -> std::result::Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = line.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            ';' => break,
+            c if c.is_whitespace() => continue,
+            ',' => Token::Comma,
+            '|' => Token::Pipe,
+            '=' => Token::Equals,
+            '{' => Token::OpenBrace,
+            '}' => Token::CloseBrace,
+            '"' => Token::Str(read_string(&mut chars, version)?),
+            _ => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(i, c)) = chars.peek() {
+                    if c.is_whitespace() || ";,|={}\"".contains(c) {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                Token::Word(line[start..end].to_string())
+            }
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Reads a string literal after its opening quote, encoding it in the
+/// encoding of the SWF.
+fn read_string(
+    chars: &mut impl Iterator<Item = (usize, char)>,
+    version: u8,
+) -> std::result::Result<Vec<u8>, String> {
+    let encoding = SwfStr::encoding_for_version(version);
+    let mut string = Vec::new();
+    loop {
+        let c = match chars.next() {
+            Some((_, '"')) => return Ok(string),
+            Some((_, '\\')) => match chars.next() {
+                Some((_, '"')) => '"',
+                Some((_, '\\')) => '\\',
+                Some((_, 'n')) => '\n',
+                Some((_, 'r')) => '\r',
+                Some((_, 't')) => '\t',
+                Some((_, 'x')) => {
+                    let digits: String = chars.take(2).map(|(_, c)| c).collect();
+                    let byte = u8::from_str_radix(&digits, 16)
+                        .ok()
+                        .filter(|_| digits.len() == 2)
+                        .ok_or_else(|| format!("Invalid escape \\x{digits}"))?;
+                    string.push(byte);
+                    continue;
+                }
+                Some((_, c)) => return Err(format!("Invalid escape \\{c}")),
+                None => return Err("Unterminated string".to_string()),
+            },
+            Some((_, c)) => c,
+            None => return Err("Unterminated string".to_string()),
+        };
+        let mut buffer = [0; 4];
+        let c_str = c.encode_utf8(&mut buffer);
+        if encoding == UTF_8 {
+            string.extend_from_slice(c_str.as_bytes());
+        } else {
+            let (bytes, _, had_errors) = encoding.encode(c_str);
+            if had_errors {
+                return Err(format!("'{c}' can't be encoded in {}", encoding.name()));
+            }
+            string.extend_from_slice(&bytes);
+        }
+    }
+}
+
+/// The arguments of an action on a single line.
+struct Args<'t> {
+    tokens: &'t [Token],
+    line: usize,
+}
+
+impl<'t> Args<'t> {
+    fn error(&self, message: impl Display) -> Error {
+        error(self.line, message)
+    }
+
+    fn peek(&self) -> Option<&'t Token> {
+        self.tokens.first()
+    }
+
+    fn next(&mut self) -> Option<&'t Token> {
+        let (token, rest) = self.tokens.split_first()?;
+        self.tokens = rest;
+        Some(token)
+    }
+
+    /// Consumes the next token if it's equal to `token`.
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: Token, name: &str) -> Result<()> {
+        if self.eat(&token) {
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected {name}")))
+        }
+    }
+
+    /// Consumes the next token if it's the given word.
+    fn eat_word(&mut self, word: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Word(w)) if w == word) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn comma(&mut self) -> Result<()> {
+        self.expect(Token::Comma, "','")
+    }
+
+    fn end(&self) -> Result<()> {
+        match self.peek() {
+            Some(token) => Err(self.error(format!("Unexpected {token:?}"))),
+            None => Ok(()),
+        }
+    }
+
+    fn word(&mut self) -> Result<&'t str> {
+        match self.next() {
+            Some(Token::Word(word)) => Ok(word),
+            _ => Err(self.error("Expected a value")),
+        }
+    }
+
+    fn string(&mut self) -> Result<&'t SwfStr> {
+        match self.next() {
+            Some(Token::Str(string)) => Ok(SwfStr::from_bytes(string)),
+            _ => Err(self.error("Expected a string")),
+        }
+    }
+
+    /// Reads a comma-separated list of strings until the end of the line or a
+    /// `{`.
+    fn strings(&mut self) -> Result<Vec<&'t SwfStr>> {
+        let mut strings = Vec::new();
+        if matches!(self.peek(), None | Some(Token::OpenBrace)) {
+            return Ok(strings);
+        }
+        strings.push(self.string()?);
+        while self.eat(&Token::Comma) {
+            strings.push(self.string()?);
+        }
+        Ok(strings)
+    }
+
+    fn number<T: FromStr>(&mut self) -> Result<T> {
+        let word = self.word()?;
+        word.parse()
+            .map_err(|_| self.error(format!("Invalid number {word}")))
+    }
+
+    fn register(&mut self) -> Result<u8> {
+        let word = self.word()?;
+        word.strip_prefix("r:")
+            .and_then(|register| register.parse().ok())
+            .ok_or_else(|| self.error(format!("Invalid register {word}")))
+    }
+
+    fn value(&mut self) -> Result<Value<'t>> {
+        let word = match self.next() {
+            Some(Token::Str(string)) => return Ok(Value::Str(SwfStr::from_bytes(string))),
+            Some(Token::Word(word)) => word.as_str(),
+            _ => return Err(self.error("Expected a value")),
+        };
+        let invalid = || self.error(format!("Invalid value {word}"));
+        let value = match word {
+            "undefined" => Value::Undefined,
+            "null" => Value::Null,
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ if word.starts_with("r:") => {
+                Value::Register(word[2..].parse().map_err(|_| invalid())?)
+            }
+            _ if word.starts_with("c:") => {
+                let index = word[2..].parse().map_err(|_| invalid())?;
+                // The string of the constant is only there for readability.
+                if self.eat(&Token::Equals) {
+                    self.string()?;
+                }
+                Value::ConstantPool(index)
+            }
+            _ if word.ends_with('f') => {
+                Value::Float(parse_float(&word[..word.len() - 1]).ok_or_else(invalid)?)
+            }
+            _ => match word.parse() {
+                Ok(value) => Value::Int(value),
+                Err(_) => Value::Double(parse_float(word).ok_or_else(invalid)?),
+            },
+        };
+        Ok(value)
+    }
+
+    fn jump_target(&mut self) -> Result<JumpTarget<'t>> {
+        let word = self.word()?;
+        match word.strip_prefix('#') {
+            Some(offset) => offset
+                .parse()
+                .map(JumpTarget::Offset)
+                .map_err(|_| self.error(format!("Invalid offset {word}"))),
+            None => Ok(JumpTarget::Label(word)),
+        }
+    }
+}
+
+fn parse_float<T: FromStr + From<f32>>(value: &str) -> Option<T> {
+    match value {
+        "NaN" => Some(f32::NAN.into()),
+        "Infinity" => Some(f32::INFINITY.into()),
+        "-Infinity" => Some(f32::NEG_INFINITY.into()),
+        // Don't accept Rust's own spellings of the above.
+        _ if value
+            .bytes()
+            .any(|c| c.is_ascii_alphabetic() && c != b'e' && c != b'E') =>
+        {
+            None
+        }
+        _ => value.parse().ok(),
+    }
+}
+
+fn opcode_from_name(name: &str) -> Option<OpCode> {
+    (0..=u8::MAX)
+        .filter_map(OpCode::from_u8)
+        .find(|opcode| format!("{opcode:?}") == name)
+}
+
+enum JumpTarget<'t> {
+    Label(&'t str),
+    Offset(i16),
+}
+
+/// A jump whose offset is filled in once the labels of its block are known.
+struct Fixup {
+    /// The position of the offset in the block.
+    position: usize,
+    label: String,
+    line: usize,
+}
+
+/// The line number and remaining tokens of the `}` that ends a block.
+type BlockEnd = Option<(usize, Vec<Token>)>;
+
+struct Assembler {
+    lines: std::vec::IntoIter<(usize, Vec<Token>)>,
+    version: u8,
+}
+
+impl Assembler {
+    /// Assembles lines until the end of the text or a line starting with `}`.
+    fn block(&mut self) -> Result<(Vec<u8>, BlockEnd)> {
+        let mut output = Vec::new();
+        let mut labels = HashMap::new();
+        let mut fixups = Vec::new();
+        let mut end = None;
+
+        while let Some((line, tokens)) = self.lines.next() {
+            match &tokens[..] {
+                [Token::CloseBrace, rest @ ..] => {
+                    end = Some((line, rest.to_vec()));
+                    break;
+                }
+                [Token::Word(word)] if word.ends_with(':') => {
+                    let label = word[..word.len() - 1].to_string();
+                    if labels.insert(label, output.len()).is_some() {
+                        return Err(error(line, format!("Duplicate label {word}")));
+                    }
+                }
+                [Token::Word(name), args @ ..] => {
+                    let args = Args { tokens: args, line };
+                    self.action(name, args, &mut output, &mut fixups)?;
+                }
+                _ => return Err(error(line, "Expected an action or a label")),
+            }
+        }
+
+        for fixup in fixups {
+            let target = labels
+                .get(&fixup.label)
+                .ok_or_else(|| error(fixup.line, format!("Unknown label {}", fixup.label)))?;
+            let offset = (*target as isize - (fixup.position + 2) as isize)
+                .try_into()
+                .map_err(|_| error(fixup.line, format!("Label {} is too far", fixup.label)))?;
+            output[fixup.position..fixup.position + 2].copy_from_slice(&i16::to_le_bytes(offset));
+        }
+        Ok((output, end))
+    }
+
+    /// Assembles a nested block after a `{`, returning the tokens after the
+    /// closing `}`.
+    fn body(&mut self, line: usize) -> Result<(Vec<u8>, usize, Vec<Token>)> {
+        match self.block()? {
+            (data, Some((line, rest))) if data.len() <= u16::MAX.into() => Ok((data, line, rest)),
+            (_, Some((line, _))) => Err(error(line, "Block is too large")),
+            (_, None) => Err(error(line, "Missing '}'")),
+        }
+    }
+
+    fn action(
+        &mut self,
+        name: &str,
+        mut args: Args,
+        output: &mut Vec<u8>,
+        fixups: &mut Vec<Fixup>,
+    ) -> Result<()> {
+        let line = args.line;
+        if name == "Raw" {
+            while let Some(token) = args.next() {
+                let byte = match token {
+                    Token::Word(byte) if byte.len() == 2 => u8::from_str_radix(byte, 16).ok(),
+                    _ => None,
+                };
+                output.push(byte.ok_or_else(|| error(line, format!("Invalid byte {token:?}")))?);
+            }
+            return Ok(());
+        }
+
+        let opcode =
+            opcode_from_name(name).ok_or_else(|| error(line, format!("Unknown action {name}")))?;
+        if (opcode as u8) < 0x80 {
+            args.end()?;
+            output.push(opcode as u8);
+            return Ok(());
+        }
+
+        match opcode {
+            OpCode::Call => {
+                args.end()?;
+                self.write(output, &Action::Call, line)
+            }
+            OpCode::ConstantPool => {
+                let strings = args.strings()?;
+                args.end()?;
+                self.write(
+                    output,
+                    &Action::ConstantPool(ConstantPool { strings }),
+                    line,
+                )
+            }
+            OpCode::DefineFunction => {
+                let name = args.string()?;
+                let params = if args.eat(&Token::Comma) {
+                    args.strings()?
+                } else {
+                    Vec::new()
+                };
+                let actions = self.single_body(args)?;
+                let action = Action::DefineFunction(DefineFunction {
+                    name,
+                    params,
+                    actions: &actions,
+                });
+                self.write(output, &action, line)
+            }
+            OpCode::DefineFunction2 => {
+                let name = args.string()?;
+                args.comma()?;
+                let register_count = args.number()?;
+                args.comma()?;
+                let mut flags = FunctionFlags::empty();
+                let mut flag = args.word()?;
+                if flag != "0" {
+                    loop {
+                        flags |= FunctionFlags::from_name(flag)
+                            .ok_or_else(|| error(line, format!("Unknown flag {flag}")))?;
+                        if !args.eat(&Token::Pipe) {
+                            break;
+                        }
+                        flag = args.word()?;
+                    }
+                }
+                let mut params = Vec::new();
+                while args.eat(&Token::Comma) {
+                    let name = args.string()?;
+                    let register_index = match args.peek() {
+                        Some(Token::Word(_)) => Some(
+                            NonZeroU8::new(args.register()?)
+                                .ok_or_else(|| error(line, "Invalid register r:0"))?,
+                        ),
+                        _ => None,
+                    };
+                    params.push(FunctionParam {
+                        name,
+                        register_index,
+                    });
+                }
+                let actions = self.single_body(args)?;
+                let action = Action::DefineFunction2(DefineFunction2 {
+                    name,
+                    register_count,
+                    params,
+                    flags,
+                    actions: &actions,
+                });
+                self.write(output, &action, line)
+            }
+            OpCode::GetUrl => {
+                let url = args.string()?;
+                args.comma()?;
+                let target = args.string()?;
+                args.end()?;
+                self.write(output, &Action::GetUrl(GetUrl { url, target }), line)
+            }
+            OpCode::GetUrl2 => {
+                let method = match args.word()? {
+                    "None" => SendVarsMethod::None,
+                    "Get" => SendVarsMethod::Get,
+                    "Post" => SendVarsMethod::Post,
+                    method => return Err(error(line, format!("Unknown method {method}"))),
+                };
+                let mut get_url = GetUrl2::for_get_url(method);
+                while args.eat(&Token::Pipe) {
+                    get_url.0 |= match args.word()? {
+                        "LOAD_TARGET" => GetUrlFlags::LOAD_TARGET,
+                        "LOAD_VARIABLES" => GetUrlFlags::LOAD_VARIABLES,
+                        flag => return Err(error(line, format!("Unknown flag {flag}"))),
+                    };
+                }
+                args.end()?;
+                self.write(output, &Action::GetUrl2(get_url), line)
+            }
+            OpCode::GotoFrame => {
+                let frame = args.number()?;
+                args.end()?;
+                self.write(output, &Action::GotoFrame(GotoFrame { frame }), line)
+            }
+            OpCode::GotoFrame2 => {
+                let set_playing = args.number()?;
+                args.comma()?;
+                let scene_offset = args.number()?;
+                args.end()?;
+                let action = Action::GotoFrame2(GotoFrame2 {
+                    set_playing,
+                    scene_offset,
+                });
+                self.write(output, &action, line)
+            }
+            OpCode::GotoLabel => {
+                let label = args.string()?;
+                args.end()?;
+                self.write(output, &Action::GotoLabel(GotoLabel { label }), line)
+            }
+            OpCode::If | OpCode::Jump => {
+                let offset = match args.jump_target()? {
+                    JumpTarget::Offset(offset) => offset,
+                    JumpTarget::Label(label) => {
+                        fixups.push(Fixup {
+                            // After the opcode and length.
+                            position: output.len() + 3,
+                            label: label.to_string(),
+                            line,
+                        });
+                        0
+                    }
+                };
+                args.end()?;
+                let action = if opcode == OpCode::If {
+                    Action::If(If { offset })
+                } else {
+                    Action::Jump(Jump { offset })
+                };
+                self.write(output, &action, line)
+            }
+            OpCode::Push => {
+                let mut values = vec![args.value()?];
+                while args.eat(&Token::Comma) {
+                    values.push(args.value()?);
+                }
+                args.end()?;
+                self.write(output, &Action::Push(Push { values }), line)
+            }
+            OpCode::SetTarget => {
+                let target = args.string()?;
+                args.end()?;
+                self.write(output, &Action::SetTarget(SetTarget { target }), line)
+            }
+            OpCode::StoreRegister => {
+                let register = args.number()?;
+                args.end()?;
+                self.write(
+                    output,
+                    &Action::StoreRegister(StoreRegister { register }),
+                    line,
+                )
+            }
+            OpCode::Try => {
+                args.expect(Token::OpenBrace, "'{'")?;
+                args.end()?;
+                let (try_body, line, rest) = self.body(line)?;
+                let catch_rest;
+                let mut args = Args {
+                    tokens: &rest,
+                    line,
+                };
+                let mut catch = None;
+                if args.eat_word("catch") {
+                    let catch_var = match args.peek() {
+                        Some(Token::Str(_)) => CatchVar::Var(args.string()?),
+                        _ => CatchVar::Register(args.register()?),
+                    };
+                    args.expect(Token::OpenBrace, "'{'")?;
+                    args.end()?;
+                    let (catch_body, line, rest) = self.body(line)?;
+                    catch = Some((catch_var, catch_body));
+                    catch_rest = rest;
+                    args = Args {
+                        tokens: &catch_rest,
+                        line,
+                    };
+                }
+                let finally = if args.eat_word("finally") {
+                    Some(self.single_body(args)?)
+                } else {
+                    args.end()?;
+                    None
+                };
+                let action = Action::Try(Try {
+                    try_body: &try_body,
+                    catch_body: catch
+                        .as_ref()
+                        .map(|(catch_var, catch_body)| (catch_var.clone(), &catch_body[..])),
+                    finally_body: finally.as_deref(),
+                });
+                self.write(output, &action, line)
+            }
+            OpCode::WaitForFrame => {
+                let frame = args.number()?;
+                args.comma()?;
+                let num_actions_to_skip = args.number()?;
+                args.end()?;
+                let action = Action::WaitForFrame(WaitForFrame {
+                    frame,
+                    num_actions_to_skip,
+                });
+                self.write(output, &action, line)
+            }
+            OpCode::WaitForFrame2 => {
+                let num_actions_to_skip = args.number()?;
+                args.end()?;
+                let action = Action::WaitForFrame2(WaitForFrame2 {
+                    num_actions_to_skip,
+                });
+                self.write(output, &action, line)
+            }
+            OpCode::With => {
+                let actions = self.single_body(args)?;
+                self.write(output, &Action::With(With { actions: &actions }), line)
+            }
+            _ => Err(error(line, format!("Unknown action {name}"))),
+        }
+    }
+
+    /// Assembles the block after an action ending in `{`, which mustn't be
+    /// followed by anything else.
+    fn single_body(&mut self, mut args: Args) -> Result<Vec<u8>> {
+        args.expect(Token::OpenBrace, "'{'")?;
+        args.end()?;
+        let (body, line, rest) = self.body(args.line)?;
+        Args {
+            tokens: &rest,
+            line,
+        }
+        .end()?;
+        Ok(body)
+    }
+
+    fn write(&self, output: &mut Vec<u8>, action: &Action, line: usize) -> Result<()> {
+        let start = output.len();
+        Writer::new(&mut *output, self.version).write_action(action)?;
+        // The opcode and length aren't part of the length.
+        if output.len() - start > usize::from(u16::MAX) + 3 {
+            return Err(error(line, "Action is too large"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::disasm::disassemble;
+    use crate::test_data;
+    use crate::types::Tag;
+
+    fn round_trip(data: &[u8], version: u8) {
+        let text = disassemble(data, version);
+        let assembled = assemble(&text, version)
+            .unwrap_or_else(|e| panic!("Couldn't assemble:\n{text}\nError: {e}"));
+        assert_eq!(assembled, data, "Incorrectly assembled:\n{text}");
+    }
+
+    #[test]
+    fn round_trip_actions() {
+        for (swf_version, _, action_bytes) in test_data::avm1_tests() {
+            round_trip(&action_bytes, swf_version);
+        }
+    }
+
+    fn action_blocks<'a>(tags: &[Tag<'a>], blocks: &mut Vec<&'a [u8]>) {
+        for tag in tags {
+            match tag {
+                Tag::DoAction(data) => blocks.push(data),
+                Tag::DoInitAction { action_data, .. } => blocks.push(action_data),
+                Tag::PlaceObject(place_object) => blocks.extend(
+                    place_object
+                        .clip_actions
+                        .iter()
+                        .flatten()
+                        .map(|clip_action| clip_action.action_data),
+                ),
+                Tag::DefineButton(button) | Tag::DefineButton2(button) => {
+                    blocks.extend(button.actions.iter().map(|action| action.action_data))
+                }
+                Tag::DefineSprite(sprite) => action_blocks(&sprite.tags, blocks),
+                _ => (),
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_swfs() {
+        let mut num_blocks = 0;
+        for entry in std::fs::read_dir("tests/swfs").unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|extension| extension == "swf") {
+                let data = std::fs::read(&path).unwrap();
+                let swf_buf = crate::decompress_swf(&data[..]).unwrap();
+                let swf = crate::parse_swf(&swf_buf).unwrap();
+                let mut blocks = Vec::new();
+                action_blocks(&swf.tags, &mut blocks);
+                for block in blocks {
+                    round_trip(block, swf.header.version());
+                    num_blocks += 1;
+                }
+            }
+        }
+        assert!(num_blocks > 0);
+    }
+
+    #[test]
+    fn assemble_labels() {
+        let text = r#"
+            ConstantPool "i"
+        L1:
+            Push c:0="i", 1
+            If L2 ; Exit the loop.
+            DefineFunction "f", "a" {
+                Jump L3
+            L3:
+            }
+            Jump L1
+        L2:
+        "#;
+        assert_eq!(
+            assemble(text, 10).unwrap(),
+            [
+                0x88, 0x04, 0x00, 0x01, 0x00, b'i', 0x00, // ConstantPool
+                0x96, 0x07, 0x00, 0x08, 0x00, 0x07, 0x01, 0x00, 0x00, 0x00, // Push
+                0x9D, 0x02, 0x00, 0x15, 0x00, // If
+                0x9B, 0x08, 0x00, b'f', 0x00, 0x01, 0x00, b'a', 0x00, 0x05,
+                0x00, // DefineFunction
+                0x99, 0x02, 0x00, 0x00, 0x00, // Jump
+                0x99, 0x02, 0x00, 0xDC, 0xFF, // Jump
+            ]
+        );
+    }
+
+    #[test]
+    fn assemble_strings() {
+        let text = r#"Push "\"é\"\x00\xFF\n""#;
+        assert_eq!(
+            assemble(text, 10).unwrap(),
+            [0x96, 0x09, 0x00, 0x00, b'"', 0xC3, 0xA9, b'"', 0x00, 0xFF, b'\n', 0x00]
+        );
+        assert_eq!(
+            assemble(text, 5).unwrap(),
+            [0x96, 0x08, 0x00, 0x00, b'"', 0xE9, b'"', 0x00, 0xFF, b'\n', 0x00]
+        );
+        assert!(assemble("Push \"あ\"", 5).is_err());
+    }
+
+    #[test]
+    fn assemble_errors() {
+        assert!(assemble("Jump L1", 10).is_err());
+        assert!(assemble("L1:\nL1:", 10).is_err());
+        assert!(assemble("Unknown", 10).is_err());
+        assert!(assemble("Stop 1", 10).is_err());
+        assert!(assemble("With {", 10).is_err());
+        assert!(assemble("}", 10).is_err());
+        assert!(assemble("Push \"x", 10).is_err());
+        assert!(assemble("Raw 9", 10).is_err());
+    }
+}
@@ -0,0 +1,532 @@
+//! A disassembler for AVM1 bytecode.
+//!
+//! The output can be assembled back into the exact same bytecode with
+//! `avm1::asm::assemble`. Each action is printed on its own line, with jump
+//! targets replaced by labels and the strings of the current constant pool
+//! shown next to constant pool indices:
+//!
+//! ```text
+//!     ConstantPool "x", "trace"
+//! L1:
+//!     Push c:0="x", 1
+//!     If L2
+//!     DefineFunction2 "f", 2, PRELOAD_THIS, "a" r:1 {
+//!         Push r:1
+//!         Return
+//!     }
+//!     Jump L1
+//! L2:
+//! ```
+//!
+//! Actions that can't be represented exactly in this form, such as
+//! malformed actions or unknown opcodes, are printed as `Raw` bytes.
+
+use crate::avm1::{opcode::OpCode, read::Reader, types::*, write::Writer};
+use crate::string::{SwfStr, UTF_8};
+use crate::types::Tag;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Disassembles a block of AVM1 bytecode, such as the contents of a `DoAction`
+/// tag.
+pub fn disassemble(data: &[u8], version: u8) -> String {
+    let mut disassembler = Disassembler::new(version);
+    disassembler.block(data, 1);
+    disassembler.output
+}
+
+/// Disassembles all AVM1 bytecode in a list of tags, including the tags
+/// nested in `DefineSprite` tags.
+///
+/// This includes `DoAction`, `DoInitAction`, clip actions and button actions.
+/// Each block of bytecode is preceded by a comment saying where it's from.
+pub fn disassemble_tags(tags: &[Tag<'_>], version: u8) -> String {
+    let mut disassembler = Disassembler::new(version);
+    disassembler.tags(tags, "", &mut 1);
+    disassembler.output
+}
+
+fn format_f64(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "Infinity" } else { "-Infinity" }.to_string()
+    } else {
+        format!("{value:?}")
+    }
+}
+
+fn format_f32(value: f32) -> String {
+    if value.is_finite() {
+        format!("{value:?}f")
+    } else {
+        format!("{}f", format_f64(value.into()))
+    }
+}
+
+/// Formats a string as a quoted string literal.
+///
+/// Characters are printed as-is if they're valid in the encoding of the SWF,
+/// and all other bytes are escaped as `\xNN`.
+fn format_string(string: &[u8], version: u8) -> String {
+    fn push_char(output: &mut String, c: char, bytes: &[u8]) {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if c.is_control() => {
+                for byte in bytes {
+                    let _ = write!(output, "\\x{byte:02X}");
+                }
+            }
+            c => output.push(c),
+        }
+    }
+
+    let mut output = String::from("\"");
+    let encoding = SwfStr::encoding_for_version(version);
+    if encoding == UTF_8 {
+        for chunk in string.utf8_chunks() {
+            let mut bytes = chunk.valid().as_bytes();
+            for c in chunk.valid().chars() {
+                let (char_bytes, rest) = bytes.split_at(c.len_utf8());
+                push_char(&mut output, c, char_bytes);
+                bytes = rest;
+            }
+            for byte in chunk.invalid() {
+                let _ = write!(output, "\\x{byte:02X}");
+            }
+        }
+    } else {
+        // The other encodings used for SWFs before version 6 are single-byte.
+        for byte in string.chunks(1) {
+            let (decoded, _) = encoding.decode_without_bom_handling(byte);
+            match decoded.chars().next() {
+                Some(c) if decoded.len() == c.len_utf8() => push_char(&mut output, c, byte),
+                _ => {
+                    let _ = write!(output, "\\x{:02X}", byte[0]);
+                }
+            }
+        }
+    }
+    output.push('"');
+    output
+}
+
+/// A decoded action and the bytes it was decoded from.
+struct Item<'a> {
+    offset: usize,
+    bytes: &'a [u8],
+
+    /// The action, or `None` if it's printed as raw bytes.
+    action: Option<Action<'a>>,
+}
+
+struct Disassembler<'a> {
+    output: String,
+    version: u8,
+    constant_pool: Vec<&'a SwfStr>,
+    next_label: usize,
+}
+
+impl<'a> Disassembler<'a> {
+    fn new(version: u8) -> Self {
+        Self {
+            output: String::new(),
+            version,
+            constant_pool: Vec::new(),
+            next_label: 1,
+        }
+    }
+
+    fn tags(&mut self, tags: &[Tag<'a>], path: &str, frame: &mut u16) {
+        for tag in tags {
+            match tag {
+                Tag::ShowFrame => *frame += 1,
+                Tag::DoAction(data) => {
+                    self.header(format_args!("{path}DoAction (frame {frame})"));
+                    self.block(data, 1);
+                }
+                Tag::DoInitAction { id, action_data } => {
+                    self.header(format_args!("{path}DoInitAction (character {id})"));
+                    self.block(action_data, 1);
+                }
+                Tag::PlaceObject(place_object) => {
+                    for clip_action in place_object.clip_actions.iter().flatten() {
+                        self.header(format_args!(
+                            "{path}ClipAction (frame {frame}, depth {}, events {:?})",
+                            place_object.depth, clip_action.events
+                        ));
+                        self.block(clip_action.action_data, 1);
+                    }
+                }
+                Tag::DefineButton(button) | Tag::DefineButton2(button) => {
+                    for action in &button.actions {
+                        self.header(format_args!(
+                            "{path}ButtonAction (character {}, conditions {:?})",
+                            button.id, action.conditions
+                        ));
+                        self.block(action.action_data, 1);
+                    }
+                }
+                Tag::DefineSprite(sprite) => {
+                    let path = format!("{path}DefineSprite {} > ", sprite.id);
+                    self.tags(&sprite.tags, &path, &mut 1);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn header(&mut self, header: std::fmt::Arguments) {
+        if !self.output.is_empty() {
+            self.output.push('\n');
+        }
+        let _ = writeln!(self.output, "; {header}");
+        self.constant_pool.clear();
+    }
+
+    fn indent(&mut self, depth: usize) {
+        for _ in 0..depth {
+            self.output.push_str("    ");
+        }
+    }
+
+    /// Decodes the actions of a block, falling back to raw bytes for actions
+    /// that wouldn't be encoded back to the same bytes.
+    fn decode(&self, data: &'a [u8]) -> Vec<Item<'a>> {
+        let mut items = Vec::new();
+        let mut offset = 0;
+        while offset < data.len() {
+            let mut reader = Reader::new(&data[offset..], self.version);
+            let (bytes, action) = match reader.read_action() {
+                Ok(action) => {
+                    let bytes = &data[offset..data.len() - reader.get_ref().len()];
+                    let action = Some(action).filter(|action| self.is_exact(action, bytes));
+                    (bytes, action)
+                }
+                Err(_) => (&data[offset..], None),
+            };
+            items.push(Item {
+                offset,
+                bytes,
+                action,
+            });
+            offset += bytes.len();
+        }
+        items
+    }
+
+    /// Returns whether an action is disassembled into text that assembles
+    /// back to the given bytes.
+    fn is_exact(&self, action: &Action, bytes: &[u8]) -> bool {
+        match action {
+            Action::Unknown(_) => return false,
+            Action::Push(push) => {
+                // Only the default NaN can be written as text.
+                let exact = push.values.iter().all(|value| match *value {
+                    Value::Float(value) => !value.is_nan() || value.to_bits() == f32::NAN.to_bits(),
+                    Value::Double(value) => {
+                        !value.is_nan() || value.to_bits() == f64::NAN.to_bits()
+                    }
+                    _ => true,
+                });
+                if !exact {
+                    return false;
+                }
+            }
+            _ => (),
+        }
+        let mut output = Vec::with_capacity(bytes.len());
+        Writer::new(&mut output, self.version)
+            .write_action(action)
+            .is_ok()
+            && output == bytes
+    }
+
+    fn block(&mut self, data: &'a [u8], depth: usize) {
+        let items = self.decode(data);
+
+        // Find the jump targets that are at the start of an action, or at the
+        // end of the block, and give them labels.
+        let mut labels = BTreeMap::new();
+        for item in &items {
+            if let Some(target) = Self::jump_target(item) {
+                let is_boundary = target == data.len()
+                    || items
+                        .binary_search_by_key(&target, |item| item.offset)
+                        .is_ok();
+                if is_boundary {
+                    labels.insert(target, 0);
+                }
+            }
+        }
+        for label in labels.values_mut() {
+            *label = self.next_label;
+            self.next_label += 1;
+        }
+
+        for item in &items {
+            if let Some(label) = labels.get(&item.offset) {
+                self.indent(depth - 1);
+                let _ = writeln!(self.output, "L{label}:");
+            }
+            self.indent(depth);
+            match &item.action {
+                Some(action) => self.action(item, action, &labels, depth),
+                None => self.raw(item.bytes),
+            }
+            self.output.push('\n');
+        }
+        if let Some(label) = labels.get(&data.len()) {
+            self.indent(depth - 1);
+            let _ = writeln!(self.output, "L{label}:");
+        }
+    }
+
+    /// Returns the offset in the block that a `Jump` or `If` action jumps to.
+    fn jump_target(item: &Item) -> Option<usize> {
+        let offset = match item.action {
+            Some(Action::Jump(Jump { offset })) | Some(Action::If(If { offset })) => offset,
+            _ => return None,
+        };
+        (item.offset + item.bytes.len()).checked_add_signed(offset.into())
+    }
+
+    fn raw(&mut self, bytes: &[u8]) {
+        self.output.push_str("Raw");
+        for byte in bytes {
+            let _ = write!(self.output, " {byte:02X}");
+        }
+    }
+
+    fn string(&mut self, string: &SwfStr) {
+        let string = format_string(string.as_bytes(), self.version);
+        self.output.push_str(&string);
+    }
+
+    fn strings(&mut self, strings: &[&SwfStr]) {
+        for (i, string) in strings.iter().enumerate() {
+            if i > 0 {
+                self.output.push_str(", ");
+            }
+            self.string(string);
+        }
+    }
+
+    fn action(
+        &mut self,
+        item: &Item<'a>,
+        action: &Action<'a>,
+        labels: &BTreeMap<usize, usize>,
+        depth: usize,
+    ) {
+        // The action is encoded exactly, so it starts with its opcode.
+        let _ = write!(self.output, "{}", OpCode::format(item.bytes[0]));
+
+        match action {
+            Action::ConstantPool(constant_pool) => {
+                self.output.push(' ');
+                self.strings(&constant_pool.strings);
+                self.constant_pool = constant_pool.strings.clone();
+            }
+            Action::DefineFunction(function) => {
+                self.output.push(' ');
+                self.string(function.name);
+                for param in &function.params {
+                    self.output.push_str(", ");
+                    self.string(param);
+                }
+                self.body(function.actions, depth);
+            }
+            Action::DefineFunction2(function) => {
+                self.output.push(' ');
+                self.string(function.name);
+                let _ = write!(self.output, ", {}, ", function.register_count);
+                self.flags(function.flags.iter_names().map(|(name, _)| name));
+                for param in &function.params {
+                    self.output.push_str(", ");
+                    self.string(param.name);
+                    if let Some(register) = param.register_index {
+                        let _ = write!(self.output, " r:{register}");
+                    }
+                }
+                self.body(function.actions, depth);
+            }
+            Action::GetUrl(get_url) => {
+                self.output.push(' ');
+                self.strings(&[get_url.url, get_url.target]);
+            }
+            Action::GetUrl2(get_url) => {
+                let _ = write!(self.output, " {:?}", get_url.send_vars_method());
+                if get_url.is_target_sprite() {
+                    self.output.push_str(" | LOAD_TARGET");
+                }
+                if get_url.is_load_vars() {
+                    self.output.push_str(" | LOAD_VARIABLES");
+                }
+            }
+            Action::GotoFrame(goto_frame) => {
+                let _ = write!(self.output, " {}", goto_frame.frame);
+            }
+            Action::GotoFrame2(goto_frame) => {
+                let _ = write!(
+                    self.output,
+                    " {}, {}",
+                    goto_frame.set_playing, goto_frame.scene_offset
+                );
+            }
+            Action::GotoLabel(goto_label) => {
+                self.output.push(' ');
+                self.string(goto_label.label);
+            }
+            Action::If(If { offset }) | Action::Jump(Jump { offset }) => {
+                match Self::jump_target(item).and_then(|target| labels.get(&target)) {
+                    Some(label) => {
+                        let _ = write!(self.output, " L{label}");
+                    }
+                    None => {
+                        let _ = write!(self.output, " #{offset}");
+                    }
+                }
+            }
+            Action::Push(push) => {
+                for (i, value) in push.values.iter().enumerate() {
+                    self.output.push_str(if i > 0 { ", " } else { " " });
+                    self.value(value);
+                }
+            }
+            Action::SetTarget(set_target) => {
+                self.output.push(' ');
+                self.string(set_target.target);
+            }
+            Action::StoreRegister(store_register) => {
+                let _ = write!(self.output, " {}", store_register.register);
+            }
+            Action::Try(try_block) => {
+                self.body(try_block.try_body, depth);
+                if let Some((catch_var, catch_body)) = &try_block.catch_body {
+                    self.output.push_str(" catch ");
+                    match catch_var {
+                        CatchVar::Var(name) => self.string(name),
+                        CatchVar::Register(register) => {
+                            let _ = write!(self.output, "r:{register}");
+                        }
+                    }
+                    self.body(catch_body, depth);
+                }
+                if let Some(finally_body) = try_block.finally_body {
+                    self.output.push_str(" finally");
+                    self.body(finally_body, depth);
+                }
+            }
+            Action::WaitForFrame(wait_for_frame) => {
+                let _ = write!(
+                    self.output,
+                    " {}, {}",
+                    wait_for_frame.frame, wait_for_frame.num_actions_to_skip
+                );
+            }
+            Action::WaitForFrame2(wait_for_frame) => {
+                let _ = write!(self.output, " {}", wait_for_frame.num_actions_to_skip);
+            }
+            Action::With(with) => self.body(with.actions, depth),
+            _ => (),
+        }
+    }
+
+    fn flags<'b>(&mut self, mut names: impl Iterator<Item = &'b str>) {
+        match names.next() {
+            Some(name) => {
+                self.output.push_str(name);
+                for name in names {
+                    let _ = write!(self.output, " | {name}");
+                }
+            }
+            None => self.output.push('0'),
+        }
+    }
+
+    /// Prints a nested block of actions in braces.
+    fn body(&mut self, data: &'a [u8], depth: usize) {
+        self.output.push_str(" {\n");
+        self.block(data, depth + 1);
+        self.indent(depth);
+        self.output.push('}');
+    }
+
+    fn value(&mut self, value: &Value) {
+        match *value {
+            Value::Undefined => self.output.push_str("undefined"),
+            Value::Null => self.output.push_str("null"),
+            Value::Bool(value) => {
+                let _ = write!(self.output, "{value}");
+            }
+            Value::Int(value) => {
+                let _ = write!(self.output, "{value}");
+            }
+            Value::Float(value) => {
+                self.output.push_str(&format_f32(value));
+            }
+            Value::Double(value) => self.output.push_str(&format_f64(value)),
+            Value::Str(string) => self.string(string),
+            Value::Register(register) => {
+                let _ = write!(self.output, "r:{register}");
+            }
+            Value::ConstantPool(index) => {
+                let _ = write!(self.output, "c:{index}");
+                if let Some(string) = self.constant_pool.get(usize::from(index)) {
+                    self.output.push('=');
+                    self.string(string);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disassemble_labels() {
+        let data = [
+            0x88, 0x04, 0x00, 0x01, 0x00, b'i', 0x00, // ConstantPool
+            0x96, 0x07, 0x00, 0x08, 0x00, 0x07, 0x01, 0x00, 0x00, 0x00, // Push
+            0x9D, 0x02, 0x00, 0x15, 0x00, // If
+            0x9B, 0x08, 0x00, b'f', 0x00, 0x01, 0x00, b'a', 0x00, 0x05,
+            0x00, // DefineFunction
+            0x99, 0x02, 0x00, 0x00, 0x00, // Jump
+            0x99, 0x02, 0x00, 0xDC, 0xFF, // Jump
+            0x99, 0x02, 0x00, 0x01, 0x00, // Jump
+            0xFF, 0x01, // Unknown
+        ];
+        assert_eq!(
+            disassemble(&data, 10),
+            r#"    ConstantPool "i"
+L1:
+    Push c:0="i", 1
+    If L2
+    DefineFunction "f", "a" {
+        Jump L3
+    L3:
+    }
+    Jump L1
+L2:
+    Jump #1
+    Raw FF 01
+"#
+        );
+    }
+
+    #[test]
+    fn disassemble_strings() {
+        assert_eq!(
+            format_string(b"a\"\\\n\x01\xC3\xA9\xFF", 10),
+            r#""a\"\\\n\x01é\xFF""#
+        );
+        assert_eq!(format_string(b"\xE9\x81", 5), r#""é\x81""#);
+    }
+}
@@ -1,3 +1,5 @@
+pub mod asm;
+pub mod disasm;
 pub mod read;
 pub mod types;
 pub mod write;
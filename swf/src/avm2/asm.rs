@@ -0,0 +1,1459 @@
+//! An assembler for ABC files.
+//!
+//! This parses the text format written by `avm2::disasm` into an `AbcFile`.
+//! Each line holds a single item, instruction or label, and items with
+//! contents, such as classes and method bodies, end with a `{` and are
+//! followed by their contents and a closing `}`. Comments start with `;` and
+//! run to the end of the line.
+//!
+//! Constant pool entries don't have to be listed. Any value that's not in its
+//! constant pool yet is added to it, which makes it easy to write ABC files by
+//! hand:
+//!
+//! ```
+//! use swf::avm2::asm::assemble;
+//!
+//! let abc = assemble(
+//!     r#"
+//!     method #0
+//!     script #0 init #0
+//!     body #0 method #0 max_stack 2 locals 1 init_scope_depth 0 max_scope_depth 1 {
+//!         getlocal 0
+//!         pushscope
+//!         findpropstrict QName(Package(""), "trace")
+//!         pushstring "Hello"
+//!         callpropvoid QName(Package(""), "trace"), 1
+//!         returnvoid
+//!     }
+//!     "#,
+//! )
+//! .unwrap();
+//! assert_eq!(abc.constant_pool.strings, [b"".to_vec(), b"trace".to_vec(), b"Hello".to_vec()]);
+//! ```
+
+use crate::avm2::{opcode::OpCode, read::Reader, types::*, write::Writer};
+use crate::error::{Error, Result};
+use num_traits::FromPrimitive;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Assembles an ABC file from text.
+pub fn assemble(text: &str) -> Result<AbcFile> {
+    let mut lines = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let tokens = tokenize(line).map_err(|message| error(i + 1, message))?;
+        if !tokens.is_empty() {
+            lines.push((i + 1, tokens));
+        }
+    }
+
+    let mut assembler = Assembler {
+        lines: lines.into_iter(),
+        abc: AbcFile {
+            major_version: 46,
+            minor_version: 16,
+            constant_pool: ConstantPool {
+                ints: Vec::new(),
+                uints: Vec::new(),
+                doubles: Vec::new(),
+                strings: Vec::new(),
+                namespaces: Vec::new(),
+                namespace_sets: Vec::new(),
+                multinames: Vec::new(),
+            },
+            methods: Vec::new(),
+            metadata: Vec::new(),
+            instances: Vec::new(),
+            classes: Vec::new(),
+            scripts: Vec::new(),
+            method_bodies: Vec::new(),
+        },
+    };
+    while let Some((line, tokens)) = assembler.lines.next() {
+        assembler.item(Args {
+            tokens: &tokens,
+            line,
+        })?;
+    }
+    Ok(assembler.abc)
+}
+
+fn error(line: usize, message: impl Display) -> Error {
+    Error::invalid_data(format!("Line {line}: {message}"))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Str(Vec<u8>),
+    Comma,
+    Pipe,
+    OpenParen,
+    CloseParen,
+    OpenBracket,
+    CloseBracket,
+    OpenBrace,
+    CloseBrace,
+}
+
+fn tokenize(line: &str) -> std::result::Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = line.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            ';' => break,
+            c if c.is_whitespace() => continue,
+            ',' => Token::Comma,
+            '|' => Token::Pipe,
+            '(' => Token::OpenParen,
+            ')' => Token::CloseParen,
+            '[' => Token::OpenBracket,
+            ']' => Token::CloseBracket,
+            '{' => Token::OpenBrace,
+            '}' => Token::CloseBrace,
+            '"' => Token::Str(read_string(&mut chars)?),
+            _ => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(i, c)) = chars.peek() {
+                    if c.is_whitespace() || ";,|()[]{}\"".contains(c) {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                Token::Word(line[start..end].to_string())
+            }
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Reads a string literal after its opening quote.
+fn read_string(
+    chars: &mut impl Iterator<Item = (usize, char)>,
+) -> std::result::Result<Vec<u8>, String> {
+    let mut string = Vec::new();
+    loop {
+        let c = match chars.next() {
+            Some((_, '"')) => return Ok(string),
+            Some((_, '\\')) => match chars.next() {
+                Some((_, '"')) => '"',
+                Some((_, '\\')) => '\\',
+                Some((_, 'n')) => '\n',
+                Some((_, 'r')) => '\r',
+                Some((_, 't')) => '\t',
+                Some((_, 'x')) => {
+                    let digits: String = chars.take(2).map(|(_, c)| c).collect();
+                    let byte = u8::from_str_radix(&digits, 16)
+                        .ok()
+                        .filter(|_| digits.len() == 2)
+                        .ok_or_else(|| format!("Invalid escape \\x{digits}"))?;
+                    string.push(byte);
+                    continue;
+                }
+                Some((_, c)) => return Err(format!("Invalid escape \\{c}")),
+                None => return Err("Unterminated string".to_string()),
+            },
+            Some((_, c)) => c,
+            None => return Err("Unterminated string".to_string()),
+        };
+        string.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+    }
+}
+
+fn parse_f64(value: &str) -> Option<f64> {
+    match value {
+        "NaN" => Some(f64::NAN),
+        "Infinity" => Some(f64::INFINITY),
+        "-Infinity" => Some(f64::NEG_INFINITY),
+        // Don't accept Rust's own spellings of the above.
+        _ if value
+            .bytes()
+            .any(|c| c.is_ascii_alphabetic() && c != b'e' && c != b'E') =>
+        {
+            None
+        }
+        _ => value.parse().ok(),
+    }
+}
+
+fn opcode_from_name(name: &str) -> Option<OpCode> {
+    (0..=u8::MAX)
+        .filter_map(OpCode::from_u8)
+        .find(|opcode| format!("{opcode:?}").eq_ignore_ascii_case(name))
+}
+
+/// Returns the index of a value in a constant pool, adding it if it's not
+/// there yet.
+fn intern<T: PartialEq, U>(pool: &mut Vec<T>, value: T) -> Index<U> {
+    let i = match pool.iter().position(|entry| *entry == value) {
+        Some(i) => i,
+        None => {
+            pool.push(value);
+            pool.len() - 1
+        }
+    };
+    Index::new(i as u32 + 1)
+}
+
+/// The tokens of a line.
+struct Args<'t> {
+    tokens: &'t [Token],
+    line: usize,
+}
+
+impl<'t> Args<'t> {
+    fn error(&self, message: impl Display) -> Error {
+        error(self.line, message)
+    }
+
+    fn peek(&self) -> Option<&'t Token> {
+        self.tokens.first()
+    }
+
+    fn next(&mut self) -> Option<&'t Token> {
+        let (token, rest) = self.tokens.split_first()?;
+        self.tokens = rest;
+        Some(token)
+    }
+
+    /// Consumes the next token if it's equal to `token`.
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: Token, name: &str) -> Result<()> {
+        if self.eat(&token) {
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected {name}")))
+        }
+    }
+
+    fn comma(&mut self) -> Result<()> {
+        self.expect(Token::Comma, "','")
+    }
+
+    fn end(&self) -> Result<()> {
+        match self.peek() {
+            Some(token) => Err(self.error(format!("Unexpected {token:?}"))),
+            None => Ok(()),
+        }
+    }
+
+    fn word(&mut self) -> Result<&'t str> {
+        match self.next() {
+            Some(Token::Word(word)) => Ok(word),
+            _ => Err(self.error("Expected a value")),
+        }
+    }
+
+    /// Reads the name of the next attribute, if there is one before the end
+    /// of the line or a `{`.
+    fn key(&mut self) -> Result<Option<&'t str>> {
+        match self.peek() {
+            None | Some(Token::OpenBrace) => Ok(None),
+            _ => self.word().map(Some),
+        }
+    }
+
+    fn number<T: FromStr>(&mut self) -> Result<T> {
+        let word = self.word()?;
+        word.parse()
+            .map_err(|_| self.error(format!("Invalid number {word}")))
+    }
+
+    /// Reads a comma and a number.
+    fn next_number<T: FromStr>(&mut self) -> Result<T> {
+        self.comma()?;
+        self.number()
+    }
+
+    /// Reads an index such as `#3`.
+    fn index<T>(&mut self) -> Result<Index<T>> {
+        let word = self.word()?;
+        word.strip_prefix('#')
+            .and_then(|index| index.parse().ok())
+            .map(Index::new)
+            .ok_or_else(|| self.error(format!("Invalid index {word}")))
+    }
+
+    /// Reads the index of a new item, which must be the next one.
+    fn definition(&mut self, expected: usize) -> Result<()> {
+        let line = self.line;
+        let index: Index<()> = self.index()?;
+        if index.0 as usize != expected {
+            return Err(error(line, format!("Expected #{expected}")));
+        }
+        Ok(())
+    }
+
+    /// Reads `*` for index 0, or an index such as `#3`.
+    fn raw_index<T>(&mut self) -> Option<Index<T>> {
+        match self.peek() {
+            Some(Token::Word(word)) if word == "*" => {
+                self.next();
+                Some(Index::new(0))
+            }
+            Some(Token::Word(word)) if word.starts_with('#') => {
+                let index = word[1..].parse().ok()?;
+                self.next();
+                Some(Index::new(index))
+            }
+            _ => None,
+        }
+    }
+
+    fn flags<T>(&mut self, from_name: impl Fn(&str) -> Option<T>) -> Result<Vec<T>> {
+        let mut flags = Vec::new();
+        loop {
+            let name = self.word()?;
+            flags.push(from_name(name).ok_or_else(|| self.error(format!("Unknown flag {name}")))?);
+            if !self.eat(&Token::Pipe) {
+                return Ok(flags);
+            }
+        }
+    }
+
+    fn target(&mut self, targets: &mut Vec<Option<&'t str>>) -> Result<i32> {
+        let word = self.word()?;
+        match word.strip_prefix('#') {
+            Some(offset) => {
+                targets.push(None);
+                offset
+                    .parse()
+                    .ok()
+                    .filter(|offset| (-0x80_0000..0x80_0000).contains(offset))
+                    .ok_or_else(|| self.error(format!("Invalid offset {word}")))
+            }
+            None => {
+                targets.push(Some(word));
+                Ok(0)
+            }
+        }
+    }
+
+    fn code_offset(&mut self) -> Result<CodeOffset<'t>> {
+        let word = self.word()?;
+        match word.strip_prefix('#') {
+            Some(offset) => offset
+                .parse()
+                .map(CodeOffset::Offset)
+                .map_err(|_| self.error(format!("Invalid offset {word}"))),
+            None => Ok(CodeOffset::Label(word)),
+        }
+    }
+}
+
+enum CodeOffset<'t> {
+    Label(&'t str),
+    Offset(u32),
+}
+
+/// A branch whose offset is filled in once the labels of its body are known.
+struct Fixup {
+    /// The position of the offset in the code.
+    position: usize,
+
+    /// The position that the offset is relative to.
+    base: usize,
+
+    label: String,
+    line: usize,
+}
+
+type Lines = std::vec::IntoIter<(usize, Vec<Token>)>;
+
+struct Assembler {
+    lines: Lines,
+    abc: AbcFile,
+}
+
+impl Assembler {
+    /// Reads the lines between a `{` and a `}`, if the line ends with a `{`.
+    fn contents(&mut self, args: &mut Args) -> Result<Vec<(usize, Vec<Token>)>> {
+        let mut contents = Vec::new();
+        if args.eat(&Token::OpenBrace) {
+            args.end()?;
+            loop {
+                match self.lines.next() {
+                    Some((_, tokens)) if tokens == [Token::CloseBrace] => break,
+                    Some(line) => contents.push(line),
+                    None => return Err(args.error("Missing '}'")),
+                }
+            }
+        }
+        args.end()?;
+        Ok(contents)
+    }
+
+    fn item(&mut self, mut args: Args) -> Result<()> {
+        let pool = &mut self.abc.constant_pool;
+        match args.word()? {
+            "version" => {
+                let version = args.word()?;
+                let (major, minor) = version
+                    .split_once('.')
+                    .and_then(|(major, minor)| Some((major.parse().ok()?, minor.parse().ok()?)))
+                    .ok_or_else(|| args.error(format!("Invalid version {version}")))?;
+                self.abc.major_version = major;
+                self.abc.minor_version = minor;
+                args.end()
+            }
+            "int" => {
+                args.definition(pool.ints.len() + 1)?;
+                let value = args.number()?;
+                args.end()?;
+                pool.ints.push(value);
+                Ok(())
+            }
+            "uint" => {
+                args.definition(pool.uints.len() + 1)?;
+                let value = args.number()?;
+                args.end()?;
+                pool.uints.push(value);
+                Ok(())
+            }
+            "double" => {
+                args.definition(pool.doubles.len() + 1)?;
+                let value = args.word()?;
+                let value = parse_f64(value)
+                    .ok_or_else(|| args.error(format!("Invalid number {value}")))?;
+                args.end()?;
+                pool.doubles.push(value);
+                Ok(())
+            }
+            "string" => {
+                args.definition(pool.strings.len() + 1)?;
+                let value = match args.next() {
+                    Some(Token::Str(value)) => value.clone(),
+                    _ => return Err(args.error("Expected a string")),
+                };
+                args.end()?;
+                pool.strings.push(value);
+                Ok(())
+            }
+            "namespace" => {
+                args.definition(pool.namespaces.len() + 1)?;
+                let namespace = self.namespace(&mut args)?;
+                args.end()?;
+                self.abc.constant_pool.namespaces.push(namespace);
+                Ok(())
+            }
+            "namespace_set" => {
+                args.definition(pool.namespace_sets.len() + 1)?;
+                let namespace_set = self.list(&mut args, Self::namespace_ref)?;
+                args.end()?;
+                self.abc.constant_pool.namespace_sets.push(namespace_set);
+                Ok(())
+            }
+            "multiname" => {
+                args.definition(pool.multinames.len() + 1)?;
+                let multiname = self.multiname(&mut args)?;
+                args.end()?;
+                self.abc.constant_pool.multinames.push(multiname);
+                Ok(())
+            }
+            "method" => self.method(args),
+            "metadata" => self.metadata(args),
+            "class" => self.class(args),
+            "script" => self.script(args),
+            "body" => self.method_body(args),
+            word => Err(args.error(format!("Unknown item {word}"))),
+        }
+    }
+
+    /// Reads a list of values in brackets.
+    fn list<'t, T>(
+        &mut self,
+        args: &mut Args<'t>,
+        mut value: impl FnMut(&mut Self, &mut Args<'t>) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        args.expect(Token::OpenBracket, "'['")?;
+        let mut values = Vec::new();
+        if args.eat(&Token::CloseBracket) {
+            return Ok(values);
+        }
+        loop {
+            values.push(value(self, args)?);
+            if args.eat(&Token::CloseBracket) {
+                return Ok(values);
+            }
+            args.comma()?;
+        }
+    }
+
+    fn int_ref(&mut self, args: &mut Args) -> Result<Index<i32>> {
+        match args.raw_index() {
+            Some(index) => Ok(index),
+            None => Ok(intern(&mut self.abc.constant_pool.ints, args.number()?)),
+        }
+    }
+
+    fn uint_ref(&mut self, args: &mut Args) -> Result<Index<u32>> {
+        match args.raw_index() {
+            Some(index) => Ok(index),
+            None => Ok(intern(&mut self.abc.constant_pool.uints, args.number()?)),
+        }
+    }
+
+    fn double_ref(&mut self, args: &mut Args) -> Result<Index<f64>> {
+        if let Some(index) = args.raw_index() {
+            return Ok(index);
+        }
+        let word = args.word()?;
+        let value = parse_f64(word).ok_or_else(|| args.error(format!("Invalid number {word}")))?;
+        // Compare the bits, so that NaN and -0.0 are found.
+        let doubles = &mut self.abc.constant_pool.doubles;
+        let i = match doubles.iter().position(|n| n.to_bits() == value.to_bits()) {
+            Some(i) => i,
+            None => {
+                doubles.push(value);
+                doubles.len() - 1
+            }
+        };
+        Ok(Index::new(i as u32 + 1))
+    }
+
+    fn string_ref(&mut self, args: &mut Args) -> Result<Index<String>> {
+        if let Some(index) = args.raw_index() {
+            return Ok(index);
+        }
+        match args.next() {
+            Some(Token::Str(string)) => {
+                Ok(intern(&mut self.abc.constant_pool.strings, string.clone()))
+            }
+            _ => Err(args.error("Expected a string")),
+        }
+    }
+
+    fn namespace_ref(&mut self, args: &mut Args) -> Result<Index<Namespace>> {
+        match args.raw_index() {
+            Some(index) => Ok(index),
+            None => {
+                let namespace = self.namespace(args)?;
+                Ok(intern(&mut self.abc.constant_pool.namespaces, namespace))
+            }
+        }
+    }
+
+    fn namespace(&mut self, args: &mut Args) -> Result<Namespace> {
+        let kind = args.word()?;
+        args.expect(Token::OpenParen, "'('")?;
+        let name = self.string_ref(args)?;
+        args.expect(Token::CloseParen, "')'")?;
+        Ok(match kind {
+            "Namespace" => Namespace::Namespace(name),
+            "Package" => Namespace::Package(name),
+            "PackageInternal" => Namespace::PackageInternal(name),
+            "Protected" => Namespace::Protected(name),
+            "Explicit" => Namespace::Explicit(name),
+            "StaticProtected" => Namespace::StaticProtected(name),
+            "Private" => Namespace::Private(name),
+            _ => return Err(args.error(format!("Unknown namespace kind {kind}"))),
+        })
+    }
+
+    fn namespace_set_ref(&mut self, args: &mut Args) -> Result<Index<NamespaceSet>> {
+        match args.raw_index() {
+            Some(index) => Ok(index),
+            None => {
+                let namespace_set = self.list(args, Self::namespace_ref)?;
+                Ok(intern(
+                    &mut self.abc.constant_pool.namespace_sets,
+                    namespace_set,
+                ))
+            }
+        }
+    }
+
+    fn multiname_ref(&mut self, args: &mut Args) -> Result<Index<Multiname>> {
+        match args.raw_index() {
+            Some(index) => Ok(index),
+            None => {
+                let multiname = self.multiname(args)?;
+                Ok(intern(&mut self.abc.constant_pool.multinames, multiname))
+            }
+        }
+    }
+
+    fn multiname(&mut self, args: &mut Args) -> Result<Multiname> {
+        let kind = args.word()?;
+        if kind == "RTQNameL" {
+            return Ok(Multiname::RTQNameL);
+        } else if kind == "RTQNameLA" {
+            return Ok(Multiname::RTQNameLA);
+        }
+
+        args.expect(Token::OpenParen, "'('")?;
+        let multiname = match kind {
+            "QName" | "QNameA" => {
+                let namespace = self.namespace_ref(args)?;
+                args.comma()?;
+                let name = self.string_ref(args)?;
+                if kind == "QName" {
+                    Multiname::QName { namespace, name }
+                } else {
+                    Multiname::QNameA { namespace, name }
+                }
+            }
+            "RTQName" => Multiname::RTQName {
+                name: self.string_ref(args)?,
+            },
+            "RTQNameA" => Multiname::RTQNameA {
+                name: self.string_ref(args)?,
+            },
+            "Multiname" | "MultinameA" => {
+                let namespace_set = self.namespace_set_ref(args)?;
+                args.comma()?;
+                let name = self.string_ref(args)?;
+                if kind == "Multiname" {
+                    Multiname::Multiname {
+                        namespace_set,
+                        name,
+                    }
+                } else {
+                    Multiname::MultinameA {
+                        namespace_set,
+                        name,
+                    }
+                }
+            }
+            "MultinameL" => Multiname::MultinameL {
+                namespace_set: self.namespace_set_ref(args)?,
+            },
+            "MultinameLA" => Multiname::MultinameLA {
+                namespace_set: self.namespace_set_ref(args)?,
+            },
+            "TypeName" => {
+                let base_type = self.multiname_ref(args)?;
+                args.comma()?;
+                let parameters = self.list(args, Self::multiname_ref)?;
+                Multiname::TypeName {
+                    base_type,
+                    parameters,
+                }
+            }
+            _ => return Err(args.error(format!("Unknown multiname kind {kind}"))),
+        };
+        args.expect(Token::CloseParen, "')'")?;
+        Ok(multiname)
+    }
+
+    fn default_value(&mut self, args: &mut Args) -> Result<DefaultValue> {
+        let kind = args.word()?;
+        match kind {
+            "True" => return Ok(DefaultValue::True),
+            "False" => return Ok(DefaultValue::False),
+            "Null" => return Ok(DefaultValue::Null),
+            "Undefined" => return Ok(DefaultValue::Undefined),
+            _ => (),
+        }
+
+        args.expect(Token::OpenParen, "'('")?;
+        let value = match kind {
+            "Int" => DefaultValue::Int(self.int_ref(args)?),
+            "Uint" => DefaultValue::Uint(self.uint_ref(args)?),
+            "Double" => DefaultValue::Double(self.double_ref(args)?),
+            "String" => DefaultValue::String(self.string_ref(args)?),
+            "Namespace" => DefaultValue::Namespace(self.namespace_ref(args)?),
+            "Package" => DefaultValue::Package(self.namespace_ref(args)?),
+            "PackageInternal" => DefaultValue::PackageInternal(self.namespace_ref(args)?),
+            "Protected" => DefaultValue::Protected(self.namespace_ref(args)?),
+            "Explicit" => DefaultValue::Explicit(self.namespace_ref(args)?),
+            "StaticProtected" => DefaultValue::StaticProtected(self.namespace_ref(args)?),
+            "Private" => DefaultValue::Private(self.namespace_ref(args)?),
+            _ => return Err(args.error(format!("Unknown value kind {kind}"))),
+        };
+        args.expect(Token::CloseParen, "')'")?;
+        Ok(value)
+    }
+
+    fn method(&mut self, mut args: Args) -> Result<()> {
+        args.definition(self.abc.methods.len())?;
+        let mut method = Method {
+            name: Index::new(0),
+            params: Vec::new(),
+            return_type: Index::new(0),
+            flags: MethodFlags::empty(),
+        };
+        while let Some(key) = args.key()? {
+            match key {
+                "name" => method.name = self.string_ref(&mut args)?,
+                "returns" => method.return_type = self.multiname_ref(&mut args)?,
+                "flags" => method.flags = args.flags(MethodFlags::from_name)?.into_iter().collect(),
+                _ => return Err(args.error(format!("Unknown attribute {key}"))),
+            }
+        }
+
+        for (line, tokens) in self.contents(&mut args)? {
+            let mut args = Args {
+                tokens: &tokens,
+                line,
+            };
+            if args.word()? != "param" {
+                return Err(args.error("Expected a parameter"));
+            }
+            let mut param = MethodParam {
+                kind: self.multiname_ref(&mut args)?,
+                name: None,
+                default_value: None,
+            };
+            while let Some(key) = args.key()? {
+                match key {
+                    "name" => param.name = Some(self.string_ref(&mut args)?),
+                    "default" => param.default_value = Some(self.default_value(&mut args)?),
+                    _ => return Err(args.error(format!("Unknown attribute {key}"))),
+                }
+            }
+            args.end()?;
+            method.params.push(param);
+        }
+
+        self.abc.methods.push(method);
+        Ok(())
+    }
+
+    fn metadata(&mut self, mut args: Args) -> Result<()> {
+        args.definition(self.abc.metadata.len())?;
+        let mut metadata = Metadata {
+            name: Index::new(0),
+            items: Vec::new(),
+        };
+        while let Some(key) = args.key()? {
+            match key {
+                "name" => metadata.name = self.string_ref(&mut args)?,
+                _ => return Err(args.error(format!("Unknown attribute {key}"))),
+            }
+        }
+
+        for (line, tokens) in self.contents(&mut args)? {
+            let mut args = Args {
+                tokens: &tokens,
+                line,
+            };
+            if args.word()? != "item" {
+                return Err(args.error("Expected an item"));
+            }
+            let key = self.string_ref(&mut args)?;
+            let value = self.string_ref(&mut args)?;
+            args.end()?;
+            metadata.items.push(MetadataItem { key, value });
+        }
+
+        self.abc.metadata.push(metadata);
+        Ok(())
+    }
+
+    fn class(&mut self, mut args: Args) -> Result<()> {
+        args.definition(self.abc.classes.len())?;
+        let mut instance = Instance {
+            name: Index::new(0),
+            super_name: Index::new(0),
+            is_sealed: false,
+            is_final: false,
+            is_interface: false,
+            protected_namespace: None,
+            interfaces: Vec::new(),
+            init_method: Index::new(0),
+            traits: Vec::new(),
+        };
+        let mut class = Class {
+            init_method: Index::new(0),
+            traits: Vec::new(),
+        };
+        while let Some(key) = args.key()? {
+            match key {
+                "name" => instance.name = self.multiname_ref(&mut args)?,
+                "extends" => instance.super_name = self.multiname_ref(&mut args)?,
+                "sealed" => instance.is_sealed = true,
+                "final" => instance.is_final = true,
+                "interface" => instance.is_interface = true,
+                "protected" => instance.protected_namespace = Some(self.namespace_ref(&mut args)?),
+                "implements" => instance.interfaces = self.list(&mut args, Self::multiname_ref)?,
+                "iinit" => instance.init_method = args.index()?,
+                "cinit" => class.init_method = args.index()?,
+                _ => return Err(args.error(format!("Unknown attribute {key}"))),
+            }
+        }
+
+        for (line, tokens) in self.contents(&mut args)? {
+            let mut args = Args {
+                tokens: &tokens,
+                line,
+            };
+            if args.eat(&Token::Word("static".to_string())) {
+                class.traits.push(self.trait_line(args)?);
+            } else {
+                instance.traits.push(self.trait_line(args)?);
+            }
+        }
+
+        self.abc.instances.push(instance);
+        self.abc.classes.push(class);
+        Ok(())
+    }
+
+    fn script(&mut self, mut args: Args) -> Result<()> {
+        args.definition(self.abc.scripts.len())?;
+        let mut script = Script {
+            init_method: Index::new(0),
+            traits: Vec::new(),
+        };
+        while let Some(key) = args.key()? {
+            match key {
+                "init" => script.init_method = args.index()?,
+                _ => return Err(args.error(format!("Unknown attribute {key}"))),
+            }
+        }
+
+        for (line, tokens) in self.contents(&mut args)? {
+            let args = Args {
+                tokens: &tokens,
+                line,
+            };
+            script.traits.push(self.trait_line(args)?);
+        }
+
+        self.abc.scripts.push(script);
+        Ok(())
+    }
+
+    fn trait_line(&mut self, mut args: Args) -> Result<Trait> {
+        if args.word()? != "trait" {
+            return Err(args.error("Expected a trait"));
+        }
+        let kind = args.word()?;
+        let name = self.multiname_ref(&mut args)?;
+        let mut id = 0;
+        let mut type_name = Index::new(0);
+        let mut value = None;
+        let mut index = None;
+        let mut t = Trait {
+            name,
+            kind: TraitKind::Slot {
+                slot_id: 0,
+                type_name: Index::new(0),
+                value: None,
+            },
+            metadata: Vec::new(),
+            is_final: false,
+            is_override: false,
+        };
+        while let Some(key) = args.key()? {
+            match key {
+                "slot_id" | "disp_id" => id = args.number()?,
+                "type" => type_name = self.multiname_ref(&mut args)?,
+                "value" => value = Some(self.default_value(&mut args)?),
+                "method" | "class" | "function" => index = Some(args.index::<()>()?.0),
+                "final" => t.is_final = true,
+                "override" => t.is_override = true,
+                "metadata" => t.metadata = self.list(&mut args, |_, args| args.index())?,
+                _ => return Err(args.error(format!("Unknown attribute {key}"))),
+            }
+        }
+        args.end()?;
+
+        let index = || index.ok_or_else(|| args.error("Missing index"));
+        t.kind = match kind {
+            "slot" => TraitKind::Slot {
+                slot_id: id,
+                type_name,
+                value,
+            },
+            "method" => TraitKind::Method {
+                disp_id: id,
+                method: Index::new(index()?),
+            },
+            "getter" => TraitKind::Getter {
+                disp_id: id,
+                method: Index::new(index()?),
+            },
+            "setter" => TraitKind::Setter {
+                disp_id: id,
+                method: Index::new(index()?),
+            },
+            "class" => TraitKind::Class {
+                slot_id: id,
+                class: Index::new(index()?),
+            },
+            "function" => TraitKind::Function {
+                slot_id: id,
+                function: Index::new(index()?),
+            },
+            "const" => TraitKind::Const {
+                slot_id: id,
+                type_name,
+                value,
+            },
+            _ => return Err(args.error(format!("Unknown trait kind {kind}"))),
+        };
+        Ok(t)
+    }
+
+    fn method_body(&mut self, mut args: Args) -> Result<()> {
+        args.definition(self.abc.method_bodies.len())?;
+        let mut body = MethodBody {
+            method: Index::new(0),
+            max_stack: 0,
+            num_locals: 0,
+            init_scope_depth: 0,
+            max_scope_depth: 0,
+            code: Vec::new(),
+            exceptions: Vec::new(),
+            traits: Vec::new(),
+        };
+        while let Some(key) = args.key()? {
+            match key {
+                "method" => body.method = args.index()?,
+                "max_stack" => body.max_stack = args.number()?,
+                "locals" => body.num_locals = args.number()?,
+                "init_scope_depth" => body.init_scope_depth = args.number()?,
+                "max_scope_depth" => body.max_scope_depth = args.number()?,
+                _ => return Err(args.error(format!("Unknown attribute {key}"))),
+            }
+        }
+
+        let contents = self.contents(&mut args)?;
+        let mut labels = HashMap::new();
+        let mut fixups = Vec::new();
+        let mut exceptions = Vec::new();
+        for (line, tokens) in &contents {
+            let mut args = Args {
+                tokens,
+                line: *line,
+            };
+            let word = args.word()?;
+            match word {
+                _ if word.ends_with(':') && args.peek().is_none() => {
+                    let label = &word[..word.len() - 1];
+                    if labels.insert(label, body.code.len()).is_some() {
+                        return Err(args.error(format!("Duplicate label {label}")));
+                    }
+                }
+                "exception" => {
+                    let mut offsets = [
+                        CodeOffset::Offset(0),
+                        CodeOffset::Offset(0),
+                        CodeOffset::Offset(0),
+                    ];
+                    let mut exception = Exception {
+                        from_offset: 0,
+                        to_offset: 0,
+                        target_offset: 0,
+                        variable_name: Index::new(0),
+                        type_name: Index::new(0),
+                    };
+                    while let Some(key) = args.key()? {
+                        match key {
+                            "from" => offsets[0] = args.code_offset()?,
+                            "to" => offsets[1] = args.code_offset()?,
+                            "target" => offsets[2] = args.code_offset()?,
+                            "type" => exception.type_name = self.multiname_ref(&mut args)?,
+                            "var" => exception.variable_name = self.multiname_ref(&mut args)?,
+                            _ => return Err(args.error(format!("Unknown attribute {key}"))),
+                        }
+                    }
+                    args.end()?;
+                    exceptions.push((*line, exception, offsets));
+                }
+                "trait" => {
+                    body.traits.push(self.trait_line(Args {
+                        tokens,
+                        line: *line,
+                    })?);
+                }
+                "raw" => {
+                    while let Some(token) = args.next() {
+                        let byte = match token {
+                            Token::Word(byte) if byte.len() == 2 => {
+                                u8::from_str_radix(byte, 16).ok()
+                            }
+                            _ => None,
+                        };
+                        body.code.push(
+                            byte.ok_or_else(|| args.error(format!("Invalid byte {token:?}")))?,
+                        );
+                    }
+                }
+                name => self.op(name, args, &mut body.code, &mut fixups)?,
+            }
+        }
+
+        for fixup in fixups {
+            let target = labels
+                .get(fixup.label.as_str())
+                .ok_or_else(|| error(fixup.line, format!("Unknown label {}", fixup.label)))?;
+            let offset = *target as i64 - fixup.base as i64;
+            if !(-0x80_0000..0x80_0000).contains(&offset) {
+                return Err(error(
+                    fixup.line,
+                    format!("Label {} is too far", fixup.label),
+                ));
+            }
+            body.code[fixup.position..fixup.position + 3]
+                .copy_from_slice(&(offset as i32).to_le_bytes()[..3]);
+        }
+
+        for (line, mut exception, offsets) in exceptions {
+            let [from_offset, to_offset, target_offset] = offsets.map(|offset| match offset {
+                CodeOffset::Offset(offset) => Ok(offset),
+                CodeOffset::Label(label) => labels
+                    .get(label)
+                    .map(|offset| *offset as u32)
+                    .ok_or_else(|| error(line, format!("Unknown label {label}"))),
+            });
+            exception.from_offset = from_offset?;
+            exception.to_offset = to_offset?;
+            exception.target_offset = target_offset?;
+            body.exceptions.push(exception);
+        }
+
+        self.abc.method_bodies.push(body);
+        Ok(())
+    }
+
+    fn op(
+        &mut self,
+        name: &str,
+        mut args: Args,
+        code: &mut Vec<u8>,
+        fixups: &mut Vec<Fixup>,
+    ) -> Result<()> {
+        let mut targets = Vec::new();
+        let op = match name {
+            "applytype" => Op::ApplyType {
+                num_types: args.number()?,
+            },
+            "astype" => Op::AsType {
+                type_name: self.multiname_ref(&mut args)?,
+            },
+            "bkptline" => Op::BkptLine {
+                line_num: args.number()?,
+            },
+            "call" => Op::Call {
+                num_args: args.number()?,
+            },
+            "callmethod" => Op::CallMethod {
+                index: args.number()?,
+                num_args: args.next_number()?,
+            },
+            "callproperty" => Op::CallProperty {
+                index: self.multiname_ref(&mut args)?,
+                num_args: args.next_number()?,
+            },
+            "callproplex" => Op::CallPropLex {
+                index: self.multiname_ref(&mut args)?,
+                num_args: args.next_number()?,
+            },
+            "callpropvoid" => Op::CallPropVoid {
+                index: self.multiname_ref(&mut args)?,
+                num_args: args.next_number()?,
+            },
+            "callstatic" => Op::CallStatic {
+                index: args.index()?,
+                num_args: args.next_number()?,
+            },
+            "callsuper" => Op::CallSuper {
+                index: self.multiname_ref(&mut args)?,
+                num_args: args.next_number()?,
+            },
+            "callsupervoid" => Op::CallSuperVoid {
+                index: self.multiname_ref(&mut args)?,
+                num_args: args.next_number()?,
+            },
+            "coerce" => Op::Coerce {
+                index: self.multiname_ref(&mut args)?,
+            },
+            "construct" => Op::Construct {
+                num_args: args.number()?,
+            },
+            "constructprop" => Op::ConstructProp {
+                index: self.multiname_ref(&mut args)?,
+                num_args: args.next_number()?,
+            },
+            "constructsuper" => Op::ConstructSuper {
+                num_args: args.number()?,
+            },
+            "debug" => Op::Debug {
+                is_local_register: args.number()?,
+                register_name: {
+                    args.comma()?;
+                    self.string_ref(&mut args)?
+                },
+                register: args.next_number()?,
+            },
+            "debugfile" => Op::DebugFile {
+                file_name: self.string_ref(&mut args)?,
+            },
+            "debugline" => Op::DebugLine {
+                line_num: args.number()?,
+            },
+            "declocal" => Op::DecLocal {
+                index: args.number()?,
+            },
+            "declocali" => Op::DecLocalI {
+                index: args.number()?,
+            },
+            "deleteproperty" => Op::DeleteProperty {
+                index: self.multiname_ref(&mut args)?,
+            },
+            "dxns" => Op::Dxns {
+                index: self.string_ref(&mut args)?,
+            },
+            "finddef" => Op::FindDef {
+                index: self.multiname_ref(&mut args)?,
+            },
+            "findproperty" => Op::FindProperty {
+                index: self.multiname_ref(&mut args)?,
+            },
+            "findpropstrict" => Op::FindPropStrict {
+                index: self.multiname_ref(&mut args)?,
+            },
+            "getdescendants" => Op::GetDescendants {
+                index: self.multiname_ref(&mut args)?,
+            },
+            "getglobalslot" => Op::GetGlobalSlot {
+                index: args.number()?,
+            },
+            "getlex" => Op::GetLex {
+                index: self.multiname_ref(&mut args)?,
+            },
+            "getlocal" => Op::GetLocal {
+                index: args.number()?,
+            },
+            "getouterscope" => Op::GetOuterScope {
+                index: args.number()?,
+            },
+            "getproperty" => Op::GetProperty {
+                index: self.multiname_ref(&mut args)?,
+            },
+            "getscopeobject" => Op::GetScopeObject {
+                index: args.number()?,
+            },
+            "getslot" => Op::GetSlot {
+                index: args.number()?,
+            },
+            "getsuper" => Op::GetSuper {
+                index: self.multiname_ref(&mut args)?,
+            },
+            "hasnext2" => Op::HasNext2 {
+                object_register: args.number()?,
+                index_register: args.next_number()?,
+            },
+            "ifeq" => Op::IfEq {
+                offset: args.target(&mut targets)?,
+            },
+            "iffalse" => Op::IfFalse {
+                offset: args.target(&mut targets)?,
+            },
+            "ifge" => Op::IfGe {
+                offset: args.target(&mut targets)?,
+            },
+            "ifgt" => Op::IfGt {
+                offset: args.target(&mut targets)?,
+            },
+            "ifle" => Op::IfLe {
+                offset: args.target(&mut targets)?,
+            },
+            "iflt" => Op::IfLt {
+                offset: args.target(&mut targets)?,
+            },
+            "ifne" => Op::IfNe {
+                offset: args.target(&mut targets)?,
+            },
+            "ifnge" => Op::IfNge {
+                offset: args.target(&mut targets)?,
+            },
+            "ifngt" => Op::IfNgt {
+                offset: args.target(&mut targets)?,
+            },
+            "ifnle" => Op::IfNle {
+                offset: args.target(&mut targets)?,
+            },
+            "ifnlt" => Op::IfNlt {
+                offset: args.target(&mut targets)?,
+            },
+            "ifstricteq" => Op::IfStrictEq {
+                offset: args.target(&mut targets)?,
+            },
+            "ifstrictne" => Op::IfStrictNe {
+                offset: args.target(&mut targets)?,
+            },
+            "iftrue" => Op::IfTrue {
+                offset: args.target(&mut targets)?,
+            },
+            "inclocal" => Op::IncLocal {
+                index: args.number()?,
+            },
+            "inclocali" => Op::IncLocalI {
+                index: args.number()?,
+            },
+            "initproperty" => Op::InitProperty {
+                index: self.multiname_ref(&mut args)?,
+            },
+            "istype" => Op::IsType {
+                index: self.multiname_ref(&mut args)?,
+            },
+            "jump" => Op::Jump {
+                offset: args.target(&mut targets)?,
+            },
+            "kill" => Op::Kill {
+                index: args.number()?,
+            },
+            "lookupswitch" => {
+                let default_offset = args.target(&mut targets)?;
+                args.comma()?;
+                let case_offsets = self.list(&mut args, |_, args| args.target(&mut targets))?;
+                if case_offsets.is_empty() {
+                    return Err(args.error("Expected at least one case"));
+                }
+                Op::LookupSwitch(Box::new(LookupSwitch {
+                    default_offset,
+                    case_offsets: case_offsets.into(),
+                }))
+            }
+            "newarray" => Op::NewArray {
+                num_args: args.number()?,
+            },
+            "newcatch" => Op::NewCatch {
+                index: args.index()?,
+            },
+            "newclass" => Op::NewClass {
+                index: args.index()?,
+            },
+            "newfunction" => Op::NewFunction {
+                index: args.index()?,
+            },
+            "newobject" => Op::NewObject {
+                num_args: args.number()?,
+            },
+            "pushbyte" => Op::PushByte {
+                value: args.number()?,
+            },
+            "pushdouble" => Op::PushDouble {
+                value: self.double_ref(&mut args)?,
+            },
+            "pushint" => Op::PushInt {
+                value: self.int_ref(&mut args)?,
+            },
+            "pushnamespace" => Op::PushNamespace {
+                value: self.namespace_ref(&mut args)?,
+            },
+            "pushshort" => Op::PushShort {
+                value: args.number()?,
+            },
+            "pushstring" => Op::PushString {
+                value: self.string_ref(&mut args)?,
+            },
+            "pushuint" => Op::PushUint {
+                value: self.uint_ref(&mut args)?,
+            },
+            "setglobalslot" => Op::SetGlobalSlot {
+                index: args.number()?,
+            },
+            "setlocal" => Op::SetLocal {
+                index: args.number()?,
+            },
+            "setproperty" => Op::SetProperty {
+                index: self.multiname_ref(&mut args)?,
+            },
+            "setslot" => Op::SetSlot {
+                index: args.number()?,
+            },
+            "setsuper" => Op::SetSuper {
+                index: self.multiname_ref(&mut args)?,
+            },
+            _ => {
+                // The remaining instructions are just an opcode.
+                let opcode = opcode_from_name(name)
+                    .ok_or_else(|| args.error(format!("Unknown instruction {name}")))?;
+                Reader::new(&[opcode as u8])
+                    .read_op()
+                    .map_err(|_| args.error(format!("Missing operands for {name}")))?
+            }
+        };
+        args.end()?;
+
+        let start = code.len();
+        Writer::new(&mut *code).write_op(&op)?;
+        let end = code.len();
+
+        // Branch offsets are relative to the end of the instruction, and
+        // `lookupswitch` offsets are relative to its start.
+        let (base, positions) = match &op {
+            Op::LookupSwitch(lookup_switch) => {
+                let num_cases = lookup_switch.case_offsets.len();
+                let cases = (0..num_cases).map(|i| end - 3 * (num_cases - i));
+                (start, std::iter::once(start + 1).chain(cases).collect())
+            }
+            _ => (end, vec![start + 1]),
+        };
+        for (position, target) in positions.into_iter().zip(targets) {
+            if let Some(label) = target {
+                fixups.push(Fixup {
+                    position,
+                    base,
+                    label: label.to_string(),
+                    line: args.line,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::disasm::disassemble;
+    use crate::test_data;
+    use crate::types::Tag;
+
+    fn write(abc: AbcFile) -> Vec<u8> {
+        let mut out = vec![];
+        Writer::new(&mut out).write(abc).unwrap();
+        out
+    }
+
+    fn round_trip(abc: AbcFile) {
+        let text = disassemble(&abc);
+        let assembled =
+            assemble(&text).unwrap_or_else(|e| panic!("Couldn't assemble:\n{text}\nError: {e}"));
+        // Compare the written files, since doubles may be NaN.
+        assert_eq!(
+            write(assembled),
+            write(abc),
+            "Incorrectly assembled:\n{text}"
+        );
+    }
+
+    #[test]
+    fn round_trip_abc() {
+        for (_, abc_file, _) in test_data::avm2_tests() {
+            round_trip(abc_file);
+        }
+    }
+
+    #[test]
+    fn round_trip_swfs() {
+        let mut num_files = 0;
+        for entry in std::fs::read_dir("tests/swfs").unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|extension| extension == "swf") {
+                let data = std::fs::read(&path).unwrap();
+                let swf_buf = crate::decompress_swf(&data[..]).unwrap();
+                let swf = crate::parse_swf(&swf_buf).unwrap();
+                for tag in &swf.tags {
+                    let data = match tag {
+                        Tag::DoAbc(data) => data,
+                        Tag::DoAbc2(do_abc) => do_abc.data,
+                        _ => continue,
+                    };
+                    round_trip(Reader::new(data).read().unwrap());
+                    num_files += 1;
+                }
+            }
+        }
+        assert!(num_files > 0);
+    }
+
+    #[test]
+    fn assemble_labels() {
+        let abc = assemble(
+            r#"
+            method #0
+            body #0 method #0 max_stack 1 locals 1 init_scope_depth 0 max_scope_depth 0 {
+            L1:
+                pushbyte 1
+                lookupswitch L2, [L1, L3]
+            L2:
+                jump L1
+            L3:
+                returnvoid
+                exception from L1 to L3 target L3 type * var QName(Package(""), "e")
+            }
+            "#,
+        )
+        .unwrap();
+        let body = &abc.method_bodies[0];
+        assert_eq!(
+            body.code,
+            [
+                0x24, 1, // pushbyte 1
+                0x1B, 11, 0, 0, 1, 0xFE, 0xFF, 0xFF, 15, 0, 0, // lookupswitch
+                0x10, 0xEF, 0xFF, 0xFF, // jump
+                0x47, // returnvoid
+            ]
+        );
+        assert_eq!(
+            body.exceptions,
+            [Exception {
+                from_offset: 0,
+                to_offset: 17,
+                target_offset: 17,
+                variable_name: Index::new(1),
+                type_name: Index::new(0),
+            }]
+        );
+        assert_eq!(
+            abc.constant_pool.multinames,
+            [Multiname::QName {
+                namespace: Index::new(1),
+                name: Index::new(2),
+            }]
+        );
+    }
+
+    #[test]
+    fn assemble_pools() {
+        let abc = assemble(
+            r#"
+            version 47.12
+            string #1 "a\x80"
+            string #2 "a\x80"
+            namespace #1 Private(#2)
+            multiname #1 TypeName(#2, [*])
+            multiname #2 QName(#1, "a\x80")
+            "#,
+        )
+        .unwrap();
+        assert_eq!((abc.major_version, abc.minor_version), (47, 12));
+        let pool = &abc.constant_pool;
+        assert_eq!(pool.strings, [b"a\x80".to_vec(), b"a\x80".to_vec()]);
+        assert_eq!(pool.namespaces, [Namespace::Private(Index::new(2))]);
+        assert_eq!(
+            pool.multinames,
+            [
+                Multiname::TypeName {
+                    base_type: Index::new(2),
+                    parameters: vec![Index::new(0)],
+                },
+                Multiname::QName {
+                    namespace: Index::new(1),
+                    name: Index::new(1),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn assemble_errors() {
+        let error = |text| assemble(text).unwrap_err().to_string();
+        assert!(error("method #1").contains("Line 1: Expected #0"));
+        assert!(error("int #1 x").contains("Line 1: Invalid number x"));
+        assert!(error("\nfoo").contains("Line 2: Unknown item foo"));
+        assert!(error("string #1 \"a").contains("Line 1: Unterminated string"));
+        assert!(error("method #0\nbody #0 {\n  jump L1\n}").contains("Line 3: Unknown label L1"));
+        assert!(error("method #0\nbody #0 {\n  getlocal\n}").contains("Line 3: Expected a value"));
+        assert!(error("method #0\nbody #0 {\n  frobnicate\n}").contains("Unknown instruction"));
+        assert!(error("script #0 {").contains("Missing '}'"));
+    }
+}
@@ -0,0 +1,1027 @@
+//! A disassembler for ABC files.
+//!
+//! The output lists the constant pools, methods, metadata, classes, scripts
+//! and method bodies of an `AbcFile`, and can be parsed back into the same
+//! `AbcFile` with `avm2::asm::assemble`:
+//!
+//! ```text
+//! version 46.16
+//!
+//! string #1 ""
+//! string #2 "Test"
+//!
+//! namespace #1 Package("")
+//!
+//! multiname #1 QName(Package(""), "Test")
+//!
+//! method #0 name "Test"
+//!
+//! script #0 init #0 {
+//!     trait method QName(Package(""), "Test") disp_id 1 method #0
+//! }
+//!
+//! body #0 method #0 max_stack 1 locals 1 init_scope_depth 0 max_scope_depth 1 {
+//!     getlocal 0
+//!     pushscope
+//!     iftrue L1
+//!     pushstring "Test"
+//! L1:
+//!     returnvoid
+//! }
+//! ```
+//!
+//! Constant pool entries are referred to by their value wherever that's
+//! unambiguous, and by index, such as `#3`, otherwise. `*` stands for index 0,
+//! which means "any" or "none". Method bodies use labels for branch targets
+//! and exception offsets, and code that can't be represented exactly as
+//! instructions is written as `raw` bytes.
+
+use crate::avm2::{read::Reader, types::*, write::Writer};
+use crate::extensions::ReadSwfExt;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+use std::hash::Hash;
+
+/// Disassembles an ABC file into text.
+pub fn disassemble(abc: &AbcFile) -> String {
+    let mut disassembler = Disassembler::new(abc);
+    disassembler.abc();
+    disassembler.output
+}
+
+fn format_f64(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "Infinity" } else { "-Infinity" }.to_string()
+    } else {
+        format!("{value:?}")
+    }
+}
+
+/// Formats a string as a quoted string literal, escaping any bytes that
+/// aren't valid UTF-8 as `\xNN`.
+fn format_string(string: &[u8]) -> String {
+    let mut output = String::from("\"");
+    for chunk in string.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '"' => output.push_str("\\\""),
+                '\\' => output.push_str("\\\\"),
+                '\n' => output.push_str("\\n"),
+                '\r' => output.push_str("\\r"),
+                '\t' => output.push_str("\\t"),
+                c if c.is_control() => {
+                    for byte in c.encode_utf8(&mut [0; 4]).bytes() {
+                        let _ = write!(output, "\\x{byte:02X}");
+                    }
+                }
+                c => output.push(c),
+            }
+        }
+        for byte in chunk.invalid() {
+            let _ = write!(output, "\\x{byte:02X}");
+        }
+    }
+    output.push('"');
+    output
+}
+
+/// Returns whether each entry of a constant pool can be referred to by its
+/// value.
+///
+/// This is the case if the entry is the first one with its key, so that
+/// looking the value up finds it, and if its own references can be written
+/// as values.
+fn by_value<T, K: Eq + Hash>(
+    entries: &[T],
+    key: impl Fn(&T) -> K,
+    is_valid: impl Fn(usize, &T) -> bool,
+) -> Vec<bool> {
+    let mut keys = HashSet::new();
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| keys.insert(key(entry)) && is_valid(i + 1, entry))
+        .collect()
+}
+
+/// Whether an index of a constant pool can be written as a value.
+fn is_by_value(by_value: &[bool], index: u32) -> bool {
+    index == 0
+        || usize::try_from(index - 1)
+            .ok()
+            .and_then(|i| by_value.get(i))
+            .is_some_and(|by_value| *by_value)
+}
+
+fn namespace_key(namespace: &Namespace) -> (u8, u32) {
+    match namespace {
+        Namespace::Namespace(name) => (0x08, name.0),
+        Namespace::Package(name) => (0x16, name.0),
+        Namespace::PackageInternal(name) => (0x17, name.0),
+        Namespace::Protected(name) => (0x18, name.0),
+        Namespace::Explicit(name) => (0x19, name.0),
+        Namespace::StaticProtected(name) => (0x1A, name.0),
+        Namespace::Private(name) => (0x05, name.0),
+    }
+}
+
+fn multiname_key(multiname: &Multiname) -> Vec<u32> {
+    match multiname {
+        Multiname::QName { namespace, name } => vec![0x07, namespace.0, name.0],
+        Multiname::QNameA { namespace, name } => vec![0x0D, namespace.0, name.0],
+        Multiname::RTQName { name } => vec![0x0F, name.0],
+        Multiname::RTQNameA { name } => vec![0x10, name.0],
+        Multiname::RTQNameL => vec![0x11],
+        Multiname::RTQNameLA => vec![0x12],
+        Multiname::Multiname {
+            namespace_set,
+            name,
+        } => vec![0x09, namespace_set.0, name.0],
+        Multiname::MultinameA {
+            namespace_set,
+            name,
+        } => vec![0x0E, namespace_set.0, name.0],
+        Multiname::MultinameL { namespace_set } => vec![0x1B, namespace_set.0],
+        Multiname::MultinameLA { namespace_set } => vec![0x1C, namespace_set.0],
+        Multiname::TypeName {
+            base_type,
+            parameters,
+        } => [0x1D, base_type.0]
+            .into_iter()
+            .chain(parameters.iter().map(|parameter| parameter.0))
+            .collect(),
+    }
+}
+
+/// Returns the offset that a branch instruction jumps by, relative to the
+/// end of the instruction.
+fn branch_offset(op: &Op) -> Option<i32> {
+    match *op {
+        Op::IfEq { offset }
+        | Op::IfFalse { offset }
+        | Op::IfGe { offset }
+        | Op::IfGt { offset }
+        | Op::IfLe { offset }
+        | Op::IfLt { offset }
+        | Op::IfNe { offset }
+        | Op::IfNge { offset }
+        | Op::IfNgt { offset }
+        | Op::IfNle { offset }
+        | Op::IfNlt { offset }
+        | Op::IfStrictEq { offset }
+        | Op::IfStrictNe { offset }
+        | Op::IfTrue { offset }
+        | Op::Jump { offset } => Some(offset),
+        _ => None,
+    }
+}
+
+/// The name of an instruction, such as `getlocal`.
+fn op_name(op: &Op) -> String {
+    let name = format!("{op:?}");
+    let end = name
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(name.len());
+    name[..end].to_ascii_lowercase()
+}
+
+/// A decoded instruction and the bytes it was decoded from.
+struct Item<'a> {
+    offset: usize,
+    bytes: &'a [u8],
+
+    /// The instruction, or `None` if it's printed as raw bytes.
+    op: Option<Op>,
+}
+
+impl Item<'_> {
+    fn end(&self) -> usize {
+        self.offset + self.bytes.len()
+    }
+
+    /// The offsets in the code that this instruction can jump to.
+    fn targets(&self) -> Vec<Option<usize>> {
+        let relative_to = |base: usize, offset: i32| base.checked_add_signed(offset as isize);
+        match &self.op {
+            Some(Op::LookupSwitch(lookup_switch)) => std::iter::once(lookup_switch.default_offset)
+                .chain(lookup_switch.case_offsets.iter().copied())
+                .map(|offset| relative_to(self.offset, offset))
+                .collect(),
+            Some(op) => branch_offset(op)
+                .map(|offset| relative_to(self.end(), offset))
+                .into_iter()
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+struct Disassembler<'a> {
+    abc: &'a AbcFile,
+    output: String,
+
+    ints: Vec<bool>,
+    uints: Vec<bool>,
+    doubles: Vec<bool>,
+    strings: Vec<bool>,
+    namespaces: Vec<bool>,
+    namespace_sets: Vec<bool>,
+    multinames: Vec<bool>,
+}
+
+impl<'a> Disassembler<'a> {
+    fn new(abc: &'a AbcFile) -> Self {
+        let pool = &abc.constant_pool;
+        let ints = by_value(&pool.ints, |n| *n, |_, _| true);
+        let uints = by_value(&pool.uints, |n| *n, |_, _| true);
+        let doubles = by_value(
+            &pool.doubles,
+            |n| n.to_bits(),
+            |_, n| {
+                // Only the default NaN can be written as text.
+                !n.is_nan() || n.to_bits() == f64::NAN.to_bits()
+            },
+        );
+        let strings = by_value(&pool.strings, |s| s.clone(), |_, _| true);
+        let namespaces = by_value(&pool.namespaces, namespace_key, |_, namespace| {
+            is_by_value(&strings, namespace_key(namespace).1)
+        });
+        let namespace_sets = by_value(
+            &pool.namespace_sets,
+            |set| set.iter().map(|namespace| namespace.0).collect::<Vec<_>>(),
+            |_, set| {
+                set.iter()
+                    .all(|namespace| is_by_value(&namespaces, namespace.0))
+            },
+        );
+        let mut multinames: Vec<bool> = Vec::with_capacity(pool.multinames.len());
+        let mut keys = HashSet::new();
+        for (i, multiname) in pool.multinames.iter().enumerate() {
+            let is_valid = match multiname {
+                Multiname::QName { namespace, name } | Multiname::QNameA { namespace, name } => {
+                    is_by_value(&namespaces, namespace.0) && is_by_value(&strings, name.0)
+                }
+                Multiname::RTQName { name } | Multiname::RTQNameA { name } => {
+                    is_by_value(&strings, name.0)
+                }
+                Multiname::RTQNameL | Multiname::RTQNameLA => true,
+                Multiname::Multiname {
+                    namespace_set,
+                    name,
+                }
+                | Multiname::MultinameA {
+                    namespace_set,
+                    name,
+                } => is_by_value(&namespace_sets, namespace_set.0) && is_by_value(&strings, name.0),
+                Multiname::MultinameL { namespace_set }
+                | Multiname::MultinameLA { namespace_set } => {
+                    is_by_value(&namespace_sets, namespace_set.0)
+                }
+                // Only earlier multinames are known when this one is parsed.
+                Multiname::TypeName {
+                    base_type,
+                    parameters,
+                } => std::iter::once(base_type)
+                    .chain(parameters)
+                    .all(|parameter| is_by_value(&multinames, parameter.0)),
+            };
+            multinames.push(keys.insert(multiname_key(multiname)) && is_valid);
+            debug_assert_eq!(multinames.len(), i + 1);
+        }
+
+        Self {
+            abc,
+            output: String::new(),
+            ints,
+            uints,
+            doubles,
+            strings,
+            namespaces,
+            namespace_sets,
+            multinames,
+        }
+    }
+
+    fn abc(&mut self) {
+        let abc = self.abc;
+        let _ = writeln!(
+            self.output,
+            "version {}.{}",
+            abc.major_version, abc.minor_version
+        );
+
+        self.constant_pool();
+
+        for (i, method) in abc.methods.iter().enumerate() {
+            self.section_break(i);
+            self.method(i, method);
+        }
+        for (i, metadata) in abc.metadata.iter().enumerate() {
+            self.section_break(i);
+            self.metadata(i, metadata);
+        }
+        // Instances and classes are paired up, like in the file format.
+        for (i, (instance, class)) in abc.instances.iter().zip(&abc.classes).enumerate() {
+            self.output.push('\n');
+            self.class(i, instance, class);
+        }
+        for (i, script) in abc.scripts.iter().enumerate() {
+            self.output.push('\n');
+            let _ = write!(self.output, "script #{i} init #{}", script.init_method.0);
+            self.traits_block(&script.traits);
+        }
+        for (i, body) in abc.method_bodies.iter().enumerate() {
+            self.output.push('\n');
+            self.method_body(i, body);
+        }
+    }
+
+    /// Separates groups of one-line items with a blank line.
+    fn section_break(&mut self, i: usize) {
+        if i == 0 || self.output.ends_with("}\n") {
+            self.output.push('\n');
+        }
+    }
+
+    fn constant_pool(&mut self) {
+        let pool = &self.abc.constant_pool;
+        if !pool.ints.is_empty() || !pool.uints.is_empty() || !pool.doubles.is_empty() {
+            self.output.push('\n');
+        }
+        for (i, n) in pool.ints.iter().enumerate() {
+            let _ = writeln!(self.output, "int #{} {n}", i + 1);
+        }
+        for (i, n) in pool.uints.iter().enumerate() {
+            let _ = writeln!(self.output, "uint #{} {n}", i + 1);
+        }
+        for (i, n) in pool.doubles.iter().enumerate() {
+            let _ = writeln!(self.output, "double #{} {}", i + 1, format_f64(*n));
+        }
+
+        if !pool.strings.is_empty() {
+            self.output.push('\n');
+        }
+        for (i, string) in pool.strings.iter().enumerate() {
+            let _ = writeln!(self.output, "string #{} {}", i + 1, format_string(string));
+        }
+
+        if !pool.namespaces.is_empty() || !pool.namespace_sets.is_empty() {
+            self.output.push('\n');
+        }
+        for (i, namespace) in pool.namespaces.iter().enumerate() {
+            let namespace = self.namespace(namespace);
+            let _ = writeln!(self.output, "namespace #{} {namespace}", i + 1);
+        }
+        for (i, namespace_set) in pool.namespace_sets.iter().enumerate() {
+            let namespace_set = self.namespace_set(namespace_set);
+            let _ = writeln!(self.output, "namespace_set #{} {namespace_set}", i + 1);
+        }
+
+        if !pool.multinames.is_empty() {
+            self.output.push('\n');
+        }
+        for (i, multiname) in pool.multinames.iter().enumerate() {
+            let multiname = self.multiname(multiname, i as u32 + 1);
+            let _ = writeln!(self.output, "multiname #{} {multiname}", i + 1);
+        }
+    }
+
+    fn int_ref(&self, index: &Index<i32>) -> String {
+        match index.0 {
+            0 => "*".to_string(),
+            i if is_by_value(&self.ints, i) => {
+                self.abc.constant_pool.ints[i as usize - 1].to_string()
+            }
+            i => format!("#{i}"),
+        }
+    }
+
+    fn uint_ref(&self, index: &Index<u32>) -> String {
+        match index.0 {
+            0 => "*".to_string(),
+            i if is_by_value(&self.uints, i) => {
+                self.abc.constant_pool.uints[i as usize - 1].to_string()
+            }
+            i => format!("#{i}"),
+        }
+    }
+
+    fn double_ref(&self, index: &Index<f64>) -> String {
+        match index.0 {
+            0 => "*".to_string(),
+            i if is_by_value(&self.doubles, i) => {
+                format_f64(self.abc.constant_pool.doubles[i as usize - 1])
+            }
+            i => format!("#{i}"),
+        }
+    }
+
+    fn string_ref(&self, index: &Index<String>) -> String {
+        match index.0 {
+            0 => "*".to_string(),
+            i if is_by_value(&self.strings, i) => {
+                format_string(&self.abc.constant_pool.strings[i as usize - 1])
+            }
+            i => format!("#{i}"),
+        }
+    }
+
+    fn namespace_ref(&self, index: &Index<Namespace>) -> String {
+        match index.0 {
+            0 => "*".to_string(),
+            i if is_by_value(&self.namespaces, i) => {
+                self.namespace(&self.abc.constant_pool.namespaces[i as usize - 1])
+            }
+            i => format!("#{i}"),
+        }
+    }
+
+    fn namespace(&self, namespace: &Namespace) -> String {
+        let (kind, name) = match namespace {
+            Namespace::Namespace(name) => ("Namespace", name),
+            Namespace::Package(name) => ("Package", name),
+            Namespace::PackageInternal(name) => ("PackageInternal", name),
+            Namespace::Protected(name) => ("Protected", name),
+            Namespace::Explicit(name) => ("Explicit", name),
+            Namespace::StaticProtected(name) => ("StaticProtected", name),
+            Namespace::Private(name) => ("Private", name),
+        };
+        format!("{kind}({})", self.string_ref(name))
+    }
+
+    fn namespace_set_ref(&self, index: &Index<NamespaceSet>) -> String {
+        match index.0 {
+            0 => "*".to_string(),
+            i if is_by_value(&self.namespace_sets, i) => {
+                self.namespace_set(&self.abc.constant_pool.namespace_sets[i as usize - 1])
+            }
+            i => format!("#{i}"),
+        }
+    }
+
+    fn namespace_set(&self, namespace_set: &NamespaceSet) -> String {
+        let namespaces: Vec<_> = namespace_set
+            .iter()
+            .map(|namespace| self.namespace_ref(namespace))
+            .collect();
+        format!("[{}]", namespaces.join(", "))
+    }
+
+    fn multiname_ref(&self, index: &Index<Multiname>) -> String {
+        self.multiname_ref_before(index, u32::MAX)
+    }
+
+    /// Writes a reference to a multiname, which can only be written as a
+    /// value if it's before the multiname at index `limit`.
+    fn multiname_ref_before(&self, index: &Index<Multiname>, limit: u32) -> String {
+        match index.0 {
+            0 => "*".to_string(),
+            i if i < limit && is_by_value(&self.multinames, i) => {
+                self.multiname(&self.abc.constant_pool.multinames[i as usize - 1], i)
+            }
+            i => format!("#{i}"),
+        }
+    }
+
+    /// Writes the multiname at index `index`.
+    fn multiname(&self, multiname: &Multiname, index: u32) -> String {
+        match multiname {
+            Multiname::QName { namespace, name } => format!(
+                "QName({}, {})",
+                self.namespace_ref(namespace),
+                self.string_ref(name)
+            ),
+            Multiname::QNameA { namespace, name } => format!(
+                "QNameA({}, {})",
+                self.namespace_ref(namespace),
+                self.string_ref(name)
+            ),
+            Multiname::RTQName { name } => format!("RTQName({})", self.string_ref(name)),
+            Multiname::RTQNameA { name } => format!("RTQNameA({})", self.string_ref(name)),
+            Multiname::RTQNameL => "RTQNameL".to_string(),
+            Multiname::RTQNameLA => "RTQNameLA".to_string(),
+            Multiname::Multiname {
+                namespace_set,
+                name,
+            } => format!(
+                "Multiname({}, {})",
+                self.namespace_set_ref(namespace_set),
+                self.string_ref(name)
+            ),
+            Multiname::MultinameA {
+                namespace_set,
+                name,
+            } => format!(
+                "MultinameA({}, {})",
+                self.namespace_set_ref(namespace_set),
+                self.string_ref(name)
+            ),
+            Multiname::MultinameL { namespace_set } => {
+                format!("MultinameL({})", self.namespace_set_ref(namespace_set))
+            }
+            Multiname::MultinameLA { namespace_set } => {
+                format!("MultinameLA({})", self.namespace_set_ref(namespace_set))
+            }
+            Multiname::TypeName {
+                base_type,
+                parameters,
+            } => {
+                let parameters: Vec<_> = parameters
+                    .iter()
+                    .map(|parameter| self.multiname_ref_before(parameter, index))
+                    .collect();
+                format!(
+                    "TypeName({}, [{}])",
+                    self.multiname_ref_before(base_type, index),
+                    parameters.join(", ")
+                )
+            }
+        }
+    }
+
+    fn default_value(&self, value: &DefaultValue) -> String {
+        match value {
+            DefaultValue::Int(i) => format!("Int({})", self.int_ref(i)),
+            DefaultValue::Uint(i) => format!("Uint({})", self.uint_ref(i)),
+            DefaultValue::Double(i) => format!("Double({})", self.double_ref(i)),
+            DefaultValue::String(i) => format!("String({})", self.string_ref(i)),
+            DefaultValue::True => "True".to_string(),
+            DefaultValue::False => "False".to_string(),
+            DefaultValue::Null => "Null".to_string(),
+            DefaultValue::Undefined => "Undefined".to_string(),
+            DefaultValue::Namespace(i) => format!("Namespace({})", self.namespace_ref(i)),
+            DefaultValue::Package(i) => format!("Package({})", self.namespace_ref(i)),
+            DefaultValue::PackageInternal(i) => {
+                format!("PackageInternal({})", self.namespace_ref(i))
+            }
+            DefaultValue::Protected(i) => format!("Protected({})", self.namespace_ref(i)),
+            DefaultValue::Explicit(i) => format!("Explicit({})", self.namespace_ref(i)),
+            DefaultValue::StaticProtected(i) => {
+                format!("StaticProtected({})", self.namespace_ref(i))
+            }
+            DefaultValue::Private(i) => format!("Private({})", self.namespace_ref(i)),
+        }
+    }
+
+    fn flags<'b>(&mut self, mut names: impl Iterator<Item = &'b str>) {
+        if let Some(name) = names.next() {
+            let _ = write!(self.output, " flags {name}");
+            for name in names {
+                let _ = write!(self.output, " | {name}");
+            }
+        }
+    }
+
+    fn method(&mut self, i: usize, method: &Method) {
+        let _ = write!(self.output, "method #{i}");
+        if method.name.0 != 0 {
+            let _ = write!(self.output, " name {}", self.string_ref(&method.name));
+        }
+        if method.return_type.0 != 0 {
+            let _ = write!(
+                self.output,
+                " returns {}",
+                self.multiname_ref(&method.return_type)
+            );
+        }
+        self.flags(method.flags.iter_names().map(|(name, _)| name));
+        if method.params.is_empty() {
+            self.output.push('\n');
+            return;
+        }
+
+        self.output.push_str(" {\n");
+        for param in &method.params {
+            let _ = write!(self.output, "    param {}", self.multiname_ref(&param.kind));
+            if let Some(name) = &param.name {
+                let _ = write!(self.output, " name {}", self.string_ref(name));
+            }
+            if let Some(value) = &param.default_value {
+                let _ = write!(self.output, " default {}", self.default_value(value));
+            }
+            self.output.push('\n');
+        }
+        self.output.push_str("}\n");
+    }
+
+    fn metadata(&mut self, i: usize, metadata: &Metadata) {
+        let _ = write!(
+            self.output,
+            "metadata #{i} name {}",
+            self.string_ref(&metadata.name)
+        );
+        if metadata.items.is_empty() {
+            self.output.push('\n');
+            return;
+        }
+
+        self.output.push_str(" {\n");
+        for item in &metadata.items {
+            let _ = writeln!(
+                self.output,
+                "    item {} {}",
+                self.string_ref(&item.key),
+                self.string_ref(&item.value)
+            );
+        }
+        self.output.push_str("}\n");
+    }
+
+    fn class(&mut self, i: usize, instance: &Instance, class: &Class) {
+        let _ = write!(
+            self.output,
+            "class #{i} name {} extends {}",
+            self.multiname_ref(&instance.name),
+            self.multiname_ref(&instance.super_name)
+        );
+        if instance.is_sealed {
+            self.output.push_str(" sealed");
+        }
+        if instance.is_final {
+            self.output.push_str(" final");
+        }
+        if instance.is_interface {
+            self.output.push_str(" interface");
+        }
+        if let Some(namespace) = &instance.protected_namespace {
+            let _ = write!(self.output, " protected {}", self.namespace_ref(namespace));
+        }
+        if !instance.interfaces.is_empty() {
+            let interfaces: Vec<_> = instance
+                .interfaces
+                .iter()
+                .map(|interface| self.multiname_ref(interface))
+                .collect();
+            let _ = write!(self.output, " implements [{}]", interfaces.join(", "));
+        }
+        let _ = write!(
+            self.output,
+            " iinit #{} cinit #{}",
+            instance.init_method.0, class.init_method.0
+        );
+
+        if instance.traits.is_empty() && class.traits.is_empty() {
+            self.output.push('\n');
+            return;
+        }
+        self.output.push_str(" {\n");
+        for t in &instance.traits {
+            self.output.push_str("    ");
+            self.trait_line(t);
+        }
+        for t in &class.traits {
+            self.output.push_str("    static ");
+            self.trait_line(t);
+        }
+        self.output.push_str("}\n");
+    }
+
+    /// Writes a list of traits in braces, ending the current line.
+    fn traits_block(&mut self, traits: &[Trait]) {
+        if traits.is_empty() {
+            self.output.push('\n');
+            return;
+        }
+        self.output.push_str(" {\n");
+        for t in traits {
+            self.output.push_str("    ");
+            self.trait_line(t);
+        }
+        self.output.push_str("}\n");
+    }
+
+    fn trait_line(&mut self, t: &Trait) {
+        let kind = match t.kind {
+            TraitKind::Slot { .. } => "slot",
+            TraitKind::Method { .. } => "method",
+            TraitKind::Getter { .. } => "getter",
+            TraitKind::Setter { .. } => "setter",
+            TraitKind::Class { .. } => "class",
+            TraitKind::Function { .. } => "function",
+            TraitKind::Const { .. } => "const",
+        };
+        let _ = write!(self.output, "trait {kind} {}", self.multiname_ref(&t.name));
+
+        match &t.kind {
+            TraitKind::Slot {
+                slot_id,
+                type_name,
+                value,
+            }
+            | TraitKind::Const {
+                slot_id,
+                type_name,
+                value,
+            } => {
+                let _ = write!(
+                    self.output,
+                    " slot_id {slot_id} type {}",
+                    self.multiname_ref(type_name)
+                );
+                if let Some(value) = value {
+                    let _ = write!(self.output, " value {}", self.default_value(value));
+                }
+            }
+            TraitKind::Method { disp_id, method }
+            | TraitKind::Getter { disp_id, method }
+            | TraitKind::Setter { disp_id, method } => {
+                let _ = write!(self.output, " disp_id {disp_id} method #{}", method.0);
+            }
+            TraitKind::Class { slot_id, class } => {
+                let _ = write!(self.output, " slot_id {slot_id} class #{}", class.0);
+            }
+            TraitKind::Function { slot_id, function } => {
+                let _ = write!(self.output, " slot_id {slot_id} function #{}", function.0);
+            }
+        }
+
+        if t.is_final {
+            self.output.push_str(" final");
+        }
+        if t.is_override {
+            self.output.push_str(" override");
+        }
+        if !t.metadata.is_empty() {
+            let metadata: Vec<_> = t
+                .metadata
+                .iter()
+                .map(|metadata| format!("#{}", metadata.0))
+                .collect();
+            let _ = write!(self.output, " metadata [{}]", metadata.join(", "));
+        }
+        self.output.push('\n');
+    }
+
+    /// Decodes the instructions of a method body, falling back to raw bytes
+    /// for instructions that wouldn't be encoded back to the same bytes.
+    fn decode(code: &[u8]) -> Vec<Item<'_>> {
+        let mut items = Vec::new();
+        let mut offset = 0;
+        while offset < code.len() {
+            let mut reader = Reader::new(&code[offset..]);
+            let (bytes, op) = match reader.read_op() {
+                Ok(op) => {
+                    let bytes = &code[offset..code.len() - reader.as_slice().len()];
+                    let mut output = Vec::with_capacity(bytes.len());
+                    let is_exact =
+                        Writer::new(&mut output).write_op(&op).is_ok() && output == bytes;
+                    (bytes, Some(op).filter(|_| is_exact))
+                }
+                Err(_) => (&code[offset..], None),
+            };
+            items.push(Item { offset, bytes, op });
+            offset += bytes.len();
+        }
+        items
+    }
+
+    fn method_body(&mut self, i: usize, body: &MethodBody) {
+        let _ = writeln!(
+            self.output,
+            "body #{i} method #{} max_stack {} locals {} init_scope_depth {} max_scope_depth {} {{",
+            body.method.0,
+            body.max_stack,
+            body.num_locals,
+            body.init_scope_depth,
+            body.max_scope_depth
+        );
+
+        let items = Self::decode(&body.code);
+
+        // Give labels to the branch targets and exception offsets that are at
+        // the start of an instruction, or at the end of the code.
+        let mut labels = BTreeMap::new();
+        let is_boundary = |target: usize| {
+            target == body.code.len()
+                || items
+                    .binary_search_by_key(&target, |item| item.offset)
+                    .is_ok()
+        };
+        let targets =
+            items
+                .iter()
+                .flat_map(Item::targets)
+                .flatten()
+                .chain(body.exceptions.iter().flat_map(|exception| {
+                    [
+                        exception.from_offset,
+                        exception.to_offset,
+                        exception.target_offset,
+                    ]
+                    .map(|offset| offset as usize)
+                }));
+        for target in targets {
+            if is_boundary(target) {
+                labels.insert(target, 0);
+            }
+        }
+        for (i, label) in labels.values_mut().enumerate() {
+            *label = i + 1;
+        }
+
+        for item in &items {
+            if let Some(label) = labels.get(&item.offset) {
+                let _ = writeln!(self.output, "L{label}:");
+            }
+            self.output.push_str("    ");
+            match &item.op {
+                Some(op) => self.op(item, op, &labels),
+                None => self.raw(item.bytes),
+            }
+            self.output.push('\n');
+        }
+        if let Some(label) = labels.get(&body.code.len()) {
+            let _ = writeln!(self.output, "L{label}:");
+        }
+
+        let offset = |offset: u32| match labels.get(&(offset as usize)) {
+            Some(label) => format!("L{label}"),
+            None => format!("#{offset}"),
+        };
+        for exception in &body.exceptions {
+            let _ = writeln!(
+                self.output,
+                "    exception from {} to {} target {} type {} var {}",
+                offset(exception.from_offset),
+                offset(exception.to_offset),
+                offset(exception.target_offset),
+                self.multiname_ref(&exception.type_name),
+                self.multiname_ref(&exception.variable_name)
+            );
+        }
+        for t in &body.traits {
+            self.output.push_str("    ");
+            self.trait_line(t);
+        }
+        self.output.push_str("}\n");
+    }
+
+    fn raw(&mut self, bytes: &[u8]) {
+        // Long runs of bytes are split over several lines.
+        for (i, chunk) in bytes.chunks(16).enumerate() {
+            if i > 0 {
+                self.output.push_str("\n    ");
+            }
+            self.output.push_str("raw");
+            for byte in chunk {
+                let _ = write!(self.output, " {byte:02X}");
+            }
+        }
+    }
+
+    fn op(&mut self, item: &Item, op: &Op, labels: &BTreeMap<usize, usize>) {
+        let label = |target: Option<usize>, offset: i32| match target
+            .and_then(|target| labels.get(&target))
+        {
+            Some(label) => format!("L{label}"),
+            None => format!("#{offset}"),
+        };
+        let method = |index: &Index<Method>| format!("#{}", index.0);
+
+        let args = match op {
+            Op::ApplyType { num_types: n }
+            | Op::BkptLine { line_num: n }
+            | Op::Call { num_args: n }
+            | Op::Construct { num_args: n }
+            | Op::ConstructSuper { num_args: n }
+            | Op::DebugLine { line_num: n }
+            | Op::DecLocal { index: n }
+            | Op::DecLocalI { index: n }
+            | Op::GetGlobalSlot { index: n }
+            | Op::GetLocal { index: n }
+            | Op::GetOuterScope { index: n }
+            | Op::GetSlot { index: n }
+            | Op::IncLocal { index: n }
+            | Op::IncLocalI { index: n }
+            | Op::Kill { index: n }
+            | Op::NewArray { num_args: n }
+            | Op::NewObject { num_args: n }
+            | Op::SetGlobalSlot { index: n }
+            | Op::SetLocal { index: n }
+            | Op::SetSlot { index: n } => vec![n.to_string()],
+            Op::GetScopeObject { index: n } | Op::PushByte { value: n } => vec![n.to_string()],
+            Op::PushShort { value } => vec![value.to_string()],
+            Op::AsType { type_name: index }
+            | Op::Coerce { index }
+            | Op::DeleteProperty { index }
+            | Op::FindDef { index }
+            | Op::FindProperty { index }
+            | Op::FindPropStrict { index }
+            | Op::GetDescendants { index }
+            | Op::GetLex { index }
+            | Op::GetProperty { index }
+            | Op::GetSuper { index }
+            | Op::InitProperty { index }
+            | Op::IsType { index }
+            | Op::SetProperty { index }
+            | Op::SetSuper { index } => vec![self.multiname_ref(index)],
+            Op::CallProperty { index, num_args }
+            | Op::CallPropLex { index, num_args }
+            | Op::CallPropVoid { index, num_args }
+            | Op::CallSuper { index, num_args }
+            | Op::CallSuperVoid { index, num_args }
+            | Op::ConstructProp { index, num_args } => {
+                vec![self.multiname_ref(index), num_args.to_string()]
+            }
+            Op::CallMethod { index, num_args } => vec![index.to_string(), num_args.to_string()],
+            Op::CallStatic { index, num_args } => vec![method(index), num_args.to_string()],
+            Op::Debug {
+                is_local_register,
+                register_name,
+                register,
+            } => vec![
+                is_local_register.to_string(),
+                self.string_ref(register_name),
+                register.to_string(),
+            ],
+            Op::DebugFile { file_name: index }
+            | Op::Dxns { index }
+            | Op::PushString { value: index } => vec![self.string_ref(index)],
+            Op::HasNext2 {
+                object_register,
+                index_register,
+            } => vec![object_register.to_string(), index_register.to_string()],
+            Op::LookupSwitch(lookup_switch) => {
+                let targets = item.targets();
+                let cases: Vec<_> = lookup_switch
+                    .case_offsets
+                    .iter()
+                    .zip(&targets[1..])
+                    .map(|(offset, target)| label(*target, *offset))
+                    .collect();
+                vec![
+                    label(targets[0], lookup_switch.default_offset),
+                    format!("[{}]", cases.join(", ")),
+                ]
+            }
+            Op::NewCatch { index } => vec![format!("#{}", index.0)],
+            Op::NewClass { index } => vec![format!("#{}", index.0)],
+            Op::NewFunction { index } => vec![method(index)],
+            Op::PushDouble { value } => vec![self.double_ref(value)],
+            Op::PushInt { value } => vec![self.int_ref(value)],
+            Op::PushNamespace { value } => vec![self.namespace_ref(value)],
+            Op::PushUint { value } => vec![self.uint_ref(value)],
+            op => match branch_offset(op) {
+                Some(offset) => vec![label(item.targets()[0], offset)],
+                None => Vec::new(),
+            },
+        };
+
+        self.output.push_str(&op_name(op));
+        if !args.is_empty() {
+            let _ = write!(self.output, " {}", args.join(", "));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::asm::assemble;
+
+    #[test]
+    fn disassemble_abc() {
+        let text = r#"version 46.16
+
+int #1 -3
+double #1 NaN
+
+string #1 ""
+string #2 "A"
+string #3 "e"
+string #4 "a\x80\n"
+
+namespace #1 Package("")
+namespace #2 Protected("A")
+
+multiname #1 QName(Package(""), "A")
+multiname #2 QName(Package(""), "e")
+
+method #0 name "A" flags HAS_OPTIONAL {
+    param * default Int(-3)
+}
+
+method #1
+
+class #0 name QName(Package(""), "A") extends * sealed protected Protected("A") iinit #0 cinit #1 {
+    trait slot QName(Package(""), "e") slot_id 1 type * value Double(NaN)
+    static trait method QName(Package(""), "A") disp_id 0 method #1 final
+}
+
+body #0 method #0 max_stack 1 locals 2 init_scope_depth 0 max_scope_depth 1 {
+L1:
+    pushstring "a\x80\n"
+    iftrue L2
+    lookupswitch L1, [L2, #-1]
+L2:
+    returnvoid
+    raw FF
+    exception from L1 to L2 target L2 type QName(Package(""), "A") var QName(Package(""), "e")
+}
+"#;
+        let abc = assemble(text).unwrap();
+        assert_eq!(disassemble(&abc), text);
+    }
+}
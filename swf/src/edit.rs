@@ -0,0 +1,443 @@
+//! Editing of existing SWF files.
+//!
+//! `SwfEditor` keeps the top-level tags of an SWF as raw bytes, so tags that
+//! aren't edited, including unknown tags, are written back byte-for-byte.
+
+use crate::{
+    error::{Error, Result},
+    read::{TagEntry, TagIterator},
+    tag_code::TagCode,
+    types::*,
+    write::{write_swf_raw_tags, write_tag},
+};
+use std::borrow::Cow;
+use std::io::Write;
+
+/// Returns whether a tag with the given code defines a character, with the
+/// character ID being the first field of the tag.
+fn is_definition(tag_code: TagCode) -> bool {
+    matches!(
+        tag_code,
+        TagCode::DefineBinaryData
+            | TagCode::DefineBits
+            | TagCode::DefineBitsJpeg2
+            | TagCode::DefineBitsJpeg3
+            | TagCode::DefineBitsJpeg4
+            | TagCode::DefineBitsLossless
+            | TagCode::DefineBitsLossless2
+            | TagCode::DefineButton
+            | TagCode::DefineButton2
+            | TagCode::DefineEditText
+            | TagCode::DefineFont
+            | TagCode::DefineFont2
+            | TagCode::DefineFont3
+            | TagCode::DefineFont4
+            | TagCode::DefineMorphShape
+            | TagCode::DefineMorphShape2
+            | TagCode::DefineShape
+            | TagCode::DefineShape2
+            | TagCode::DefineShape3
+            | TagCode::DefineShape4
+            | TagCode::DefineSound
+            | TagCode::DefineSprite
+            | TagCode::DefineText
+            | TagCode::DefineText2
+            | TagCode::DefineVideoStream
+    )
+}
+
+/// Returns whether a tag with the given code adds to the definition of a
+/// character defined by an earlier tag, with the character ID being the first
+/// field of the tag.
+fn is_definition_extension(tag_code: TagCode) -> bool {
+    matches!(
+        tag_code,
+        TagCode::DefineButtonCxform
+            | TagCode::DefineButtonSound
+            | TagCode::DefineFontAlignZones
+            | TagCode::DefineFontInfo
+            | TagCode::DefineFontInfo2
+            | TagCode::DefineFontName
+            | TagCode::DefineScalingGrid
+            | TagCode::NameCharacter
+            | TagCode::VideoFrame
+    )
+}
+
+/// Returns the ID of the character that a tag defines or adds to, if any.
+fn character_id(entry: &TagEntry<'_>, include_extensions: bool) -> Option<CharacterId> {
+    let tag_code = entry.tag_code()?;
+    if is_definition(tag_code) || (include_extensions && is_definition_extension(tag_code)) {
+        let id = entry.data.get(..2)?;
+        Some(u16::from_le_bytes([id[0], id[1]]))
+    } else {
+        None
+    }
+}
+
+/// An editable SWF.
+///
+/// The top-level tags of the SWF can be inserted, replaced and removed, either
+/// by their index or by the ID of the character they define, and the header
+/// can be changed before the SWF is written out again.
+///
+/// # Example
+/// ```
+/// # std::env::set_current_dir(env!("CARGO_MANIFEST_DIR"));
+/// use swf::*;
+///
+/// let data = std::fs::read("tests/swfs/DefineSprite.swf").unwrap();
+/// let swf_buf = decompress_swf(&data[..]).unwrap();
+/// let mut editor = SwfEditor::new(&swf_buf).unwrap();
+/// editor.header.frame_rate = Fixed8::from_f32(30.0);
+/// editor.header.compression = Compression::Lzma;
+/// editor.remove(1).unwrap();
+///
+/// let mut output = Vec::new();
+/// editor.write(&mut output).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct SwfEditor<'a> {
+    /// The header of the SWF, which is used when writing the SWF.
+    ///
+    /// Note that the version is also used to encode new tags, and that
+    /// `num_frames` is replaced by the number of `ShowFrame` tags when writing.
+    pub header: Header,
+
+    /// The top-level tags, each including its tag header, but excluding the
+    /// final `End` tag.
+    tags: Vec<Cow<'a, [u8]>>,
+}
+
+impl<'a> SwfEditor<'a> {
+    /// Creates an editor for a decompressed SWF.
+    ///
+    /// Returns an error if the tag stream of the SWF is truncated.
+    pub fn new(swf_buf: &'a SwfBuf) -> Result<Self> {
+        let data = &swf_buf.data[..];
+        let mut tags = Vec::new();
+        for entry in crate::read::iter_tags(swf_buf) {
+            let entry = entry?;
+            if entry.sprite_id.is_some() {
+                continue;
+            }
+            let data_offset = entry.data.as_ptr() as usize - data.as_ptr() as usize;
+            tags.push(Cow::Borrowed(
+                &data[entry.offset..data_offset + entry.length],
+            ));
+        }
+
+        Ok(Self {
+            header: swf_buf.header.swf_header().clone(),
+            tags,
+        })
+    }
+
+    /// Returns the number of top-level tags, excluding the final `End` tag.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tags.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// Returns the tag at the given index.
+    pub fn get(&self, index: usize) -> Option<TagEntry<'_>> {
+        let tag = self.tags.get(index)?;
+        TagIterator::new(tag, self.header.version).next()?.ok()
+    }
+
+    /// Returns an iterator over the top-level tags.
+    pub fn iter(&self) -> impl Iterator<Item = TagEntry<'_>> {
+        (0..self.len()).filter_map(|index| self.get(index))
+    }
+
+    /// Returns the raw bytes of the tag at the given index, including its tag
+    /// header.
+    #[inline]
+    pub fn get_raw(&self, index: usize) -> Option<&[u8]> {
+        self.tags.get(index).map(|tag| &tag[..])
+    }
+
+    /// Returns the ID of the character defined by the tag at the given index.
+    pub fn character_id(&self, index: usize) -> Option<CharacterId> {
+        character_id(&self.get(index)?, false)
+    }
+
+    /// Returns the index of the tag defining the given character.
+    pub fn position(&self, id: CharacterId) -> Option<usize> {
+        self.iter()
+            .position(|entry| character_id(&entry, false) == Some(id))
+    }
+
+    fn position_or_err(&self, id: CharacterId) -> Result<usize> {
+        self.position(id)
+            .ok_or_else(|| Error::invalid_data(format!("Character {id} is not defined")))
+    }
+
+    /// Encodes a tag, including its tag header.
+    fn encode(&self, tag: &Tag<'_>) -> Result<Cow<'a, [u8]>> {
+        let mut data = Vec::new();
+        write_tag(tag, self.header.version, &mut data)?;
+        Ok(Cow::Owned(data))
+    }
+
+    /// Checks that raw tag bytes contain exactly one tag.
+    fn check_raw(&self, tag: Cow<'a, [u8]>) -> Result<Cow<'a, [u8]>> {
+        let entry = TagIterator::new(&tag, self.header.version)
+            .next()
+            .ok_or_else(|| Error::invalid_data("Raw tag is an End tag"))??;
+        if entry.data.as_ptr() as usize + entry.length != tag.as_ptr() as usize + tag.len() {
+            return Err(Error::invalid_data("Raw tag has trailing data"));
+        }
+        Ok(tag)
+    }
+
+    /// Inserts a tag at the given index.
+    pub fn insert(&mut self, index: usize, tag: &Tag<'_>) -> Result<()> {
+        let tag = self.encode(tag)?;
+        self.tags.insert(index, tag);
+        Ok(())
+    }
+
+    /// Inserts a tag, given as raw bytes including its tag header, at the
+    /// given index.
+    ///
+    /// The tag is written byte-for-byte.
+    pub fn insert_raw(&mut self, index: usize, tag: impl Into<Cow<'a, [u8]>>) -> Result<()> {
+        let tag = self.check_raw(tag.into())?;
+        self.tags.insert(index, tag);
+        Ok(())
+    }
+
+    /// Replaces the tag defining the given character.
+    ///
+    /// The new tag should usually define the same character ID.
+    pub fn replace(&mut self, id: CharacterId, tag: &Tag<'_>) -> Result<()> {
+        let index = self.position_or_err(id)?;
+        self.tags[index] = self.encode(tag)?;
+        Ok(())
+    }
+
+    /// Replaces the tag defining the given character with a tag given as raw
+    /// bytes, including its tag header.
+    ///
+    /// The tag is written byte-for-byte. This can be used along with
+    /// `set_character_id` to copy a character from another SWF.
+    pub fn replace_raw(&mut self, id: CharacterId, tag: impl Into<Cow<'a, [u8]>>) -> Result<()> {
+        let index = self.position_or_err(id)?;
+        self.tags[index] = self.check_raw(tag.into())?;
+        Ok(())
+    }
+
+    /// Removes the tag defining the given character, along with any tags that
+    /// add to its definition, such as `DefineFontInfo` or `DefineScalingGrid`.
+    ///
+    /// Tags placing the character on a timeline are not removed.
+    pub fn remove(&mut self, id: CharacterId) -> Result<()> {
+        self.position_or_err(id)?;
+        let version = self.header.version;
+        self.tags.retain(|tag| {
+            let entry = TagIterator::new(tag, version).next();
+            !matches!(entry, Some(Ok(entry)) if character_id(&entry, true) == Some(id))
+        });
+        Ok(())
+    }
+
+    /// Removes the tag at the given index and returns its raw bytes, or `None`
+    /// if the index is out of range.
+    pub fn remove_at(&mut self, index: usize) -> Option<Cow<'a, [u8]>> {
+        (index < self.tags.len()).then(|| self.tags.remove(index))
+    }
+
+    /// Changes the ID of the character defined or added to by the tag at the
+    /// given index.
+    ///
+    /// Other tags referring to the character are not changed.
+    pub fn set_character_id(&mut self, index: usize, id: CharacterId) -> Result<()> {
+        let entry = self
+            .get(index)
+            .ok_or_else(|| Error::invalid_data("Tag index out of range"))?;
+        if character_id(&entry, true).is_none() {
+            return Err(Error::invalid_data("Tag does not refer to a character"));
+        }
+        let id_offset = entry.data.as_ptr() as usize - self.tags[index].as_ptr() as usize;
+        self.tags[index].to_mut()[id_offset..id_offset + 2].copy_from_slice(&id.to_le_bytes());
+        Ok(())
+    }
+
+    /// Writes the SWF to an output stream, compressed according to the header.
+    ///
+    /// The frame count in the header is set to the number of `ShowFrame` tags.
+    pub fn write<W: Write>(&self, output: W) -> Result<()> {
+        let num_frames = self
+            .iter()
+            .filter(|entry| entry.tag_code() == Some(TagCode::ShowFrame))
+            .count();
+        let header = Header {
+            num_frames: u16::try_from(num_frames)
+                .map_err(|_| Error::invalid_data("Too many frames"))?,
+            ..self.header.clone()
+        };
+
+        let mut data = Vec::with_capacity(self.tags.iter().map(|tag| tag.len()).sum::<usize>() + 2);
+        for tag in &self.tags {
+            data.extend_from_slice(tag);
+        }
+        // End tag.
+        data.extend_from_slice(&[0, 0]);
+        write_swf_raw_tags(&header, &data, output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read::{decompress_swf, parse_swf};
+    use crate::write::write_swf;
+
+    fn define_binary_data(id: CharacterId, data: &[u8]) -> Tag<'_> {
+        Tag::DefineBinaryData(DefineBinaryData { id, data })
+    }
+
+    fn dummy_swf() -> SwfBuf {
+        let header = Header::default_with_swf_version(10);
+        let tags = [
+            define_binary_data(1, b"one"),
+            Tag::DefineScalingGrid {
+                id: 1,
+                splitter_rect: Default::default(),
+            },
+            define_binary_data(2, b"two"),
+            Tag::ShowFrame,
+        ];
+        let mut data = Vec::new();
+        write_swf(&header, &tags, &mut data).unwrap();
+        decompress_swf(&data[..]).unwrap()
+    }
+
+    fn rewrite(editor: &SwfEditor) -> SwfBuf {
+        let mut data = Vec::new();
+        editor.write(&mut data).unwrap();
+        decompress_swf(&data[..]).unwrap()
+    }
+
+    #[test]
+    fn rewrite_unchanged() {
+        for path in ["tests/swfs/DefineSprite.swf", "tests/swfs/zlib.swf"] {
+            let data = std::fs::read(path).unwrap();
+            let swf_buf = decompress_swf(&data[..]).unwrap();
+            let editor = SwfEditor::new(&swf_buf).unwrap();
+
+            assert_eq!(rewrite(&editor).data, swf_buf.data, "{path}");
+        }
+    }
+
+    #[test]
+    fn change_header() {
+        let swf_buf = dummy_swf();
+        let mut editor = SwfEditor::new(&swf_buf).unwrap();
+        let stage_size = Rectangle {
+            x_min: Twips::ZERO,
+            x_max: Twips::from_pixels(320.0),
+            y_min: Twips::ZERO,
+            y_max: Twips::from_pixels(240.0),
+        };
+
+        for compression in [Compression::None, Compression::Zlib, Compression::Lzma] {
+            editor.header = Header {
+                compression,
+                version: 8,
+                stage_size: stage_size.clone(),
+                frame_rate: Fixed8::from_f32(12.5),
+                num_frames: 1,
+            };
+            let output = rewrite(&editor);
+
+            assert_eq!(output.header.swf_header(), &editor.header);
+            assert_eq!(output.data[..], swf_buf.data[..]);
+        }
+    }
+
+    #[test]
+    fn edit_characters() {
+        let swf_buf = dummy_swf();
+        let mut editor = SwfEditor::new(&swf_buf).unwrap();
+        assert_eq!(editor.len(), 4);
+        assert_eq!(editor.position(2), Some(2));
+        assert_eq!(editor.position(3), None);
+        assert_eq!(editor.character_id(1), None);
+        assert_eq!(editor.character_id(2), Some(2));
+
+        editor.replace(2, &define_binary_data(2, b"deux")).unwrap();
+        editor.insert(0, &define_binary_data(3, b"three")).unwrap();
+        editor.remove(1).unwrap();
+        assert!(editor.remove(1).is_err());
+        assert!(editor.replace(4, &Tag::ShowFrame).is_err());
+        assert!(editor.remove_at(3).is_none());
+
+        let output = rewrite(&editor);
+        assert_eq!(
+            parse_swf(&output).unwrap().tags,
+            [
+                define_binary_data(3, b"three"),
+                define_binary_data(2, b"deux"),
+                Tag::ShowFrame,
+            ]
+        );
+    }
+
+    #[test]
+    fn recount_frames() {
+        let swf_buf = dummy_swf();
+        let mut editor = SwfEditor::new(&swf_buf).unwrap();
+        editor.header.num_frames = 5;
+        assert_eq!(rewrite(&editor).header.num_frames(), 1);
+
+        editor.insert(2, &Tag::ShowFrame).unwrap();
+        editor.insert(4, &Tag::ShowFrame).unwrap();
+        assert_eq!(rewrite(&editor).header.num_frames(), 3);
+
+        let removed = editor.remove_at(4).unwrap();
+        assert_eq!(removed[..], [0x40, 0]);
+        editor.remove_at(2).unwrap();
+        editor.remove_at(3).unwrap();
+        assert_eq!(rewrite(&editor).header.num_frames(), 0);
+    }
+
+    #[test]
+    fn raw_tags() {
+        let swf_buf = dummy_swf();
+        let mut editor = SwfEditor::new(&swf_buf).unwrap();
+
+        // An unknown tag with a long tag header, which must be kept as is.
+        let unknown = [0xFF, 0x3F, 2, 0, 0, 0, 0xAB, 0xCD];
+        editor.insert_raw(3, &unknown[..]).unwrap();
+        assert!(editor.insert_raw(0, &unknown[..7]).is_err());
+        assert!(editor.insert_raw(0, &[0x40, 0, 0x40, 0][..]).is_err());
+
+        // Copy the first character to a new ID.
+        let copy = editor.get_raw(0).unwrap().to_vec();
+        editor.insert_raw(4, copy).unwrap();
+        editor.set_character_id(4, 5).unwrap();
+        assert!(editor.set_character_id(5, 6).is_err());
+
+        let output = rewrite(&editor);
+        let copy = &output.data[swf_buf.data.len() - 4 + unknown.len()..][..9];
+        assert_eq!(copy, [0xC9, 0x15, 5, 0, 0, 0, 0, 0, b'o']);
+        assert!(output.data.windows(unknown.len()).any(|w| w == unknown));
+
+        let tags = parse_swf(&output).unwrap().tags;
+        assert_eq!(
+            tags[3],
+            Tag::Unknown {
+                tag_code: 255,
+                data: &[0xAB, 0xCD]
+            }
+        );
+        assert_eq!(tags[4], define_binary_data(5, b"one"));
+    }
+}
@@ -17,6 +17,7 @@ extern crate num_traits;
 
 pub mod avm1;
 pub mod avm2;
+pub mod edit;
 pub mod error;
 // TODO: Make this private?
 pub mod extensions;
@@ -30,7 +31,8 @@ pub mod write;
 mod test_data;
 
 /// Re-exports
-pub use read::{decompress_swf, parse_swf};
+pub use edit::SwfEditor;
+pub use read::{decompress_swf, iter_tags, parse_swf};
 pub use string::*;
 pub use tag_code::TagCode;
 pub use types::*;
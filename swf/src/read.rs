@@ -30,6 +30,27 @@ pub fn parse_swf(swf_buf: &SwfBuf) -> Result<Swf<'_>> {
     })
 }
 
+/// Iterate over the tags of a decompressed SWF without parsing them.
+///
+/// Unlike `parse_swf`, this doesn't parse every tag up front; see
+/// `TagIterator` for details.
+///
+/// # Example
+/// ```
+/// # std::env::set_current_dir(env!("CARGO_MANIFEST_DIR"));
+/// let data = std::fs::read("tests/swfs/DefineSprite.swf").unwrap();
+/// let stream = swf::decompress_swf(&data[..]).unwrap();
+/// for entry in swf::iter_tags(&stream) {
+///     let entry = entry.unwrap();
+///     if entry.tag_code() == Some(swf::TagCode::DefineSprite) {
+///         println!("Sprite at offset {}: {:?}", entry.offset, entry.parse());
+///     }
+/// }
+/// ```
+pub fn iter_tags(swf_buf: &SwfBuf) -> TagIterator<'_> {
+    TagIterator::new(&swf_buf.data[..], swf_buf.header.version())
+}
+
 /// Extracts an SWF inside of an SWZ file.
 pub fn extract_swz(input: &[u8]) -> Result<Vec<u8>> {
     let asn1_blocks =
@@ -2540,6 +2561,213 @@ impl<'a> Reader<'a> {
     }
 }
 
+/// A tag found by a `TagIterator`.
+///
+/// Only the tag header has been read; call `parse` to parse the tag itself.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TagEntry<'a> {
+    /// The code of the tag, which may not be a known `TagCode`.
+    pub code: u16,
+
+    /// The offset of the tag header from the start of the tag stream.
+    pub offset: usize,
+
+    /// The length of the tag data, not including the tag header.
+    pub length: usize,
+
+    /// The ID of the `DefineSprite` that this tag is nested in, or `None` for
+    /// top-level tags.
+    pub sprite_id: Option<CharacterId>,
+
+    /// The tag data, not including the tag header.
+    pub data: &'a [u8],
+
+    version: u8,
+}
+
+impl<'a> TagEntry<'a> {
+    /// Returns the code of this tag, or `None` if it isn't a known tag.
+    #[inline]
+    pub fn tag_code(&self) -> Option<TagCode> {
+        TagCode::from_u16(self.code)
+    }
+
+    /// Parses this tag.
+    ///
+    /// This is equivalent to `Reader::read_tag`. Note that a `DefineSprite` is
+    /// parsed along with all of its nested tags, which the `TagIterator` will
+    /// also yield separately.
+    pub fn parse(&self) -> Result<Tag<'a>> {
+        let mut reader = Reader::new(self.data, self.version);
+        if let Some(tag_code) = self.tag_code() {
+            reader.read_tag_with_code(tag_code, self.length)
+        } else {
+            Ok(Tag::Unknown {
+                tag_code: self.code,
+                data: self.data,
+            })
+        }
+        .map_err(|e| Error::swf_parse_error(self.code, e))
+    }
+}
+
+/// An iterator over the tags of an SWF tag stream, including the tags nested
+/// inside `DefineSprite` tags.
+///
+/// Only tag headers are read while iterating, and tags are only parsed when
+/// `TagEntry::parse` is called, so scanning an SWF for a few kinds of tags is
+/// cheap. The tags nested inside a `DefineSprite` are yielded right after the
+/// `DefineSprite` itself. `End` tags are not yielded, and iteration stops at
+/// the top-level `End` tag.
+///
+/// If the tag stream is truncated, the tags before the truncated tag are
+/// yielded, followed by an error. `offset` then returns the offset of the
+/// truncated tag, so that iteration can be resumed with `from_offset` once
+/// more data is available. A `DefineSprite` is only entered once all of its
+/// data is available, so the resumed iteration always starts at a top-level
+/// tag.
+///
+/// An invalid tag nested inside a `DefineSprite` yields an error, and
+/// iteration continues after the `DefineSprite`. Any other error ends the
+/// iteration.
+///
+/// # Example
+/// ```
+/// # std::env::set_current_dir(env!("CARGO_MANIFEST_DIR"));
+/// let data = std::fs::read("tests/swfs/DefineSprite.swf").unwrap();
+/// let swf_buf = swf::decompress_swf(&data[..]).unwrap();
+/// let version = swf_buf.header.version();
+///
+/// // Pretend that only the first 20 bytes of the tag stream have been loaded.
+/// let mut tags = swf::read::TagIterator::new(&swf_buf.data[..20], version);
+/// let loaded: Vec<_> = tags.by_ref().take_while(|entry| entry.is_ok()).collect();
+///
+/// // Resume once the rest of the SWF has been loaded.
+/// let tags = swf::read::TagIterator::from_offset(&swf_buf.data, version, tags.offset());
+/// println!("{} + {} tags", loaded.len(), tags.count());
+/// ```
+#[derive(Clone, Debug)]
+pub struct TagIterator<'a> {
+    data: &'a [u8],
+    version: u8,
+
+    /// The offset of the next tag header.
+    offset: usize,
+
+    /// The end offsets and IDs of the `DefineSprite` tags being iterated
+    /// through, innermost last.
+    sprites: Vec<(usize, CharacterId)>,
+
+    finished: bool,
+}
+
+impl<'a> TagIterator<'a> {
+    /// Creates an iterator over the given tag stream, such as `SwfBuf::data`.
+    #[inline]
+    pub fn new(data: &'a [u8], version: u8) -> Self {
+        Self::from_offset(data, version, 0)
+    }
+
+    /// Creates an iterator starting at the tag header at the given offset of
+    /// the tag stream.
+    ///
+    /// The tag at the offset is treated as a top-level tag.
+    pub fn from_offset(data: &'a [u8], version: u8, offset: usize) -> Self {
+        Self {
+            data,
+            version,
+            offset,
+            sprites: Vec::new(),
+            finished: false,
+        }
+    }
+
+    /// Returns the offset of the next tag header to be read.
+    ///
+    /// After a truncated tag is reported, this is the offset of the truncated
+    /// tag.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Reads the header of the tag at `self.offset`, which must end before
+    /// `end`, and returns the tag's code, header length and data.
+    fn read_tag_header(&self, end: usize) -> Result<(u16, usize, &'a [u8])> {
+        let mut reader = Reader::new(
+            self.data.get(self.offset..end).unwrap_or_default(),
+            self.version,
+        );
+        let (code, length) = reader.read_tag_code_and_length()?;
+        let header_length = end - self.offset - reader.get_ref().len();
+        let data = reader
+            .read_slice(length)
+            .map_err(|e| Error::swf_parse_error(code, e))?;
+        Ok((code, header_length, data))
+    }
+}
+
+impl<'a> Iterator for TagIterator<'a> {
+    type Item = Result<TagEntry<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.finished {
+                return None;
+            }
+
+            // Leave any sprites that we've reached the end of, even if they're
+            // missing their `End` tag.
+            while matches!(self.sprites.last(), Some(&(end, _)) if self.offset >= end) {
+                self.sprites.pop();
+            }
+
+            let end = self.sprites.last().map_or(self.data.len(), |&(end, _)| end);
+            let (code, header_length, data) = match self.read_tag_header(end) {
+                Ok(header) => header,
+                Err(e) => {
+                    if self.sprites.is_empty() {
+                        self.finished = true;
+                    } else {
+                        // Skip the rest of the invalid sprite.
+                        self.offset = end;
+                        self.sprites.pop();
+                    }
+                    return Some(Err(e));
+                }
+            };
+
+            let offset = self.offset;
+            let sprite_id = self.sprites.last().map(|&(_, id)| id);
+            self.offset += header_length + data.len();
+
+            if code == TagCode::End as u16 {
+                match self.sprites.pop() {
+                    Some((end, _)) => self.offset = end,
+                    None => self.finished = true,
+                }
+                continue;
+            }
+
+            if code == TagCode::DefineSprite as u16 && data.len() >= 4 {
+                // The nested tags start after the sprite's ID and frame count.
+                let id = u16::from_le_bytes([data[0], data[1]]);
+                self.sprites.push((self.offset, id));
+                self.offset = offset + header_length + 4;
+            }
+
+            return Some(Ok(TagEntry {
+                code,
+                offset,
+                length: data.len(),
+                sprite_id,
+                data,
+                version: self.version,
+            }));
+        }
+    }
+}
+
 pub fn read_compression_type<R: Read>(mut input: R) -> Result<Compression> {
     let mut signature = [0u8; 3];
     input.read_exact(&mut signature)?;
@@ -3020,6 +3248,87 @@ pub mod tests {
         }
     }
 
+    /// Flattens a tag list the way `TagIterator` yields it, with the nested
+    /// tags of a sprite following the sprite.
+    fn flatten_tags<'a, 'b>(
+        tags: &'b [Tag<'a>],
+        sprite_id: Option<CharacterId>,
+    ) -> Vec<(Option<CharacterId>, &'b Tag<'a>)> {
+        let mut flattened = Vec::new();
+        for tag in tags {
+            flattened.push((sprite_id, tag));
+            if let Tag::DefineSprite(sprite) = tag {
+                flattened.extend(flatten_tags(&sprite.tags, Some(sprite.id)));
+            }
+        }
+        flattened
+    }
+
+    fn parse_entries<'a>(
+        entries: impl Iterator<Item = Result<TagEntry<'a>>>,
+    ) -> Vec<(Option<CharacterId>, Tag<'a>)> {
+        entries
+            .map(|entry| {
+                let entry = entry.unwrap();
+                (entry.sprite_id, entry.parse().unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn iter_tags() {
+        let swf_buf = read_from_file("tests/swfs/DefineSprite.swf");
+        let swf = parse_swf(&swf_buf).unwrap();
+        let tags = flatten_tags(&swf.tags, None);
+        assert!(tags.iter().any(|(sprite_id, _)| sprite_id.is_some()));
+
+        let entries = parse_entries(super::iter_tags(&swf_buf));
+        let entries: Vec<_> = entries.iter().map(|(id, tag)| (*id, tag)).collect();
+        assert_eq!(entries, tags);
+    }
+
+    #[test]
+    fn iter_tags_truncated() {
+        let swf_buf = read_from_file("tests/swfs/DefineSprite.swf");
+        let version = swf_buf.header.version();
+        let tags = parse_entries(super::iter_tags(&swf_buf));
+
+        for len in 0..swf_buf.data.len() {
+            let mut iter = TagIterator::new(&swf_buf.data[..len], version);
+            let mut partial = parse_entries(iter.by_ref().take_while(Result::is_ok));
+            assert!(iter.next().is_none());
+
+            partial.extend(parse_entries(TagIterator::from_offset(
+                &swf_buf.data,
+                version,
+                iter.offset(),
+            )));
+            assert_eq!(partial, tags, "truncated to {len} bytes");
+        }
+    }
+
+    #[test]
+    fn iter_tags_invalid_sprite() {
+        // A DefineSprite containing a ShowFrame that is too long, followed by
+        // a ShowFrame and an End tag.
+        let define_sprite = [0xC6, 0x09, 1, 0, 1, 0, 0x45, 0];
+        let buf = [&define_sprite[..], &[0x40, 0], &[0, 0]].concat();
+        let mut iter = TagIterator::new(&buf[..], 1);
+
+        let sprite = iter.next().unwrap().unwrap();
+        assert_eq!(sprite.tag_code(), Some(TagCode::DefineSprite));
+        assert_eq!((sprite.offset, sprite.length), (0, 6));
+        assert!(sprite.parse().is_err());
+        assert!(iter.next().unwrap().is_err());
+
+        let show_frame = iter.next().unwrap().unwrap();
+        assert_eq!(show_frame.sprite_id, None);
+        assert_eq!(show_frame.offset, 8);
+        assert_eq!(show_frame.parse().unwrap(), Tag::ShowFrame);
+        assert!(iter.next().is_none());
+        assert_eq!(iter.offset(), buf.len());
+    }
+
     /// Ensure that we return an error on invalid data.
     #[test]
     fn read_invalid_tag() {
@@ -39,6 +39,11 @@ pub fn write_swf<W: Write>(header: &Header, tags: &[Tag<'_>], output: W) -> Resu
     write_swf_raw_tags(header, &swf_body, output)
 }
 
+/// Writes a single SWF tag, including its tag header, to an output stream.
+pub fn write_tag<W: Write>(tag: &Tag<'_>, version: u8, output: W) -> Result<()> {
+    Writer::new(output, version).write_tag(tag)
+}
+
 /// Writes a SWF to the output stream, where the tag list has already been serialized to bytes.
 /// This still appends other header information such as stage size.
 pub fn write_swf_raw_tags<W: Write>(header: &Header, tags: &[u8], mut output: W) -> Result<()> {
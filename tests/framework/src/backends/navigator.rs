@@ -19,7 +19,10 @@ use vfs::VfsPath;
 struct TestResponse {
     url: String,
     body: Vec<u8>,
-    chunk_gotten: bool,
+    /// The size of the chunks that the body is streamed in.
+    chunk_size: usize,
+    /// Where the next chunk of the body starts, or `None` once the whole body was streamed.
+    next_chunk_start: Option<usize>,
     status: u16,
     redirected: bool,
 }
@@ -34,10 +37,11 @@ impl SuccessResponse for TestResponse {
     }
 
     fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error> {
-        if !self.chunk_gotten {
-            self.chunk_gotten = true;
-            let body = self.body.clone();
-            Box::pin(async move { Ok(Some(body)) })
+        if let Some(start) = self.next_chunk_start {
+            let end = start.saturating_add(self.chunk_size).min(self.body.len());
+            self.next_chunk_start = (end < self.body.len()).then_some(end);
+            let chunk = self.body[start..end].to_vec();
+            Box::pin(async move { Ok(Some(chunk)) })
         } else {
             Box::pin(async move { Ok(None) })
         }
@@ -64,6 +68,8 @@ impl SuccessResponse for TestResponse {
 /// * "?debug-success" -> Simulates a successful fetch, with body "Hello, World!"
 /// * "?debug-error-statuscode" -> Simulates a failed fetch due to a unsuccessful status
 /// * "?debug-error-dns" -> Simulates a failed fetch due to a dns resolution error
+/// * "?debug-chunk-size=N" -> Streams the body of a local file in chunks of N bytes,
+///   rather than all at once
 ///
 /// These are formatted as query params, rather than domains/whole URLs, so that real/real-invalid
 /// URLs can be used in Flash Player when writing tests
@@ -117,7 +123,8 @@ impl NavigatorBackend for TestNavigatorBackend {
                 let response: Box<dyn SuccessResponse> = Box::new(TestResponse {
                     url: request.url().to_string(),
                     body: b"Hello, World!".to_vec(),
-                    chunk_gotten: false,
+                    chunk_size: usize::MAX,
+                    next_chunk_start: Some(0),
                     status: 200,
                     redirected: false,
                 });
@@ -177,6 +184,13 @@ impl NavigatorBackend for TestNavigatorBackend {
 
         let base_path = self.relative_base_path.clone();
 
+        let chunk_size = url
+            .query_pairs()
+            .find(|(key, _)| key == "debug-chunk-size")
+            .and_then(|(_, value)| value.parse().ok())
+            .filter(|&chunk_size| chunk_size > 0)
+            .unwrap_or(usize::MAX);
+
         Box::pin(async move {
             let path = if url.scheme() == "file" {
                 // Flash supports query parameters with local urls.
@@ -231,7 +245,8 @@ impl NavigatorBackend for TestNavigatorBackend {
             let response: Box<dyn SuccessResponse> = Box::new(TestResponse {
                 url: url.to_string(),
                 body,
-                chunk_gotten: false,
+                chunk_size,
+                next_chunk_start: Some(0),
                 status: 0,
                 redirected: false,
             });
@@ -1,9 +1,12 @@
+use crate::backends::TestLogBackend;
 use crate::test::Font;
 use chrono::{DateTime, Utc};
 use image::EncodableLayout;
+use ruffle_core::backend::log::LogBackend;
 use ruffle_core::backend::ui::{
-    DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter, FontDefinition,
-    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+    ClipboardFormat, CustomMouseCursor, DialogLoaderError, DialogResultFuture, FileDialogResult,
+    FileFilter, FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor, UiBackend,
+    US_ENGLISH,
 };
 use url::Url;
 
@@ -79,17 +82,20 @@ impl FileDialogResult for TestFileDialogResult {
 ///   otherwise a user cancellation will be simulated
 /// * Attempting to display a file save dialog with a file name hint of "debug-success.txt" will simulate successfully selecting a destination
 ///   otherwise a user cancellation will be simulated
-/// * Simulated in-memory clipboard
+/// * Simulated in-memory clipboard, supporting every clipboard format
+/// * Mouse cursor changes and custom cursor registrations can be logged
 pub struct TestUiBackend {
     fonts: Vec<Font>,
-    clipboard: String,
+    clipboard: Vec<(ClipboardFormat, String)>,
+    log: Option<TestLogBackend>,
 }
 
 impl TestUiBackend {
-    pub fn new(fonts: Vec<Font>) -> Self {
+    pub fn new(fonts: Vec<Font>, log: Option<TestLogBackend>) -> Self {
         Self {
             fonts,
-            clipboard: "".to_string(),
+            clipboard: Vec::new(),
+            log,
         }
     }
 }
@@ -101,13 +107,53 @@ impl UiBackend for TestUiBackend {
 
     fn set_mouse_visible(&mut self, _visible: bool) {}
 
-    fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
+    fn set_mouse_cursor(&mut self, cursor: MouseCursor) {
+        if let Some(log) = &self.log {
+            log.avm_trace(&format!("UiBackend::set_mouse_cursor: {:?}", cursor));
+        }
+    }
+
+    fn register_mouse_cursor(&mut self, name: String, cursor: CustomMouseCursor) {
+        if let Some(log) = &self.log {
+            log.avm_trace("UiBackend::register_mouse_cursor:");
+            log.avm_trace(&format!("  Name: {}", name));
+            log.avm_trace(&format!("  Size: {}x{}", cursor.width, cursor.height));
+            log.avm_trace(&format!("  Hotspot: {:?}", cursor.hotspot));
+            log.avm_trace(&format!("  Frame rate: {}", cursor.frame_rate));
+            for frame in &cursor.frames {
+                // Only the top-left pixel of each frame, to keep the output readable.
+                log.avm_trace(&format!("  Frame: {:02X?}", &frame[..4]));
+            }
+        }
+    }
+
+    fn unregister_mouse_cursor(&mut self, name: &str) {
+        if let Some(log) = &self.log {
+            log.avm_trace(&format!("UiBackend::unregister_mouse_cursor: {}", name));
+        }
+    }
 
     fn clipboard_content(&mut self) -> String {
-        self.clipboard.clone()
+        self.clipboard_content_in_format(ClipboardFormat::Text)
+            .unwrap_or_default()
     }
 
     fn set_clipboard_content(&mut self, content: String) {
+        self.clipboard = vec![(ClipboardFormat::Text, content)];
+    }
+
+    fn clipboard_formats(&mut self) -> Vec<ClipboardFormat> {
+        self.clipboard.iter().map(|(format, _)| *format).collect()
+    }
+
+    fn clipboard_content_in_format(&mut self, format: ClipboardFormat) -> Option<String> {
+        self.clipboard
+            .iter()
+            .find(|(existing_format, _)| *existing_format == format)
+            .map(|(_, content)| content.clone())
+    }
+
+    fn set_clipboard_content_in_formats(&mut self, content: Vec<(ClipboardFormat, String)>) {
         self.clipboard = content;
     }
 
@@ -0,0 +1,181 @@
+//! Artifacts that help to review failed image comparisons.
+
+use image::{Rgb, RgbImage, RgbaImage};
+
+/// Side length of the square windows that SSIM is computed over.
+const SSIM_WINDOW: u32 = 8;
+
+/// How far apart SSIM windows are. Windows overlap, so small differences aren't missed
+/// by falling on a window boundary.
+const SSIM_STRIDE: u32 = 4;
+
+/// Computes the mean structural similarity (SSIM) of two images of the same size.
+///
+/// This is 1.0 for identical images, and drops towards 0.0 the more the structure
+/// of the images differs, which matches how visible a difference is far better than
+/// counting differing pixels. Images are compared by luminance, with transparent
+/// pixels counting as black.
+pub fn ssim(expected: &RgbaImage, actual: &RgbaImage) -> f64 {
+    const C1: f64 = (0.01 * 255.0) * (0.01 * 255.0);
+    const C2: f64 = (0.03 * 255.0) * (0.03 * 255.0);
+
+    let (width, height) = expected.dimensions();
+    let window_width = SSIM_WINDOW.min(width);
+    let window_height = SSIM_WINDOW.min(height);
+    if window_width == 0 || window_height == 0 {
+        return 1.0;
+    }
+
+    let expected = luminance(expected);
+    let actual = luminance(actual);
+
+    let mut total = 0.0;
+    let mut windows = 0;
+    for top in window_starts(height, window_height) {
+        for left in window_starts(width, window_width) {
+            let mut sum_x = 0.0;
+            let mut sum_y = 0.0;
+            let mut sum_xx = 0.0;
+            let mut sum_yy = 0.0;
+            let mut sum_xy = 0.0;
+            for y in top..top + window_height {
+                for x in left..left + window_width {
+                    let index = (y * width + x) as usize;
+                    let (a, b) = (expected[index], actual[index]);
+                    sum_x += a;
+                    sum_y += b;
+                    sum_xx += a * a;
+                    sum_yy += b * b;
+                    sum_xy += a * b;
+                }
+            }
+
+            let n = (window_width * window_height) as f64;
+            let mean_x = sum_x / n;
+            let mean_y = sum_y / n;
+            let variance_x = sum_xx / n - mean_x * mean_x;
+            let variance_y = sum_yy / n - mean_y * mean_y;
+            let covariance = sum_xy / n - mean_x * mean_y;
+
+            total += ((2.0 * mean_x * mean_y + C1) * (2.0 * covariance + C2))
+                / ((mean_x * mean_x + mean_y * mean_y + C1) * (variance_x + variance_y + C2));
+            windows += 1;
+        }
+    }
+
+    total / windows as f64
+}
+
+fn luminance(image: &RgbaImage) -> Vec<f64> {
+    image
+        .pixels()
+        .map(|p| {
+            let [r, g, b, a] = p.0.map(f64::from);
+            (0.299 * r + 0.587 * g + 0.114 * b) * a / 255.0
+        })
+        .collect()
+}
+
+/// The offsets of every window along one axis, making sure the last window touches the edge.
+fn window_starts(length: u32, window: u32) -> impl Iterator<Item = u32> {
+    let last = length - window;
+    (0..last)
+        .step_by(SSIM_STRIDE as usize)
+        .chain(std::iter::once(last))
+}
+
+/// Creates a heat map of the largest per-channel difference of each pixel.
+///
+/// Identical pixels are black, and larger differences go through red and yellow to white.
+pub fn heat_map(difference_data: &[u8], width: u32, height: u32) -> RgbImage {
+    let mut image = RgbImage::new(width, height);
+    for (pixel, difference) in image.pixels_mut().zip(difference_data.chunks_exact(4)) {
+        let max = difference.iter().copied().max().unwrap_or_default();
+        let t = f32::from(max) / 255.0;
+        let channel = |offset: f32| ((t * 3.0 - offset).clamp(0.0, 1.0) * 255.0) as u8;
+        *pixel = Rgb([channel(0.0), channel(1.0), channel(2.0)]);
+    }
+    image
+}
+
+/// Creates an HTML page showing the images of a failed comparison side by side.
+///
+/// `images` are pairs of a caption and a file name relative to the report.
+pub fn report_html(name: &str, summary: &str, images: &[(&str, String)]) -> String {
+    let mut html = format!(
+        "<!DOCTYPE html>\n\
+         <html>\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <title>Image comparison '{name}'</title>\n\
+         <style>\n\
+         body {{ font-family: sans-serif; }}\n\
+         figure {{ display: inline-block; margin: 8px; }}\n\
+         img {{ image-rendering: pixelated; border: 1px solid #888; \
+         background: repeating-conic-gradient(#ccc 0% 25%, #fff 0% 50%) 50% / 16px 16px; }}\n\
+         </style>\n\
+         </head>\n\
+         <body>\n\
+         <h1>Image comparison '{name}'</h1>\n\
+         <p>{summary}</p>\n",
+        name = escape_html(name),
+        summary = escape_html(summary),
+    );
+    for (caption, path) in images {
+        html.push_str(&format!(
+            "<figure><img src=\"{path}\"><figcaption>{caption}</figcaption></figure>\n",
+            path = escape_html(path),
+            caption = escape_html(caption),
+        ));
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn ssim_of_identical_images_is_one() {
+        let image = RgbaImage::from_fn(20, 10, |x, y| {
+            Rgba([(x * 12) as u8, (y * 20) as u8, 0, 255])
+        });
+        assert!((ssim(&image, &image) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ssim_drops_with_larger_differences() {
+        let expected = RgbaImage::from_fn(16, 16, |x, _| Rgba([(x * 16) as u8, 0, 0, 255]));
+        let slightly = RgbaImage::from_fn(16, 16, |x, _| Rgba([(x * 16 + 4) as u8, 0, 0, 255]));
+        let very = RgbaImage::from_fn(16, 16, |x, _| Rgba([255 - (x * 16) as u8, 0, 0, 255]));
+
+        let slightly = ssim(&expected, &slightly);
+        let very = ssim(&expected, &very);
+        assert!(slightly < 1.0);
+        assert!(very < slightly);
+    }
+
+    #[test]
+    fn ssim_of_tiny_images() {
+        let expected = RgbaImage::from_pixel(3, 1, Rgba([0, 0, 0, 255]));
+        let actual = RgbaImage::from_pixel(3, 1, Rgba([255, 255, 255, 255]));
+        assert!(ssim(&expected, &actual) < 0.1);
+    }
+
+    #[test]
+    fn heat_map_colors() {
+        let image = heat_map(&[0, 0, 0, 0, 0, 85, 0, 0, 0, 0, 0, 255], 3, 1);
+        assert_eq!(image.get_pixel(0, 0), &Rgb([0, 0, 0]));
+        assert_eq!(image.get_pixel(1, 0), &Rgb([255, 0, 0]));
+        assert_eq!(image.get_pixel(2, 0), &Rgb([255, 255, 255]));
+    }
+}
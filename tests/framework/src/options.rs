@@ -1,7 +1,8 @@
 use crate::backends::TestAudioBackend;
 use crate::environment::{Environment, RenderInterface};
+use crate::image_diff::{heat_map, report_html, ssim};
 use crate::image_trigger::ImageTrigger;
-use crate::util::write_image;
+use crate::util::{write_bytes, write_image};
 use anyhow::{anyhow, Result};
 use approx::relative_eq;
 use image::ImageFormat;
@@ -29,6 +30,7 @@ pub struct TestOptions {
     pub approximations: Option<Approximations>,
     pub player_options: PlayerOptions,
     pub log_fetch: bool,
+    pub log_mouse_cursors: bool,
     pub required_features: RequiredFeatures,
     pub fonts: HashMap<String, FontOptions>,
 }
@@ -47,6 +49,7 @@ impl Default for TestOptions {
             approximations: None,
             player_options: PlayerOptions::default(),
             log_fetch: false,
+            log_mouse_cursors: false,
             required_features: RequiredFeatures::default(),
             fonts: Default::default(),
         }
@@ -306,6 +309,12 @@ impl ImageComparison {
         if outliers > self.max_outliers {
             save_actual_image()?;
 
+            let similarity = ssim(&expected_image, &actual_image);
+            let summary = format!(
+                "{} outliers found (limit {}), max difference {}, SSIM {:.4}",
+                outliers, self.max_outliers, max_difference, similarity
+            );
+
             let mut difference_color = Vec::with_capacity(
                 actual_image.width() as usize * actual_image.height() as usize * 3,
             );
@@ -326,6 +335,16 @@ impl ImageComparison {
                     &difference_image,
                     ImageFormat::Png,
                 )?;
+
+                write_image(
+                    &test_path.join(format!("{name}.difference-heatmap-{environment_name}.png"))?,
+                    &heat_map(
+                        &difference_data,
+                        actual_image.width(),
+                        actual_image.height(),
+                    ),
+                    ImageFormat::Png,
+                )?;
             }
 
             if is_alpha_different {
@@ -353,12 +372,40 @@ impl ImageComparison {
                 }
             }
 
+            if !known_failure {
+                let mut images = vec![
+                    ("Expected", format!("{name}.expected.png")),
+                    ("Actual", format!("{name}.actual-{environment_name}.png")),
+                    (
+                        "Difference heat map",
+                        format!("{name}.difference-heatmap-{environment_name}.png"),
+                    ),
+                    (
+                        "Color difference",
+                        format!("{name}.difference-color-{environment_name}.png"),
+                    ),
+                ];
+                if is_alpha_different {
+                    images.push((
+                        "Alpha difference",
+                        format!("{name}.difference-alpha-{environment_name}.png"),
+                    ));
+                }
+                let report_path =
+                    test_path.join(format!("{name}.report-{environment_name}.html"))?;
+                write_bytes(
+                    &report_path,
+                    report_html(name, &summary, &images).as_bytes(),
+                )?;
+            }
+
             return Err(anyhow!(
-                "Image '{}' failed: Number of outliers ({}) is bigger than allowed limit of {}. Max difference is {}",
+                "Image '{}' failed: Number of outliers ({}) is bigger than allowed limit of {}. Max difference is {}, SSIM is {:.4}",
                 name,
                 outliers,
                 self.max_outliers,
-                max_difference
+                max_difference,
+                similarity
             ));
         } else {
             println!("Image '{name}' succeeded: {outliers} outliers found, max difference {max_difference}",);
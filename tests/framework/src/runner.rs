@@ -4,19 +4,20 @@ use crate::fs_commands::{FsCommand, TestFsCommandProvider};
 use crate::image_trigger::ImageTrigger;
 use crate::options::{ImageComparison, TestOptions};
 use crate::test::Test;
-use crate::util::{read_bytes, write_image};
+use crate::util::{read_bytes, write_bytes, write_image};
 use anyhow::{anyhow, Result};
 use image::ImageFormat;
 use pretty_assertions::Comparison;
 use ruffle_core::backend::navigator::NullExecutor;
+use ruffle_core::backend::ui::ClipboardFormat as RuffleClipboardFormat;
 use ruffle_core::events::MouseButton as RuffleMouseButton;
 use ruffle_core::events::{KeyCode, TextControlCode as RuffleTextControlCode};
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{Player, PlayerBuilder, PlayerEvent};
 use ruffle_input_format::{
-    AutomatedEvent, InputInjector, MouseButton as InputMouseButton,
-    TextControlCode as InputTextControlCode,
+    AutomatedEvent, ClipboardFormat as InputClipboardFormat, InputInjector,
+    MouseButton as InputMouseButton, TextControlCode as InputTextControlCode,
 };
 use ruffle_render::backend::{RenderBackend, ViewportDimensions};
 use ruffle_socket_format::SocketEvent;
@@ -47,6 +48,7 @@ pub struct TestRunner {
     images: HashMap<String, ImageComparison>,
     remaining_iterations: u32,
     current_iteration: u32,
+    bless: bool,
 }
 
 impl TestRunner {
@@ -87,7 +89,10 @@ impl TestRunner {
             .with_navigator(navigator)
             .with_max_execution_duration(Duration::from_secs(300))
             .with_fs_commands(Box::new(fs_command_provider))
-            .with_ui(TestUiBackend::new(test.fonts()?))
+            .with_ui(TestUiBackend::new(
+                test.fonts()?,
+                test.options.log_mouse_cursors.then(|| log.clone()),
+            ))
             .with_viewport_dimensions(
                 viewport_dimensions.width,
                 viewport_dimensions.height,
@@ -132,6 +137,7 @@ impl TestRunner {
             images,
             remaining_iterations,
             current_iteration: 0,
+            bless: test.bless && !test.options.known_failure,
             options: test.options.clone(),
         })
     }
@@ -187,6 +193,7 @@ impl TestRunner {
                             &name,
                             image_comparison,
                             self.options.known_failure,
+                            self.bless,
                             self.render_interface.as_deref(),
                         )?;
                     } else {
@@ -206,6 +213,27 @@ impl TestRunner {
                 return;
             }
 
+            if let AutomatedEvent::SetClipboardData { data } = evt {
+                let content = data
+                    .iter()
+                    .map(|(format, content)| {
+                        let format = match format {
+                            InputClipboardFormat::Text => RuffleClipboardFormat::Text,
+                            InputClipboardFormat::Html => RuffleClipboardFormat::Html,
+                            InputClipboardFormat::RichText => RuffleClipboardFormat::RichText,
+                            InputClipboardFormat::Url => RuffleClipboardFormat::Url,
+                        };
+                        (format, content.to_owned())
+                    })
+                    .collect();
+                self.player
+                    .lock()
+                    .unwrap()
+                    .ui_mut()
+                    .set_clipboard_content_in_formats(content);
+                return;
+            }
+
             self.player.lock().unwrap().handle_event(match evt {
                 AutomatedEvent::MouseDown { pos, btn } => PlayerEvent::MouseDown {
                     x: pos.0,
@@ -252,7 +280,9 @@ impl TestRunner {
                         InputTextControlCode::Delete => RuffleTextControlCode::Delete,
                     },
                 },
-                AutomatedEvent::Wait | AutomatedEvent::SetClipboardText { .. } => unreachable!(),
+                AutomatedEvent::Wait
+                | AutomatedEvent::SetClipboardText { .. }
+                | AutomatedEvent::SetClipboardData { .. } => unreachable!(),
             });
         });
         // Rendering has side-effects (such as processing 'DisplayObject.scrollRect' updates)
@@ -274,6 +304,7 @@ impl TestRunner {
                 &name,
                 image_comparison,
                 self.options.known_failure,
+                self.bless,
                 self.render_interface.as_deref(),
             )?;
         }
@@ -298,6 +329,7 @@ impl TestRunner {
                     &name,
                     image_comparison,
                     self.options.known_failure,
+                    self.bless,
                     self.render_interface.as_deref(),
                 )?;
             }
@@ -342,6 +374,16 @@ impl TestRunner {
     }
 
     pub fn compare_output(&self, actual_output: &str) -> Result<()> {
+        let result = self.check_output(actual_output);
+        if result.is_err() && self.bless {
+            write_bytes(&self.output_path, actual_output.as_bytes())?;
+            println!("Output blessed");
+            return Ok(());
+        }
+        result
+    }
+
+    fn check_output(&self, actual_output: &str) -> Result<()> {
         let expected_output = self.output_path.read_to_string()?.replace("\r\n", "\n");
 
         if let Some(approximations) = &self.options.approximations {
@@ -429,6 +471,7 @@ fn capture_and_compare_image(
     name: &String,
     image_comparison: ImageComparison,
     known_failure: bool,
+    bless: bool,
     render_interface: Option<&dyn RenderInterface>,
 ) -> Result<()> {
     use anyhow::Context;
@@ -445,14 +488,30 @@ fn capture_and_compare_image(
                 .context("Failed to open expected image")?
                 .into_rgba8();
 
-            image_comparison.test(
-                name,
-                actual_image,
-                expected_image,
-                base_path,
-                render_interface.name(),
-                known_failure,
-            )?;
+            if bless {
+                // The expected image is about to be replaced, so don't write any failure artifacts.
+                let result = image_comparison.test(
+                    name,
+                    actual_image.clone(),
+                    expected_image,
+                    base_path,
+                    render_interface.name(),
+                    true,
+                );
+                if result.is_err() {
+                    write_image(&expected_image_path, &actual_image, ImageFormat::Png)?;
+                    println!("Image '{name}' blessed");
+                }
+            } else {
+                image_comparison.test(
+                    name,
+                    actual_image,
+                    expected_image,
+                    base_path,
+                    render_interface.name(),
+                    known_failure,
+                )?;
+            }
         } else if known_failure {
             return Err(anyhow!(
                 "No image to compare to, pretending this failed since we don't know if it worked."
@@ -23,6 +23,7 @@ pub struct Test {
     pub output_path: VfsPath,
     pub root_path: VfsPath,
     pub name: String,
+    pub bless: bool,
 }
 
 impl Test {
@@ -40,9 +41,19 @@ impl Test {
             output_path,
             root_path: test_dir,
             name,
+            bless: false,
         })
     }
 
+    /// Makes this test overwrite its expected output and images with the actual results,
+    /// instead of failing when they differ.
+    ///
+    /// Tests that are known to fail are never blessed, as their expectations come from Flash Player.
+    pub fn with_bless(mut self, bless: bool) -> Self {
+        self.bless = bless;
+        self
+    }
+
     pub fn create_test_runner(&self, environment: &impl Environment) -> Result<TestRunner> {
         let movie = self.movie()?;
         let viewport_dimensions = self.options.player_options.viewport_dimensions(&movie);
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Position of a mouse cursor on the screen.
 ///
@@ -36,6 +37,15 @@ pub enum TextControlCode {
     Delete,
 }
 
+/// A format that clipboard content can be stored in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ClipboardFormat {
+    Text,
+    Html,
+    RichText,
+    Url,
+}
+
 /// All automated event types supported by FlashTAS.
 ///
 /// A FlashTAS input file consists of a string of `AutomatedEvent`s which are
@@ -76,4 +86,9 @@ pub enum AutomatedEvent {
 
     /// Populate clipboard with the given text
     SetClipboardText { text: String },
+
+    /// Populate clipboard with the given content, in one or more formats
+    SetClipboardData {
+        data: BTreeMap<ClipboardFormat, String>,
+    },
 }
@@ -4,7 +4,10 @@
 
 use crate::environment::NativeEnvironment;
 use crate::external_interface::tests::{external_interface_avm1, external_interface_avm2};
-use crate::shared_object::{shared_object_avm1, shared_object_avm2, shared_object_self_ref_avm1};
+use crate::shared_object::{
+    shared_object_avm1, shared_object_avm2, shared_object_self_ref_avm1,
+    shared_object_single_file_avm2,
+};
 use anyhow::Context;
 use anyhow::Result;
 use libtest_mimic::{Arguments, Trial};
@@ -42,7 +45,16 @@ fn is_candidate(args: &Arguments, test_name: &str) -> bool {
 }
 
 fn main() {
-    let args = Arguments::from_args();
+    // `--bless` is our own flag, so it's taken out before libtest-mimic parses the rest.
+    let mut bless = false;
+    let args = Arguments::from_iter(std::env::args().filter(|arg| {
+        if arg == "--bless" {
+            bless = true;
+            false
+        } else {
+            true
+        }
+    }));
 
     let _ = env_logger::Builder::from_env(
         env_logger::Env::default().default_filter_or("info,wgpu_core=warn,wgpu_hal=warn"),
@@ -72,7 +84,7 @@ fn main() {
                 .to_string_lossy()
                 .replace('\\', "/");
             if is_candidate(&args, &name) {
-                Some(run_test(&args, file, name))
+                Some(run_test(&args, bless, file, name))
             } else {
                 None
             }
@@ -89,6 +101,9 @@ fn main() {
     tests.push(Trial::test("shared_object_avm2", || {
         shared_object_avm2(&NativeEnvironment)
     }));
+    tests.push(Trial::test("shared_object_single_file_avm2", || {
+        shared_object_single_file_avm2(&NativeEnvironment)
+    }));
     tests.push(Trial::test("external_interface_avm1", || {
         external_interface_avm1(&NativeEnvironment)
     }));
@@ -101,7 +116,7 @@ fn main() {
     libtest_mimic::run(&args, tests).exit()
 }
 
-fn run_test(args: &Arguments, file: DirEntry, name: String) -> Trial {
+fn run_test(args: &Arguments, bless: bool, file: DirEntry, name: String) -> Trial {
     let root = VfsPath::new(PhysicalFS::new(file.path().parent().unwrap()));
     let test = Test::from_options(
         TestOptions::read(&root.join("test.toml").unwrap())
@@ -111,7 +126,8 @@ fn run_test(args: &Arguments, file: DirEntry, name: String) -> Trial {
         name.clone(),
     )
     .with_context(|| format!("Couldn't create test {name}"))
-    .unwrap();
+    .unwrap()
+    .with_bless(bless);
 
     let ignore = !test.should_run(!args.list, &NativeEnvironment);
 
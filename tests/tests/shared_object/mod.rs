@@ -1,4 +1,5 @@
 use ruffle_core::backend::storage::{MemoryStorageBackend, StorageBackend};
+use ruffle_frontend_utils::backends::storage::SingleFileStorageBackend;
 use ruffle_test_framework::environment::Environment;
 use ruffle_test_framework::options::TestOptions;
 use ruffle_test_framework::runner::TestStatus;
@@ -221,3 +222,82 @@ pub fn shared_object_avm2(environment: &impl Environment) -> Result<(), libtest_
 
     Ok(())
 }
+
+pub fn shared_object_single_file_avm2(
+    environment: &impl Environment,
+) -> Result<(), libtest_mimic::Failed> {
+    // Test SharedObject persistence through a single-file storage backend,
+    // reopening the file between runs as a frontend would.
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("SharedObjects.zip");
+    let mut storage_backend: Box<dyn StorageBackend> =
+        Box::new(SingleFileStorageBackend::new(path.clone()));
+
+    // Initial run; no shared object data.
+    let test1 = &Test::from_options(
+        TestOptions {
+            num_frames: Some(1),
+            output_path: "output1.txt".into(),
+            ..Default::default()
+        },
+        VfsPath::new(PhysicalFS::new("tests/swfs/avm2/shared_object/")),
+        "shared_object_single_file_avm2".to_string(),
+    )?;
+    let mut runner = test1.create_test_runner(environment)?;
+    {
+        let mut player = runner.player().lock().unwrap();
+        std::mem::swap(player.storage_mut(), &mut storage_backend);
+    }
+
+    loop {
+        runner.tick();
+        match runner.test()? {
+            TestStatus::Continue => {}
+            TestStatus::Sleep(duration) => sleep(duration),
+            TestStatus::Finished => break,
+        }
+    }
+    drop(runner);
+
+    // Verify that the flash cookie was written to the file.
+    let mut storage_backend: Box<dyn StorageBackend> =
+        Box::new(SingleFileStorageBackend::new(path));
+    let expected = std::fs::read("tests/swfs/avm2/shared_object/RuffleTest.sol")?;
+    std::assert_eq!(
+        expected,
+        storage_backend
+            .get("localhost//RuffleTest")
+            .unwrap_or_default()
+    );
+    std::assert_eq!(
+        storage_backend.list_keys(),
+        vec!["localhost//RuffleTest".to_string()]
+    );
+
+    // Re-run the SWF, verifying that the shared object persists.
+    let test2 = &Test::from_options(
+        TestOptions {
+            num_frames: Some(1),
+            output_path: "output2.txt".into(),
+            ..Default::default()
+        },
+        VfsPath::new(PhysicalFS::new("tests/swfs/avm2/shared_object/")),
+        "shared_object_single_file_avm2".to_string(),
+    )?;
+    let mut runner = test2.create_test_runner(environment)?;
+    {
+        let mut player = runner.player().lock().unwrap();
+        std::mem::swap(player.storage_mut(), &mut storage_backend);
+    }
+
+    loop {
+        runner.tick();
+        match runner.test()? {
+            TestStatus::Continue => {}
+            TestStatus::Sleep(duration) => sleep(duration),
+            TestStatus::Finished => break,
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,213 @@
+#!/usr/bin/env python3
+"""Writes test.swf, an SWF 8 movie whose only frame runs the code in as_source.txt.
+
+No ActionScript 2 compiler was available, so the code is spelled out below as
+expressions and statements that are assembled to AVM1 actions directly.
+"""
+
+import struct
+
+# Expressions.
+
+
+def var(name):
+    return ("var", name)
+
+
+def get(obj, name):
+    return ("get", obj, name)
+
+
+def call(obj, name, *args):
+    return ("call", obj, name, args)
+
+
+def new(obj, name, *args):
+    return ("new", obj, name, args)
+
+
+def add(*terms):
+    return ("add", terms)
+
+
+def eq(a, b):
+    return ("eq", a, b)
+
+
+def obj(**props):
+    return ("obj", props)
+
+
+def push(*values):
+    body = b""
+    for value in values:
+        if value is None:
+            body += b"\x02"
+        elif isinstance(value, bool):
+            body += b"\x05" + bytes([value])
+        elif isinstance(value, int):
+            body += b"\x07" + struct.pack("<i", value)
+        elif isinstance(value, str):
+            body += b"\x00" + value.encode("utf-8") + b"\x00"
+        else:
+            raise TypeError(value)
+    return b"\x96" + struct.pack("<H", len(body)) + body
+
+
+def action(code):
+    return bytes([code])
+
+
+def expr(e):
+    if not isinstance(e, tuple):
+        return push(e)
+    kind = e[0]
+    if kind == "var":
+        return push(e[1]) + action(0x1C)
+    if kind == "get":
+        return expr(e[1]) + push(e[2]) + action(0x4E)
+    if kind in ("call", "new"):
+        _, target, name, args = e
+        code = b"".join(expr(arg) for arg in reversed(args))
+        code += push(len(args)) + expr(target) + push(name)
+        return code + action(0x52 if kind == "call" else 0x53)
+    if kind == "add":
+        terms = e[1]
+        code = expr(terms[0])
+        for term in terms[1:]:
+            code += expr(term) + action(0x47)
+        return code
+    if kind == "eq":
+        return expr(e[1]) + expr(e[2]) + action(0x49)
+    if kind == "obj":
+        code = b""
+        for key, value in e[1].items():
+            code += push(key) + expr(value)
+        return code + push(len(e[1])) + action(0x43)
+    raise ValueError(kind)
+
+
+# Statements.
+
+
+def trace(e):
+    return expr(e) + action(0x26)
+
+
+def set_var(name, e):
+    return push(name) + expr(e) + action(0x1D)
+
+
+def set_member(target, name, e):
+    return expr(target) + push(name) + expr(e) + action(0x4F)
+
+
+def discard(e):
+    return expr(e) + action(0x17)
+
+
+# The test itself. See as_source.txt for the equivalent ActionScript.
+
+css = var("css")
+tf = var("tf")
+tf2 = var("tf2")
+
+
+def dump_format(field, index):
+    fmt = call(field, "getTextFormat", index, add(index, 1))
+    return set_var("fmt", fmt) + trace(
+        add(
+            "  [",
+            index,
+            "] color: ",
+            call(get(var("fmt"), "color"), "toString", 16),
+            ", size: ",
+            get(var("fmt"), "size"),
+            ", bold: ",
+            get(var("fmt"), "bold"),
+            ", italic: ",
+            get(var("fmt"), "italic"),
+        )
+    )
+
+
+def dump(field, last_index):
+    return (
+        trace(add("htmlText: ", get(field, "htmlText")))
+        + trace(add("text: ", get(field, "text")))
+        + dump_format(field, 0)
+        + dump_format(field, last_index)
+    )
+
+
+def create_text_field(name, depth):
+    return discard(call(var("_root"), "createTextField", name, depth, 0, 0, 300, 100)) + set_var(
+        name, get(var("_root"), name)
+    )
+
+
+code = b"".join(
+    [
+        set_var("css", new(var("TextField"), "StyleSheet")),
+        discard(call(css, "parseCSS", "p { color: #FF0000; } .big { font-size: 20px; font-weight: bold; }")),
+        create_text_field("tf", 1),
+        set_member(tf, "html", True),
+        trace("// styleSheet before assignment"),
+        trace(get(tf, "styleSheet")),
+        trace("// htmlText set after the style sheet"),
+        set_member(tf, "styleSheet", css),
+        set_member(tf, "htmlText", "<p>Hello <span class='big'>World</span></p>"),
+        trace(eq(get(tf, "styleSheet"), css)),
+        dump(tf, 10),
+        trace("// setStyle without reassigning the style sheet"),
+        discard(call(css, "setStyle", "p", obj(color="#00FF00"))),
+        dump(tf, 10),
+        trace("// Reassigning the style sheet after setStyle"),
+        set_member(tf, "styleSheet", css),
+        dump(tf, 10),
+        trace("// Reassigning the style sheet after parseCSS"),
+        discard(call(css, "parseCSS", ".big { font-size: 30px; font-style: italic; }")),
+        set_member(tf, "styleSheet", css),
+        dump(tf, 10),
+        trace("// text set with a style sheet"),
+        set_member(tf, "text", "Plain text"),
+        dump(tf, 9),
+        trace("// htmlText set before the style sheet"),
+        create_text_field("tf2", 2),
+        set_member(tf2, "html", True),
+        set_member(tf2, "htmlText", "<p>Second <span class='big'>field</span></p>"),
+        set_member(tf2, "styleSheet", css),
+        dump(tf2, 11),
+        trace("// Removing the style sheet"),
+        set_member(tf2, "styleSheet", None),
+        trace(get(tf2, "styleSheet")),
+        dump(tf2, 11),
+    ]
+) + action(0x00)
+
+# The SWF.
+
+
+def tag(code, body):
+    if len(body) < 0x3F:
+        return struct.pack("<H", code << 6 | len(body)) + body
+    return struct.pack("<HI", code << 6 | 0x3F, len(body)) + body
+
+
+def rect(x_min, x_max, y_min, y_max):
+    bits = 15
+    value = bits
+    for coord in (x_min, x_max, y_min, y_max):
+        value = value << bits | coord
+    total = 5 + 4 * bits
+    value <<= -total % 8
+    return value.to_bytes((total + 7) // 8, "big")
+
+
+body = rect(0, 550 * 20, 0, 400 * 20) + struct.pack("<HH", 24 << 8, 1)
+body += tag(9, b"\xff\xff\xff")
+body += tag(12, code)
+body += tag(1, b"")
+body += tag(0, b"")
+with open("test.swf", "wb") as f:
+    f.write(b"FWS" + bytes([8]) + struct.pack("<I", 8 + len(body)) + body)
@@ -0,0 +1,105 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.JPEGEncoderOptions;
+	import flash.display.Loader;
+	import flash.display.PNGEncoderOptions;
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.geom.Rectangle;
+	import flash.utils.ByteArray;
+
+	public class Test extends Sprite {
+		private var pending:Array = [];
+
+		public function Test() {
+			var opaque:BitmapData = new BitmapData(4, 2, false, 0x336699);
+			opaque.setPixel(0, 0, 0xFF0000);
+			opaque.setPixel(1, 0, 0x00FF00);
+			opaque.setPixel(3, 1, 0x0000FF);
+
+			var transparent:BitmapData = new BitmapData(2, 2, true, 0xFFFF0000);
+			transparent.setPixel32(1, 0, 0x00000000);
+			transparent.setPixel32(0, 1, 0xFF00FF00);
+
+			traceHistogram("opaque", opaque.histogram());
+			traceHistogram("opaque (0, 0, 2, 1)", opaque.histogram(new Rectangle(0, 0, 2, 1)));
+			traceHistogram("transparent", transparent.histogram());
+
+			var png:ByteArray = opaque.encode(opaque.rect, new PNGEncoderOptions());
+			trace("opaque PNG signature: " + header(png, 8));
+			pending.push(["opaque PNG", png]);
+
+			var given:ByteArray = new ByteArray();
+			given.writeUTFBytes("xyz");
+			var returned:ByteArray = opaque.encode(new Rectangle(1, 0, 2, 2), new PNGEncoderOptions(true), given);
+			trace("encode returns the given ByteArray: " + (returned === given));
+			trace("encode appends at the position: " + (given.length > 3) + ", position " + given.position);
+			var subRect:ByteArray = new ByteArray();
+			subRect.writeBytes(given, 3);
+			pending.push(["opaque PNG (1, 0, 2, 2)", subRect]);
+
+			pending.push(["transparent PNG", transparent.encode(transparent.rect, new PNGEncoderOptions())]);
+
+			var jpeg:ByteArray = opaque.encode(opaque.rect, new JPEGEncoderOptions(80));
+			trace("JPEG header: " + header(jpeg, 3));
+			trace("JPEG ends with EOI: " + (jpeg[jpeg.length - 2] == 0xFF && jpeg[jpeg.length - 1] == 0xD9));
+			trace("JPEG size is plausible: " + (jpeg.length > 100 && jpeg.length < 2000));
+			pending.push(["JPEG", jpeg]);
+
+			try {
+				opaque.encode(opaque.rect, {});
+			} catch (e:Error) {
+				trace("encode with an unknown compressor: " + e.errorID);
+			}
+
+			loadNext();
+		}
+
+		private function loadNext():void {
+			if (pending.length == 0) {
+				trace("Done");
+				return;
+			}
+			var next:Array = pending.shift();
+			var name:String = next[0];
+			var loader:Loader = new Loader();
+			loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+				var data:BitmapData = loader.content["bitmapData"];
+				trace(name + " loaded: " + data.width + "x" + data.height);
+				if (name != "JPEG") {
+					var pixels:Array = [];
+					for (var y:int = 0; y < data.height; y++) {
+						for (var x:int = 0; x < data.width; x++) {
+							pixels.push(data.getPixel32(x, y).toString(16));
+						}
+					}
+					trace(name + " pixels: " + pixels.join(" "));
+				}
+				loadNext();
+			});
+			loader.loadBytes(next[1]);
+		}
+
+		private static function header(bytes:ByteArray, count:int):String {
+			var result:Array = [];
+			for (var i:int = 0; i < count; i++) {
+				result.push(bytes[i].toString(16));
+			}
+			return result.join(" ");
+		}
+
+		private static function traceHistogram(name:String, histogram:Vector.<Vector.<Number>>):void {
+			trace(name + " histogram has " + histogram.length + " channels of " + histogram[0].length);
+			var channels:Array = ["red", "green", "blue", "alpha"];
+			for (var channel:int = 0; channel < histogram.length; channel++) {
+				var counts:Array = [];
+				for (var value:int = 0; value < histogram[channel].length; value++) {
+					if (histogram[channel][value] != 0) {
+						counts.push(value + ":" + histogram[channel][value]);
+					}
+				}
+				trace("  " + channels[channel] + ": " + counts.join(" "));
+			}
+		}
+	}
+}
@@ -0,0 +1,127 @@
+package {
+    import flash.desktop.Clipboard;
+    import flash.desktop.ClipboardFormats;
+    import flash.display.MovieClip;
+    import flash.events.Event;
+
+    public class Test extends MovieClip {
+        private static const FORMATS:Array = [
+            ClipboardFormats.TEXT_FORMAT,
+            ClipboardFormats.HTML_FORMAT,
+            ClipboardFormats.RICH_TEXT_FORMAT,
+            ClipboardFormats.URL_FORMAT
+        ];
+
+        private var frame:int = 0;
+
+        public function Test() {
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private static function dump(clipboard:Clipboard):void {
+            trace("formats: " + clipboard.formats.sort());
+            for each (var format:String in FORMATS) {
+                trace("  hasFormat(" + format + "): " + clipboard.hasFormat(format));
+            }
+        }
+
+        private static function dumpData(clipboard:Clipboard):void {
+            for each (var format:String in FORMATS) {
+                trace("  getData(" + format + "): " + clipboard.getData(format));
+            }
+        }
+
+        private function onEnterFrame(e:Event):void {
+            frame++;
+            if (frame != 3) {
+                return;
+            }
+            removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+
+            var clipboard:Clipboard = Clipboard.generalClipboard;
+
+            trace("/// Outside of a paste event");
+            tryRead("formats", function():* {
+                return clipboard.formats;
+            });
+            tryRead("hasFormat(html)", function():* {
+                return clipboard.hasFormat(ClipboardFormats.HTML_FORMAT);
+            });
+            tryRead("getData(html)", function():* {
+                return clipboard.getData(ClipboardFormats.HTML_FORMAT);
+            });
+            clipboard.setDataHandler(ClipboardFormats.TEXT_FORMAT, function():String {
+                trace("handler called outside of a paste event");
+                return "too early";
+            });
+            tryRead("getData(text) with a handler", function():* {
+                return clipboard.getData(ClipboardFormats.TEXT_FORMAT);
+            });
+            clipboard.clearData(ClipboardFormats.TEXT_FORMAT);
+
+            stage.addEventListener(Event.PASTE, onPaste);
+        }
+
+        private static function tryRead(name:String, read:Function):void {
+            try {
+                trace(name + ": " + read());
+            } catch (e:Error) {
+                trace(name + ": " + Object(e).constructor + " " + e.errorID);
+            }
+        }
+
+        private function onPaste(e:Event):void {
+            stage.removeEventListener(Event.PASTE, onPaste);
+            trace("/// Paste event: " + e);
+
+            var clipboard:Clipboard = Clipboard.generalClipboard;
+
+            trace("/// Pre-filled clipboard");
+            dump(clipboard);
+            dumpData(clipboard);
+
+            trace("/// setData(text)");
+            trace("returned: " + clipboard.setData(ClipboardFormats.TEXT_FORMAT, "plain text"));
+            dump(clipboard);
+            dumpData(clipboard);
+
+            trace("/// setDataHandler(html)");
+            var calls:int = 0;
+            trace("returned: " + clipboard.setDataHandler(ClipboardFormats.HTML_FORMAT, function():String {
+                calls++;
+                trace("handler called");
+                return "<b>deferred</b>";
+            }));
+            trace("handler calls: " + calls);
+            dump(clipboard);
+            trace("getData(html): " + clipboard.getData(ClipboardFormats.HTML_FORMAT));
+            trace("getData(html): " + clipboard.getData(ClipboardFormats.HTML_FORMAT));
+            trace("handler calls: " + calls);
+
+            trace("/// setDataHandler replaced by setData");
+            clipboard.setDataHandler(ClipboardFormats.URL_FORMAT, function():String {
+                trace("replaced handler called");
+                return "http://example.com/replaced";
+            });
+            clipboard.setData(ClipboardFormats.URL_FORMAT, "http://example.com/data");
+            trace("getData(url): " + clipboard.getData(ClipboardFormats.URL_FORMAT));
+
+            trace("/// setDataHandler(unsupported format)");
+            trace("returned: " + clipboard.setDataHandler("air:bitmap", function():Object {
+                return null;
+            }));
+
+            trace("/// clearData(html)");
+            clipboard.clearData(ClipboardFormats.HTML_FORMAT);
+            dump(clipboard);
+
+            trace("/// clear()");
+            clipboard.setDataHandler(ClipboardFormats.RICH_TEXT_FORMAT, function():String {
+                trace("cleared handler called");
+                return "";
+            });
+            clipboard.clear();
+            dump(clipboard);
+        }
+    }
+}
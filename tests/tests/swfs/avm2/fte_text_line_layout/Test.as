@@ -0,0 +1,237 @@
+package {
+    import flash.display.MovieClip;
+    import flash.display.Shape;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.MouseEvent;
+    import flash.geom.Rectangle;
+    import flash.text.engine.ContentElement;
+    import flash.text.engine.ElementFormat;
+    import flash.text.engine.FontDescription;
+    import flash.text.engine.GraphicElement;
+    import flash.text.engine.GroupElement;
+    import flash.text.engine.LineJustification;
+    import flash.text.engine.SpaceJustifier;
+    import flash.text.engine.TabAlignment;
+    import flash.text.engine.TabStop;
+    import flash.text.engine.TextBlock;
+    import flash.text.engine.TextElement;
+    import flash.text.engine.TextLine;
+
+    public class Test extends MovieClip {
+        private var graphicLine:TextLine;
+
+        public function Test() {
+            testMetrics();
+            testAtoms();
+            testTabStops();
+            testJustification();
+            testInsufficientWidth();
+            testEmergency();
+            testMultipleLines();
+            testGraphicElement();
+        }
+
+        private static function format(size:Number = 20):ElementFormat {
+            return new ElementFormat(new FontDescription("Tinos"), size);
+        }
+
+        private static function round(value:Number):Number {
+            return Math.round(value * 100) / 100;
+        }
+
+        private static function describeRect(rect:Rectangle):String {
+            if (rect == null) {
+                return "null";
+            }
+            return "(x=" + round(rect.x) + ", y=" + round(rect.y) + ", w=" + round(rect.width) + ", h=" + round(rect.height) + ")";
+        }
+
+        private static function describeLine(line:TextLine):String {
+            if (line == null) {
+                return "null";
+            }
+            return "begin=" + line.textBlockBeginIndex + " length=" + line.rawTextLength +
+                " atoms=" + line.atomCount + " unjustifiedTextWidth=" + round(line.unjustifiedTextWidth);
+        }
+
+        private function testMetrics():void {
+            trace("/// metrics");
+            var block:TextBlock = new TextBlock(new TextElement("Hello world", format(20)));
+            var line:TextLine = block.createTextLine(null, 500);
+            trace(describeLine(line));
+            trace("ascent: " + round(line.ascent));
+            trace("descent: " + round(line.descent));
+            trace("textWidth == unjustifiedTextWidth: " + (round(line.textWidth) == round(line.unjustifiedTextWidth)));
+            trace("result: " + block.textLineCreationResult);
+
+            var big:TextBlock = new TextBlock(new TextElement("Hello world", format(40)));
+            var bigLine:TextLine = big.createTextLine(null, 500);
+            trace("bigger font has bigger ascent: " + (bigLine.ascent > line.ascent));
+            trace("bigger font has bigger descent: " + (bigLine.descent > line.descent));
+            trace("bigger font is wider: " + (bigLine.unjustifiedTextWidth > line.unjustifiedTextWidth));
+        }
+
+        private function testAtoms():void {
+            trace("/// atoms");
+            var block:TextBlock = new TextBlock(new TextElement("abc", format(20)));
+            var line:TextLine = block.createTextLine(null, 500);
+            line.x = 100;
+            line.y = 50;
+            addChild(line);
+            for (var i:int = 0; i < line.atomCount; i++) {
+                trace("atom " + i + ": " + describeRect(line.getAtomBounds(i)) +
+                    " begin=" + line.getAtomTextBlockBeginIndex(i) +
+                    " end=" + line.getAtomTextBlockEndIndex(i));
+            }
+            var second:Rectangle = line.getAtomBounds(1);
+            trace("getAtomIndexAtPoint(second atom): " +
+                line.getAtomIndexAtPoint(100 + second.x + second.width / 2, 50 + second.y + second.height / 2));
+            trace("getAtomIndexAtPoint(outside): " + line.getAtomIndexAtPoint(10, 10));
+            trace("getAtomIndexAtCharIndex(2): " + line.getAtomIndexAtCharIndex(2));
+            removeChild(line);
+        }
+
+        private function testTabStops():void {
+            trace("/// tab stops");
+            var block:TextBlock = new TextBlock(new TextElement("a\tb", format(20)));
+            var tabStops:Vector.<TabStop> = new Vector.<TabStop>();
+            tabStops.push(new TabStop(TabAlignment.START, 100));
+            block.tabStops = tabStops;
+            var line:TextLine = block.createTextLine(null, 500);
+            trace(describeLine(line));
+            trace("hasTabs: " + line.hasTabs);
+            trace("third atom starts at tab stop: " + (round(line.getAtomBounds(2).x) == 100));
+            trace("tab atom ends at tab stop: " + (round(line.getAtomBounds(1).right) == 100));
+        }
+
+        private function testJustification():void {
+            trace("/// justification");
+            var text:String = "one two three four five six seven eight";
+            var plain:TextBlock = new TextBlock(new TextElement(text, format(20)));
+            var plainLine:TextLine = plain.createTextLine(null, 200);
+
+            var justified:TextBlock = new TextBlock(new TextElement(text, format(20)));
+            justified.textJustifier = new SpaceJustifier("en", LineJustification.ALL_BUT_LAST);
+            var first:TextLine = justified.createTextLine(null, 200);
+            trace("first line: " + describeLine(first));
+            trace("same break as unjustified: " + (first.rawTextLength == plainLine.rawTextLength));
+            trace("same unjustifiedTextWidth: " + (round(first.unjustifiedTextWidth) == round(plainLine.unjustifiedTextWidth)));
+            var lastAtom:Rectangle = first.getAtomBounds(first.atomCount - 2);
+            trace("last word reaches the width: " + (Math.abs(lastAtom.right - 200) < 1));
+
+            var last:TextLine = first;
+            while (justified.textLineCreationResult == "success") {
+                var next:TextLine = justified.createTextLine(last, 200);
+                if (next == null) {
+                    break;
+                }
+                last = next;
+            }
+            trace("last line is not justified: " + (round(last.textWidth) == round(last.unjustifiedTextWidth)));
+            trace("result: " + justified.textLineCreationResult);
+        }
+
+        private function testInsufficientWidth():void {
+            trace("/// insufficientWidth");
+            var block:TextBlock = new TextBlock(new TextElement("Wide", format(40)));
+            var line:TextLine = block.createTextLine(null, 5);
+            trace("line: " + describeLine(line));
+            trace("result: " + block.textLineCreationResult);
+            line = block.createTextLine(null, 5, 0, true);
+            trace("fitSomething line: " + (line != null ? line.rawTextLength : -1));
+            trace("result: " + block.textLineCreationResult);
+        }
+
+        private function testEmergency():void {
+            trace("/// emergency");
+            var block:TextBlock = new TextBlock(new TextElement("Supercalifragilistic", format(20)));
+            var line:TextLine = block.createTextLine(null, 60);
+            trace("first line shorter than word: " + (line.rawTextLength < 20));
+            trace("result: " + block.textLineCreationResult);
+            var total:int = line.rawTextLength;
+            var lines:int = 1;
+            while ((line = block.createTextLine(line, 60)) != null) {
+                total += line.rawTextLength;
+                lines++;
+            }
+            trace("all text covered: " + (total == 20) + ", more than one line: " + (lines > 1));
+            trace("result: " + block.textLineCreationResult);
+        }
+
+        private function testMultipleLines():void {
+            trace("/// multiple lines");
+            var elements:Vector.<ContentElement> = new Vector.<ContentElement>();
+            elements.push(new TextElement("First paragraph\n", format(20)));
+            elements.push(new TextElement("second one is a little longer", format(10)));
+            var block:TextBlock = new TextBlock(new GroupElement(elements));
+            var line:TextLine = block.createTextLine(null, 150);
+            while (line != null) {
+                trace(describeLine(line) + " result=" + block.textLineCreationResult);
+                line = block.createTextLine(line, 150);
+            }
+            trace("result: " + block.textLineCreationResult);
+            trace("firstLine.nextLine == lastLine: " + (block.firstLine.nextLine == block.lastLine));
+
+            var long:String = "";
+            for (var i:int = 0; i < 400; i++) {
+                long += "word ";
+            }
+            var wide:TextBlock = new TextBlock(new TextElement(long, format(10)));
+            line = wide.createTextLine(null, 100000);
+            trace("long line: " + line.rawTextLength + " result=" + wide.textLineCreationResult);
+            var narrow:TextBlock = new TextBlock(new TextElement(long, format(10)));
+            var total:int = 0;
+            var lines:int = 0;
+            line = narrow.createTextLine(null, 200);
+            while (line != null) {
+                total += line.rawTextLength;
+                lines++;
+                line = narrow.createTextLine(line, 200);
+            }
+            trace("narrow lines: " + lines + ", total length: " + total + " result=" + narrow.textLineCreationResult);
+        }
+
+        private function testGraphicElement():void {
+            trace("/// GraphicElement");
+            var square:Sprite = new Sprite();
+            square.graphics.beginFill(0xFF0000);
+            square.graphics.drawRect(0, 0, 30, 30);
+            square.graphics.endFill();
+            square.name = "square";
+            square.addEventListener(Event.ADDED, function(e:Event):void {
+                trace("added: " + e.target.name + ", parent is TextLine: " + (square.parent is TextLine));
+            });
+            square.addEventListener(MouseEvent.CLICK, function(e:MouseEvent):void {
+                trace("square clicked, currentTarget: " + e.currentTarget.name);
+            });
+
+            var elements:Vector.<ContentElement> = new Vector.<ContentElement>();
+            elements.push(new TextElement("ab", format(20)));
+            elements.push(new GraphicElement(square, 30, 30, format(20)));
+            elements.push(new TextElement("cd", format(20)));
+            var block:TextBlock = new TextBlock(new GroupElement(elements));
+            graphicLine = block.createTextLine(null, 500);
+            graphicLine.name = "line";
+            graphicLine.x = 100;
+            graphicLine.y = 200;
+            graphicLine.addEventListener(MouseEvent.CLICK, function(e:MouseEvent):void {
+                trace("line clicked, target: " + e.target.name);
+            });
+            addChild(graphicLine);
+
+            trace(describeLine(graphicLine));
+            trace("hasGraphicElement: " + graphicLine.hasGraphicElement);
+            trace("numChildren: " + graphicLine.numChildren);
+            trace("graphic parent is line: " + (square.parent == graphicLine));
+            trace("getAtomGraphic(2) is graphic: " + (graphicLine.getAtomGraphic(2) == square));
+            trace("getAtomGraphic(0): " + graphicLine.getAtomGraphic(0));
+            trace("graphic atom: " + describeRect(graphicLine.getAtomBounds(2)));
+            trace("graphic position: " + square.x + ", " + square.y);
+            trace("ascent covers graphic: " + (graphicLine.ascent >= 30));
+            trace("text after graphic starts after it: " + (graphicLine.getAtomBounds(3).x >= graphicLine.getAtomBounds(2).right));
+            var bounds:Rectangle = square.getBounds(this);
+            trace("graphic bounds on stage: " + describeRect(bounds));
+        }
+    }
+}
@@ -0,0 +1,187 @@
+package {
+    import flash.display.MovieClip;
+    import flash.globalization.Collator;
+    import flash.globalization.CollatorMode;
+    import flash.globalization.CurrencyFormatter;
+    import flash.globalization.CurrencyParseResult;
+    import flash.globalization.DateTimeFormatter;
+    import flash.globalization.DateTimeNameStyle;
+    import flash.globalization.DateTimeStyle;
+    import flash.globalization.LocaleID;
+    import flash.globalization.NumberFormatter;
+    import flash.globalization.NumberParseResult;
+    import flash.globalization.StringTools;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            testNumberFormatter();
+            testCurrencyFormatter();
+            testDateTimeFormatter();
+            testCollator();
+            testStringTools();
+            testLocaleID();
+        }
+
+        private static function status(name:String, object:Object):void {
+            trace("  " + name + " lastOperationStatus: " + object.lastOperationStatus);
+        }
+
+        private static function testNumberFormatter():void {
+            trace("// NumberFormatter");
+            for each (var locale:String in ["en-US", "de-DE", "fr-FR", "en_GB", "de-AT", "xx-XX", LocaleID.DEFAULT]) {
+                var nf:NumberFormatter = new NumberFormatter(locale);
+                trace(locale + " -> " + nf.actualLocaleIDName);
+                status("constructor", nf);
+                trace("  formatNumber(1234567.891): " + nf.formatNumber(1234567.891));
+                trace("  formatInt(-1234): " + nf.formatInt(-1234));
+                trace("  formatUint(4294967295): " + nf.formatUint(4294967295));
+                status("format", nf);
+            }
+
+            var nf:NumberFormatter = new NumberFormatter("en-US");
+            nf.fractionalDigits = 3;
+            nf.trailingZeros = true;
+            nf.useGrouping = false;
+            nf.leadingZero = false;
+            trace("en-US with 3 trailing digits, no grouping or leading zero: " +
+                nf.formatNumber(1234.5) + " " + nf.formatNumber(0.25));
+            nf.negativeNumberFormat = 0;
+            trace("negativeNumberFormat 0: " + nf.formatNumber(-1.5));
+            try {
+                nf.negativeNumberFormat = 5;
+            } catch (e:Error) {
+                trace("negativeNumberFormat 5: " + e.errorID);
+            }
+
+            nf = new NumberFormatter("en-US");
+            for each (var input:String in ["1,234.5", "  -42 apples", "abc", "12abc34", ""]) {
+                var result:NumberParseResult = nf.parse(input);
+                trace("parse(\"" + input + "\"): " + result.value + " [" + result.startIndex + ", " + result.endIndex + "]");
+                status("parse", nf);
+                trace("parseNumber(\"" + input + "\"): " + nf.parseNumber(input));
+                status("parseNumber", nf);
+            }
+
+            nf = new NumberFormatter("de-DE");
+            trace("de-DE parseNumber(\"1.234,5\"): " + nf.parseNumber("1.234,5"));
+        }
+
+        private static function testCurrencyFormatter():void {
+            trace("// CurrencyFormatter");
+            for each (var locale:String in ["en-US", "de-DE", "ja-JP", "fr-FR"]) {
+                var cf:CurrencyFormatter = new CurrencyFormatter(locale);
+                trace(locale + " -> " + cf.actualLocaleIDName + ", " + cf.currencyISOCode + ", " + cf.currencySymbol);
+                status("constructor", cf);
+                trace("  format(1234.5): " + cf.format(1234.5));
+                trace("  format(-1234.5, true): " + cf.format(-1234.5, true));
+                status("format", cf);
+                trace("  formattingWithCurrencySymbolIsSafe(\"USD\"): " + cf.formattingWithCurrencySymbolIsSafe("USD"));
+            }
+
+            cf = new CurrencyFormatter("en-US");
+            for each (var input:String in ["$1,234.50", "-$12", "USD 7", "nothing"]) {
+                var result:CurrencyParseResult = cf.parse(input);
+                trace("parse(\"" + input + "\"): " + result.value + " " + result.currencyString);
+                status("parse", cf);
+            }
+
+            cf.setCurrency("EUR", "€");
+            trace("setCurrency(EUR): " + cf.format(5, true) + " " + cf.currencyISOCode);
+        }
+
+        private static function testDateTimeFormatter():void {
+            trace("// DateTimeFormatter");
+            var date:Date = new Date(Date.UTC(2001, 1, 3, 16, 5, 6));
+            for each (var locale:String in ["en-US", "de-DE", "ja-JP", "xx"]) {
+                var df:DateTimeFormatter = new DateTimeFormatter(locale);
+                trace(locale + " -> " + df.actualLocaleIDName);
+                status("constructor", df);
+                trace("  long: " + df.formatUTC(date));
+                status("formatUTC", df);
+                df.setDateTimeStyles(DateTimeStyle.SHORT, DateTimeStyle.NONE);
+                trace("  short date: " + df.formatUTC(date) + " (" + df.getDateTimePattern() + ")");
+                df.setDateTimeStyles(DateTimeStyle.NONE, DateTimeStyle.MEDIUM);
+                trace("  medium time: " + df.formatUTC(date) + " (" + df.getDateTimePattern() + ")");
+                trace("  month names: " + df.getMonthNames(DateTimeNameStyle.SHORT_ABBREVIATION).slice(0, 3));
+                trace("  weekday names: " + df.getWeekdayNames().slice(0, 2));
+                trace("  first weekday: " + df.getFirstWeekday());
+            }
+
+            df = new DateTimeFormatter("en-US");
+            df.setDateTimePattern("yyyy-MM-dd'T'HH:mm:ss EEE MMMM");
+            trace("custom pattern: " + df.formatUTC(date));
+            status("setDateTimePattern", df);
+        }
+
+        private static function testCollator():void {
+            trace("// Collator");
+            var words:Array = ["b", "B", "ä", "A", "a", "ab", "Ä", "item10", "item9"];
+            for each (var mode:String in [CollatorMode.SORTING, CollatorMode.MATCHING]) {
+                var collator:Collator = new Collator("en-US", mode);
+                trace(mode + ": " + collator.actualLocaleIDName);
+                status("constructor", collator);
+                trace("  ignoreCase: " + collator.ignoreCase + ", ignoreDiacritics: " + collator.ignoreDiacritics +
+                    ", ignoreSymbols: " + collator.ignoreSymbols + ", numericComparison: " + collator.numericComparison);
+                trace("  sorted: " + words.concat().sort(collator.compare));
+                trace("  compare(\"a\", \"B\"): " + collator.compare("a", "B"));
+                trace("  equals(\"Résumé\", \"resume\"): " + collator.equals("Résumé", "resume"));
+                trace("  equals(\"e-mail\", \"email\"): " + collator.equals("e-mail", "email"));
+                status("compare", collator);
+            }
+
+            collator = new Collator("en-US");
+            collator.numericComparison = true;
+            trace("numeric: " + ["item10", "item9", "item1"].sort(collator.compare));
+
+            collator = new Collator("xx-XX");
+            trace("xx-XX: " + collator.actualLocaleIDName);
+            status("constructor", collator);
+
+            try {
+                new Collator("en-US", "bogus");
+            } catch (e:Error) {
+                trace("bogus mode: " + e.errorID);
+            }
+        }
+
+        private static function testStringTools():void {
+            trace("// StringTools");
+            var st:StringTools = new StringTools("de-DE");
+            trace("de-DE -> " + st.actualLocaleIDName);
+            status("constructor", st);
+            trace("  toUpperCase(\"straße\"): " + st.toUpperCase("straße"));
+            trace("  toLowerCase(\"ÄÖÜ\"): " + st.toLowerCase("ÄÖÜ"));
+            status("toLowerCase", st);
+            st = new StringTools(LocaleID.DEFAULT);
+            status("default constructor", st);
+            try {
+                st.toUpperCase(null);
+            } catch (e:Error) {
+                trace("  toUpperCase(null): " + e.errorID);
+            }
+        }
+
+        private static function testLocaleID():void {
+            trace("// LocaleID");
+            for each (var name:String in ["en-US", "zh_Hant_TW", "de-DE@collation=phonebook;currency=EUR", "ar-EG", "sr-Latn"]) {
+                var id:LocaleID = new LocaleID(name);
+                trace(name + " -> " + id.name);
+                trace("  language: " + id.getLanguage() + ", script: " + id.getScript() +
+                    ", region: " + id.getRegion() + ", variant: " + id.getVariant());
+                var keys:Object = id.getKeysAndValues();
+                var pairs:Array = [];
+                for (var key:String in keys) {
+                    pairs.push(key + "=" + keys[key]);
+                }
+                trace("  keys: " + pairs.sort());
+                trace("  isRightToLeft: " + id.isRightToLeft());
+                status("isRightToLeft", id);
+            }
+
+            var preferred:Vector.<String> = LocaleID.determinePreferredLocales(
+                Vector.<String>(["fr-CA", "de-DE", "es"]),
+                Vector.<String>(["en-US", "de-DE", "fr-FR", "es-MX"]));
+            trace("determinePreferredLocales: " + preferred);
+        }
+    }
+}
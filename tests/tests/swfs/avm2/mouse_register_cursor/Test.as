@@ -0,0 +1,96 @@
+package {
+    import flash.display.BitmapData;
+    import flash.display.MovieClip;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.geom.Point;
+    import flash.ui.Mouse;
+    import flash.ui.MouseCursor;
+    import flash.ui.MouseCursorData;
+
+    public class Test extends MovieClip {
+        private var frame:int = 0;
+
+        public function Test() {
+            var button:Sprite = new Sprite();
+            button.graphics.beginFill(0x00FF00);
+            button.graphics.drawRect(0, 0, 100, 100);
+            button.graphics.endFill();
+            button.x = 200;
+            button.y = 200;
+            button.buttonMode = true;
+            addChild(button);
+
+            trace("/// Mouse.cursor: " + Mouse.cursor);
+
+            var staticData:MouseCursorData = new MouseCursorData();
+            var staticFrames:Vector.<BitmapData> = new Vector.<BitmapData>();
+            staticFrames.push(new BitmapData(16, 16, true, 0x80FF0000));
+            staticData.data = staticFrames;
+            staticData.hotSpot = new Point(3, 4);
+            trace("/// registerCursor(static)");
+            Mouse.registerCursor("static", staticData);
+
+            var animatedData:MouseCursorData = new MouseCursorData();
+            var animatedFrames:Vector.<BitmapData> = new Vector.<BitmapData>();
+            animatedFrames.push(new BitmapData(32, 32, true, 0xFFFF0000));
+            animatedFrames.push(new BitmapData(32, 32, true, 0xFF0000FF));
+            animatedData.data = animatedFrames;
+            animatedData.frameRate = 10;
+            trace("/// registerCursor(animated)");
+            Mouse.registerCursor("animated", animatedData);
+
+            var bigData:MouseCursorData = new MouseCursorData();
+            var bigFrames:Vector.<BitmapData> = new Vector.<BitmapData>();
+            bigFrames.push(new BitmapData(33, 16, true, 0xFF00FF00));
+            bigData.data = bigFrames;
+            trace("/// registerCursor(big)");
+            try {
+                Mouse.registerCursor("big", bigData);
+            } catch (e:Error) {
+                trace(e);
+            }
+            trace("/// Mouse.cursor = big");
+            try {
+                Mouse.cursor = "big";
+            } catch (e:Error) {
+                trace(e);
+            }
+
+            trace("/// Mouse.cursor = static");
+            Mouse.cursor = "static";
+            trace("Mouse.cursor: " + Mouse.cursor);
+
+            trace("/// Mouse.cursor = unregistered");
+            try {
+                Mouse.cursor = "unregistered";
+            } catch (e:Error) {
+                trace(e);
+            }
+            trace("Mouse.cursor: " + Mouse.cursor);
+
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function onEnterFrame(e:Event):void {
+            frame++;
+            trace("/// Frame " + frame);
+            if (frame == 2) {
+                trace("/// Mouse.cursor = auto");
+                Mouse.cursor = MouseCursor.AUTO;
+            } else if (frame == 4) {
+                trace("/// Mouse.cursor = animated");
+                Mouse.cursor = "animated";
+            } else if (frame == 6) {
+                trace("/// unregisterCursor(animated)");
+                Mouse.unregisterCursor("animated");
+                trace("Mouse.cursor: " + Mouse.cursor);
+            } else if (frame == 7) {
+                trace("/// Mouse.cursor = ibeam");
+                Mouse.cursor = MouseCursor.IBEAM;
+                trace("Mouse.cursor: " + Mouse.cursor);
+                removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+            }
+        }
+    }
+}
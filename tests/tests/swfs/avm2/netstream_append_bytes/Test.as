@@ -0,0 +1,121 @@
+package {
+    import flash.display.MovieClip;
+    import flash.events.Event;
+    import flash.events.NetStatusEvent;
+    import flash.net.NetConnection;
+    import flash.net.NetStream;
+    import flash.net.NetStreamAppendBytesAction;
+    import flash.net.URLLoader;
+    import flash.net.URLLoaderDataFormat;
+    import flash.net.URLRequest;
+    import flash.utils.ByteArray;
+
+    public class Test extends MovieClip {
+        private static const CHUNK_SIZE:int = 8192;
+
+        private var flv:ByteArray;
+        private var ns:NetStream;
+        private var position:uint = 0;
+        private var end:uint = 0;
+        private var seekOffset:uint = 0;
+        private var phase:String = "loading";
+
+        public function Test() {
+            var loader:URLLoader = new URLLoader();
+            loader.dataFormat = URLLoaderDataFormat.BINARY;
+            loader.addEventListener(Event.COMPLETE, function(e:Event):void {
+                flv = loader.data;
+                trace("Loaded FLV: " + flv.length + " bytes");
+                start();
+            });
+            loader.load(new URLRequest("test_video.flv"));
+        }
+
+        // Returns the offset of the first video keyframe tag at or after `from`.
+        private function findKeyframe(from:uint):uint {
+            // FLV header, followed by the size of the (nonexistent) previous tag.
+            var offset:uint = flv[5] << 24 | flv[6] << 16 | flv[7] << 8 | flv[8];
+            offset += 4;
+            while (offset + 11 < flv.length) {
+                var type:uint = flv[offset] & 0x1F;
+                var size:uint = flv[offset + 1] << 16 | flv[offset + 2] << 8 | flv[offset + 3];
+                if (offset >= from && type == 9 && (flv[offset + 11] >> 4) == 1) {
+                    return offset;
+                }
+                offset += 11 + size + 4;
+            }
+            return flv.length;
+        }
+
+        private function start():void {
+            var nc:NetConnection = new NetConnection();
+            nc.connect(null);
+            ns = new NetStream(nc);
+            ns.client = {
+                onMetaData: function(metadata:Object):void {
+                    trace("onMetaData");
+                }
+            };
+            ns.addEventListener(NetStatusEvent.NET_STATUS, onNetStatus);
+
+            trace("/// appendBytes(not a ByteArray)");
+            try {
+                var notBytes:* = {};
+                ns.appendBytes(notBytes);
+            } catch (e:TypeError) {
+                trace("TypeError: " + e.errorID);
+            }
+
+            trace("/// play(null)");
+            ns.play(null);
+            ns.appendBytesAction(NetStreamAppendBytesAction.RESET_BEGIN);
+
+            seekOffset = findKeyframe(flv.length / 2);
+            end = flv.length / 2;
+            phase = "first half";
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function appendChunk():Boolean {
+            if (position >= end) {
+                return false;
+            }
+            var chunk:ByteArray = new ByteArray();
+            var length:uint = Math.min(CHUNK_SIZE, end - position);
+            chunk.writeBytes(flv, position, length);
+            ns.appendBytes(chunk);
+            position += length;
+            return true;
+        }
+
+        private function onEnterFrame(e:Event):void {
+            if (phase == "first half") {
+                if (!appendChunk()) {
+                    trace("/// appended the first half, seeking");
+                    phase = "seeking";
+                    ns.seek(1);
+                }
+            } else if (phase == "second half") {
+                if (!appendChunk()) {
+                    trace("/// appended the second half, ending the sequence");
+                    phase = "ended";
+                    ns.appendBytesAction(NetStreamAppendBytesAction.END_SEQUENCE);
+                }
+            }
+        }
+
+        private function onNetStatus(e:NetStatusEvent):void {
+            trace("netStatus: " + e.info.code);
+            if (e.info.code == "NetStream.Seek.Notify" && phase == "seeking") {
+                trace("/// RESET_SEEK, appending from the keyframe after the first half");
+                ns.appendBytesAction(NetStreamAppendBytesAction.RESET_SEEK);
+                position = seekOffset;
+                end = flv.length;
+                phase = "second half";
+            } else if (e.info.code == "NetStream.Play.Stop") {
+                trace("/// done");
+                removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+            }
+        }
+    }
+}
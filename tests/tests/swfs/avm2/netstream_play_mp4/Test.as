@@ -0,0 +1,63 @@
+package {
+    import flash.display.MovieClip;
+    import flash.events.Event;
+    import flash.events.NetStatusEvent;
+    import flash.media.Video;
+    import flash.net.NetConnection;
+    import flash.net.NetStream;
+
+    public class Test extends MovieClip {
+        private var ns:NetStream;
+        private var frames:int = 0;
+        private var seeked:Boolean = false;
+
+        public function Test() {
+            var nc:NetConnection = new NetConnection();
+            nc.connect(null);
+            ns = new NetStream(nc);
+            ns.client = {
+                onMetaData: function(metadata:Object):void {
+                    trace("onMetaData");
+                    var keys:Array = [];
+                    for (var key:String in metadata) {
+                        keys.push(key);
+                    }
+                    keys.sort();
+                    for each (key in keys) {
+                        if (key == "trackinfo") {
+                            for each (var track:Object in metadata.trackinfo) {
+                                trace("  trackinfo: " + track.sampledescription[0].sampletype +
+                                    " timescale=" + track.timescale + " length=" + track.length);
+                            }
+                        } else {
+                            trace("  " + key + ": " + metadata[key]);
+                        }
+                    }
+                }
+            };
+            ns.addEventListener(NetStatusEvent.NET_STATUS, function(e:NetStatusEvent):void {
+                trace("netStatus: " + e.info.code);
+                if (e.info.code == "NetStream.Seek.Complete") {
+                    trace("time after seek: " + ns.time);
+                }
+            });
+
+            var video:Video = new Video(512, 512);
+            video.attachNetStream(ns);
+            addChild(video);
+
+            ns.play("test_video.mp4");
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function onEnterFrame(e:Event):void {
+            frames++;
+            if (frames == 12 && !seeked) {
+                seeked = true;
+                trace("/// time before seek is past the start: " + (ns.time > 0));
+                trace("/// seek(1.1)");
+                ns.seek(1.1);
+            }
+        }
+    }
+}
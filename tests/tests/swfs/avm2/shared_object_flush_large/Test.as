@@ -0,0 +1,31 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.NetStatusEvent;
+	import flash.net.SharedObject;
+
+	// Flushes shared objects well over Flash Player's default 100 KB domain quota.
+	// Storage backends don't enforce a quota unless one has been set, so these succeed.
+	public class Test extends Sprite {
+		public function Test() {
+			var data:String = "0123456789";
+			while (data.length < 200 * 1024) {
+				data += data;
+			}
+
+			var first:SharedObject = SharedObject.getLocal("LargeFlushFirst");
+			first.addEventListener(NetStatusEvent.NET_STATUS, onNetStatus);
+			first.data.value = data;
+			trace("first.flush(): " + first.flush());
+			trace("first.size > 200 KB: " + (first.size > 200 * 1024));
+
+			var second:SharedObject = SharedObject.getLocal("LargeFlushSecond");
+			second.addEventListener(NetStatusEvent.NET_STATUS, onNetStatus);
+			second.data.value = data;
+			trace("second.flush(1 MB): " + second.flush(1024 * 1024));
+		}
+
+		private function onNetStatus(event:NetStatusEvent):void {
+			trace("netStatus: " + event.info.code);
+		}
+	}
+}
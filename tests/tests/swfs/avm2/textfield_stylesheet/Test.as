@@ -0,0 +1,72 @@
+package {
+    import flash.display.MovieClip;
+    import flash.text.StyleSheet;
+    import flash.text.TextField;
+    import flash.text.TextFormat;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            var css:StyleSheet = new StyleSheet();
+            css.parseCSS("p { color: #FF0000; } .big { font-size: 20px; font-weight: bold; }");
+
+            var tf:TextField = new TextField();
+            tf.width = 300;
+            tf.height = 100;
+            addChild(tf);
+
+            trace("// styleSheet before assignment");
+            trace(tf.styleSheet);
+
+            trace("// htmlText set after the style sheet");
+            tf.styleSheet = css;
+            tf.htmlText = "<p>Hello <span class='big'>World</span></p>";
+            trace(tf.styleSheet == css);
+            dump(tf);
+
+            trace("// setStyle without reassigning the style sheet");
+            css.setStyle("p", {color: "#00FF00"});
+            dump(tf);
+
+            trace("// Reassigning the style sheet after setStyle");
+            tf.styleSheet = css;
+            dump(tf);
+
+            trace("// Reassigning the style sheet after parseCSS");
+            css.parseCSS(".big { font-size: 30px; font-style: italic; }");
+            tf.styleSheet = css;
+            dump(tf);
+
+            trace("// text set with a style sheet");
+            tf.text = "Plain text";
+            dump(tf);
+
+            trace("// htmlText set before the style sheet");
+            var tf2:TextField = new TextField();
+            tf2.htmlText = "<p>Second <span class='big'>field</span></p>";
+            tf2.styleSheet = css;
+            dump(tf2);
+
+            trace("// Removing the style sheet");
+            tf2.styleSheet = null;
+            trace(tf2.styleSheet);
+            dump(tf2);
+        }
+
+        private static function dump(tf:TextField):void {
+            trace("htmlText: " + tf.htmlText);
+            trace("text: " + tf.text);
+            dumpFormat(tf, 0);
+            dumpFormat(tf, tf.text.length - 1);
+        }
+
+        private static function dumpFormat(tf:TextField, index:int):void {
+            if (index < 0) {
+                return;
+            }
+            var format:TextFormat = tf.getTextFormat(index, index + 1);
+            var color:String = format.color == null ? "null" : uint(format.color).toString(16);
+            trace("  [" + index + "] color: " + color + ", size: " + format.size +
+                ", bold: " + format.bold + ", italic: " + format.italic);
+        }
+    }
+}
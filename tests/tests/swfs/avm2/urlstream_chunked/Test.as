@@ -0,0 +1,33 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.ProgressEvent;
+	import flash.net.URLRequest;
+	import flash.net.URLStream;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var stream = new URLStream();
+			var progressCount = 0;
+			stream.addEventListener(Event.OPEN, function(e) {
+				trace("open, bytesAvailable: " + stream.bytesAvailable);
+			});
+			stream.addEventListener(ProgressEvent.PROGRESS, function(e: ProgressEvent) {
+				progressCount++;
+				trace("progress, bytesLoaded: " + e.bytesLoaded + " bytesTotal: " + e.bytesTotal + " bytesAvailable: " + stream.bytesAvailable);
+				if (progressCount == 2) {
+					// Data that was already read is no longer available.
+					trace("Read while loading: " + stream.readUTFBytes(4));
+					trace("bytesAvailable after read: " + stream.bytesAvailable);
+				}
+			});
+			stream.addEventListener(Event.COMPLETE, function(e) {
+				trace("complete, bytesAvailable: " + stream.bytesAvailable);
+				trace("Read string: " + stream.readUTFBytes(stream.bytesAvailable));
+				trace("bytesAvailable: " + stream.bytesAvailable);
+			});
+			// The test runner streams this file in chunks of 16 bytes.
+			stream.load(new URLRequest("data.txt?debug-chunk-size=16"));
+		}
+	}
+}
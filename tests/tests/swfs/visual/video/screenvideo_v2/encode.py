@@ -0,0 +1,357 @@
+#!/usr/bin/env python3
+"""Encodes the Screen Video V2 test media.
+
+Usage:
+    encode.py                  writes test.swf and output.expected.png here
+    encode.py --flv IN OUT     re-encodes the first frame of a Screen Video V1
+                               FLV as a Screen Video V2 FLV
+
+No encoder that we know of can write all the parts of the format that we want
+to test, so this writes them by hand. Every block is coded losslessly, which
+lets the expected output be written directly from the source images.
+"""
+
+import struct
+import sys
+import zlib
+
+# The default palette of the hybrid color depth, as 0xRRGGBB.
+DEFAULT_PALETTE = [
+    0x000000, 0x333333, 0x666666, 0x999999, 0xCCCCCC, 0xFFFFFF,
+    0x330000, 0x660000, 0x990000, 0xCC0000, 0xFF0000, 0x003300,
+    0x006600, 0x009900, 0x00CC00, 0x00FF00, 0x000033, 0x000066,
+    0x000099, 0x0000CC, 0x0000FF, 0x333300, 0x666600, 0x999900,
+    0xCCCC00, 0xFFFF00, 0x003333, 0x006666, 0x009999, 0x00CCCC,
+    0x00FFFF, 0x330033, 0x660066, 0x990099, 0xCC00CC, 0xFF00FF,
+    0xFFFF33, 0xFFFF66, 0xFFFF99, 0xFFFFCC, 0xFF33FF, 0xFF66FF,
+    0xFF99FF, 0xFFCCFF, 0x33FFFF, 0x66FFFF, 0x99FFFF, 0xCCFFFF,
+    0xCCCC33, 0xCCCC66, 0xCCCC99, 0xCCCCFF, 0xCC33CC, 0xCC66CC,
+    0xCC99CC, 0xCCFFCC, 0x33CCCC, 0x66CCCC, 0x99CCCC, 0xFFCCCC,
+    0x999933, 0x999966, 0x9999CC, 0x9999FF, 0x993399, 0x996699,
+    0x99CC99, 0x99FF99, 0x339999, 0x669999, 0xCC9999, 0xFF9999,
+    0x666633, 0x666699, 0x6666CC, 0x6666FF, 0x663366, 0x669966,
+    0x66CC66, 0x66FF66, 0x336666, 0x996666, 0xCC6666, 0xFF6666,
+    0x333366, 0x333399, 0x3333CC, 0x3333FF, 0x336633, 0x339933,
+    0x33CC33, 0x33FF33, 0x663333, 0x993333, 0xCC3333, 0xFF3333,
+    0x003366, 0x336600, 0x660033, 0x006633, 0x330066, 0x663300,
+    0x336699, 0x669933, 0x993366, 0x339966, 0x663399, 0x996633,
+    0x6699CC, 0x99CC66, 0xCC6699, 0x66CC99, 0x9966CC, 0xCC9966,
+    0x99CCFF, 0xCCFF99, 0xFF99CC, 0x99FFCC, 0xCC99FF, 0xFFCC99,
+    0x111111, 0x222222, 0x444444, 0x555555, 0xAAAAAA, 0xBBBBBB,
+    0xDDDDDD, 0xEEEEEE,
+]
+
+
+def rgb(color):
+    return ((color >> 16) & 0xFF, (color >> 8) & 0xFF, color & 0xFF)
+
+
+class Image:
+    """A top-down RGB image."""
+
+    def __init__(self, width, height, pixels):
+        self.width = width
+        self.height = height
+        self.pixels = pixels
+
+    @staticmethod
+    def filled(width, height, color):
+        return Image(width, height, [[color] * width for _ in range(height)])
+
+    def copy(self):
+        return Image(self.width, self.height, [list(row) for row in self.pixels])
+
+    def fill(self, x, y, width, height, color):
+        for row in self.pixels[y:y + height]:
+            row[x:x + width] = [color] * width
+
+    def block(self, x, y, width, height, rows=None):
+        """The pixels of a block as Screen Video stores them: bottom-up.
+
+        `y` counts from the bottom of the image, as do block rows.
+        """
+        first, count = rows or (0, height)
+        return [
+            self.pixels[self.height - 1 - (y + row)][x:x + width]
+            for row in range(first, first + count)
+        ]
+
+    def write_png(self, path):
+        raw = b"".join(
+            b"\0" + bytes(c for pixel in row for c in (*pixel, 255))
+            for row in self.pixels
+        )
+
+        def chunk(kind, data):
+            body = kind + data
+            return struct.pack(">I", len(data)) + body + struct.pack(">I", zlib.crc32(body))
+
+        header = struct.pack(">IIBBBBB", self.width, self.height, 8, 6, 0, 0, 0)
+        with open(path, "wb") as f:
+            f.write(b"\x89PNG\r\n\x1a\n")
+            f.write(chunk(b"IHDR", header))
+            f.write(chunk(b"IDAT", zlib.compress(raw, 9)))
+            f.write(chunk(b"IEND", b""))
+
+
+def bgr_payload(rows):
+    return bytes(c for row in rows for (r, g, b) in row for c in (b, g, r))
+
+
+def hybrid_payload(rows, palette):
+    """Codes pixels as palette indices or 15-bit colors, if that's lossless."""
+    indices = {color: index for index, color in reversed(list(enumerate(palette)))}
+    payload = bytearray()
+    for row in rows:
+        for pixel in row:
+            if pixel in indices:
+                payload.append(indices[pixel])
+                continue
+            if any((c >> 3 << 3 | c >> 5) != c for c in pixel):
+                return None
+            r, g, b = (c >> 3 for c in pixel)
+            payload += struct.pack(">H", 0x8000 | r << 10 | g << 5 | b)
+    return bytes(payload)
+
+
+def compress(payload, prime=None):
+    if prime is None:
+        return zlib.compress(payload, 9)
+    # Continue the stream that the prime was compressed into.
+    compressor = zlib.compressobj(9)
+    compressor.compress(prime)
+    compressor.flush(zlib.Z_SYNC_FLUSH)
+    return compressor.compress(payload) + compressor.flush()
+
+
+def image_block(payload, hybrid, diff=None, prime_current=None, prime=None):
+    flags = (0x10 if hybrid else 0) | (4 if diff else 0)
+    header = b""
+    if diff:
+        header += bytes(diff)
+    if prime_current:
+        flags |= 2
+        header += bytes(prime_current)
+    elif prime is not None:
+        flags |= 1
+    body = bytes([flags]) + header + compress(payload, prime)
+    return struct.pack(">H", len(body)) + body
+
+
+class Encoder:
+    def __init__(self, width, height, block_size):
+        self.width = width
+        self.height = height
+        self.block_size = block_size
+        self.palette = [rgb(color) for color in DEFAULT_PALETTE]
+        self.keyframe_payloads = {}
+
+    def blocks(self):
+        size = self.block_size
+        for y in range(0, self.height, size):
+            for x in range(0, self.width, size):
+                yield x, y, min(size, self.width - x), min(size, self.height - y)
+
+    def payload(self, rows, prefer_bgr=False):
+        hybrid = None if prefer_bgr else hybrid_payload(rows, self.palette)
+        if hybrid is not None:
+            return hybrid, True
+        return bgr_payload(rows), False
+
+    def frame(self, image, previous=None, palette=None, prime_current=None):
+        """Codes a frame, as a keyframe if there's no previous image.
+
+        Blocks that changed since the previous image only send the changed
+        rows, primed with the same block of the keyframe. `prime_current` maps
+        block indices to the index of an earlier block of the same frame to
+        prime with instead.
+        """
+        keyframe = previous is None
+        prime_current = prime_current or {}
+        size = self.block_size
+        header = struct.pack(
+            ">HH",
+            (size // 16 - 1) << 12 | self.width,
+            (size // 16 - 1) << 12 | self.height,
+        )
+        if palette is not None:
+            self.palette = palette
+            header += bytes([1])
+            header += image_block(bgr_payload([palette]), False)
+        else:
+            header += bytes([0])
+
+        payloads = {}
+        columns = -(-self.width // size)
+        for index, (x, y, width, height) in enumerate(self.blocks()):
+            if keyframe:
+                payload, hybrid = self.payload(image.block(x, y, width, height), index % 3 == 0)
+                payloads[index] = payload
+                header += image_block(payload, hybrid)
+                continue
+
+            old = previous.block(x, y, width, height)
+            new = image.block(x, y, width, height)
+            changed = [row for row in range(height) if old[row] != new[row]]
+            if not changed:
+                header += struct.pack(">H", 0)
+                continue
+
+            rows = (changed[0], changed[-1] - changed[0] + 1)
+            payload, hybrid = self.payload(image.block(x, y, width, height, rows))
+            payloads[index] = payload
+            if index in prime_current:
+                source = prime_current[index]
+                position = (source % columns, source // columns)
+                header += image_block(payload, hybrid, rows, position, payloads[source])
+            else:
+                prime = self.keyframe_payloads[index]
+                header += image_block(payload, hybrid, rows, prime=prime)
+
+        if keyframe:
+            self.keyframe_payloads = payloads
+        # The FrameType and CodecID byte of VIDEODATA starts every frame.
+        return bytes([(1 if keyframe else 2) << 4 | 6]) + header
+
+
+class BitWriter:
+    def __init__(self):
+        self.bits = ""
+
+    def write(self, value, count):
+        self.bits += format(value & ((1 << count) - 1), "0{}b".format(count))
+
+    def bytes(self):
+        bits = self.bits + "0" * (-len(self.bits) % 8)
+        return bytes(int(bits[i:i + 8], 2) for i in range(0, len(bits), 8))
+
+
+def tag(code, body):
+    if len(body) < 0x3F:
+        return struct.pack("<H", code << 6 | len(body)) + body
+    return struct.pack("<HI", code << 6 | 0x3F, len(body)) + body
+
+
+def swf(width, height, frames):
+    rect = BitWriter()
+    rect.write(15, 5)
+    for value in (0, width * 20, 0, height * 20):
+        rect.write(value, 15)
+
+    tags = tag(69, struct.pack("<I", 0))  # FileAttributes
+    tags += tag(9, bytes([0x80, 0x80, 0x80]))  # SetBackgroundColor
+    # DefineVideoStream: ID, NumFrames, Width, Height, no deblocking or
+    # smoothing, Screen Video V2.
+    tags += tag(60, struct.pack("<HHHHBB", 1, len(frames), width, height, 0, 6))
+    for number, frame in enumerate(frames):
+        tags += tag(61, struct.pack("<HH", 1, number) + frame)  # VideoFrame
+        if number == 0:
+            # PlaceObject2 with a character, an identity matrix and a ratio.
+            tags += tag(26, struct.pack("<BHHBH", 0x16, 1, 1, 0, number))
+        else:
+            # PlaceObject2 that moves the video to the next ratio.
+            tags += tag(26, struct.pack("<BHH", 0x11, 1, number))
+        tags += tag(1, b"")  # ShowFrame
+    tags += tag(0, b"")  # End
+
+    body = rect.bytes() + struct.pack("<HH", 24 << 8, len(frames)) + tags
+    return b"FWS" + bytes([10]) + struct.pack("<I", len(body) + 8) + body
+
+
+def flv(frames, interval):
+    data = b"FLV\x01\x01" + struct.pack(">I", 9) + struct.pack(">I", 0)
+    for number, frame in enumerate(frames):
+        time = number * interval
+        header = bytes([9]) + struct.pack(">I", len(frame))[1:]
+        header += struct.pack(">I", time)[1:] + bytes([time >> 24, 0, 0, 0])
+        data += header + frame + struct.pack(">I", len(header) + len(frame))
+    return data
+
+
+def test_images():
+    width, height = 100, 60
+    first = Image.filled(width, height, (0xFF, 0xFF, 0xFF))
+    # A gradient that can only be coded as BGR.
+    for y in range(height):
+        for x in range(40):
+            first.pixels[y][x] = (x * 6 + 1, y * 4 + 1, 0x81)
+    # Palette colors and 15-bit colors, for the hybrid color depth.
+    first.fill(40, 0, 30, 20, (0xCC, 0x66, 0x99))
+    first.fill(44, 4, 10, 10, (0x08, 0x84, 0xFF))
+    first.fill(70, 20, 30, 40, (0x33, 0x33, 0xFF))
+    first.fill(96, 50, 4, 10, (0x10, 0x29, 0x4A))
+
+    # Change some rows of some blocks.
+    second = first.copy()
+    second.fill(10, 36, 50, 6, (0x00, 0xCC, 0xCC))
+    second.fill(80, 2, 20, 4, (0x18, 0x18, 0x18))
+
+    # Change colors to ones that are only in a custom palette, and repeat a
+    # block to prime with.
+    palette = [(0x12, 0x34, 0x56), (0x65, 0x43, 0x21)] + [(i, i, i) for i in range(126)]
+    third = second.copy()
+    third.fill(0, 52, 96, 8, (0x12, 0x34, 0x56))
+    third.fill(64, 56, 32, 2, (0x65, 0x43, 0x21))
+    return width, height, [first, second, third], palette
+
+
+def main():
+    width, height, images, palette = test_images()
+    encoder = Encoder(width, height, 32)
+    frames = [
+        encoder.frame(images[0]),
+        encoder.frame(images[1], images[0]),
+        # The third block repeats the second one.
+        encoder.frame(images[2], images[1], palette, {2: 1}),
+    ]
+    with open("test.swf", "wb") as f:
+        f.write(swf(width, height, frames))
+    images[-1].write_png("output.expected.png")
+
+
+def decode_v1_keyframe(path):
+    with open(path, "rb") as f:
+        data = f.read()
+    offset = struct.unpack(">I", data[5:9])[0] + 4
+    size = struct.unpack(">I", b"\0" + data[offset + 1:offset + 4])[0]
+    frame = data[offset + 11:offset + 11 + size]
+    assert frame[0] == 0x13, "not a Screen Video keyframe"
+
+    hdr0, hdr1 = struct.unpack(">HH", frame[1:5])
+    block_width, width = (hdr0 >> 12) * 16 + 16, hdr0 & 0xFFF
+    block_height, height = (hdr1 >> 12) * 16 + 16, hdr1 & 0xFFF
+    image = Image.filled(width, height, (0, 0, 0))
+    position = 5
+    for y in range(0, height, block_height):
+        for x in range(0, width, block_width):
+            size = struct.unpack(">H", frame[position:position + 2])[0]
+            pixels = zlib.decompress(frame[position + 2:position + 2 + size])
+            position += 2 + size
+            block_w = min(block_width, width - x)
+            for row in range(min(block_height, height - y)):
+                line = pixels[row * block_w * 3:(row + 1) * block_w * 3]
+                image.pixels[height - 1 - (y + row)][x:x + block_w] = [
+                    (line[i + 2], line[i + 1], line[i]) for i in range(0, len(line), 3)
+                ]
+    return image
+
+
+def main_flv(source, destination):
+    image = decode_v1_keyframe(source)
+    encoder = Encoder(image.width, image.height, 32)
+    # The interframe resends some rows without changing them.
+    resent = image.copy()
+    resent.pixels[-1] = [(0, 0, 0)] * image.width
+    frames = [
+        encoder.frame(image),
+        encoder.frame(image, resent),
+    ]
+    with open(destination, "wb") as f:
+        f.write(flv(frames, 100))
+
+
+if __name__ == "__main__":
+    if sys.argv[1:2] == ["--flv"]:
+        main_flv(sys.argv[2], sys.argv[3])
+    else:
+        main()
@@ -10,9 +10,24 @@ pub mod vp6;
 #[cfg(feature = "screenvideo")]
 pub mod screen;
 
+#[cfg(feature = "screenvideo")]
+pub mod screen_v2;
+
+#[cfg(feature = "h264")]
+pub mod h264;
+
 /// Trait for video decoders.
 /// This should be implemented for each video codec.
 pub trait VideoDecoder {
+    /// Configure the decoder.
+    ///
+    /// This is given any codec parameters that are sent separately from the
+    /// video frames, such as the H.264 decoder configuration record. Codecs
+    /// that don't need any configuration can ignore it.
+    fn configure_decoder(&mut self, _configuration_data: &[u8]) -> Result<(), Error> {
+        Ok(())
+    }
+
     /// Preload a frame.
     ///
     /// No decoding is intended to happen at this point in time. Instead, the
@@ -0,0 +1,321 @@
+use crate::decoder::VideoDecoder;
+use openh264::decoder::Decoder;
+use openh264::formats::YUVSource;
+use ruffle_render::bitmap::BitmapFormat;
+use ruffle_video::error::Error;
+use ruffle_video::frame::{DecodedFrame, EncodedFrame, FrameDependency};
+
+/// The NAL unit type of a slice of an IDR (instantaneous decoder refresh) picture.
+const NAL_UNIT_TYPE_IDR_SLICE: u8 = 5;
+
+/// Prefixed to every NAL unit in an Annex B byte stream, which is what OpenH264 consumes.
+const START_CODE: [u8; 4] = [0, 0, 0, 1];
+
+#[derive(thiserror::Error, Debug)]
+pub enum H264Error {
+    #[error("Decoder error: {0}")]
+    DecoderError(#[from] openh264::Error),
+
+    #[error("Video data was received before the decoder configuration")]
+    NotConfigured,
+
+    #[error("Invalid AVC decoder configuration record")]
+    InvalidConfiguration,
+
+    #[error("NAL unit is longer than the video packet")]
+    InvalidNalUnitLength,
+
+    #[error("Picture wasn't found in the video stream")]
+    NoPictureInVideoStream,
+}
+
+impl From<H264Error> for Error {
+    fn from(error: H264Error) -> Self {
+        Error::DecoderError(Box::new(error))
+    }
+}
+
+/// H.264 video decoder.
+///
+/// Video packets are expected to be in the AVC format used by FLV and MP4, where each
+/// NAL unit is prefixed with its length. The size of those lengths, along with the
+/// sequence and picture parameter sets, come from the AVC decoder configuration record,
+/// which must be passed to `configure_decoder` before any frames.
+pub struct H264Decoder {
+    /// The number of bytes used for the length of each NAL unit, once configured.
+    length_size: Option<usize>,
+    decoder: Option<Decoder>,
+    last_frame: Option<DecodedFrame>,
+}
+
+impl H264Decoder {
+    pub fn new() -> Self {
+        Self {
+            length_size: None,
+            decoder: None,
+            last_frame: None,
+        }
+    }
+
+    /// Converts a packet of length-prefixed NAL units into an Annex B byte stream.
+    fn to_annex_b(&self, data: &[u8]) -> Result<Vec<u8>, H264Error> {
+        let length_size = self.length_size.ok_or(H264Error::NotConfigured)?;
+        let mut stream = Vec::with_capacity(data.len() + START_CODE.len());
+        for nal_unit in nal_units(data, length_size) {
+            stream.extend_from_slice(&START_CODE);
+            stream.extend_from_slice(nal_unit?);
+        }
+        Ok(stream)
+    }
+}
+
+impl Default for H264Decoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VideoDecoder for H264Decoder {
+    fn configure_decoder(&mut self, configuration_data: &[u8]) -> Result<(), Error> {
+        let configuration = parse_configuration_record(configuration_data)?;
+
+        let mut stream = Vec::new();
+        for parameter_set in configuration.parameter_sets {
+            stream.extend_from_slice(&START_CODE);
+            stream.extend_from_slice(parameter_set);
+        }
+
+        // A new configuration record starts a new sequence, which may have a different
+        // size or profile, so always start from a fresh decoder.
+        let mut decoder = Decoder::new().map_err(H264Error::DecoderError)?;
+        decoder.decode(&stream).map_err(H264Error::DecoderError)?;
+
+        self.length_size = Some(configuration.length_size);
+        self.decoder = Some(decoder);
+        Ok(())
+    }
+
+    fn preload_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<FrameDependency, Error> {
+        let length_size = self.length_size.ok_or(H264Error::NotConfigured)?;
+        for nal_unit in nal_units(encoded_frame.data(), length_size) {
+            let nal_unit = nal_unit?;
+            if nal_unit.first().map(|header| header & 0x1F) == Some(NAL_UNIT_TYPE_IDR_SLICE) {
+                return Ok(FrameDependency::None);
+            }
+        }
+        Ok(FrameDependency::Past)
+    }
+
+    fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error> {
+        let stream = self.to_annex_b(encoded_frame.data())?;
+        let decoder = self.decoder.as_mut().ok_or(H264Error::NotConfigured)?;
+
+        if let Some(picture) = decoder.decode(&stream).map_err(H264Error::DecoderError)? {
+            let (width, height) = picture.dimensions();
+            let (y_stride, u_stride, v_stride) = picture.strides();
+            let chroma_width = (width + 1) / 2;
+            let chroma_height = (height + 1) / 2;
+
+            let mut data = Vec::with_capacity(width * height + 2 * chroma_width * chroma_height);
+            copy_plane(&mut data, picture.y(), y_stride, width, height);
+            copy_plane(
+                &mut data,
+                picture.u(),
+                u_stride,
+                chroma_width,
+                chroma_height,
+            );
+            copy_plane(
+                &mut data,
+                picture.v(),
+                v_stride,
+                chroma_width,
+                chroma_height,
+            );
+
+            self.last_frame = Some(DecodedFrame::new(
+                width as u32,
+                height as u32,
+                BitmapFormat::Yuv420p,
+                data,
+            ));
+        }
+
+        // The decoder may hold on to a picture until it knows the display order, in which
+        // case the previous picture is shown for a little longer instead.
+        self.last_frame
+            .clone()
+            .ok_or_else(|| H264Error::NoPictureInVideoStream.into())
+    }
+}
+
+/// The parts of an AVC decoder configuration record needed for decoding.
+struct Configuration<'a> {
+    length_size: usize,
+    parameter_sets: Vec<&'a [u8]>,
+}
+
+/// Parses an `AVCDecoderConfigurationRecord`, as defined by ISO/IEC 14496-15.
+fn parse_configuration_record(data: &[u8]) -> Result<Configuration<'_>, H264Error> {
+    let mut reader = data;
+
+    // Version, profile, profile compatibility and level.
+    let header = take(&mut reader, 5)?;
+    if header[0] != 1 {
+        return Err(H264Error::InvalidConfiguration);
+    }
+    // NAL unit lengths can be 1, 2 or 4 bytes long, but not 3.
+    let length_size = (header[4] & 0b11) as usize + 1;
+    if length_size == 3 {
+        return Err(H264Error::InvalidConfiguration);
+    }
+
+    let mut parameter_sets = Vec::new();
+    // First the sequence parameter sets, then the picture parameter sets.
+    for count_mask in [0x1F, 0xFF] {
+        let count = take(&mut reader, 1)?[0] & count_mask;
+        for _ in 0..count {
+            let len = take(&mut reader, 2)?;
+            let len = u16::from_be_bytes([len[0], len[1]]) as usize;
+            parameter_sets.push(take(&mut reader, len)?);
+        }
+    }
+
+    Ok(Configuration {
+        length_size,
+        parameter_sets,
+    })
+}
+
+/// Takes the next `len` bytes of a configuration record.
+fn take<'a>(reader: &mut &'a [u8], len: usize) -> Result<&'a [u8], H264Error> {
+    if reader.len() < len {
+        return Err(H264Error::InvalidConfiguration);
+    }
+    let (bytes, rest) = reader.split_at(len);
+    *reader = rest;
+    Ok(bytes)
+}
+
+/// Iterates over the NAL units of a packet, each prefixed by a big-endian length of `length_size` bytes.
+fn nal_units(
+    mut data: &[u8],
+    length_size: usize,
+) -> impl Iterator<Item = Result<&[u8], H264Error>> {
+    std::iter::from_fn(move || {
+        if data.is_empty() {
+            return None;
+        }
+        if data.len() < length_size {
+            data = &[];
+            return Some(Err(H264Error::InvalidNalUnitLength));
+        }
+
+        let (length, rest) = data.split_at(length_size);
+        let length = length
+            .iter()
+            .fold(0usize, |length, byte| (length << 8) | *byte as usize);
+        if rest.len() < length {
+            data = &[];
+            return Some(Err(H264Error::InvalidNalUnitLength));
+        }
+
+        let (nal_unit, rest) = rest.split_at(length);
+        data = rest;
+        Some(Ok(nal_unit))
+    })
+}
+
+/// Appends a plane of `width` by `height` samples, leaving out any padding at the end of each row.
+fn copy_plane(data: &mut Vec<u8>, plane: &[u8], stride: usize, width: usize, height: usize) {
+    for row in plane.chunks(stride).take(height) {
+        data.extend_from_slice(&row[..width]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A configuration record with 4-byte NAL unit lengths, one SPS and one PPS.
+    const CONFIGURATION_RECORD: &[u8] = &[
+        0x01, 0x42, 0xC0, 0x1E, 0xFF, // Version 1, Baseline, level 3.0, 4-byte lengths
+        0xE1, 0x00, 0x04, 0x67, 0x42, 0xC0, 0x1E, // 1 SPS
+        0x01, 0x00, 0x02, 0x68, 0xCE, // 1 PPS
+    ];
+
+    #[test]
+    fn configuration_record() {
+        let configuration = parse_configuration_record(CONFIGURATION_RECORD).unwrap();
+        assert_eq!(configuration.length_size, 4);
+        assert_eq!(
+            configuration.parameter_sets,
+            [&[0x67, 0x42, 0xC0, 0x1E][..], &[0x68, 0xCE][..]]
+        );
+    }
+
+    #[test]
+    fn configuration_record_truncated() {
+        for len in 0..CONFIGURATION_RECORD.len() {
+            assert!(
+                matches!(
+                    parse_configuration_record(&CONFIGURATION_RECORD[..len]),
+                    Err(H264Error::InvalidConfiguration)
+                ),
+                "record truncated to {len} bytes was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn configuration_record_bad_version() {
+        let mut data = CONFIGURATION_RECORD.to_vec();
+        data[0] = 2;
+        assert!(matches!(
+            parse_configuration_record(&data),
+            Err(H264Error::InvalidConfiguration)
+        ));
+    }
+
+    #[test]
+    fn configuration_record_bad_length_size() {
+        let mut data = CONFIGURATION_RECORD.to_vec();
+        data[4] = 0xFE;
+        assert!(matches!(
+            parse_configuration_record(&data),
+            Err(H264Error::InvalidConfiguration)
+        ));
+    }
+
+    #[test]
+    fn nal_units_split() {
+        let data = [
+            0x00, 0x02, 0x65, 0x88, 0x00, 0x00, 0x00, 0x03, 0x41, 0x9A, 0x24,
+        ];
+        let units: Vec<_> = nal_units(&data, 2).collect::<Result<_, _>>().unwrap();
+        assert_eq!(units, [&[0x65, 0x88][..], &[][..], &[0x41, 0x9A, 0x24][..]]);
+    }
+
+    #[test]
+    fn nal_units_length_past_end() {
+        let data = [0x00, 0x01, 0x65, 0x00, 0x04, 0x41, 0x9A];
+        let mut units = nal_units(&data, 2);
+        assert_eq!(units.next().unwrap().unwrap(), [0x65]);
+        assert!(matches!(
+            units.next(),
+            Some(Err(H264Error::InvalidNalUnitLength))
+        ));
+        assert!(units.next().is_none());
+    }
+
+    #[test]
+    fn nal_units_truncated_length() {
+        let data = [0x00, 0x00, 0x01];
+        let mut units = nal_units(&data, 4);
+        assert!(matches!(
+            units.next(),
+            Some(Err(H264Error::InvalidNalUnitLength))
+        ));
+        assert!(units.next().is_none());
+    }
+}
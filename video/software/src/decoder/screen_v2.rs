@@ -0,0 +1,416 @@
+// The frame and block layout follows the Screen Video V2 description in the SWF and FLV
+// specifications. The custom palette, blocks primed with the current frame, and I-frame images
+// are our own reading of those specifications, and haven't been checked against media from a
+// real encoder; see the RESEARCHME notes below.
+
+use crate::decoder::screen::{bgr_to_rgb_flipped, ByteReader, ScreenError};
+use crate::decoder::VideoDecoder;
+use ruffle_render::bitmap::BitmapFormat;
+use ruffle_video::error::Error;
+
+use flate2::{Decompress, FlushDecompress};
+use ruffle_video::frame::{DecodedFrame, EncodedFrame, FrameDependency};
+
+/// The palette used by the hybrid color depth, until a stream sends its own.
+/// Colors are given as `0xRRGGBB`.
+#[rustfmt::skip]
+const DEFAULT_PALETTE: [u32; 128] = [
+    0x000000, 0x333333, 0x666666, 0x999999, 0xCCCCCC, 0xFFFFFF,
+    0x330000, 0x660000, 0x990000, 0xCC0000, 0xFF0000, 0x003300,
+    0x006600, 0x009900, 0x00CC00, 0x00FF00, 0x000033, 0x000066,
+    0x000099, 0x0000CC, 0x0000FF, 0x333300, 0x666600, 0x999900,
+    0xCCCC00, 0xFFFF00, 0x003333, 0x006666, 0x009999, 0x00CCCC,
+    0x00FFFF, 0x330033, 0x660066, 0x990099, 0xCC00CC, 0xFF00FF,
+    0xFFFF33, 0xFFFF66, 0xFFFF99, 0xFFFFCC, 0xFF33FF, 0xFF66FF,
+    0xFF99FF, 0xFFCCFF, 0x33FFFF, 0x66FFFF, 0x99FFFF, 0xCCFFFF,
+    0xCCCC33, 0xCCCC66, 0xCCCC99, 0xCCCCFF, 0xCC33CC, 0xCC66CC,
+    0xCC99CC, 0xCCFFCC, 0x33CCCC, 0x66CCCC, 0x99CCCC, 0xFFCCCC,
+    0x999933, 0x999966, 0x9999CC, 0x9999FF, 0x993399, 0x996699,
+    0x99CC99, 0x99FF99, 0x339999, 0x669999, 0xCC9999, 0xFF9999,
+    0x666633, 0x666699, 0x6666CC, 0x6666FF, 0x663366, 0x669966,
+    0x66CC66, 0x66FF66, 0x336666, 0x996666, 0xCC6666, 0xFF6666,
+    0x333366, 0x333399, 0x3333CC, 0x3333FF, 0x336633, 0x339933,
+    0x33CC33, 0x33FF33, 0x663333, 0x993333, 0xCC3333, 0xFF3333,
+    0x003366, 0x336600, 0x660033, 0x006633, 0x330066, 0x663300,
+    0x336699, 0x669933, 0x993366, 0x339966, 0x663399, 0x996633,
+    0x6699CC, 0x99CC66, 0xCC6699, 0x66CC99, 0x9966CC, 0xCC9966,
+    0x99CCFF, 0xCCFF99, 0xFF99CC, 0x99FFCC, 0xCC99FF, 0xFFCC99,
+    0x111111, 0x222222, 0x444444, 0x555555, 0xAAAAAA, 0xBBBBBB,
+    0xDDDDDD, 0xEEEEEE,
+];
+
+/// How the pixels of an image block are stored.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ColorDepth {
+    /// Three bytes per pixel, in BGR order.
+    Bgr,
+
+    /// One byte per pixel for palette colors, or two bytes for 15-bit colors.
+    Hybrid,
+}
+
+/// The decompressed data of every block of a frame, or `None` for blocks
+/// that weren't sent.
+type BlockData = Vec<Option<Vec<u8>>>;
+
+/// A single `IMAGEBLOCKV2` that has any data.
+struct ImageBlock<'a> {
+    color_depth: ColorDepth,
+
+    /// The first row and the number of rows that the block updates, if it
+    /// doesn't update all of them.
+    diff: Option<(usize, usize)>,
+
+    /// The column and row of a block of the current frame to prime
+    /// decompression with.
+    prime_current: Option<(usize, usize)>,
+
+    /// Whether to prime decompression with the same block of the last
+    /// keyframe.
+    prime_previous: bool,
+
+    /// The compressed pixels of the block.
+    data: &'a [u8],
+}
+
+impl<'a> ImageBlock<'a> {
+    /// Reads an image block, or `None` for a block that is left unchanged.
+    fn read(src: &mut ByteReader<'a>) -> Result<Option<Self>, ScreenError> {
+        let data_size = src.read_u16be()? as usize;
+        if data_size == 0 {
+            return Ok(None);
+        }
+        let mut block = ByteReader::new(src.read_buf_ref(data_size)?);
+
+        let flags = block.read_byte()?;
+        let color_depth = match (flags >> 3) & 0b11 {
+            0 => ColorDepth::Bgr,
+            2 => ColorDepth::Hybrid,
+            x => return Err(ScreenError::InvalidColorDepth(x)),
+        };
+        let diff = if flags & 0b100 != 0 {
+            let start = block.read_byte()? as usize;
+            let height = block.read_byte()? as usize;
+            Some((start, height))
+        } else {
+            None
+        };
+        let prime_current = if flags & 0b10 != 0 {
+            let column = block.read_byte()? as usize;
+            let row = block.read_byte()? as usize;
+            Some((column, row))
+        } else {
+            None
+        };
+        let prime_previous = flags & 0b1 != 0;
+
+        let data = block.read_to_end();
+        Ok(Some(Self {
+            color_depth,
+            diff,
+            prime_current,
+            prime_previous,
+            data,
+        }))
+    }
+}
+
+/// Screen Video V2 decoder.
+pub struct ScreenVideoV2Decoder {
+    w: usize,
+    h: usize,
+    block_w: usize,
+    block_h: usize,
+
+    tile: Vec<u8>, // acts as a scratch buffer
+
+    /// The palette of the hybrid color depth, as BGR.
+    palette: Vec<[u8; 3]>,
+
+    /// The decompressed data of every block of the last keyframe, which
+    /// later frames can prime decompression with.
+    keyframe_blocks: BlockData,
+
+    last_frame: Option<Vec<u8>>,
+}
+
+impl ScreenVideoV2Decoder {
+    pub fn new() -> Self {
+        Self {
+            w: 0,
+            h: 0,
+            block_w: 0,
+            block_h: 0,
+            tile: vec![],
+            palette: default_palette(),
+            keyframe_blocks: vec![],
+            last_frame: None,
+        }
+    }
+
+    fn num_blocks(&self) -> usize {
+        self.w.div_ceil(self.block_w) * self.h.div_ceil(self.block_h)
+    }
+
+    /// Decompresses a block into the scratch buffer, returning the number of
+    /// decompressed bytes.
+    ///
+    /// A primed block continues the zlib stream of another block, so that
+    /// it can refer back to that block's data. The other block is fed to the
+    /// decompressor as stored deflate blocks first, to get it into the
+    /// window.
+    fn decompress(&mut self, data: &[u8], prime: Option<&[u8]>) -> Result<usize, ScreenError> {
+        let mut decompress = Decompress::new(true);
+        if let Some(prime) = prime {
+            let mut stream = vec![0x78, 0x01];
+            for chunk in prime.chunks(u16::MAX as usize) {
+                let len = chunk.len() as u16;
+                stream.push(0);
+                stream.extend_from_slice(&len.to_le_bytes());
+                stream.extend_from_slice(&(!len).to_le_bytes());
+                stream.extend_from_slice(chunk);
+            }
+            let mut window = vec![0; prime.len()];
+            decompress.decompress(&stream, &mut window, FlushDecompress::Sync)?;
+        }
+
+        let primed_len = decompress.total_out();
+        decompress.decompress(data, &mut self.tile, FlushDecompress::Finish)?;
+        Ok((decompress.total_out() - primed_len) as usize)
+    }
+
+    /// Reads the palette of the hybrid color depth, which is sent as an
+    /// image block of BGR colors.
+    // RESEARCHME: Unverified. Does a short palette really keep black for the missing entries, and
+    // does it persist until the next keyframe?
+    fn decode_palette(&mut self, src: &mut ByteReader) -> Result<(), ScreenError> {
+        if let Some(block) = ImageBlock::read(src)? {
+            let len = self.decompress(block.data, None)?;
+            self.palette = self.tile[..len]
+                .chunks_exact(3)
+                .take(128)
+                .map(|bgr| [bgr[0], bgr[1], bgr[2]])
+                .collect();
+            self.palette.resize(128, [0, 0, 0]);
+        }
+        Ok(())
+    }
+
+    /// Decodes the image blocks of a frame, returning whether all of them
+    /// were fully updated, and the decompressed data of every block.
+    fn decode_v2(
+        &mut self,
+        src: &mut ByteReader,
+        data: &mut [u8],
+        stride: usize,
+    ) -> Result<(bool, BlockData), ScreenError> {
+        let columns = self.w.div_ceil(self.block_w);
+        let mut blocks = Vec::with_capacity(self.num_blocks());
+        let mut is_intra = true;
+        for (yy, row) in data.chunks_mut(stride * self.block_h).enumerate() {
+            let cur_h = (self.h - yy * self.block_h).min(self.block_h);
+            for x in (0..self.w).step_by(self.block_w) {
+                let cur_w = (self.w - x).min(self.block_w);
+
+                let Some(block) = ImageBlock::read(src)? else {
+                    is_intra = false;
+                    blocks.push(None);
+                    continue;
+                };
+
+                let (start, height) = block.diff.unwrap_or((0, cur_h));
+                if start + height > cur_h {
+                    return Err(ScreenError::InvalidDiffBlock);
+                }
+                if block.diff.is_some() {
+                    is_intra = false;
+                }
+
+                // RESEARCHME: Unverified. Priming with the current frame assumes that the column
+                // and row refer to an already decoded block of this frame.
+                let prime = if let Some((column, block_row)) = block.prime_current {
+                    let index = block_row * columns + column;
+                    Some(blocks.get(index).cloned().flatten())
+                } else if block.prime_previous {
+                    let index = blocks.len();
+                    Some(self.keyframe_blocks.get(index).cloned().flatten())
+                } else {
+                    None
+                };
+                let prime = match prime {
+                    Some(None) => return Err(ScreenError::MissingPrimingBlock),
+                    Some(Some(prime)) => Some(prime),
+                    None => None,
+                };
+
+                let len = self.decompress(block.data, prime.as_deref())?;
+                let pixels = &self.tile[..len];
+                let rows = row[x * 3..].chunks_mut(stride).skip(start).take(height);
+                match block.color_depth {
+                    ColorDepth::Bgr => {
+                        if pixels.len() < cur_w * height * 3 {
+                            return Err(ScreenError::UnexpectedEOF);
+                        }
+                        for (dst, src) in rows.zip(pixels.chunks(cur_w * 3)) {
+                            dst[..cur_w * 3].copy_from_slice(src);
+                        }
+                    }
+                    ColorDepth::Hybrid => {
+                        let mut pixels = ByteReader::new(pixels);
+                        for dst in rows {
+                            for bgr in dst[..cur_w * 3].chunks_mut(3) {
+                                bgr.copy_from_slice(&self.read_hybrid_pixel(&mut pixels)?);
+                            }
+                        }
+                    }
+                }
+
+                blocks.push(Some(self.tile[..len].to_vec()));
+            }
+        }
+        Ok((is_intra, blocks))
+    }
+
+    /// Reads a pixel of the hybrid color depth, which is either a 7-bit
+    /// palette index, or a 15-bit color with the top bit set.
+    fn read_hybrid_pixel(&self, pixels: &mut ByteReader) -> Result<[u8; 3], ScreenError> {
+        let byte = pixels.read_byte()?;
+        if byte & 0x80 == 0 {
+            return Ok(self.palette[byte as usize]);
+        }
+
+        let color = u16::from_be_bytes([byte, pixels.read_byte()?]);
+        let expand = |component: u16| {
+            let component = (component & 0x1F) as u8;
+            (component << 3) | (component >> 2)
+        };
+        Ok([expand(color), expand(color >> 5), expand(color >> 10)])
+    }
+
+    /// Reads the blocks of an I-frame image, which aren't displayed, but
+    /// replace the keyframe blocks that later frames are primed with.
+    // RESEARCHME: Unverified, and not covered by any test.
+    fn decode_iframe_image(&mut self, src: &mut ByteReader) -> Result<(), ScreenError> {
+        let mut blocks = Vec::with_capacity(self.num_blocks());
+        for _ in 0..self.num_blocks() {
+            let block = match ImageBlock::read(src)? {
+                Some(block) => {
+                    let len = self.decompress(block.data, None)?;
+                    Some(self.tile[..len].to_vec())
+                }
+                None => None,
+            };
+            blocks.push(block);
+        }
+        self.keyframe_blocks = blocks;
+        Ok(())
+    }
+
+    fn flush(&mut self) {
+        self.palette = default_palette();
+        self.keyframe_blocks.clear();
+        self.last_frame = None;
+    }
+}
+
+impl VideoDecoder for ScreenVideoV2Decoder {
+    fn preload_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<FrameDependency, Error> {
+        // Like with V1, the VideoFrame tags and FLV video tags are preceded by the FrameType and
+        // CodecID byte of VIDEODATA, which tells whether the frame is a keyframe.
+
+        // Just a quick sanity check for codec IDs...
+        debug_assert!(encoded_frame.data[0] & 0xF == 6);
+
+        match encoded_frame.data[0] >> 4 {
+            1 => Ok(FrameDependency::None),
+            2 => Ok(FrameDependency::Past),
+            x => Err(ScreenError::InvalidFrameType(x).into()),
+        }
+    }
+
+    fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error> {
+        let is_keyframe = encoded_frame.data[0] >> 4 == 1;
+
+        if !is_keyframe && self.last_frame.is_none() {
+            return Err(ScreenError::MissingReferenceFrame.into());
+        }
+
+        // Need to drop the extra preceding byte
+        let mut br = ByteReader::new(&encoded_frame.data[1..]);
+
+        let hdr0 = br.read_u16be()? as usize;
+        let blk_w = (hdr0 >> 12) * 16 + 16;
+        let w = hdr0 & 0xFFF;
+
+        let hdr1 = br.read_u16be()? as usize;
+        let blk_h = (hdr1 >> 12) * 16 + 16;
+        let h = hdr1 & 0xFFF;
+
+        debug_assert!(w != 0 && h != 0 && blk_w != 0 && blk_h != 0);
+
+        if self.w != w || self.h != h || self.block_w != blk_w || self.block_h != blk_h {
+            self.flush();
+            self.tile.resize(blk_w * blk_h * 3, 0);
+            self.w = w;
+            self.h = h;
+            self.block_w = blk_w;
+            self.block_h = blk_h;
+        }
+
+        let flags = br.read_byte()?;
+        let has_iframe_image = flags & 0b10 != 0;
+        let has_palette_info = flags & 0b1 != 0;
+
+        if has_palette_info {
+            self.decode_palette(&mut br)?;
+        }
+
+        let mut data = self
+            .last_frame
+            .clone()
+            .unwrap_or_else(|| vec![0; w * h * 3]);
+
+        let stride = w * 3;
+
+        let (is_intra, blocks) = self.decode_v2(&mut br, data.as_mut_slice(), stride)?;
+
+        if is_intra != is_keyframe {
+            return Err(ScreenError::KeyframeInvalid.into());
+        }
+
+        if is_keyframe {
+            self.keyframe_blocks = blocks;
+        }
+        if has_iframe_image {
+            self.decode_iframe_image(&mut br)?;
+        }
+
+        let rgb = bgr_to_rgb_flipped(&data, w, h);
+
+        self.last_frame = Some(data);
+
+        Ok(DecodedFrame::new(
+            w as u32,
+            h as u32,
+            BitmapFormat::Rgb,
+            rgb,
+        ))
+    }
+}
+
+/// The default palette, as BGR.
+fn default_palette() -> Vec<[u8; 3]> {
+    DEFAULT_PALETTE
+        .iter()
+        .map(|&color| {
+            let [_, r, g, b] = color.to_be_bytes();
+            [b, g, r]
+        })
+        .collect()
+}
+
+impl Default for ScreenVideoV2Decoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
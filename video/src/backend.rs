@@ -26,6 +26,18 @@ pub trait VideoBackend {
         filter: VideoDeblocking,
     ) -> Result<VideoStreamHandle, Error>;
 
+    /// Configure the decoder of a given video stream.
+    ///
+    /// Some codecs, such as H.264, send the parameters needed to decode
+    /// the stream separately from the video frames. This should be called
+    /// with that data before any frames that depend on it are preloaded
+    /// or decoded.
+    fn configure_video_stream_decoder(
+        &mut self,
+        stream: VideoStreamHandle,
+        configuration_data: &[u8],
+    ) -> Result<(), Error>;
+
     /// Preload a frame of a given video stream.
     ///
     /// No decoding is intended to happen at this point in time. Instead, the
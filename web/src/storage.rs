@@ -33,4 +33,16 @@ impl StorageBackend for LocalStorageBackend {
     fn remove_key(&mut self, name: &str) {
         let _ = self.storage.delete(name);
     }
+
+    fn list_keys(&self) -> Vec<String> {
+        let length = self.storage.length().unwrap_or_default();
+        let mut keys: Vec<String> = (0..length)
+            .filter_map(|i| self.storage.key(i).ok().flatten())
+            // Local storage is shared with the rest of the page, so only keep the keys
+            // that look like shared object names (`host/path/name`).
+            .filter(|key| key.contains('/'))
+            .collect();
+        keys.sort();
+        keys
+    }
 }
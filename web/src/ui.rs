@@ -154,6 +154,8 @@ impl WebUiBackend {
                 MouseCursor::Hand => "pointer",
                 MouseCursor::IBeam => "text",
                 MouseCursor::Grab => "grab",
+                // Custom cursors aren't registered on web, so fall back to the default.
+                MouseCursor::Custom(_) => "auto",
             }
         } else {
             "none"